@@ -0,0 +1,340 @@
+use crate::altair as spec;
+
+use crate::crypto::SecretKey;
+use crate::phase0::{compute_epoch_at_slot, is_selected, sign_with_domain, verify_signed_data};
+use crate::primitives::{BlsPublicKey, BlsSignature, DomainType, Slot, ValidatorIndex};
+use crate::state_transition::{Context, Error};
+use spec::helpers::get_domain;
+use spec::{
+    BeaconState, ContributionAndProof, SignedContributionAndProof, SyncAggregatorSelectionData,
+    SyncCommitteeContribution, SYNC_COMMITTEE_SUBNET_COUNT,
+    TARGET_AGGREGATORS_PER_SYNC_SUBCOMMITTEE,
+};
+
+pub fn get_sync_committee_selection_proof<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    slot: Slot,
+    subcommittee_index: u64,
+    signing_key: &SecretKey,
+    context: &Context,
+) -> Result<BlsSignature, Error> {
+    let epoch = compute_epoch_at_slot(slot, context);
+    let domain = get_domain(
+        state,
+        DomainType::SyncCommitteeSelectionProof,
+        Some(epoch),
+        context,
+    )?;
+    let mut signing_data = SyncAggregatorSelectionData {
+        slot,
+        subcommittee_index,
+    };
+    sign_with_domain(&mut signing_data, signing_key, domain)
+}
+
+pub fn verify_sync_committee_selection_proof<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    slot: Slot,
+    subcommittee_index: u64,
+    selection_proof: &BlsSignature,
+    public_key: &BlsPublicKey,
+    context: &Context,
+) -> Result<(), Error> {
+    let epoch = compute_epoch_at_slot(slot, context);
+    let domain = get_domain(
+        state,
+        DomainType::SyncCommitteeSelectionProof,
+        Some(epoch),
+        context,
+    )?;
+    let mut signing_data = SyncAggregatorSelectionData {
+        slot,
+        subcommittee_index,
+    };
+    verify_signed_data(&mut signing_data, selection_proof, public_key, domain)
+}
+
+pub fn is_sync_committee_aggregator(selection_proof: &BlsSignature, context: &Context) -> bool {
+    let modulo = usize::max(
+        1,
+        context.sync_committee_size
+            / SYNC_COMMITTEE_SUBNET_COUNT
+            / TARGET_AGGREGATORS_PER_SYNC_SUBCOMMITTEE,
+    );
+    is_selected(selection_proof, modulo as u64)
+}
+
+pub fn get_contribution_and_proof<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const SYNC_SUBCOMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    aggregator_index: ValidatorIndex,
+    contribution: SyncCommitteeContribution<SYNC_SUBCOMMITTEE_SIZE>,
+    signing_key: &SecretKey,
+    context: &Context,
+) -> Result<ContributionAndProof<SYNC_SUBCOMMITTEE_SIZE>, Error> {
+    let selection_proof = get_sync_committee_selection_proof(
+        state,
+        contribution.slot,
+        contribution.subcommittee_index,
+        signing_key,
+        context,
+    )?;
+    Ok(ContributionAndProof {
+        aggregator_index,
+        contribution,
+        selection_proof,
+    })
+}
+
+pub fn get_contribution_and_proof_signature<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const SYNC_SUBCOMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    contribution_and_proof: &mut ContributionAndProof<SYNC_SUBCOMMITTEE_SIZE>,
+    signing_key: &SecretKey,
+    context: &Context,
+) -> Result<BlsSignature, Error> {
+    let epoch = compute_epoch_at_slot(contribution_and_proof.contribution.slot, context);
+    let domain = get_domain(
+        state,
+        DomainType::ContributionAndProof,
+        Some(epoch),
+        context,
+    )?;
+    sign_with_domain(contribution_and_proof, signing_key, domain)
+}
+
+/// Verify both the selection proof and the signature of the aggregator
+/// in `signed_contribution_and_proof`.
+pub fn verify_contribution_and_proof_signature<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const SYNC_SUBCOMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    signed_contribution_and_proof: &mut SignedContributionAndProof<SYNC_SUBCOMMITTEE_SIZE>,
+    context: &Context,
+) -> Result<(), Error> {
+    let contribution_and_proof = &mut signed_contribution_and_proof.message;
+    let aggregator_index = contribution_and_proof.aggregator_index;
+    let aggregator = state
+        .validators
+        .get(aggregator_index)
+        .ok_or(Error::OutOfBounds {
+            requested: aggregator_index,
+            bound: state.validators.len(),
+        })?;
+
+    let contribution = &contribution_and_proof.contribution;
+    verify_sync_committee_selection_proof(
+        state,
+        contribution.slot,
+        contribution.subcommittee_index,
+        &contribution_and_proof.selection_proof,
+        &aggregator.public_key,
+        context,
+    )?;
+
+    let epoch = compute_epoch_at_slot(contribution.slot, context);
+    let domain = get_domain(
+        state,
+        DomainType::ContributionAndProof,
+        Some(epoch),
+        context,
+    )?;
+    verify_signed_data(
+        contribution_and_proof,
+        &signed_contribution_and_proof.signature,
+        &aggregator.public_key,
+        domain,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::altair::mainnet;
+    use crate::interop::interop_secret_key;
+
+    // the compressed generator of G2, whose digest is not `0 mod 8`
+    const G2_GENERATOR: &str = "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8";
+
+    #[test]
+    fn test_is_sync_committee_aggregator() {
+        let mut context = Context::for_mainnet();
+        // the digest of the point at infinity is `0 mod 8`
+        let infinity = BlsSignature::default();
+        let generator =
+            BlsSignature::try_from(hex::decode(G2_GENERATOR).unwrap().as_ref()).unwrap();
+
+        // a subcommittee of 128 has an aggregator modulo of 8
+        assert!(is_sync_committee_aggregator(&infinity, &context));
+        assert!(!is_sync_committee_aggregator(&generator, &context));
+
+        // every member of a subcommittee smaller than the target is an aggregator
+        context.sync_committee_size = 32;
+        assert!(is_sync_committee_aggregator(&generator, &context));
+    }
+
+    #[test]
+    fn test_contribution_and_proof_round_trip() {
+        let context = Context::for_mainnet();
+        let mut state = mainnet::BeaconState::default();
+        let signing_key = interop_secret_key(0).unwrap();
+        for index in 0..2 {
+            state.validators.push(crate::phase0::Validator {
+                public_key: interop_secret_key(index).unwrap().public_key(),
+                ..Default::default()
+            });
+        }
+
+        let (slot, subcommittee_index) = (5, 1);
+        let public_key = &state.validators[0].public_key;
+        let selection_proof = get_sync_committee_selection_proof(
+            &state,
+            slot,
+            subcommittee_index,
+            &signing_key,
+            &context,
+        )
+        .unwrap();
+        assert!(verify_sync_committee_selection_proof(
+            &state,
+            slot,
+            subcommittee_index,
+            &selection_proof,
+            public_key,
+            &context
+        )
+        .is_ok());
+        assert!(verify_sync_committee_selection_proof(
+            &state,
+            slot,
+            subcommittee_index + 1,
+            &selection_proof,
+            public_key,
+            &context
+        )
+        .is_err());
+
+        let contribution = mainnet::SyncCommitteeContribution {
+            slot,
+            subcommittee_index,
+            ..Default::default()
+        };
+        let mut contribution_and_proof =
+            get_contribution_and_proof(&state, 0, contribution, &signing_key, &context).unwrap();
+        assert_eq!(contribution_and_proof.selection_proof, selection_proof);
+        let signature = get_contribution_and_proof_signature(
+            &state,
+            &mut contribution_and_proof,
+            &signing_key,
+            &context,
+        )
+        .unwrap();
+        let mut signed_contribution_and_proof = SignedContributionAndProof {
+            message: contribution_and_proof,
+            signature,
+        };
+        assert!(verify_contribution_and_proof_signature(
+            &state,
+            &mut signed_contribution_and_proof,
+            &context
+        )
+        .is_ok());
+
+        signed_contribution_and_proof.message.aggregator_index = 1;
+        assert!(verify_contribution_and_proof_signature(
+            &state,
+            &mut signed_contribution_and_proof,
+            &context
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,650 @@
+use crate::altair as spec;
+
+use crate::phase0::{
+    apply_block_header, apply_deposit, apply_eth1_vote, apply_randao_reveal, apply_voluntary_exit,
+    attesting_indices, compute_epoch_at_slot, slashable_attester_indices, verify_attestation,
+    verify_deposit_count, verify_proposer_slashing, Attestation, AttesterSlashing,
+    BeaconBlockHeader, Deposit, ProposerSlashing, SignedVoluntaryExit, Validator,
+};
+use crate::primitives::{DomainType, Gwei, ValidatorIndex};
+use crate::state_transition::{Context, Error};
+use spec::helpers::{
+    get_beacon_proposer_index, get_current_epoch, get_domain, get_total_active_balance,
+    increase_balance,
+};
+use spec::{
+    add_flag, base_reward_per_increment, get_attestation_participation_flag_indices,
+    get_base_reward_per_increment, has_flag, slash_validator, verify_sync_aggregate_signature,
+    BeaconBlock, BeaconBlockBody, BeaconState, ParticipationFlags, SyncAggregate, SyncCommittee,
+    PARTICIPATION_FLAG_WEIGHTS, PROPOSER_WEIGHT, SYNC_REWARD_WEIGHT, WEIGHT_DENOMINATOR,
+};
+use ssz_rs::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+pub fn process_proposer_slashing<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    proposer_slashing: &mut ProposerSlashing,
+    context: &Context,
+) -> Result<(), Error> {
+    let epoch = compute_epoch_at_slot(proposer_slashing.signed_header_1.message.slot, context);
+    let domain = get_domain(state, DomainType::BeaconProposer, Some(epoch), context)?;
+    let proposer_index = verify_proposer_slashing(
+        &state.validators,
+        get_current_epoch(state, context),
+        proposer_slashing,
+        domain,
+    )?;
+    slash_validator(state, proposer_index, None, context)
+}
+
+pub fn process_attester_slashing<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    attester_slashing: &mut AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>,
+    context: &Context,
+) -> Result<(), Error> {
+    let current_epoch = get_current_epoch(state, context);
+    let indices = slashable_attester_indices(
+        &state.validators,
+        &state.fork,
+        state.genesis_validators_root,
+        current_epoch,
+        attester_slashing,
+        context,
+    )?;
+    for index in indices {
+        slash_validator(state, index, None, context)?;
+    }
+    Ok(())
+}
+
+pub fn process_attestation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    attestation: &Attestation<MAX_VALIDATORS_PER_COMMITTEE>,
+    context: &Context,
+) -> Result<(), Error> {
+    verify_attestation(
+        &state.validators,
+        &state.randao_mixes,
+        state.slot,
+        &state.fork,
+        state.genesis_validators_root,
+        attestation,
+        context,
+    )?;
+
+    let data = &attestation.data;
+    let inclusion_delay = state.slot - data.slot;
+    let participation_flag_indices =
+        get_attestation_participation_flag_indices(state, data, inclusion_delay, context)?;
+    let attesting_indices = attesting_indices(
+        &state.validators,
+        &state.randao_mixes,
+        data,
+        &attestation.aggregation_bits,
+        context,
+    )?;
+    let base_reward_per_increment = get_base_reward_per_increment(state, context)?;
+
+    let epoch_participation = if data.target.epoch == get_current_epoch(state, context) {
+        &mut state.current_epoch_participation
+    } else {
+        &mut state.previous_epoch_participation
+    };
+    let proposer_reward_numerator = add_participation_flags(
+        epoch_participation,
+        &state.validators,
+        &attesting_indices,
+        &participation_flag_indices,
+        base_reward_per_increment,
+        context,
+    );
+
+    let proposer_reward_denominator =
+        (WEIGHT_DENOMINATOR - PROPOSER_WEIGHT) * WEIGHT_DENOMINATOR / PROPOSER_WEIGHT;
+    let proposer_reward = proposer_reward_numerator / proposer_reward_denominator;
+    let proposer_index = get_beacon_proposer_index(state, context)?;
+    increase_balance(state, proposer_index, proposer_reward);
+    Ok(())
+}
+
+/// Add the `participation_flag_indices` earned by an attestation to the participation of
+/// its `attesting_indices`, returning the numerator of the reward owed to the proposer.
+pub(crate) fn add_participation_flags<const VALIDATOR_REGISTRY_LIMIT: usize>(
+    epoch_participation: &mut List<ParticipationFlags, VALIDATOR_REGISTRY_LIMIT>,
+    validators: &[Validator],
+    attesting_indices: &HashSet<ValidatorIndex>,
+    participation_flag_indices: &[usize],
+    base_reward_per_increment: Gwei,
+    context: &Context,
+) -> Gwei {
+    let mut proposer_reward_numerator = 0;
+    for &index in attesting_indices {
+        let increments = validators[index].effective_balance / context.effective_balance_increment;
+        let base_reward = increments * base_reward_per_increment;
+        for (flag_index, weight) in PARTICIPATION_FLAG_WEIGHTS.iter().enumerate() {
+            if participation_flag_indices.contains(&flag_index)
+                && !has_flag(epoch_participation[index], flag_index)
+            {
+                epoch_participation[index] = add_flag(epoch_participation[index], flag_index);
+                proposer_reward_numerator += base_reward * weight;
+            }
+        }
+    }
+    proposer_reward_numerator
+}
+
+pub fn process_deposit<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    deposit: &mut Deposit,
+    context: &Context,
+) -> Result<(), Error> {
+    if apply_deposit(
+        &mut state.eth1_deposit_index,
+        &state.eth1_data,
+        &mut state.validators,
+        &mut state.balances,
+        deposit,
+        context,
+    )? {
+        state
+            .previous_epoch_participation
+            .push(ParticipationFlags::default());
+        state
+            .current_epoch_participation
+            .push(ParticipationFlags::default());
+        state.inactivity_scores.push(0);
+    }
+    Ok(())
+}
+
+pub fn process_voluntary_exit<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    signed_voluntary_exit: &mut SignedVoluntaryExit,
+    context: &Context,
+) -> Result<(), Error> {
+    let current_epoch = get_current_epoch(state, context);
+    apply_voluntary_exit(
+        &mut state.validators,
+        &state.fork,
+        state.genesis_validators_root,
+        current_epoch,
+        signed_voluntary_exit,
+        context,
+    )
+}
+
+pub fn process_sync_aggregate<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    sync_aggregate: &SyncAggregate<SYNC_COMMITTEE_SIZE>,
+    context: &Context,
+) -> Result<(), Error> {
+    verify_sync_aggregate_signature(state, sync_aggregate, context)?;
+
+    let total_active_balance = get_total_active_balance(state, context)?;
+    let proposer_index = get_beacon_proposer_index(state, context)?;
+    apply_sync_aggregate_rewards(
+        &mut state.balances,
+        &state.validators,
+        &state.current_sync_committee,
+        &sync_aggregate.sync_committee_bits,
+        proposer_index,
+        total_active_balance,
+        context,
+    )
+}
+
+/// Reward the participants of the sync committee and the proposer including their
+/// aggregate, and penalize the members of the committee that did not participate.
+pub(crate) fn apply_sync_aggregate_rewards<
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    balances: &mut List<Gwei, VALIDATOR_REGISTRY_LIMIT>,
+    validators: &[Validator],
+    sync_committee: &SyncCommittee<SYNC_COMMITTEE_SIZE>,
+    sync_committee_bits: &Bitvector<SYNC_COMMITTEE_SIZE>,
+    proposer_index: ValidatorIndex,
+    total_active_balance: Gwei,
+    context: &Context,
+) -> Result<(), Error> {
+    let total_active_increments = total_active_balance / context.effective_balance_increment;
+    let total_base_rewards =
+        base_reward_per_increment(total_active_balance, context) * total_active_increments;
+    let max_participant_rewards =
+        total_base_rewards * SYNC_REWARD_WEIGHT / WEIGHT_DENOMINATOR / context.slots_per_epoch;
+    let participant_reward = max_participant_rewards / SYNC_COMMITTEE_SIZE as u64;
+    let proposer_reward =
+        participant_reward * PROPOSER_WEIGHT / (WEIGHT_DENOMINATOR - PROPOSER_WEIGHT);
+
+    let validator_indices = validators
+        .iter()
+        .enumerate()
+        .map(|(index, validator)| (&validator.public_key, index))
+        .collect::<HashMap<_, _>>();
+    for (i, public_key) in sync_committee.public_keys.iter().enumerate() {
+        let participant_index = *validator_indices
+            .get(public_key)
+            .ok_or_else(|| Error::UnknownSyncCommitteeMember(public_key.clone()))?;
+        if sync_committee_bits[i] {
+            balances[participant_index] += participant_reward;
+            balances[proposer_index] += proposer_reward;
+        } else {
+            balances[participant_index] =
+                balances[participant_index].saturating_sub(participant_reward);
+        }
+    }
+    Ok(())
+}
+
+pub fn process_block_header<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    block: &mut BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> Result<(), Error> {
+    let header = BeaconBlockHeader {
+        slot: block.slot,
+        proposer_index: block.proposer_index,
+        parent_root: block.parent_root,
+        body_root: block.body.hash_tree_root()?,
+        ..Default::default()
+    };
+    apply_block_header(
+        &mut state.latest_block_header,
+        &state.validators,
+        &state.randao_mixes,
+        state.slot,
+        header,
+        context,
+    )
+}
+
+pub fn process_randao<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    body: &BeaconBlockBody<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> Result<(), Error> {
+    let epoch = get_current_epoch(state, context);
+    let domain = get_domain(state, DomainType::Randao, Some(epoch), context)?;
+    apply_randao_reveal(
+        &mut state.randao_mixes,
+        &state.validators,
+        state.slot,
+        &body.randao_reveal,
+        domain,
+        context,
+    )
+}
+
+pub fn process_eth1_data<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    body: &BeaconBlockBody<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) {
+    apply_eth1_vote(
+        &mut state.eth1_data,
+        &mut state.eth1_data_votes,
+        &body.eth1_data,
+        context,
+    );
+}
+
+pub fn process_operations<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    body: &mut BeaconBlockBody<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> Result<(), Error> {
+    verify_deposit_count(
+        &state.eth1_data,
+        state.eth1_deposit_index,
+        body.deposits.len(),
+        context,
+    )?;
+
+    body.proposer_slashings
+        .iter_mut()
+        .try_for_each(|op| process_proposer_slashing(state, op, context))?;
+    body.attester_slashings
+        .iter_mut()
+        .try_for_each(|op| process_attester_slashing(state, op, context))?;
+    body.attestations
+        .iter()
+        .try_for_each(|op| process_attestation(state, op, context))?;
+    body.deposits
+        .iter_mut()
+        .try_for_each(|op| process_deposit(state, op, context))?;
+    body.voluntary_exits
+        .iter_mut()
+        .try_for_each(|op| process_voluntary_exit(state, op, context))?;
+    Ok(())
+}
+
+pub fn process_block<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    block: &mut BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> Result<(), Error> {
+    process_block_header(state, block, context)?;
+    process_randao(state, &block.body, context)?;
+    process_eth1_data(state, &block.body, context);
+    process_operations(state, &mut block.body, context)?;
+    process_sync_aggregate(state, &block.body.sync_aggregate, context)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interop::altair::create_mainnet_genesis_state;
+    use crate::interop::{create_deposits, interop_secret_key, interop_secret_keys};
+    use crate::phase0::DepositTree;
+
+    #[test]
+    fn test_process_deposit() {
+        let context = Context::for_mainnet();
+        let validator_count = 4;
+        let mut state = create_mainnet_genesis_state(validator_count, &context);
+
+        // a new validator, then a top-up of the first validator
+        let mut secret_keys = interop_secret_keys(validator_count + 1).unwrap();
+        secret_keys.push(interop_secret_key(0).unwrap());
+        let mut deposits = create_deposits(&secret_keys, &context).unwrap();
+        let mut tree = DepositTree::default();
+        for deposit in &deposits {
+            tree.push(&deposit.data).unwrap();
+        }
+
+        // the proof must be against the deposit at `eth1_deposit_index`
+        state.eth1_data.deposit_root = tree.root(deposits.len()).unwrap();
+        let result = process_deposit(&mut state, &mut deposits[validator_count + 1], &context);
+        assert!(matches!(result, Err(Error::InvalidBlock(..))));
+        assert_eq!(state.eth1_deposit_index, validator_count as u64);
+
+        state.eth1_data.deposit_root = tree.root(validator_count + 1).unwrap();
+        process_deposit(&mut state, &mut deposits[validator_count], &context).unwrap();
+        assert_eq!(state.eth1_deposit_index, validator_count as u64 + 1);
+        assert_eq!(state.validators.len(), validator_count + 1);
+        assert_eq!(state.balances.len(), validator_count + 1);
+        assert_eq!(
+            state.previous_epoch_participation.len(),
+            validator_count + 1
+        );
+        assert_eq!(state.current_epoch_participation.len(), validator_count + 1);
+        assert_eq!(state.inactivity_scores.len(), validator_count + 1);
+
+        let balance = state.balances[0];
+        state.eth1_data.deposit_root = tree.root(validator_count + 2).unwrap();
+        process_deposit(&mut state, &mut deposits[validator_count + 1], &context).unwrap();
+        assert_eq!(state.eth1_deposit_index, validator_count as u64 + 2);
+        assert_eq!(state.balances[0], balance + context.max_effective_balance);
+        assert_eq!(state.validators.len(), validator_count + 1);
+        assert_eq!(
+            state.previous_epoch_participation.len(),
+            validator_count + 1
+        );
+        assert_eq!(state.current_epoch_participation.len(), validator_count + 1);
+        assert_eq!(state.inactivity_scores.len(), validator_count + 1);
+    }
+}
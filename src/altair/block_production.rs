@@ -0,0 +1,370 @@
+use crate::altair as spec;
+
+use crate::phase0::{
+    eth1_vote, get_randao_reveal, Attestation, AttesterSlashing, Deposit, Eth1Block, Eth1Data,
+    ProposerSlashing, SignedVoluntaryExit,
+};
+use crate::primitives::{BlsSignature, Bytes32, Slot};
+use crate::signer::{self, Signer};
+use crate::state_transition::{Context, Error};
+use spec::helpers::get_beacon_proposer_index;
+use spec::{
+    process_attestation, process_attester_slashing, process_block, process_block_header,
+    process_deposit, process_eth1_data, process_proposer_slashing, process_randao, process_slots,
+    process_sync_aggregate, process_voluntary_exit, BeaconBlock, BeaconState, SyncAggregate,
+};
+use ssz_rs::prelude::*;
+
+/// Candidate operations for inclusion in a block.
+///
+/// Each collection may contain more operations than fit in a block and
+/// may contain operations that are invalid against the state the block is
+/// built on; `produce_block` only includes those that can be processed.
+/// The `sync_aggregate` is typically taken from `OperationPool::get_sync_aggregate`
+/// and is replaced with an empty aggregate if it is not valid against the state.
+#[derive(Default, Debug)]
+pub struct BlockOperations<
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+> {
+    pub proposer_slashings: Vec<ProposerSlashing>,
+    pub attester_slashings: Vec<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>>,
+    pub attestations: Vec<Attestation<MAX_VALIDATORS_PER_COMMITTEE>>,
+    pub deposits: Vec<Deposit>,
+    pub voluntary_exits: Vec<SignedVoluntaryExit>,
+    pub sync_aggregate: SyncAggregate<SYNC_COMMITTEE_SIZE>,
+}
+
+/// Build an unsigned block for `slot` on top of `state`.
+///
+/// The `state` is not modified; a copy is advanced to `slot` (if needed) and
+/// the resulting block is applied to it to compute the block's `state_root`.
+/// See `phase0::produce_block` for how the randao reveal, the eth1 vote and
+/// the operations are selected.
+pub async fn produce_block<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    slot: Slot,
+    signer: &impl Signer,
+    eth1_chain: &[Eth1Block],
+    graffiti: Bytes32,
+    operations: BlockOperations<MAX_VALIDATORS_PER_COMMITTEE, SYNC_COMMITTEE_SIZE>,
+    context: &Context,
+) -> Result<
+    BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    signer::Error,
+> {
+    let mut state = state.clone();
+    if state.slot < slot {
+        process_slots(&mut state, slot, context)?;
+    }
+
+    let proposer_index = get_beacon_proposer_index(&state, context)?;
+    let randao_reveal = get_randao_reveal(
+        signer,
+        &state.validators[proposer_index].public_key,
+        &state.fork,
+        state.genesis_validators_root,
+        slot,
+        context,
+    )
+    .await?;
+    let eth1_data = eth1_vote(
+        state.genesis_time,
+        state.slot,
+        &state.eth1_data,
+        &state.eth1_data_votes,
+        eth1_chain,
+        context,
+    );
+    let block = build_block(
+        &mut state,
+        randao_reveal,
+        eth1_data,
+        graffiti,
+        operations,
+        context,
+    )?;
+    Ok(block)
+}
+
+// Assemble the block for the slot of `state` from the given operations
+// and apply it to `state`, recording the resulting `state_root`.
+fn build_block<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    randao_reveal: BlsSignature,
+    eth1_data: Eth1Data,
+    graffiti: Bytes32,
+    operations: BlockOperations<MAX_VALIDATORS_PER_COMMITTEE, SYNC_COMMITTEE_SIZE>,
+    context: &Context,
+) -> Result<
+    BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    Error,
+> {
+    let mut block = BeaconBlock {
+        slot: state.slot,
+        proposer_index: get_beacon_proposer_index(state, context)?,
+        parent_root: state.latest_block_header.hash_tree_root()?,
+        ..Default::default()
+    };
+    block.body.randao_reveal = randao_reveal;
+    block.body.eth1_data = eth1_data;
+    block.body.graffiti = graffiti;
+
+    // NOTE: operations are screened against a scratch copy of the state
+    // in the same order `process_block` applies them so that the
+    // effects of earlier operations are visible to later ones
+    let mut scratch_state = state.clone();
+    process_block_header(&mut scratch_state, &mut block, context)?;
+    process_randao(&mut scratch_state, &block.body, context)?;
+    process_eth1_data(&mut scratch_state, &block.body, context);
+
+    let BlockOperations {
+        proposer_slashings,
+        attester_slashings,
+        attestations,
+        deposits,
+        voluntary_exits,
+        sync_aggregate,
+    } = operations;
+
+    for mut proposer_slashing in proposer_slashings {
+        if block.body.proposer_slashings.len() == context.max_proposer_slashings {
+            break;
+        }
+        if process_proposer_slashing(&mut scratch_state, &mut proposer_slashing, context).is_ok() {
+            block.body.proposer_slashings.push(proposer_slashing);
+        }
+    }
+
+    for mut attester_slashing in attester_slashings {
+        if block.body.attester_slashings.len() == context.max_attester_slashings {
+            break;
+        }
+        if process_attester_slashing(&mut scratch_state, &mut attester_slashing, context).is_ok() {
+            block.body.attester_slashings.push(attester_slashing);
+        }
+    }
+
+    for attestation in attestations {
+        if block.body.attestations.len() == context.max_attestations {
+            break;
+        }
+        if process_attestation(&mut scratch_state, &attestation, context).is_ok() {
+            block.body.attestations.push(attestation);
+        }
+    }
+
+    let pending_deposit_count =
+        scratch_state.eth1_data.deposit_count - scratch_state.eth1_deposit_index;
+    let deposit_count = usize::min(context.max_deposits, pending_deposit_count as usize);
+    for mut deposit in deposits.into_iter().take(deposit_count) {
+        if process_deposit(&mut scratch_state, &mut deposit, context).is_err() {
+            break;
+        }
+        block.body.deposits.push(deposit);
+    }
+
+    for mut voluntary_exit in voluntary_exits {
+        if block.body.voluntary_exits.len() == context.max_voluntary_exits {
+            break;
+        }
+        if process_voluntary_exit(&mut scratch_state, &mut voluntary_exit, context).is_ok() {
+            block.body.voluntary_exits.push(voluntary_exit);
+        }
+    }
+
+    if process_sync_aggregate(&mut scratch_state, &sync_aggregate, context).is_ok() {
+        block.body.sync_aggregate = sync_aggregate;
+    }
+
+    process_block(state, &mut block, context)?;
+    block.state_root = state.hash_tree_root()?;
+
+    Ok(block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::altair::helpers::get_domain;
+    use crate::altair::{get_sync_committee_message, mainnet, state_transition};
+    use crate::crypto::aggregate;
+    use crate::interop::altair::create_mainnet_genesis_state;
+    use crate::interop::interop_secret_key;
+    use crate::operation_pool::OperationPool;
+    use crate::phase0::sign_with_domain;
+    use crate::primitives::DomainType;
+    use crate::signer::LocalSigner;
+    use crate::state_transition::Validation;
+
+    #[tokio::test]
+    async fn test_produced_block_passes_state_transition() {
+        let context = Context::for_mainnet();
+        let genesis_state = create_mainnet_genesis_state(64, &context);
+        let slot = 1;
+
+        let mut state = genesis_state.clone();
+        process_slots(&mut state, slot, &context).unwrap();
+        let proposer_index = get_beacon_proposer_index(&state, &context).unwrap();
+        let signing_key = interop_secret_key(proposer_index).unwrap();
+        let signer = LocalSigner::new([signing_key.clone()]);
+
+        // the first sync subcommittee signs the parent block
+        let parent_root = state.latest_block_header.hash_tree_root().unwrap();
+        let mut aggregation_bits = Bitvector::default();
+        let mut signatures = vec![];
+        let public_keys = genesis_state.current_sync_committee.public_keys.iter();
+        for (i, public_key) in public_keys
+            .take(mainnet::SYNC_SUBCOMMITTEE_SIZE)
+            .enumerate()
+        {
+            let validator_index = genesis_state
+                .validators
+                .iter()
+                .position(|validator| validator.public_key == *public_key)
+                .unwrap();
+            let message = get_sync_committee_message(
+                &genesis_state,
+                parent_root,
+                validator_index,
+                &interop_secret_key(validator_index).unwrap(),
+                &context,
+            )
+            .unwrap();
+            aggregation_bits.set(i, true);
+            signatures.push(message.signature);
+        }
+        let mut pool = OperationPool::<
+            { mainnet::MAX_VALIDATORS_PER_COMMITTEE },
+            { mainnet::SYNC_SUBCOMMITTEE_SIZE },
+        >::default();
+        pool.insert_sync_contribution(mainnet::SyncCommitteeContribution {
+            slot: genesis_state.slot,
+            beacon_block_root: parent_root,
+            subcommittee_index: 0,
+            aggregation_bits,
+            signature: aggregate(&signatures).unwrap(),
+        })
+        .unwrap();
+
+        let operations = mainnet::BlockOperations {
+            sync_aggregate: pool
+                .get_sync_aggregate(genesis_state.slot, parent_root)
+                .unwrap(),
+            ..Default::default()
+        };
+        let mut block: mainnet::BeaconBlock = produce_block(
+            &genesis_state,
+            slot,
+            &signer,
+            &[],
+            Bytes32::default(),
+            operations,
+            &context,
+        )
+        .await
+        .unwrap();
+        let participant_count = (0..mainnet::SYNC_COMMITTEE_SIZE)
+            .filter(|&i| block.body.sync_aggregate.sync_committee_bits[i])
+            .count();
+        assert_eq!(participant_count, mainnet::SYNC_SUBCOMMITTEE_SIZE);
+
+        let domain = get_domain(&state, DomainType::BeaconProposer, None, &context).unwrap();
+        let signature = sign_with_domain(&mut block, &signing_key, domain).unwrap();
+        let mut signed_block = mainnet::SignedBeaconBlock {
+            message: block,
+            signature,
+        };
+        let mut post_state = genesis_state.clone();
+        state_transition(
+            &mut post_state,
+            &mut signed_block,
+            Validation::Enabled,
+            &context,
+        )
+        .unwrap();
+        assert_eq!(
+            post_state.hash_tree_root().unwrap(),
+            signed_block.message.state_root
+        );
+
+        // an aggregate with an invalid signature is left out
+        let mut operations = mainnet::BlockOperations::default();
+        operations.sync_aggregate.sync_committee_bits.set(0, true);
+        let block: mainnet::BeaconBlock = produce_block(
+            &genesis_state,
+            slot,
+            &signer,
+            &[],
+            Bytes32::default(),
+            operations,
+            &context,
+        )
+        .await
+        .unwrap();
+        assert!((0..mainnet::SYNC_COMMITTEE_SIZE)
+            .all(|i| !block.body.sync_aggregate.sync_committee_bits[i]));
+    }
+}
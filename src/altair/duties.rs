@@ -0,0 +1,222 @@
+use crate::altair as spec;
+
+use crate::phase0::compute_epoch_at_slot;
+use crate::primitives::{BlsPublicKey, Epoch, ValidatorIndex};
+use crate::state_transition::{Context, Error};
+use spec::{get_sync_subcommittee_size, BeaconState, SYNC_COMMITTEE_SUBNET_COUNT};
+use ssz_rs::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SyncCommitteeDuty {
+    #[serde(rename = "pubkey")]
+    pub public_key: BlsPublicKey,
+    #[serde(with = "crate::serde::as_string")]
+    pub validator_index: ValidatorIndex,
+    #[serde(with = "crate::serde::collection_over_string")]
+    pub validator_sync_committee_indices: Vec<usize>,
+}
+
+pub fn compute_sync_committee_period(epoch: Epoch, context: &Context) -> u64 {
+    epoch / context.epochs_per_sync_committee_period
+}
+
+/// Return the sync committee duties in the `epoch` for each of the `indices`
+/// in the sync committee for that epoch.
+///
+/// The `epoch` must be in the sync committee period of `state` or the next one.
+pub fn get_sync_committee_duties<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    epoch: Epoch,
+    indices: &[ValidatorIndex],
+    context: &Context,
+) -> Result<Vec<SyncCommitteeDuty>, Error> {
+    let current_epoch = compute_epoch_at_slot(state.slot, context);
+    let current_period = compute_sync_committee_period(current_epoch, context);
+    let period = compute_sync_committee_period(epoch, context);
+    let sync_committee = if period == current_period {
+        &state.current_sync_committee
+    } else if period == current_period + 1 {
+        &state.next_sync_committee
+    } else {
+        let lower_bound = current_period * context.epochs_per_sync_committee_period;
+        return Err(Error::EpochOutOfRange {
+            requested: epoch,
+            lower_bound,
+            upper_bound: lower_bound + 2 * context.epochs_per_sync_committee_period - 1,
+        });
+    };
+
+    let mut duties = vec![];
+    for &validator_index in indices {
+        let validator = state
+            .validators
+            .get(validator_index)
+            .ok_or(Error::OutOfBounds {
+                requested: validator_index,
+                bound: state.validators.len(),
+            })?;
+        let validator_sync_committee_indices = sync_committee
+            .public_keys
+            .iter()
+            .enumerate()
+            .filter(|(_, public_key)| *public_key == &validator.public_key)
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        if !validator_sync_committee_indices.is_empty() {
+            duties.push(SyncCommitteeDuty {
+                public_key: validator.public_key.clone(),
+                validator_index,
+                validator_sync_committee_indices,
+            });
+        }
+    }
+    Ok(duties)
+}
+
+/// Return the sync committee subnets the validator at `validator_index` must subscribe to
+/// as of the slot following `state.slot`, in the form advertised by the `syncnets` ENR field.
+pub fn compute_subnets_for_sync_committee<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    validator_index: ValidatorIndex,
+    context: &Context,
+) -> Result<Bitvector<SYNC_COMMITTEE_SUBNET_COUNT>, Error> {
+    let current_epoch = compute_epoch_at_slot(state.slot, context);
+    let next_slot_epoch = compute_epoch_at_slot(state.slot + 1, context);
+    let sync_committee = if compute_sync_committee_period(current_epoch, context)
+        == compute_sync_committee_period(next_slot_epoch, context)
+    {
+        &state.current_sync_committee
+    } else {
+        &state.next_sync_committee
+    };
+
+    let target_public_key = &state
+        .validators
+        .get(validator_index)
+        .ok_or(Error::OutOfBounds {
+            requested: validator_index,
+            bound: state.validators.len(),
+        })?
+        .public_key;
+    let sync_subcommittee_size = get_sync_subcommittee_size(SYNC_COMMITTEE_SIZE);
+    let mut subnets = Bitvector::default();
+    for (index, public_key) in sync_committee.public_keys.iter().enumerate() {
+        if public_key == target_public_key {
+            subnets.set(index / sync_subcommittee_size, true);
+        }
+    }
+    Ok(subnets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::altair::get_next_sync_committee_indices;
+    use crate::interop::altair::create_mainnet_genesis_state;
+
+    #[test]
+    fn test_sync_committee_duties_match_committee_indices() {
+        let context = Context::for_mainnet();
+        let validator_count = 64;
+        let state = create_mainnet_genesis_state(validator_count, &context);
+        // NOTE: the genesis sync committees are computed from the genesis state
+        let sync_committee_indices = get_next_sync_committee_indices(&state, &context).unwrap();
+        let indices = (0..validator_count).collect::<Vec<_>>();
+
+        for epoch in [0, context.epochs_per_sync_committee_period] {
+            let duties = get_sync_committee_duties(&state, epoch, &indices, &context).unwrap();
+            let mut positions = vec![];
+            for duty in &duties {
+                assert_eq!(
+                    duty.public_key,
+                    state.validators[duty.validator_index].public_key
+                );
+                for &position in &duty.validator_sync_committee_indices {
+                    assert_eq!(sync_committee_indices[position], duty.validator_index);
+                    positions.push(position);
+                }
+            }
+            // every position in the committee is assigned to exactly one duty
+            positions.sort();
+            assert_eq!(
+                positions,
+                (0..sync_committee_indices.len()).collect::<Vec<_>>()
+            );
+        }
+
+        let epoch = 2 * context.epochs_per_sync_committee_period;
+        assert!(get_sync_committee_duties(&state, epoch, &indices, &context).is_err());
+    }
+
+    #[test]
+    fn test_compute_subnets_for_sync_committee() {
+        let context = Context::for_mainnet();
+        let validator_count = 64;
+        let state = create_mainnet_genesis_state(validator_count, &context);
+        let sync_committee_indices = get_next_sync_committee_indices(&state, &context).unwrap();
+        let sync_subcommittee_size =
+            spec::mainnet::SYNC_COMMITTEE_SIZE / SYNC_COMMITTEE_SUBNET_COUNT;
+
+        for validator_index in 0..validator_count {
+            let subnets =
+                compute_subnets_for_sync_committee(&state, validator_index, &context).unwrap();
+            let positions = sync_committee_indices
+                .iter()
+                .enumerate()
+                .filter(|(_, &index)| index == validator_index)
+                .map(|(position, _)| position)
+                .collect::<Vec<_>>();
+            for subnet in 0..SYNC_COMMITTEE_SUBNET_COUNT {
+                let expected = positions
+                    .iter()
+                    .any(|position| position / sync_subcommittee_size == subnet);
+                assert_eq!(subnets[subnet], expected);
+            }
+            // a validator with several positions in the same subcommittee has one subnet for them
+            let subnet_count = subnets.iter().filter(|bit| **bit).count();
+            assert!(subnet_count <= positions.len());
+            if positions.len() > SYNC_COMMITTEE_SUBNET_COUNT {
+                assert!(subnet_count < positions.len());
+            }
+        }
+
+        assert!(compute_subnets_for_sync_committee(&state, validator_count, &context).is_err());
+    }
+}
@@ -0,0 +1,812 @@
+use crate::altair as spec;
+
+use crate::phase0::{
+    apply_slashings, eligible_validator_indices, justify_and_finalize, total_balance,
+    update_effective_balances, update_historical_roots, update_registry, Checkpoint, Validator,
+};
+use crate::primitives::{Epoch, Gwei, GENESIS_EPOCH};
+use crate::state_transition::{Context, Error};
+use spec::helpers::{
+    decrease_balance, get_block_root, get_current_epoch, get_previous_epoch,
+    get_total_active_balance, increase_balance,
+};
+use spec::{
+    base_reward_per_increment, get_next_sync_committee, get_unslashed_participating_indices,
+    unslashed_participating_indices, BeaconState, ParticipationFlags, PARTICIPATION_FLAG_WEIGHTS,
+    TIMELY_HEAD_FLAG_INDEX, TIMELY_TARGET_FLAG_INDEX, WEIGHT_DENOMINATOR,
+};
+use ssz_rs::prelude::*;
+use std::mem;
+
+pub fn process_justification_and_finalization<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> Result<(), Error> {
+    // Initial FFG checkpoint values have a `0x00` stub for `root`.
+    // Skip FFG updates in the first two epochs to avoid corner cases that might result in modifying this stub.
+    let current_epoch = get_current_epoch(state, context);
+    if current_epoch <= GENESIS_EPOCH + 1 {
+        return Ok(());
+    }
+    let previous_epoch = get_previous_epoch(state, context);
+    let previous_indices = get_unslashed_participating_indices(
+        state,
+        TIMELY_TARGET_FLAG_INDEX,
+        previous_epoch,
+        context,
+    )?;
+    let current_indices = get_unslashed_participating_indices(
+        state,
+        TIMELY_TARGET_FLAG_INDEX,
+        current_epoch,
+        context,
+    )?;
+    let total_active_balance = get_total_active_balance(state, context)?;
+    let previous_target_balance = total_balance(&state.validators, &previous_indices, context)?;
+    let current_target_balance = total_balance(&state.validators, &current_indices, context)?;
+    weigh_justification_and_finalization(
+        state,
+        total_active_balance,
+        previous_target_balance,
+        current_target_balance,
+        context,
+    )
+}
+
+pub fn weigh_justification_and_finalization<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    total_active_balance: Gwei,
+    previous_epoch_target_balance: Gwei,
+    current_epoch_target_balance: Gwei,
+    context: &Context,
+) -> Result<(), Error> {
+    let previous_epoch = get_previous_epoch(state, context);
+    let current_epoch = get_current_epoch(state, context);
+    let previous_epoch_target = Checkpoint {
+        epoch: previous_epoch,
+        root: *get_block_root(state, previous_epoch, context)?,
+    };
+    let current_epoch_target = Checkpoint {
+        epoch: current_epoch,
+        root: *get_block_root(state, current_epoch, context)?,
+    };
+    justify_and_finalize(
+        &mut state.justification_bits,
+        &mut state.previous_justified_checkpoint,
+        &mut state.current_justified_checkpoint,
+        &mut state.finalized_checkpoint,
+        total_active_balance,
+        (previous_epoch_target, previous_epoch_target_balance),
+        (current_epoch_target, current_epoch_target_balance),
+    );
+    Ok(())
+}
+
+pub fn get_finality_delay<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> Epoch {
+    get_previous_epoch(state, context) - state.finalized_checkpoint.epoch
+}
+
+pub fn is_in_inactivity_leak<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> bool {
+    get_finality_delay(state, context) > context.min_epochs_to_inactivity_penalty
+}
+
+pub fn process_inactivity_updates<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) {
+    // Skip the genesis epoch as score updates are based on the previous epoch participation
+    if get_current_epoch(state, context) == GENESIS_EPOCH {
+        return;
+    }
+    let previous_epoch = get_previous_epoch(state, context);
+    let is_in_inactivity_leak = is_in_inactivity_leak(state, context);
+    update_inactivity_scores(
+        &mut state.inactivity_scores,
+        &state.validators,
+        &state.previous_epoch_participation,
+        previous_epoch,
+        is_in_inactivity_leak,
+        context,
+    );
+}
+
+// NOTE: the functions below take only the fields of the `BeaconState` they use, so that
+// later forks share them with their own `BeaconState`
+pub(crate) fn update_inactivity_scores<const VALIDATOR_REGISTRY_LIMIT: usize>(
+    inactivity_scores: &mut List<u64, VALIDATOR_REGISTRY_LIMIT>,
+    validators: &[Validator],
+    previous_epoch_participation: &[ParticipationFlags],
+    previous_epoch: Epoch,
+    is_in_inactivity_leak: bool,
+    context: &Context,
+) {
+    let matching_target_indices = unslashed_participating_indices(
+        validators,
+        previous_epoch_participation,
+        TIMELY_TARGET_FLAG_INDEX,
+        previous_epoch,
+    );
+    for index in eligible_validator_indices(validators, previous_epoch) {
+        // Increase the inactivity score of inactive validators
+        if matching_target_indices.contains(&index) {
+            inactivity_scores[index] -= u64::min(1, inactivity_scores[index]);
+        } else {
+            inactivity_scores[index] += context.inactivity_score_bias;
+        }
+        // Decrease the inactivity score of all eligible validators during a leak-free epoch
+        if !is_in_inactivity_leak {
+            inactivity_scores[index] -= u64::min(
+                context.inactivity_score_recovery_rate,
+                inactivity_scores[index],
+            );
+        }
+    }
+}
+
+pub fn get_flag_index_deltas<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    flag_index: usize,
+    context: &Context,
+) -> Result<(Vec<Gwei>, Vec<Gwei>), Error> {
+    let previous_epoch = get_previous_epoch(state, context);
+    let total_active_balance = get_total_active_balance(state, context)?;
+    flag_index_deltas(
+        &state.validators,
+        &state.previous_epoch_participation,
+        flag_index,
+        previous_epoch,
+        total_active_balance,
+        is_in_inactivity_leak(state, context),
+        context,
+    )
+}
+
+pub(crate) fn flag_index_deltas(
+    validators: &[Validator],
+    previous_epoch_participation: &[ParticipationFlags],
+    flag_index: usize,
+    previous_epoch: Epoch,
+    total_active_balance: Gwei,
+    is_in_inactivity_leak: bool,
+    context: &Context,
+) -> Result<(Vec<Gwei>, Vec<Gwei>), Error> {
+    let validator_count = validators.len();
+    let mut rewards = vec![0; validator_count];
+    let mut penalties = vec![0; validator_count];
+    let unslashed_participating_indices = unslashed_participating_indices(
+        validators,
+        previous_epoch_participation,
+        flag_index,
+        previous_epoch,
+    );
+    let weight = PARTICIPATION_FLAG_WEIGHTS[flag_index];
+    let increment = context.effective_balance_increment;
+    let unslashed_participating_balance =
+        total_balance(validators, &unslashed_participating_indices, context)?;
+    let unslashed_participating_increments = unslashed_participating_balance / increment;
+    let active_increments = total_active_balance / increment;
+    let base_reward_per_increment = base_reward_per_increment(total_active_balance, context);
+    for index in eligible_validator_indices(validators, previous_epoch) {
+        let base_reward =
+            validators[index].effective_balance / increment * base_reward_per_increment;
+        if unslashed_participating_indices.contains(&index) {
+            if !is_in_inactivity_leak {
+                let reward_numerator = base_reward * weight * unslashed_participating_increments;
+                rewards[index] += reward_numerator / (active_increments * WEIGHT_DENOMINATOR);
+            }
+        } else if flag_index != TIMELY_HEAD_FLAG_INDEX {
+            penalties[index] += base_reward * weight / WEIGHT_DENOMINATOR;
+        }
+    }
+    Ok((rewards, penalties))
+}
+
+pub fn get_inactivity_penalty_deltas<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> Vec<Gwei> {
+    let previous_epoch = get_previous_epoch(state, context);
+    inactivity_penalty_deltas(
+        &state.validators,
+        &state.previous_epoch_participation,
+        &state.inactivity_scores,
+        previous_epoch,
+        context.inactivity_penalty_quotient_altair,
+        context,
+    )
+}
+
+/// Return the inactivity penalties for each validator; each fork passes its own
+/// `inactivity_penalty_quotient`.
+pub(crate) fn inactivity_penalty_deltas(
+    validators: &[Validator],
+    previous_epoch_participation: &[ParticipationFlags],
+    inactivity_scores: &[u64],
+    previous_epoch: Epoch,
+    inactivity_penalty_quotient: u64,
+    context: &Context,
+) -> Vec<Gwei> {
+    let mut penalties = vec![0; validators.len()];
+    let matching_target_indices = unslashed_participating_indices(
+        validators,
+        previous_epoch_participation,
+        TIMELY_TARGET_FLAG_INDEX,
+        previous_epoch,
+    );
+    for index in eligible_validator_indices(validators, previous_epoch) {
+        if !matching_target_indices.contains(&index) {
+            let penalty_numerator = validators[index].effective_balance * inactivity_scores[index];
+            let penalty_denominator = context.inactivity_score_bias * inactivity_penalty_quotient;
+            penalties[index] += penalty_numerator / penalty_denominator;
+        }
+    }
+    // No rewards associated with inactivity penalties
+    // Note: a slight deviation from the spec -- `rewards` is not provided in the return since it's unused
+    penalties
+}
+
+pub fn process_rewards_and_penalties<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> Result<(), Error> {
+    // No rewards are applied at the end of `GENESIS_EPOCH` because rewards are for work done in the previous epoch
+    if get_current_epoch(state, context) == GENESIS_EPOCH {
+        return Ok(());
+    }
+
+    let mut deltas = Vec::with_capacity(PARTICIPATION_FLAG_WEIGHTS.len());
+    for flag_index in 0..PARTICIPATION_FLAG_WEIGHTS.len() {
+        deltas.push(get_flag_index_deltas(state, flag_index, context)?);
+    }
+    let inactivity_penalties = get_inactivity_penalty_deltas(state, context);
+    for (rewards, penalties) in deltas {
+        for index in 0..state.validators.len() {
+            increase_balance(state, index, rewards[index]);
+            decrease_balance(state, index, penalties[index]);
+        }
+    }
+    for (index, penalty) in inactivity_penalties.into_iter().enumerate() {
+        decrease_balance(state, index, penalty);
+    }
+    Ok(())
+}
+
+pub fn process_registry_updates<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) {
+    let current_epoch = get_current_epoch(state, context);
+    update_registry(
+        &mut state.validators,
+        state.finalized_checkpoint.epoch,
+        current_epoch,
+        context,
+    );
+}
+
+pub fn process_slashings<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> Result<(), Error> {
+    let epoch = get_current_epoch(state, context);
+    apply_slashings(
+        &state.validators,
+        &mut state.balances,
+        &state.slashings,
+        epoch,
+        context.proportional_slashing_multiplier_altair,
+        context,
+    )
+}
+
+pub fn process_eth1_data_reset<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) {
+    let next_epoch = get_current_epoch(state, context) + 1;
+
+    if next_epoch % context.epochs_per_eth1_voting_period == 0 {
+        state.eth1_data_votes.clear();
+    }
+}
+
+pub fn process_effective_balance_updates<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) {
+    update_effective_balances(&mut state.validators, &state.balances, context);
+}
+
+pub fn process_slashings_reset<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) {
+    let next_epoch = get_current_epoch(state, context) + 1;
+
+    let slashings_index = next_epoch % context.epochs_per_slashings_vector;
+    state.slashings[slashings_index as usize] = 0;
+}
+
+pub fn process_randao_mixes_reset<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) {
+    let current_epoch = get_current_epoch(state, context);
+    let next_epoch = current_epoch + 1;
+    let mix_index = current_epoch % context.epochs_per_historical_vector;
+    let next_mix_index = next_epoch % context.epochs_per_historical_vector;
+    state.randao_mixes[next_mix_index as usize] = state.randao_mixes[mix_index as usize].clone();
+}
+
+pub fn process_historical_roots_update<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> Result<(), Error> {
+    let current_epoch = get_current_epoch(state, context);
+    update_historical_roots(
+        &mut state.historical_roots,
+        &mut state.block_roots,
+        &mut state.state_roots,
+        current_epoch,
+        context,
+    )
+}
+
+pub fn process_participation_flag_updates<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+) {
+    let current_epoch_participation = mem::take(&mut state.current_epoch_participation);
+    state.previous_epoch_participation = current_epoch_participation;
+    for _ in 0..state.validators.len() {
+        state
+            .current_epoch_participation
+            .push(ParticipationFlags::default());
+    }
+}
+
+pub fn process_sync_committee_updates<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> Result<(), Error> {
+    let next_epoch = get_current_epoch(state, context) + 1;
+    if next_epoch % context.epochs_per_sync_committee_period == 0 {
+        let next_sync_committee = get_next_sync_committee(state, context)?;
+        state.current_sync_committee =
+            mem::replace(&mut state.next_sync_committee, next_sync_committee);
+    }
+    Ok(())
+}
+
+pub fn process_epoch<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> Result<(), Error> {
+    process_justification_and_finalization(state, context)?;
+    process_inactivity_updates(state, context);
+    process_rewards_and_penalties(state, context)?;
+    process_registry_updates(state, context);
+    process_slashings(state, context)?;
+    process_eth1_data_reset(state, context);
+    process_effective_balance_updates(state, context);
+    process_slashings_reset(state, context);
+    process_randao_mixes_reset(state, context);
+    process_historical_roots_update(state, context)?;
+    process_participation_flag_updates(state);
+    process_sync_committee_updates(state, context)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::altair::{add_flag, TIMELY_SOURCE_FLAG_INDEX};
+    use crate::interop::altair::create_mainnet_genesis_state;
+
+    const ALL_FLAGS: [usize; 3] = [
+        TIMELY_SOURCE_FLAG_INDEX,
+        TIMELY_TARGET_FLAG_INDEX,
+        TIMELY_HEAD_FLAG_INDEX,
+    ];
+
+    #[test]
+    fn test_process_participation_flag_updates() {
+        let context = Context::for_mainnet();
+        let mut state = create_mainnet_genesis_state(64, &context);
+        for flags in state.current_epoch_participation.iter_mut() {
+            *flags = add_flag(*flags, TIMELY_TARGET_FLAG_INDEX);
+        }
+
+        process_participation_flag_updates(&mut state);
+
+        assert_eq!(state.previous_epoch_participation.len(), 64);
+        assert_eq!(state.current_epoch_participation.len(), 64);
+        assert!(state
+            .previous_epoch_participation
+            .iter()
+            .all(|flags| *flags == add_flag(0, TIMELY_TARGET_FLAG_INDEX)));
+        assert!(state
+            .current_epoch_participation
+            .iter()
+            .all(|flags| *flags == 0));
+    }
+
+    #[test]
+    fn test_process_inactivity_updates() {
+        let context = Context::for_mainnet();
+        let mut state = create_mainnet_genesis_state(64, &context);
+        for score in state.inactivity_scores.iter_mut() {
+            *score = 100;
+        }
+        state.previous_epoch_participation[0] = add_flag(0, TIMELY_TARGET_FLAG_INDEX);
+
+        let mut leak_free_state = state.clone();
+        leak_free_state.slot = context.slots_per_epoch;
+        assert!(!is_in_inactivity_leak(&leak_free_state, &context));
+        process_inactivity_updates(&mut leak_free_state, &context);
+        // 100 - 1 - 16 for the target participant, 100 + 4 - 16 otherwise
+        assert_eq!(leak_free_state.inactivity_scores[0], 83);
+        assert_eq!(leak_free_state.inactivity_scores[1], 88);
+
+        let mut leaking_state = state;
+        leaking_state.slot = 6 * context.slots_per_epoch;
+        assert!(is_in_inactivity_leak(&leaking_state, &context));
+        process_inactivity_updates(&mut leaking_state, &context);
+        assert_eq!(leaking_state.inactivity_scores[0], 99);
+        assert_eq!(leaking_state.inactivity_scores[1], 104);
+    }
+
+    #[test]
+    fn test_get_flag_index_deltas() {
+        let context = Context::for_mainnet();
+        let mut state = create_mainnet_genesis_state(64, &context);
+        state.slot = context.slots_per_epoch;
+        for flags in state.previous_epoch_participation.iter_mut().skip(1) {
+            *flags = ALL_FLAGS
+                .iter()
+                .fold(0, |flags, &index| add_flag(flags, index));
+        }
+
+        for flag_index in ALL_FLAGS {
+            let (rewards, penalties) = get_flag_index_deltas(&state, flag_index, &context).unwrap();
+            assert_eq!(rewards[0], 0);
+            assert!(rewards[1..].iter().all(|reward| *reward > 0));
+            assert!(penalties[1..].iter().all(|penalty| *penalty == 0));
+            // missing the head vote is not penalized
+            if flag_index == TIMELY_HEAD_FLAG_INDEX {
+                assert_eq!(penalties[0], 0);
+            } else {
+                assert!(penalties[0] > 0);
+            }
+        }
+    }
+}
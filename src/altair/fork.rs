@@ -0,0 +1,183 @@
+use crate::altair as spec;
+use crate::phase0;
+
+use crate::phase0::{attesting_indices, compute_epoch_at_slot, Fork, PendingAttestation};
+use crate::state_transition::{Context, Error};
+use spec::{
+    add_flag, get_attestation_participation_flag_indices, get_next_sync_committee, BeaconState,
+    ParticipationFlags,
+};
+use ssz_rs::prelude::*;
+
+pub fn translate_participation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    pending_attestations: &[PendingAttestation<MAX_VALIDATORS_PER_COMMITTEE>],
+    context: &Context,
+) -> Result<(), Error> {
+    for attestation in pending_attestations {
+        let data = &attestation.data;
+        let inclusion_delay = attestation.inclusion_delay;
+        // Translate attestation inclusion info to flag indices
+        let participation_flag_indices =
+            get_attestation_participation_flag_indices(state, data, inclusion_delay, context)?;
+
+        // Apply flags to all attesting validators
+        let attesting_indices = attesting_indices(
+            &state.validators,
+            &state.randao_mixes,
+            data,
+            &attestation.aggregation_bits,
+            context,
+        )?;
+        let epoch_participation = &mut state.previous_epoch_participation;
+        for index in attesting_indices {
+            for &flag_index in &participation_flag_indices {
+                epoch_participation[index] = add_flag(epoch_participation[index], flag_index);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn upgrade_to_altair<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &phase0::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    context: &Context,
+) -> Result<
+    BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    Error,
+> {
+    let epoch = compute_epoch_at_slot(state.slot, context);
+    let mut epoch_participation = List::default();
+    let mut inactivity_scores = List::default();
+    for _ in 0..state.validators.len() {
+        epoch_participation.push(ParticipationFlags::default());
+        inactivity_scores.push(0);
+    }
+    let mut post = BeaconState {
+        genesis_time: state.genesis_time,
+        genesis_validators_root: state.genesis_validators_root,
+        slot: state.slot,
+        fork: Fork {
+            previous_version: state.fork.current_version,
+            current_version: context.altair_fork_version,
+            epoch,
+        },
+        latest_block_header: state.latest_block_header.clone(),
+        block_roots: state.block_roots.clone(),
+        state_roots: state.state_roots.clone(),
+        historical_roots: state.historical_roots.clone(),
+        eth1_data: state.eth1_data.clone(),
+        eth1_data_votes: state.eth1_data_votes.clone(),
+        eth1_deposit_index: state.eth1_deposit_index,
+        validators: state.validators.clone(),
+        balances: state.balances.clone(),
+        randao_mixes: state.randao_mixes.clone(),
+        slashings: state.slashings.clone(),
+        previous_epoch_participation: epoch_participation.clone(),
+        current_epoch_participation: epoch_participation,
+        justification_bits: state.justification_bits.clone(),
+        previous_justified_checkpoint: state.previous_justified_checkpoint.clone(),
+        current_justified_checkpoint: state.current_justified_checkpoint.clone(),
+        finalized_checkpoint: state.finalized_checkpoint.clone(),
+        inactivity_scores,
+        current_sync_committee: Default::default(),
+        next_sync_committee: Default::default(),
+    };
+    // Fill in previous epoch participation from the pre state's pending attestations
+    translate_participation(&mut post, &state.previous_epoch_attestations, context)?;
+
+    // Fill in sync committees
+    // Note: A duplicate committee is assigned for the current and next committee at the fork boundary
+    let sync_committee = get_next_sync_committee(&post, context)?;
+    post.current_sync_committee = sync_committee.clone();
+    post.next_sync_committee = sync_committee;
+    Ok(post)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::altair::mainnet;
+    use crate::interop::phase0::create_mainnet_genesis_state;
+
+    #[test]
+    fn test_upgrade_to_altair() {
+        let context = Context::for_mainnet();
+        let mut state = create_mainnet_genesis_state(64, &context);
+        state.slot = 3 * context.slots_per_epoch + 1;
+
+        let mut post: mainnet::BeaconState = upgrade_to_altair(&state, &context).unwrap();
+
+        assert_eq!(post.slot, state.slot);
+        assert_eq!(post.fork.previous_version, state.fork.current_version);
+        assert_eq!(post.fork.current_version, context.altair_fork_version);
+        assert_eq!(post.fork.epoch, 3);
+        assert_eq!(post.balances[0], state.balances[0]);
+        let validator_count = state.validators.len();
+        assert_eq!(post.validators.len(), validator_count);
+        assert_eq!(post.previous_epoch_participation.len(), validator_count);
+        assert_eq!(post.current_epoch_participation.len(), validator_count);
+        assert_eq!(post.inactivity_scores.len(), validator_count);
+        assert!(post
+            .previous_epoch_participation
+            .iter()
+            .all(|&flags| flags == 0));
+
+        let mut expected = get_next_sync_committee(&post, &context).unwrap();
+        let expected_root = expected.hash_tree_root().unwrap();
+        assert_eq!(
+            post.current_sync_committee.hash_tree_root().unwrap(),
+            expected_root
+        );
+        assert_eq!(
+            post.next_sync_committee.hash_tree_root().unwrap(),
+            expected_root
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use crate::altair as spec;
+
+use crate::phase0::genesis::activate_genesis_validators;
+use crate::phase0::{
+    active_validator_indices, BeaconBlockHeader, Deposit, DepositData, Eth1Data, Fork,
+    DEPOSIT_CONTRACT_TREE_DEPTH,
+};
+use crate::primitives::{Hash32, GENESIS_EPOCH};
+use crate::state_transition::{Context, Error};
+use spec::{get_next_sync_committee, process_deposit, BeaconBlockBody, BeaconState};
+use ssz_rs::prelude::*;
+
+const DEPOSIT_DATA_LIST_BOUND: usize = 2usize.pow(DEPOSIT_CONTRACT_TREE_DEPTH as u32);
+
+pub fn initialize_beacon_state_from_eth1<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    eth1_block_hash: Hash32,
+    eth1_timestamp: u64,
+    deposits: &mut [Deposit],
+    context: &Context,
+) -> Result<
+    BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    Error,
+> {
+    let fork = Fork {
+        previous_version: context.altair_fork_version,
+        current_version: context.altair_fork_version,
+        epoch: GENESIS_EPOCH,
+    };
+    let eth1_data = Eth1Data {
+        block_hash: eth1_block_hash.clone(),
+        deposit_count: deposits.len() as u64,
+        ..Default::default()
+    };
+    let mut latest_block_body = BeaconBlockBody::<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+    >::default();
+    let body_root = latest_block_body.hash_tree_root()?;
+    let latest_block_header = BeaconBlockHeader {
+        body_root,
+        ..Default::default()
+    };
+    let randao_mixes = Vector::from_iter(
+        std::iter::repeat(eth1_block_hash).take(context.epochs_per_historical_vector as usize),
+    );
+    let mut state = BeaconState {
+        genesis_time: eth1_timestamp + context.genesis_delay,
+        fork,
+        eth1_data,
+        latest_block_header,
+        randao_mixes,
+        ..Default::default()
+    };
+
+    let mut leaves = List::<DepositData, DEPOSIT_DATA_LIST_BOUND>::default();
+    for deposit in deposits.iter_mut() {
+        leaves.push(deposit.data.clone());
+        state.eth1_data.deposit_root = leaves.hash_tree_root()?;
+        process_deposit(&mut state, deposit, context)?;
+    }
+
+    activate_genesis_validators(&mut state.validators, &state.balances, context);
+
+    state.genesis_validators_root = state.validators.hash_tree_root()?;
+
+    // NOTE: the same committee is assigned as the current and next committee at genesis
+    let sync_committee = get_next_sync_committee(&state, context)?;
+    state.current_sync_committee = sync_committee.clone();
+    state.next_sync_committee = sync_committee;
+
+    Ok(state)
+}
+
+pub fn is_valid_genesis_state<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> bool {
+    if state.genesis_time < context.min_genesis_time {
+        return false;
+    }
+
+    if active_validator_indices(&state.validators, GENESIS_EPOCH).len()
+        < context.min_genesis_active_validator_count
+    {
+        return false;
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{initialize_beacon_state_from_eth1, is_valid_genesis_state};
+    use crate::altair::get_next_sync_committee_indices;
+    use crate::altair::mainnet::*;
+    use crate::interop::{create_deposits, interop_secret_keys, mock_eth1_block_hash};
+    use crate::primitives::GENESIS_EPOCH;
+    use crate::state_transition::Context;
+
+    #[test]
+    fn test_initialize_beacon_state_from_eth1() {
+        let context = Context::for_mainnet();
+        let validator_count = 16;
+        let eth1_timestamp = 1_600_000_000;
+        let secret_keys = interop_secret_keys(validator_count).unwrap();
+        let mut deposits = create_deposits(&secret_keys, &context).unwrap();
+        let state = initialize_beacon_state_from_eth1::<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            MAX_PROPOSER_SLASHINGS,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+        >(
+            mock_eth1_block_hash(),
+            eth1_timestamp,
+            &mut deposits,
+            &context,
+        )
+        .unwrap();
+
+        assert_eq!(state.genesis_time, eth1_timestamp + context.genesis_delay);
+        assert_eq!(state.fork.previous_version, context.altair_fork_version);
+        assert_eq!(state.fork.current_version, context.altair_fork_version);
+        assert_eq!(state.eth1_deposit_index, validator_count as u64);
+        assert_eq!(state.validators.len(), validator_count);
+        assert_eq!(state.previous_epoch_participation.len(), validator_count);
+        assert_eq!(state.current_epoch_participation.len(), validator_count);
+        assert_eq!(state.inactivity_scores.len(), validator_count);
+        for (validator, secret_key) in state.validators.iter().zip(secret_keys.iter()) {
+            assert_eq!(validator.activation_epoch, GENESIS_EPOCH);
+            assert_eq!(validator.public_key, secret_key.public_key());
+        }
+
+        let indices = get_next_sync_committee_indices(&state, &context).unwrap();
+        let public_keys = indices
+            .iter()
+            .map(|&index| state.validators[index].public_key.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            state.current_sync_committee.public_keys.to_vec(),
+            public_keys
+        );
+        assert_eq!(
+            state.current_sync_committee.public_keys,
+            state.next_sync_committee.public_keys
+        );
+        // too few validators to launch mainnet
+        assert!(!is_valid_genesis_state(&state, &context));
+    }
+}
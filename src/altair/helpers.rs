@@ -0,0 +1,746 @@
+use crate::altair as spec;
+
+use crate::crypto::{eth_aggregate_public_keys, hash};
+use crate::phase0::{
+    active_validator_indices, beacon_committee, beacon_proposer_index, block_root_at_slot,
+    committee_count_per_slot, compute_epoch_at_slot, compute_shuffled_index,
+    compute_start_slot_at_epoch, domain, is_active_validator, seed, slash_and_penalize,
+    total_active_balance, AttestationData, Checkpoint, Validator,
+};
+use crate::primitives::{
+    Bytes32, CommitteeIndex, Domain, DomainType, Epoch, Gwei, Root, Slot, ValidatorIndex,
+    GENESIS_EPOCH,
+};
+use crate::state_transition::{
+    invalid_operation_error, Context, Error, InvalidAttestation, InvalidOperation,
+};
+use integer_sqrt::IntegerSquareRoot;
+use spec::{
+    BeaconState, ParticipationFlags, SyncCommittee, PROPOSER_WEIGHT, TIMELY_HEAD_FLAG_INDEX,
+    TIMELY_SOURCE_FLAG_INDEX, TIMELY_TARGET_FLAG_INDEX, WEIGHT_DENOMINATOR,
+};
+use ssz_rs::prelude::*;
+use std::collections::HashSet;
+
+pub(super) fn get_domain<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    domain_type: DomainType,
+    epoch: Option<Epoch>,
+    context: &Context,
+) -> Result<Domain, Error> {
+    let epoch = epoch.unwrap_or_else(|| compute_epoch_at_slot(state.slot, context));
+    domain(
+        &state.fork,
+        state.genesis_validators_root,
+        domain_type,
+        epoch,
+        context,
+    )
+}
+
+pub(super) fn get_block_root_at_slot<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    slot: Slot,
+) -> Result<&Root, Error> {
+    block_root_at_slot(&state.block_roots, state.slot, slot)
+}
+
+pub(super) fn get_current_epoch<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> Epoch {
+    compute_epoch_at_slot(state.slot, context)
+}
+
+pub(super) fn get_previous_epoch<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> Epoch {
+    let current_epoch = get_current_epoch(state, context);
+    if current_epoch == GENESIS_EPOCH {
+        GENESIS_EPOCH
+    } else {
+        current_epoch - 1
+    }
+}
+
+pub(super) fn get_block_root<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    epoch: Epoch,
+    context: &Context,
+) -> Result<&Root, Error> {
+    get_block_root_at_slot(state, compute_start_slot_at_epoch(epoch, context))
+}
+
+pub fn get_committee_count_per_slot<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    epoch: Epoch,
+    context: &Context,
+) -> usize {
+    committee_count_per_slot(&state.validators, epoch, context)
+}
+
+pub fn get_beacon_committee<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    slot: Slot,
+    index: CommitteeIndex,
+    context: &Context,
+) -> Result<Vec<ValidatorIndex>, Error> {
+    beacon_committee(&state.validators, &state.randao_mixes, slot, index, context)
+}
+
+pub fn get_beacon_proposer_index<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> Result<ValidatorIndex, Error> {
+    beacon_proposer_index(&state.validators, &state.randao_mixes, state.slot, context)
+}
+
+pub(super) fn get_total_active_balance<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> Result<Gwei, Error> {
+    let current_epoch = get_current_epoch(state, context);
+    total_active_balance(&state.validators, current_epoch, context)
+}
+
+pub(super) fn increase_balance<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    index: ValidatorIndex,
+    delta: Gwei,
+) {
+    state.balances[index] += delta;
+}
+
+pub(super) fn decrease_balance<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    index: ValidatorIndex,
+    delta: Gwei,
+) {
+    state.balances[index] = state.balances[index].saturating_sub(delta);
+}
+
+pub fn add_flag(flags: ParticipationFlags, flag_index: usize) -> ParticipationFlags {
+    let flag = 2u8.pow(flag_index as u32);
+    flags | flag
+}
+
+pub fn has_flag(flags: ParticipationFlags, flag_index: usize) -> bool {
+    let flag = 2u8.pow(flag_index as u32);
+    flags & flag == flag
+}
+
+pub fn get_base_reward_per_increment<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> Result<Gwei, Error> {
+    let total_active_balance = get_total_active_balance(state, context)?;
+    Ok(base_reward_per_increment(total_active_balance, context))
+}
+
+pub(crate) fn base_reward_per_increment(total_active_balance: Gwei, context: &Context) -> Gwei {
+    context.effective_balance_increment * context.base_reward_factor
+        / total_active_balance.integer_sqrt()
+}
+
+pub fn get_base_reward<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    index: ValidatorIndex,
+    context: &Context,
+) -> Result<Gwei, Error> {
+    let increments =
+        state.validators[index].effective_balance / context.effective_balance_increment;
+    Ok(increments * get_base_reward_per_increment(state, context)?)
+}
+
+pub fn get_unslashed_participating_indices<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    flag_index: usize,
+    epoch: Epoch,
+    context: &Context,
+) -> Result<HashSet<ValidatorIndex>, Error> {
+    let previous_epoch = get_previous_epoch(state, context);
+    let current_epoch = get_current_epoch(state, context);
+    let epoch_participation = if epoch == current_epoch {
+        &state.current_epoch_participation
+    } else if epoch == previous_epoch {
+        &state.previous_epoch_participation
+    } else {
+        return Err(Error::InvalidEpoch {
+            requested: epoch,
+            previous: previous_epoch,
+            current: current_epoch,
+        });
+    };
+    Ok(unslashed_participating_indices(
+        &state.validators,
+        epoch_participation,
+        flag_index,
+        epoch,
+    ))
+}
+
+// NOTE: the functions below take only the fields of the `BeaconState` they use, so that
+// later forks share them with their own `BeaconState`
+pub(crate) fn unslashed_participating_indices(
+    validators: &[Validator],
+    epoch_participation: &[ParticipationFlags],
+    flag_index: usize,
+    epoch: Epoch,
+) -> HashSet<ValidatorIndex> {
+    validators
+        .iter()
+        .zip(epoch_participation.iter())
+        .enumerate()
+        .filter_map(|(index, (validator, &flags))| {
+            if is_active_validator(validator, epoch)
+                && has_flag(flags, flag_index)
+                && !validator.slashed
+            {
+                Some(index)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+pub fn get_attestation_participation_flag_indices<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    data: &AttestationData,
+    inclusion_delay: u64,
+    context: &Context,
+) -> Result<Vec<usize>, Error> {
+    let justified_checkpoint = if data.target.epoch == get_current_epoch(state, context) {
+        &state.current_justified_checkpoint
+    } else {
+        &state.previous_justified_checkpoint
+    };
+    participation_flag_indices(
+        &state.block_roots,
+        state.slot,
+        justified_checkpoint,
+        data,
+        inclusion_delay,
+        context,
+    )
+}
+
+/// Return the participation flags earned by an attestation with `data` included
+/// `inclusion_delay` slots later, given the `justified_checkpoint` matching its target epoch.
+pub(crate) fn participation_flag_indices(
+    block_roots: &[Root],
+    slot: Slot,
+    justified_checkpoint: &Checkpoint,
+    data: &AttestationData,
+    inclusion_delay: u64,
+    context: &Context,
+) -> Result<Vec<usize>, Error> {
+    let is_matching_source = data.source == *justified_checkpoint;
+    if !is_matching_source {
+        return Err(invalid_operation_error(InvalidOperation::Attestation(
+            InvalidAttestation::InvalidSource {
+                expected: justified_checkpoint.clone(),
+                source_checkpoint: data.source.clone(),
+                current: compute_epoch_at_slot(slot, context),
+            },
+        )));
+    }
+    let target_slot = compute_start_slot_at_epoch(data.target.epoch, context);
+    let is_matching_target =
+        data.target.root == *block_root_at_slot(block_roots, slot, target_slot)?;
+    let is_matching_head = is_matching_target
+        && data.beacon_block_root == *block_root_at_slot(block_roots, slot, data.slot)?;
+
+    let mut participation_flag_indices = Vec::new();
+    if inclusion_delay <= context.slots_per_epoch.integer_sqrt() {
+        participation_flag_indices.push(TIMELY_SOURCE_FLAG_INDEX);
+    }
+    if is_matching_target && inclusion_delay <= context.slots_per_epoch {
+        participation_flag_indices.push(TIMELY_TARGET_FLAG_INDEX);
+    }
+    if is_matching_head && inclusion_delay == context.min_attestation_inclusion_delay {
+        participation_flag_indices.push(TIMELY_HEAD_FLAG_INDEX);
+    }
+    Ok(participation_flag_indices)
+}
+
+pub fn slash_validator<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    slashed_index: ValidatorIndex,
+    whistleblower_index: Option<ValidatorIndex>,
+    context: &Context,
+) -> Result<(), Error> {
+    let epoch = get_current_epoch(state, context);
+    slash_and_penalize(
+        &mut state.validators,
+        &mut state.balances,
+        &mut state.slashings,
+        slashed_index,
+        epoch,
+        context.min_slashing_penalty_quotient_altair,
+        context,
+    );
+
+    let proposer_index = get_beacon_proposer_index(state, context)?;
+    let whistleblower_index = whistleblower_index.unwrap_or(proposer_index);
+    let whistleblower_reward =
+        state.validators[slashed_index].effective_balance / context.whistleblower_reward_quotient;
+    let proposer_reward = whistleblower_reward * PROPOSER_WEIGHT / WEIGHT_DENOMINATOR;
+    increase_balance(state, proposer_index, proposer_reward);
+    increase_balance(
+        state,
+        whistleblower_index,
+        whistleblower_reward - proposer_reward,
+    );
+    Ok(())
+}
+
+pub fn get_next_sync_committee_indices<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> Result<Vec<ValidatorIndex>, Error> {
+    next_sync_committee_indices(
+        &state.validators,
+        &state.randao_mixes,
+        state.slot,
+        SYNC_COMMITTEE_SIZE,
+        context,
+    )
+}
+
+pub(crate) fn next_sync_committee_indices(
+    validators: &[Validator],
+    randao_mixes: &[Bytes32],
+    slot: Slot,
+    sync_committee_size: usize,
+    context: &Context,
+) -> Result<Vec<ValidatorIndex>, Error> {
+    let epoch = compute_epoch_at_slot(slot, context) + 1;
+    let max_random_byte = u8::MAX as u64;
+    let active_validator_indices = active_validator_indices(validators, epoch);
+    let active_validator_count = active_validator_indices.len();
+    if active_validator_count == 0 {
+        return Err(Error::CollectionCannotBeEmpty);
+    }
+    let seed = seed(randao_mixes, epoch, DomainType::SyncCommittee, context);
+    let mut i = 0;
+    let mut sync_committee_indices = Vec::with_capacity(sync_committee_size);
+    let mut hash_input = [0u8; 40];
+    hash_input[..32].copy_from_slice(seed.as_ref());
+    while sync_committee_indices.len() < sync_committee_size {
+        let shuffled_index = compute_shuffled_index(
+            i % active_validator_count,
+            active_validator_count,
+            &seed,
+            context,
+        )?;
+        let candidate_index = active_validator_indices[shuffled_index];
+        hash_input[32..].copy_from_slice(&((i / 32) as u64).to_le_bytes());
+        let random_byte = hash(hash_input)[i % 32] as u64;
+        let effective_balance = validators[candidate_index].effective_balance;
+        if effective_balance * max_random_byte >= context.max_effective_balance * random_byte {
+            sync_committee_indices.push(candidate_index);
+        }
+        i += 1;
+    }
+    Ok(sync_committee_indices)
+}
+
+pub fn get_next_sync_committee<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> Result<SyncCommittee<SYNC_COMMITTEE_SIZE>, Error> {
+    next_sync_committee(&state.validators, &state.randao_mixes, state.slot, context)
+}
+
+pub(crate) fn next_sync_committee<const SYNC_COMMITTEE_SIZE: usize>(
+    validators: &[Validator],
+    randao_mixes: &[Bytes32],
+    slot: Slot,
+    context: &Context,
+) -> Result<SyncCommittee<SYNC_COMMITTEE_SIZE>, Error> {
+    let indices =
+        next_sync_committee_indices(validators, randao_mixes, slot, SYNC_COMMITTEE_SIZE, context)?;
+    let public_keys = indices
+        .into_iter()
+        .map(|index| validators[index].public_key.clone())
+        .collect::<Vec<_>>();
+    let aggregate_public_key = eth_aggregate_public_keys(&public_keys)?;
+    Ok(SyncCommittee {
+        public_keys: Vector::from_iter(public_keys),
+        aggregate_public_key,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::altair::mainnet::SYNC_COMMITTEE_SIZE;
+    use crate::interop::altair::create_mainnet_genesis_state;
+
+    #[test]
+    fn test_get_next_sync_committee_indices() {
+        let context = Context::for_mainnet();
+        let validator_count = 64;
+        let mut state = create_mainnet_genesis_state(validator_count, &context);
+
+        let indices = get_next_sync_committee_indices(&state, &context).unwrap();
+        assert_eq!(indices.len(), SYNC_COMMITTEE_SIZE);
+        assert!(indices.iter().all(|&index| index < validator_count));
+        // with fewer validators than positions some are selected more than once
+        let mut distinct_indices = indices.clone();
+        distinct_indices.sort();
+        distinct_indices.dedup();
+        assert!(distinct_indices.len() < indices.len());
+        // the genesis committee is computed from the same state
+        for (index, public_key) in indices
+            .iter()
+            .zip(state.current_sync_committee.public_keys.iter())
+        {
+            assert_eq!(&state.validators[*index].public_key, public_key);
+        }
+        assert_eq!(
+            get_next_sync_committee_indices(&state, &context).unwrap(),
+            indices
+        );
+
+        // validators inactive at the next epoch are not selected
+        state.validators[indices[0]].exit_epoch = 1;
+        let indices_after_exit = get_next_sync_committee_indices(&state, &context).unwrap();
+        assert_eq!(indices_after_exit.len(), SYNC_COMMITTEE_SIZE);
+        assert!(!indices_after_exit.contains(&indices[0]));
+
+        for validator in state.validators.iter_mut() {
+            validator.exit_epoch = 1;
+        }
+        assert!(matches!(
+            get_next_sync_committee_indices(&state, &context),
+            Err(Error::CollectionCannotBeEmpty)
+        ));
+    }
+}
@@ -1,21 +1,70 @@
 //! This module provides an implementation of the `altair` fork
 //! of the consensus spec. The primary entrypoints should be one of
 //! the "presets" like `mainnet` or `minimal`.
+mod aggregation;
 mod beacon_block;
 mod beacon_state;
+mod block_processing;
+#[cfg(feature = "signer")]
+mod block_production;
+mod duties;
+mod epoch_processing;
+mod fork;
+pub mod genesis;
+mod helpers;
 pub mod light_client;
 mod presets;
+mod signing;
+mod slot_processing;
+mod state_transition;
 mod sync;
 mod validator;
 
+pub use aggregation::*;
 pub use beacon_block::*;
 pub use beacon_state::*;
+pub use block_processing::*;
+#[cfg(feature = "signer")]
+pub use block_production::*;
+pub use duties::*;
+pub use epoch_processing::*;
+pub use fork::*;
+pub use helpers::{
+    add_flag, get_attestation_participation_flag_indices, get_base_reward,
+    get_base_reward_per_increment, get_beacon_committee, get_beacon_proposer_index,
+    get_committee_count_per_slot, get_next_sync_committee, get_next_sync_committee_indices,
+    get_unslashed_participating_indices, has_flag, slash_validator,
+};
+pub(crate) use helpers::{
+    base_reward_per_increment, next_sync_committee, next_sync_committee_indices,
+    participation_flag_indices, unslashed_participating_indices,
+};
 pub use presets::Preset;
+pub use signing::*;
+pub use slot_processing::process_slots;
+pub use state_transition::state_transition;
 pub use sync::*;
 pub use validator::*;
 
+pub const TIMELY_SOURCE_FLAG_INDEX: usize = 0;
+pub const TIMELY_TARGET_FLAG_INDEX: usize = 1;
+pub const TIMELY_HEAD_FLAG_INDEX: usize = 2;
+pub const TIMELY_SOURCE_WEIGHT: u64 = 14;
+pub const TIMELY_TARGET_WEIGHT: u64 = 26;
+pub const TIMELY_HEAD_WEIGHT: u64 = 14;
+pub const SYNC_REWARD_WEIGHT: u64 = 2;
+pub const PROPOSER_WEIGHT: u64 = 8;
+pub const WEIGHT_DENOMINATOR: u64 = 64;
+pub const PARTICIPATION_FLAG_WEIGHTS: [u64; 3] = [
+    TIMELY_SOURCE_WEIGHT,
+    TIMELY_TARGET_WEIGHT,
+    TIMELY_HEAD_WEIGHT,
+];
+
 pub mod mainnet {
     pub use super::presets::mainnet::*;
 }
 
-pub mod minimal {}
+pub mod minimal {
+    pub use super::presets::minimal::*;
+}
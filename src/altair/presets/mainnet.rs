@@ -15,7 +15,6 @@ pub const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: Epoch = 256;
 pub const MIN_SYNC_COMMITTEE_PARTICIPANTS: usize = 1;
 pub const UPDATE_TIMEOUT: usize = 8192;
 
-pub const TARGET_AGGREGATORS_PER_SYNC_SUBCOMMITTEE: usize = 16;
 pub const SYNC_SUBCOMMITTEE_SIZE: usize = altair::get_sync_subcommittee_size(SYNC_COMMITTEE_SIZE);
 
 pub const PRESET: Preset = Preset {
@@ -60,6 +59,10 @@ pub type SignedBeaconBlock = altair::SignedBeaconBlock<
     SYNC_COMMITTEE_SIZE,
 >;
 
+#[cfg(feature = "signer")]
+pub type BlockOperations =
+    altair::BlockOperations<MAX_VALIDATORS_PER_COMMITTEE, SYNC_COMMITTEE_SIZE>;
+
 pub type SyncCommitteeContribution = altair::SyncCommitteeContribution<SYNC_SUBCOMMITTEE_SIZE>;
 pub type ContributionAndProof = altair::ContributionAndProof<SYNC_SUBCOMMITTEE_SIZE>;
 pub type SignedContributionAndProof = altair::SignedContributionAndProof<SYNC_SUBCOMMITTEE_SIZE>;
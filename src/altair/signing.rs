@@ -0,0 +1,354 @@
+use crate::altair as spec;
+
+use crate::crypto::{fast_aggregate_verify, SecretKey};
+use crate::phase0::{
+    block_root_at_slot, compute_epoch_at_slot, compute_signing_root, domain, sign_with_domain,
+    verify_signed_data, Fork,
+};
+use crate::primitives::{BlsPublicKey, BlsSignature, DomainType, Root, Slot, ValidatorIndex};
+use crate::state_transition::{Context, Error};
+use spec::helpers::get_domain;
+use spec::{BeaconState, SignedBeaconBlock, SyncAggregate, SyncCommittee, SyncCommitteeMessage};
+
+/// Sign the `beacon_block_root` for the current slot of `state` as a member
+/// of the sync committee.
+pub fn get_sync_committee_message<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    mut beacon_block_root: Root,
+    validator_index: ValidatorIndex,
+    signing_key: &SecretKey,
+    context: &Context,
+) -> Result<SyncCommitteeMessage, Error> {
+    let domain = get_domain(state, DomainType::SyncCommittee, None, context)?;
+    let signature = sign_with_domain(&mut beacon_block_root, signing_key, domain)?;
+    Ok(SyncCommitteeMessage {
+        slot: state.slot,
+        beacon_block_root,
+        validator_index,
+        signature,
+    })
+}
+
+pub fn verify_sync_committee_message_signature<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    message: &mut SyncCommitteeMessage,
+    context: &Context,
+) -> Result<(), Error> {
+    let validator_index = message.validator_index;
+    let validator = state
+        .validators
+        .get(validator_index)
+        .ok_or(Error::OutOfBounds {
+            requested: validator_index,
+            bound: state.validators.len(),
+        })?;
+    let epoch = compute_epoch_at_slot(message.slot, context);
+    let domain = get_domain(state, DomainType::SyncCommittee, Some(epoch), context)?;
+    verify_signed_data(
+        &mut message.beacon_block_root,
+        &message.signature,
+        &validator.public_key,
+        domain,
+    )
+}
+
+pub fn verify_block_signature<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    signed_block: &mut SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> Result<(), Error> {
+    let proposer_index = signed_block.message.proposer_index;
+    let proposer = state
+        .validators
+        .get(proposer_index)
+        .ok_or(Error::OutOfBounds {
+            requested: proposer_index,
+            bound: state.validators.len(),
+        })?;
+    let domain = get_domain(state, DomainType::BeaconProposer, None, context)?;
+    verify_signed_data(
+        &mut signed_block.message,
+        &signed_block.signature,
+        &proposer.public_key,
+        domain,
+    )
+}
+
+/// Verify the signature of a `sync_aggregate` included in a block at the slot of `state`,
+/// i.e. over the block root of the previous slot by the current sync committee.
+pub fn verify_sync_aggregate_signature<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    sync_aggregate: &SyncAggregate<SYNC_COMMITTEE_SIZE>,
+    context: &Context,
+) -> Result<(), Error> {
+    verify_sync_aggregate(
+        &state.current_sync_committee,
+        sync_aggregate,
+        &state.fork,
+        state.genesis_validators_root,
+        &state.block_roots,
+        state.slot,
+        context,
+    )
+}
+
+pub(crate) fn verify_sync_aggregate<const SYNC_COMMITTEE_SIZE: usize>(
+    sync_committee: &SyncCommittee<SYNC_COMMITTEE_SIZE>,
+    sync_aggregate: &SyncAggregate<SYNC_COMMITTEE_SIZE>,
+    fork: &Fork,
+    genesis_validators_root: Root,
+    block_roots: &[Root],
+    slot: Slot,
+    context: &Context,
+) -> Result<(), Error> {
+    let participant_public_keys = sync_committee
+        .public_keys
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| sync_aggregate.sync_committee_bits[*i])
+        .map(|(_, public_key)| public_key)
+        .collect::<Vec<&BlsPublicKey>>();
+
+    // NOTE: an empty aggregate is valid if it carries the point at infinity
+    if participant_public_keys.is_empty() {
+        return if sync_aggregate.sync_committee_signature == BlsSignature::default() {
+            Ok(())
+        } else {
+            Err(Error::InvalidSignature)
+        };
+    }
+
+    let previous_slot = u64::max(slot, 1) - 1;
+    let epoch = compute_epoch_at_slot(previous_slot, context);
+    let domain = domain(
+        fork,
+        genesis_validators_root,
+        DomainType::SyncCommittee,
+        epoch,
+        context,
+    )?;
+    let mut block_root = *block_root_at_slot(block_roots, slot, previous_slot)?;
+    let signing_root = compute_signing_root(&mut block_root, domain)?;
+    if fast_aggregate_verify(
+        &participant_public_keys,
+        signing_root.as_bytes(),
+        &sync_aggregate.sync_committee_signature,
+    ) {
+        Ok(())
+    } else {
+        Err(Error::InvalidSignature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::altair::mainnet;
+    use crate::crypto::aggregate;
+    use crate::interop::interop_secret_key;
+
+    const VALIDATOR_COUNT: usize = 4;
+
+    // a state at slot 1 whose sync committee cycles through the interop validators
+    fn state_with_sync_committee() -> mainnet::BeaconState {
+        let mut state = mainnet::BeaconState {
+            slot: 1,
+            ..Default::default()
+        };
+        state.block_roots[0] = Root::from_bytes([1u8; 32]);
+        for index in 0..VALIDATOR_COUNT {
+            state.validators.push(crate::phase0::Validator {
+                public_key: interop_secret_key(index).unwrap().public_key(),
+                ..Default::default()
+            });
+        }
+        for index in 0..mainnet::SYNC_COMMITTEE_SIZE {
+            state.current_sync_committee.public_keys[index] =
+                interop_secret_key(index % VALIDATOR_COUNT)
+                    .unwrap()
+                    .public_key();
+        }
+        state
+    }
+
+    #[test]
+    fn test_sync_committee_message_round_trip() {
+        let context = Context::for_mainnet();
+        let state = state_with_sync_committee();
+        let beacon_block_root = Root::from_bytes([2u8; 32]);
+        let signing_key = interop_secret_key(1).unwrap();
+
+        let message =
+            get_sync_committee_message(&state, beacon_block_root, 1, &signing_key, &context)
+                .unwrap();
+        assert_eq!(message.slot, state.slot);
+        assert_eq!(message.beacon_block_root, beacon_block_root);
+        assert!(
+            verify_sync_committee_message_signature(&state, &mut message.clone(), &context).is_ok()
+        );
+
+        let mut tampered = message.clone();
+        tampered.beacon_block_root = Root::from_bytes([3u8; 32]);
+        assert!(matches!(
+            verify_sync_committee_message_signature(&state, &mut tampered, &context),
+            Err(Error::InvalidSignature)
+        ));
+
+        let mut wrong_key = message.clone();
+        wrong_key.validator_index = 2;
+        assert!(matches!(
+            verify_sync_committee_message_signature(&state, &mut wrong_key, &context),
+            Err(Error::InvalidSignature)
+        ));
+
+        let mut unknown_validator = message;
+        unknown_validator.validator_index = VALIDATOR_COUNT;
+        assert!(matches!(
+            verify_sync_committee_message_signature(&state, &mut unknown_validator, &context),
+            Err(Error::OutOfBounds { .. })
+        ));
+    }
+
+    #[test]
+    fn test_sync_aggregate_signature() {
+        let context = Context::for_mainnet();
+        let state = state_with_sync_committee();
+        let block_root = state.block_roots[0];
+        let sign = |committee_index: usize| {
+            let validator_index = committee_index % VALIDATOR_COUNT;
+            let signing_key = interop_secret_key(validator_index).unwrap();
+            get_sync_committee_message(&state, block_root, validator_index, &signing_key, &context)
+                .unwrap()
+                .signature
+        };
+        let sync_aggregate = |participants: &[usize], signers: &[usize]| {
+            let mut sync_aggregate = mainnet::SyncAggregate::default();
+            for &index in participants {
+                sync_aggregate.sync_committee_bits.set(index, true);
+            }
+            let signatures = signers.iter().map(|&index| sign(index)).collect::<Vec<_>>();
+            sync_aggregate.sync_committee_signature = aggregate(&signatures).unwrap();
+            sync_aggregate
+        };
+
+        let participants = [0, 1, 6, 511];
+        let valid = sync_aggregate(&participants, &participants);
+        assert!(verify_sync_aggregate_signature(&state, &valid, &context).is_ok());
+
+        // a participant whose signature is missing from the aggregate
+        let missing_signature = sync_aggregate(&participants, &participants[..3]);
+        assert!(matches!(
+            verify_sync_aggregate_signature(&state, &missing_signature, &context),
+            Err(Error::InvalidSignature)
+        ));
+
+        // a signature in the aggregate whose signer is not marked as a participant
+        let missing_participant = sync_aggregate(&participants[..3], &participants);
+        assert!(matches!(
+            verify_sync_aggregate_signature(&state, &missing_participant, &context),
+            Err(Error::InvalidSignature)
+        ));
+
+        // the aggregate is over the block root of the previous slot
+        let mut later_state = state.clone();
+        later_state.block_roots[0] = Root::from_bytes([3u8; 32]);
+        assert!(matches!(
+            verify_sync_aggregate_signature(&later_state, &valid, &context),
+            Err(Error::InvalidSignature)
+        ));
+
+        let empty = mainnet::SyncAggregate::default();
+        assert!(verify_sync_aggregate_signature(&state, &empty, &context).is_ok());
+        let mut empty_with_signature = empty;
+        empty_with_signature.sync_committee_signature = sign(0);
+        assert!(matches!(
+            verify_sync_aggregate_signature(&state, &empty_with_signature, &context),
+            Err(Error::InvalidSignature)
+        ));
+    }
+}
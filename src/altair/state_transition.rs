@@ -0,0 +1,131 @@
+use crate::altair as spec;
+
+use crate::state_transition::{Context, Error, Validation};
+use spec::{process_block, process_slots, verify_block_signature, BeaconState, SignedBeaconBlock};
+use ssz_rs::prelude::*;
+
+pub fn state_transition<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    signed_block: &mut SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    validation: Validation,
+    context: &Context,
+) -> Result<(), Error> {
+    let validate_result = match validation {
+        Validation::Enabled => true,
+        Validation::Disabled => false,
+    };
+
+    let slot = signed_block.message.slot;
+
+    process_slots(state, slot, context)?;
+    if validate_result {
+        verify_block_signature(state, signed_block, context)?;
+    }
+    let block = &mut signed_block.message;
+    process_block(state, block, context)?;
+    if validate_result && block.state_root != state.hash_tree_root()? {
+        return Err(Error::InvalidStateRoot);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::altair::helpers::{get_beacon_proposer_index, get_domain};
+    use crate::altair::mainnet;
+    use crate::interop::altair::create_mainnet_genesis_state;
+    use crate::interop::interop_secret_key;
+    use crate::phase0::sign_with_domain;
+    use crate::primitives::{BlsSignature, DomainType};
+
+    #[test]
+    fn test_state_transition() {
+        let context = Context::for_mainnet();
+        let genesis_state = create_mainnet_genesis_state(64, &context);
+        let slot = 1;
+
+        let mut state = genesis_state.clone();
+        process_slots(&mut state, slot, &context).unwrap();
+        let proposer_index = get_beacon_proposer_index(&state, &context).unwrap();
+        let signing_key = interop_secret_key(proposer_index).unwrap();
+
+        let mut block = mainnet::BeaconBlock {
+            slot,
+            proposer_index,
+            parent_root: state.latest_block_header.hash_tree_root().unwrap(),
+            ..Default::default()
+        };
+        let domain = get_domain(&state, DomainType::Randao, None, &context).unwrap();
+        block.body.randao_reveal = sign_with_domain(&mut 0u64, &signing_key, domain).unwrap();
+        block.body.eth1_data = state.eth1_data.clone();
+        process_block(&mut state, &mut block, &context).unwrap();
+        block.state_root = state.hash_tree_root().unwrap();
+
+        let domain = get_domain(&state, DomainType::BeaconProposer, None, &context).unwrap();
+        let signature = sign_with_domain(&mut block, &signing_key, domain).unwrap();
+        let mut signed_block = mainnet::SignedBeaconBlock {
+            message: block,
+            signature,
+        };
+
+        let mut post_state = genesis_state.clone();
+        state_transition(
+            &mut post_state,
+            &mut signed_block,
+            Validation::Enabled,
+            &context,
+        )
+        .unwrap();
+        assert_eq!(
+            post_state.hash_tree_root().unwrap(),
+            signed_block.message.state_root
+        );
+        // every validator sits on the sync committee and the default aggregate is empty
+        assert!(post_state.balances[0] < genesis_state.balances[0]);
+
+        signed_block.signature = BlsSignature::default();
+        let mut post_state = genesis_state;
+        assert!(matches!(
+            state_transition(
+                &mut post_state,
+                &mut signed_block,
+                Validation::Enabled,
+                &context,
+            ),
+            Err(Error::InvalidSignature)
+        ));
+    }
+}
@@ -1,7 +1,7 @@
 use crate::primitives::{BlsPublicKey, BlsSignature};
 use ssz_rs::prelude::*;
 
-#[derive(Default, Debug, SimpleSerialize)]
+#[derive(Default, Debug, SimpleSerialize, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SyncAggregate<const SYNC_COMMITTEE_SIZE: usize> {
     pub sync_committee_bits: Bitvector<SYNC_COMMITTEE_SIZE>,
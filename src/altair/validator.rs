@@ -1,8 +1,10 @@
 use crate::primitives::{BlsSignature, Root, Slot, ValidatorIndex};
-use ssz_rs::prelude::Bitvector;
+use ssz_rs::prelude::*;
 
+pub const TARGET_AGGREGATORS_PER_SYNC_SUBCOMMITTEE: usize = 16;
 pub const SYNC_COMMITTEE_SUBNET_COUNT: usize = 4;
 
+#[derive(Default, Debug, Clone, SimpleSerialize)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SyncCommitteeMessage {
     #[serde(with = "crate::serde::as_string")]
@@ -13,10 +15,11 @@ pub struct SyncCommitteeMessage {
     pub signature: BlsSignature,
 }
 
-pub(super) const fn get_sync_subcommittee_size(sync_committee_size: usize) -> usize {
+pub(crate) const fn get_sync_subcommittee_size(sync_committee_size: usize) -> usize {
     sync_committee_size / SYNC_COMMITTEE_SUBNET_COUNT
 }
 
+#[derive(Default, Debug, Clone, SimpleSerialize)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SyncCommitteeContribution<const SYNC_SUBCOMMITTEE_SIZE: usize> {
     #[serde(with = "crate::serde::as_string")]
@@ -28,6 +31,7 @@ pub struct SyncCommitteeContribution<const SYNC_SUBCOMMITTEE_SIZE: usize> {
     pub signature: BlsSignature,
 }
 
+#[derive(Default, Debug, Clone, SimpleSerialize)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ContributionAndProof<const SYNC_SUBCOMMITTEE_SIZE: usize> {
     #[serde(with = "crate::serde::as_string")]
@@ -36,8 +40,18 @@ pub struct ContributionAndProof<const SYNC_SUBCOMMITTEE_SIZE: usize> {
     pub selection_proof: BlsSignature,
 }
 
+#[derive(Default, Debug, Clone, SimpleSerialize)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SignedContributionAndProof<const SYNC_SUBCOMMITTEE_SIZE: usize> {
     pub message: ContributionAndProof<SYNC_SUBCOMMITTEE_SIZE>,
     pub signature: BlsSignature,
 }
+
+#[derive(Default, Debug, Clone, SimpleSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SyncAggregatorSelectionData {
+    #[serde(with = "crate::serde::as_string")]
+    pub slot: Slot,
+    #[serde(with = "crate::serde::as_string")]
+    pub subcommittee_index: u64,
+}
@@ -0,0 +1,578 @@
+use crate::altair::{SyncCommitteeDuty, SyncCommitteeMessage};
+use crate::beacon_api::types::{
+    ApiError, BalanceSummary, BeaconHeaderSummary, BlockId, CommitteeFilter, CommitteeSummary,
+    DependentValue, FinalityCheckpoints, GenesisDetails, HealthStatus, NodeIdentity,
+    PeerDescription, PeerSummary, PublicKeyOrIndex, RootData, SignedBeaconBlock, StateId,
+    SyncCommitteeSummary, SyncStatus, ValidatorStatus, ValidatorSummary, Value, VersionData,
+};
+use crate::networking::PeerId;
+use crate::phase0::{
+    Attestation, AttesterDuty, AttesterSlashing, Fork, ProposerDuty, ProposerSlashing,
+    SignedVoluntaryExit,
+};
+use crate::primitives::{CommitteeIndex, Epoch, Root, Slot, ValidatorIndex};
+use reqwest::{StatusCode, Url};
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("{0}")]
+    Url(#[from] url::ParseError),
+    #[error("{0}")]
+    Http(#[from] reqwest::Error),
+    #[error("{0}")]
+    Api(#[from] ApiError),
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+}
+
+fn join_display<T: std::fmt::Display>(items: &[T]) -> String {
+    items
+        .iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// A client for the standard Beacon Node API.
+///
+/// Methods taking or returning preset-dependent operations are generic over the preset.
+/// Blocks use the `SignedBeaconBlock` of this module, which only covers the `mainnet`
+/// preset.
+#[derive(Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    endpoint: Url,
+}
+
+impl Client {
+    /// Create a client for the API served at `endpoint`, which may include a base path.
+    ///
+    /// The `path` given to the raw request methods is resolved relative to the `endpoint`
+    /// and so must not start with `/`.
+    pub fn new(mut endpoint: Url) -> Self {
+        // NOTE: `Url::join` replaces the last path segment unless the path ends with `/`
+        if !endpoint.path().ends_with('/') {
+            let path = format!("{}/", endpoint.path());
+            endpoint.set_path(&path);
+        }
+        Self {
+            http: reqwest::Client::new(),
+            endpoint,
+        }
+    }
+
+    async fn into_result<T: DeserializeOwned>(response: reqwest::Response) -> Result<T, Error> {
+        if response.status().is_success() {
+            Ok(response.json().await?)
+        } else {
+            Err(Error::Api(response.json::<ApiError>().await?))
+        }
+    }
+
+    async fn into_empty_result(response: reqwest::Response) -> Result<(), Error> {
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(Error::Api(response.json::<ApiError>().await?))
+        }
+    }
+
+    pub async fn http_get(&self, path: &str) -> Result<reqwest::Response, Error> {
+        let target = self.endpoint.join(path)?;
+        Ok(self.http.get(target).send().await?)
+    }
+
+    pub async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, Error> {
+        let response = self.http_get(path).await?;
+        Self::into_result(response).await
+    }
+
+    async fn get_with_query<T: DeserializeOwned, Q: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        query: &Q,
+    ) -> Result<T, Error> {
+        let target = self.endpoint.join(path)?;
+        let response = self.http.get(target).query(query).send().await?;
+        Self::into_result(response).await
+    }
+
+    pub async fn http_post<T: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        argument: &T,
+    ) -> Result<reqwest::Response, Error> {
+        let target = self.endpoint.join(path)?;
+        Ok(self.http.post(target).json(argument).send().await?)
+    }
+
+    pub async fn post<T: Serialize + ?Sized>(&self, path: &str, argument: &T) -> Result<(), Error> {
+        let response = self.http_post(path, argument).await?;
+        Self::into_empty_result(response).await
+    }
+
+    async fn post_with_response<T: Serialize + ?Sized, U: DeserializeOwned>(
+        &self,
+        path: &str,
+        argument: &T,
+    ) -> Result<U, Error> {
+        let response = self.http_post(path, argument).await?;
+        Self::into_result(response).await
+    }
+
+    /* beacon namespace */
+    pub async fn get_genesis_details(&self) -> Result<GenesisDetails, Error> {
+        let details: Value<GenesisDetails> = self.get("eth/v1/beacon/genesis").await?;
+        Ok(details.data)
+    }
+
+    pub async fn get_state_root(&self, id: StateId) -> Result<Root, Error> {
+        let path = format!("eth/v1/beacon/states/{id}/root");
+        let root: Value<RootData> = self.get(&path).await?;
+        Ok(root.data.root)
+    }
+
+    pub async fn get_fork(&self, id: StateId) -> Result<Fork, Error> {
+        let path = format!("eth/v1/beacon/states/{id}/fork");
+        let fork: Value<Fork> = self.get(&path).await?;
+        Ok(fork.data)
+    }
+
+    pub async fn get_finality_checkpoints(
+        &self,
+        id: StateId,
+    ) -> Result<FinalityCheckpoints, Error> {
+        let path = format!("eth/v1/beacon/states/{id}/finality_checkpoints");
+        let checkpoints: Value<FinalityCheckpoints> = self.get(&path).await?;
+        Ok(checkpoints.data)
+    }
+
+    pub async fn get_validators(
+        &self,
+        id: StateId,
+        validator_ids: &[PublicKeyOrIndex],
+        statuses: &[ValidatorStatus],
+    ) -> Result<Vec<ValidatorSummary>, Error> {
+        let path = format!("eth/v1/beacon/states/{id}/validators");
+        let mut query = vec![];
+        if !validator_ids.is_empty() {
+            query.push(("id", join_display(validator_ids)));
+        }
+        if !statuses.is_empty() {
+            let statuses = statuses
+                .iter()
+                .map(|status| {
+                    // NOTE: each status serializes to a JSON string
+                    let status = serde_json::to_string(status)?;
+                    Ok(status.trim_matches('"').to_string())
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+            query.push(("status", statuses.join(",")));
+        }
+        let validators: Value<Vec<ValidatorSummary>> = self.get_with_query(&path, &query).await?;
+        Ok(validators.data)
+    }
+
+    pub async fn get_validator(
+        &self,
+        state_id: StateId,
+        validator_id: PublicKeyOrIndex,
+    ) -> Result<ValidatorSummary, Error> {
+        let path = format!("eth/v1/beacon/states/{state_id}/validators/{validator_id}");
+        let validator: Value<ValidatorSummary> = self.get(&path).await?;
+        Ok(validator.data)
+    }
+
+    pub async fn get_balances(
+        &self,
+        id: StateId,
+        validator_ids: &[PublicKeyOrIndex],
+    ) -> Result<Vec<BalanceSummary>, Error> {
+        let path = format!("eth/v1/beacon/states/{id}/validator_balances");
+        let mut query = vec![];
+        if !validator_ids.is_empty() {
+            query.push(("id", join_display(validator_ids)));
+        }
+        let balances: Value<Vec<BalanceSummary>> = self.get_with_query(&path, &query).await?;
+        Ok(balances.data)
+    }
+
+    pub async fn get_all_committees(&self, id: StateId) -> Result<Vec<CommitteeSummary>, Error> {
+        self.get_committees(id, CommitteeFilter::default()).await
+    }
+
+    pub async fn get_committees(
+        &self,
+        id: StateId,
+        filter: CommitteeFilter,
+    ) -> Result<Vec<CommitteeSummary>, Error> {
+        let path = format!("eth/v1/beacon/states/{id}/committees");
+        let mut query = vec![];
+        if let Some(epoch) = filter.epoch {
+            query.push(("epoch", epoch.to_string()));
+        }
+        if let Some(index) = filter.index {
+            query.push(("index", index.to_string()));
+        }
+        if let Some(slot) = filter.slot {
+            query.push(("slot", slot.to_string()));
+        }
+        let committees: Value<Vec<CommitteeSummary>> = self.get_with_query(&path, &query).await?;
+        Ok(committees.data)
+    }
+
+    pub async fn get_sync_committees(
+        &self,
+        id: StateId,
+        epoch: Option<Epoch>,
+    ) -> Result<SyncCommitteeSummary, Error> {
+        let path = format!("eth/v1/beacon/states/{id}/sync_committees");
+        let mut query = vec![];
+        if let Some(epoch) = epoch {
+            query.push(("epoch", epoch.to_string()));
+        }
+        let committees: Value<SyncCommitteeSummary> = self.get_with_query(&path, &query).await?;
+        Ok(committees.data)
+    }
+
+    pub async fn get_beacon_header_at_head(&self) -> Result<BeaconHeaderSummary, Error> {
+        self.get_beacon_header(BlockId::Head).await
+    }
+
+    pub async fn get_beacon_headers(
+        &self,
+        slot: Option<Slot>,
+        parent_root: Option<Root>,
+    ) -> Result<Vec<BeaconHeaderSummary>, Error> {
+        let mut query = vec![];
+        if let Some(slot) = slot {
+            query.push(("slot", slot.to_string()));
+        }
+        if let Some(parent_root) = parent_root {
+            query.push(("parent_root", BlockId::Root(parent_root).to_string()));
+        }
+        let headers: Value<Vec<BeaconHeaderSummary>> =
+            self.get_with_query("eth/v1/beacon/headers", &query).await?;
+        Ok(headers.data)
+    }
+
+    pub async fn get_beacon_header(&self, id: BlockId) -> Result<BeaconHeaderSummary, Error> {
+        let path = format!("eth/v1/beacon/headers/{id}");
+        let header: Value<BeaconHeaderSummary> = self.get(&path).await?;
+        Ok(header.data)
+    }
+
+    pub async fn post_signed_beacon_block(&self, block: &SignedBeaconBlock) -> Result<(), Error> {
+        let path = "eth/v1/beacon/blocks";
+        match block {
+            SignedBeaconBlock::Phase0(block) => self.post(path, block).await,
+            SignedBeaconBlock::Altair(block) => self.post(path, block).await,
+            SignedBeaconBlock::Bellatrix(block) => self.post(path, block).await,
+        }
+    }
+
+    pub async fn get_beacon_block(&self, id: BlockId) -> Result<SignedBeaconBlock, Error> {
+        let path = format!("eth/v2/beacon/blocks/{id}");
+        self.get(&path).await
+    }
+
+    pub async fn get_beacon_block_root(&self, id: BlockId) -> Result<Root, Error> {
+        let path = format!("eth/v1/beacon/blocks/{id}/root");
+        let root: Value<RootData> = self.get(&path).await?;
+        Ok(root.data.root)
+    }
+
+    pub async fn get_attestations_from_beacon_block<const MAX_VALIDATORS_PER_COMMITTEE: usize>(
+        &self,
+        id: BlockId,
+    ) -> Result<Vec<Attestation<MAX_VALIDATORS_PER_COMMITTEE>>, Error> {
+        let path = format!("eth/v1/beacon/blocks/{id}/attestations");
+        let attestations: Value<Vec<Attestation<MAX_VALIDATORS_PER_COMMITTEE>>> =
+            self.get(&path).await?;
+        Ok(attestations.data)
+    }
+
+    pub async fn get_attestations_from_pool<const MAX_VALIDATORS_PER_COMMITTEE: usize>(
+        &self,
+        slot: Option<Slot>,
+        committee_index: Option<CommitteeIndex>,
+    ) -> Result<Vec<Attestation<MAX_VALIDATORS_PER_COMMITTEE>>, Error> {
+        let mut query = vec![];
+        if let Some(slot) = slot {
+            query.push(("slot", slot.to_string()));
+        }
+        if let Some(committee_index) = committee_index {
+            query.push(("committee_index", committee_index.to_string()));
+        }
+        let attestations: Value<Vec<Attestation<MAX_VALIDATORS_PER_COMMITTEE>>> = self
+            .get_with_query("eth/v1/beacon/pool/attestations", &query)
+            .await?;
+        Ok(attestations.data)
+    }
+
+    pub async fn post_attestations<const MAX_VALIDATORS_PER_COMMITTEE: usize>(
+        &self,
+        attestations: &[Attestation<MAX_VALIDATORS_PER_COMMITTEE>],
+    ) -> Result<(), Error> {
+        self.post("eth/v1/beacon/pool/attestations", attestations)
+            .await
+    }
+
+    pub async fn get_attester_slashings_from_pool<const MAX_VALIDATORS_PER_COMMITTEE: usize>(
+        &self,
+    ) -> Result<Vec<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>>, Error> {
+        let slashings: Value<Vec<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>>> =
+            self.get("eth/v1/beacon/pool/attester_slashings").await?;
+        Ok(slashings.data)
+    }
+
+    pub async fn post_attester_slashing<const MAX_VALIDATORS_PER_COMMITTEE: usize>(
+        &self,
+        attester_slashing: &AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>,
+    ) -> Result<(), Error> {
+        self.post("eth/v1/beacon/pool/attester_slashings", attester_slashing)
+            .await
+    }
+
+    pub async fn get_proposer_slashings_from_pool(&self) -> Result<Vec<ProposerSlashing>, Error> {
+        let slashings: Value<Vec<ProposerSlashing>> =
+            self.get("eth/v1/beacon/pool/proposer_slashings").await?;
+        Ok(slashings.data)
+    }
+
+    pub async fn post_proposer_slashing(
+        &self,
+        proposer_slashing: &ProposerSlashing,
+    ) -> Result<(), Error> {
+        self.post("eth/v1/beacon/pool/proposer_slashings", proposer_slashing)
+            .await
+    }
+
+    pub async fn post_sync_committee_messages(
+        &self,
+        messages: &[SyncCommitteeMessage],
+    ) -> Result<(), Error> {
+        self.post("eth/v1/beacon/pool/sync_committees", messages)
+            .await
+    }
+
+    pub async fn get_voluntary_exits_from_pool(&self) -> Result<Vec<SignedVoluntaryExit>, Error> {
+        let exits: Value<Vec<SignedVoluntaryExit>> =
+            self.get("eth/v1/beacon/pool/voluntary_exits").await?;
+        Ok(exits.data)
+    }
+
+    pub async fn post_signed_voluntary_exit(
+        &self,
+        exit: &SignedVoluntaryExit,
+    ) -> Result<(), Error> {
+        self.post("eth/v1/beacon/pool/voluntary_exits", exit).await
+    }
+
+    /* config namespace */
+    pub async fn get_spec(&self) -> Result<HashMap<String, String>, Error> {
+        let spec: Value<HashMap<String, String>> = self.get("eth/v1/config/spec").await?;
+        Ok(spec.data)
+    }
+
+    /* node namespace */
+    pub async fn get_node_identity(&self) -> Result<NodeIdentity, Error> {
+        let identity: Value<NodeIdentity> = self.get("eth/v1/node/identity").await?;
+        Ok(identity.data)
+    }
+
+    pub async fn get_node_peers(&self) -> Result<Vec<PeerDescription>, Error> {
+        let peers: Value<Vec<PeerDescription>> = self.get("eth/v1/node/peers").await?;
+        Ok(peers.data)
+    }
+
+    pub async fn get_peer_description(&self, peer_id: PeerId) -> Result<PeerDescription, Error> {
+        let path = format!("eth/v1/node/peers/{peer_id}");
+        let peer: Value<PeerDescription> = self.get(&path).await?;
+        Ok(peer.data)
+    }
+
+    pub async fn get_peer_count(&self) -> Result<PeerSummary, Error> {
+        let count: Value<PeerSummary> = self.get("eth/v1/node/peer_count").await?;
+        Ok(count.data)
+    }
+
+    pub async fn get_node_version(&self) -> Result<String, Error> {
+        let version: Value<VersionData> = self.get("eth/v1/node/version").await?;
+        Ok(version.data.version)
+    }
+
+    pub async fn get_sync_status(&self) -> Result<SyncStatus, Error> {
+        let status: Value<SyncStatus> = self.get("eth/v1/node/syncing").await?;
+        Ok(status.data)
+    }
+
+    pub async fn get_health(&self) -> Result<HealthStatus, Error> {
+        let response = self.http_get("eth/v1/node/health").await?;
+        let status = match response.status() {
+            StatusCode::OK => HealthStatus::Ready,
+            StatusCode::PARTIAL_CONTENT => HealthStatus::Syncing,
+            StatusCode::SERVICE_UNAVAILABLE => HealthStatus::NotInitialized,
+            _ => HealthStatus::Unknown,
+        };
+        Ok(status)
+    }
+
+    /* validator namespace */
+    pub async fn get_attester_duties(
+        &self,
+        epoch: Epoch,
+        indices: &[ValidatorIndex],
+    ) -> Result<(Root, Vec<AttesterDuty>), Error> {
+        let path = format!("eth/v1/validator/duties/attester/{epoch}");
+        let indices = indices.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        let duties: DependentValue<Vec<AttesterDuty>> =
+            self.post_with_response(&path, &indices).await?;
+        Ok((duties.dependent_root, duties.data))
+    }
+
+    pub async fn get_proposer_duties(
+        &self,
+        epoch: Epoch,
+    ) -> Result<(Root, Vec<ProposerDuty>), Error> {
+        let path = format!("eth/v1/validator/duties/proposer/{epoch}");
+        let duties: DependentValue<Vec<ProposerDuty>> = self.get(&path).await?;
+        Ok((duties.dependent_root, duties.data))
+    }
+
+    pub async fn get_sync_committee_duties(
+        &self,
+        epoch: Epoch,
+        indices: &[ValidatorIndex],
+    ) -> Result<Vec<SyncCommitteeDuty>, Error> {
+        let path = format!("eth/v1/validator/duties/sync/{epoch}");
+        let indices = indices.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        let duties: Value<Vec<SyncCommitteeDuty>> =
+            self.post_with_response(&path, &indices).await?;
+        Ok(duties.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::altair::mainnet as altair;
+    use crate::beacon_api::types::{ConsensusVersion, VersionedValue};
+    use axum::{
+        extract::Query, http::StatusCode as HttpStatusCode, routing::get, routing::post, Json,
+        Router,
+    };
+    use serde_json::json;
+    use std::net::TcpListener;
+
+    fn spawn_stub_server() -> Url {
+        let api = Router::new()
+            .route(
+                "/eth/v1/beacon/genesis",
+                get(|| async {
+                    Json(json!({
+                        "data": {
+                            "genesis_time": "1606824023",
+                            "genesis_validators_root": "0x4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95",
+                            "genesis_fork_version": "0x00000000",
+                        }
+                    }))
+                }),
+            )
+            .route(
+                "/eth/v2/beacon/blocks/:block_id",
+                get(|| async {
+                    let block = VersionedValue {
+                        version: ConsensusVersion::Altair,
+                        data: altair::SignedBeaconBlock::default(),
+                    };
+                    Json(serde_json::to_value(block).unwrap())
+                }),
+            )
+            .route(
+                "/eth/v1/beacon/blocks/:block_id/root",
+                get(|| async {
+                    (
+                        HttpStatusCode::NOT_FOUND,
+                        Json(json!({ "code": 404, "message": "block not found" })),
+                    )
+                }),
+            )
+            .route(
+                "/eth/v1/validator/duties/attester/:epoch",
+                post(|Json(indices): Json<Vec<String>>| async move {
+                    assert_eq!(indices, vec!["1".to_string()]);
+                    Json(json!({
+                        "dependent_root": "0xcf8e0d4e9587369b2301d0790347320302cc0943d5a1884560367e8208d920f2",
+                        "data": [{
+                            "pubkey": "0x93247f2209abcacf57b75a51dafae777f9dd38bc7053d1af526f220a7489a6d3a2753e5f3e8b1cfe39b56f43611df74a",
+                            "validator_index": "1",
+                            "committee_index": "2",
+                            "committee_length": "128",
+                            "committees_at_slot": "4",
+                            "validator_committee_index": "7",
+                            "slot": "33",
+                        }]
+                    }))
+                }),
+            )
+            .route(
+                "/eth/v1/beacon/states/:state_id/validators",
+                get(|Query(query): Query<HashMap<String, String>>| async move {
+                    assert_eq!(query["status"], "active_ongoing,exited_slashed");
+                    Json(json!({ "data": [] }))
+                }),
+            )
+            .route("/eth/v1/node/health", get(|| async { HttpStatusCode::PARTIAL_CONTENT }));
+        let app = Router::new().nest("/beacon", api);
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = axum::Server::from_tcp(listener)
+            .unwrap()
+            .serve(app.into_make_service());
+        tokio::spawn(server);
+        // NOTE: serve under a base path to check it is preserved
+        Url::parse(&format!("http://{addr}/beacon")).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_client() {
+        let client = Client::new(spawn_stub_server());
+
+        let genesis = client.get_genesis_details().await.unwrap();
+        assert_eq!(genesis.genesis_time, 1606824023);
+        assert_eq!(genesis.genesis_fork_version, [0u8; 4]);
+
+        let block = client.get_beacon_block(BlockId::Head).await.unwrap();
+        assert_eq!(block.version(), ConsensusVersion::Altair);
+        assert!(matches!(block, SignedBeaconBlock::Altair(_)));
+
+        let error = client
+            .get_beacon_block_root(BlockId::Slot(1))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::Api(ApiError { code: 404, .. })));
+
+        let (_, duties) = client.get_attester_duties(1, &[1]).await.unwrap();
+        assert_eq!(duties.len(), 1);
+        assert_eq!(duties[0].validator_index, 1);
+        assert_eq!(duties[0].slot, 33);
+
+        let statuses = [
+            ValidatorStatus::ActiveOngoing,
+            ValidatorStatus::ExitedSlashed,
+        ];
+        let validators = client
+            .get_validators(StateId::Head, &[], &statuses)
+            .await
+            .unwrap();
+        assert!(validators.is_empty());
+
+        let health = client.get_health().await.unwrap();
+        assert_eq!(health, HealthStatus::Syncing);
+    }
+}
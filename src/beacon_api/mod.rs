@@ -0,0 +1,8 @@
+//! Types, a client and a server router for the standard Beacon Node API.
+mod client;
+mod server;
+mod types;
+
+pub use client::*;
+pub use server::*;
+pub use types::*;
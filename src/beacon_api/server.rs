@@ -0,0 +1,752 @@
+use crate::altair::SyncCommitteeDuty;
+use crate::beacon_api::types::{
+    ApiError, BalanceSummary, BeaconHeaderSummary, BlockId, CommitteeFilter, CommitteeSummary,
+    ConnectionOrientation, DependentValue, FinalityCheckpoints, GenesisDetails, HealthStatus,
+    InvalidIdentifier, NodeIdentity, PeerDescription, PeerState, PeerSummary, PublicKeyOrIndex,
+    RootData, SignedBeaconBlock, StateId, SyncStatus, ValidatorStatus, ValidatorSummary, Value,
+    VersionData,
+};
+use crate::networking::PeerId;
+use crate::phase0::{AttesterDuty, Fork, ProposerDuty};
+use crate::primitives::{Epoch, Root, ValidatorIndex};
+use async_trait::async_trait;
+use axum::{
+    body::HttpBody,
+    extract::{Extension, FromRequest, Path, Query, RequestParts},
+    handler::Handler,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    BoxError, Json, Router,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Arc;
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        (status, Json(self)).into_response()
+    }
+}
+
+impl From<InvalidIdentifier> for ApiError {
+    fn from(err: InvalidIdentifier) -> Self {
+        ApiError::new(StatusCode::BAD_REQUEST.as_u16(), err.to_string())
+    }
+}
+
+// NOTE: keeps the status code chosen by `axum` for the rejection
+fn from_rejection<R: IntoResponse + Display>(rejection: R) -> ApiError {
+    let message = rejection.to_string();
+    let status = rejection.into_response().status();
+    ApiError::new(status.as_u16(), message)
+}
+
+/// Extract the path parameters, rejecting the request with an `ApiError`.
+struct ApiPath<T>(T);
+
+#[async_trait]
+impl<T, B> FromRequest<B> for ApiPath<T>
+where
+    T: DeserializeOwned + Send,
+    B: Send,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let Path(value) = Path::<T>::from_request(req).await.map_err(from_rejection)?;
+        Ok(Self(value))
+    }
+}
+
+/// Extract the query parameters, rejecting the request with an `ApiError`.
+struct ApiQuery<T>(T);
+
+#[async_trait]
+impl<T, B> FromRequest<B> for ApiQuery<T>
+where
+    T: DeserializeOwned,
+    B: Send,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request(req)
+            .await
+            .map_err(from_rejection)?;
+        Ok(Self(value))
+    }
+}
+
+/// Extract a JSON request body, rejecting the request with an `ApiError`.
+struct ApiJson<T>(T);
+
+#[async_trait]
+impl<T, B> FromRequest<B> for ApiJson<T>
+where
+    T: DeserializeOwned,
+    B: HttpBody + Send,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: &mut RequestParts<B>) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req).await.map_err(from_rejection)?;
+        Ok(Self(value))
+    }
+}
+
+async fn not_found() -> ApiError {
+    ApiError::new(StatusCode::NOT_FOUND.as_u16(), "endpoint not found")
+}
+
+fn not_implemented<T>() -> Result<T, ApiError> {
+    Err(ApiError::new(
+        StatusCode::NOT_IMPLEMENTED.as_u16(),
+        "endpoint not implemented",
+    ))
+}
+
+fn parse_list<T: FromStr<Err = InvalidIdentifier>>(
+    list: Option<&String>,
+) -> Result<Vec<T>, ApiError> {
+    let list = match list {
+        Some(list) => list,
+        None => return Ok(vec![]),
+    };
+    list.split(',')
+        .map(|item| item.trim().parse().map_err(ApiError::from))
+        .collect()
+}
+
+fn parse_indices(indices: &[String]) -> Result<Vec<ValidatorIndex>, ApiError> {
+    indices
+        .iter()
+        .map(|index| {
+            index
+                .parse()
+                .map_err(|_| ApiError::from(InvalidIdentifier(index.clone())))
+        })
+        .collect()
+}
+
+/// Query parameters accepted by `/eth/v1/node/peers`.
+#[derive(Debug, Default, Deserialize)]
+pub struct PeersQuery {
+    pub state: Option<String>,
+    pub direction: Option<String>,
+}
+
+/// Query parameters accepted by `/eth/v1/beacon/states/{state_id}/validators`
+/// and `/eth/v1/beacon/states/{state_id}/validator_balances`.
+#[derive(Debug, Default, Deserialize)]
+pub struct ValidatorsQuery {
+    pub id: Option<String>,
+    pub status: Option<String>,
+}
+
+/// The handlers backing a Beacon API server.
+///
+/// Every endpoint defaults to a `501 Not Implemented` response so implementers
+/// only need to provide the subset they serve.
+#[async_trait]
+pub trait BeaconApi: Send + Sync + 'static {
+    /* beacon namespace */
+    async fn get_genesis_details(&self) -> Result<GenesisDetails, ApiError> {
+        not_implemented()
+    }
+
+    async fn get_state_root(&self, _id: StateId) -> Result<Root, ApiError> {
+        not_implemented()
+    }
+
+    async fn get_fork(&self, _id: StateId) -> Result<Fork, ApiError> {
+        not_implemented()
+    }
+
+    async fn get_finality_checkpoints(
+        &self,
+        _id: StateId,
+    ) -> Result<FinalityCheckpoints, ApiError> {
+        not_implemented()
+    }
+
+    async fn get_validators(
+        &self,
+        _id: StateId,
+        _validator_ids: Vec<PublicKeyOrIndex>,
+        _statuses: Vec<ValidatorStatus>,
+    ) -> Result<Vec<ValidatorSummary>, ApiError> {
+        not_implemented()
+    }
+
+    async fn get_validator(
+        &self,
+        _id: StateId,
+        _validator_id: PublicKeyOrIndex,
+    ) -> Result<ValidatorSummary, ApiError> {
+        not_implemented()
+    }
+
+    async fn get_balances(
+        &self,
+        _id: StateId,
+        _validator_ids: Vec<PublicKeyOrIndex>,
+    ) -> Result<Vec<BalanceSummary>, ApiError> {
+        not_implemented()
+    }
+
+    async fn get_committees(
+        &self,
+        _id: StateId,
+        _filter: CommitteeFilter,
+    ) -> Result<Vec<CommitteeSummary>, ApiError> {
+        not_implemented()
+    }
+
+    async fn get_beacon_header(&self, _id: BlockId) -> Result<BeaconHeaderSummary, ApiError> {
+        not_implemented()
+    }
+
+    async fn get_beacon_block(&self, _id: BlockId) -> Result<SignedBeaconBlock, ApiError> {
+        not_implemented()
+    }
+
+    async fn get_beacon_block_root(&self, _id: BlockId) -> Result<Root, ApiError> {
+        not_implemented()
+    }
+
+    /* config namespace */
+    async fn get_spec(&self) -> Result<HashMap<String, String>, ApiError> {
+        not_implemented()
+    }
+
+    /* node namespace */
+    async fn get_node_identity(&self) -> Result<NodeIdentity, ApiError> {
+        not_implemented()
+    }
+
+    /// Return the peers of the node, filtered by the given `states` and `directions`
+    /// unless they are empty.
+    async fn get_node_peers(
+        &self,
+        _states: Vec<PeerState>,
+        _directions: Vec<ConnectionOrientation>,
+    ) -> Result<Vec<PeerDescription>, ApiError> {
+        not_implemented()
+    }
+
+    async fn get_peer_description(&self, _peer_id: PeerId) -> Result<PeerDescription, ApiError> {
+        not_implemented()
+    }
+
+    async fn get_peer_count(&self) -> Result<PeerSummary, ApiError> {
+        not_implemented()
+    }
+
+    async fn get_node_version(&self) -> Result<String, ApiError> {
+        not_implemented()
+    }
+
+    async fn get_sync_status(&self) -> Result<SyncStatus, ApiError> {
+        not_implemented()
+    }
+
+    async fn get_health(&self) -> HealthStatus {
+        HealthStatus::Unknown
+    }
+
+    /* validator namespace */
+    async fn get_attester_duties(
+        &self,
+        _epoch: Epoch,
+        _indices: Vec<ValidatorIndex>,
+    ) -> Result<(Root, Vec<AttesterDuty>), ApiError> {
+        not_implemented()
+    }
+
+    async fn get_proposer_duties(
+        &self,
+        _epoch: Epoch,
+    ) -> Result<(Root, Vec<ProposerDuty>), ApiError> {
+        not_implemented()
+    }
+
+    async fn get_sync_committee_duties(
+        &self,
+        _epoch: Epoch,
+        _indices: Vec<ValidatorIndex>,
+    ) -> Result<Vec<SyncCommitteeDuty>, ApiError> {
+        not_implemented()
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+async fn get_genesis_details<T: BeaconApi>(
+    Extension(api): Extension<Arc<T>>,
+) -> ApiResult<Value<GenesisDetails>> {
+    let data = api.get_genesis_details().await?;
+    Ok(Json(Value { data }))
+}
+
+async fn get_state_root<T: BeaconApi>(
+    Extension(api): Extension<Arc<T>>,
+    ApiPath(id): ApiPath<String>,
+) -> ApiResult<Value<RootData>> {
+    let root = api.get_state_root(id.parse()?).await?;
+    Ok(Json(Value {
+        data: RootData { root },
+    }))
+}
+
+async fn get_fork<T: BeaconApi>(
+    Extension(api): Extension<Arc<T>>,
+    ApiPath(id): ApiPath<String>,
+) -> ApiResult<Value<Fork>> {
+    let data = api.get_fork(id.parse()?).await?;
+    Ok(Json(Value { data }))
+}
+
+async fn get_finality_checkpoints<T: BeaconApi>(
+    Extension(api): Extension<Arc<T>>,
+    ApiPath(id): ApiPath<String>,
+) -> ApiResult<Value<FinalityCheckpoints>> {
+    let data = api.get_finality_checkpoints(id.parse()?).await?;
+    Ok(Json(Value { data }))
+}
+
+async fn get_validators<T: BeaconApi>(
+    Extension(api): Extension<Arc<T>>,
+    ApiPath(id): ApiPath<String>,
+    ApiQuery(query): ApiQuery<ValidatorsQuery>,
+) -> ApiResult<Value<Vec<ValidatorSummary>>> {
+    let validator_ids = parse_list(query.id.as_ref())?;
+    let statuses = parse_list(query.status.as_ref())?;
+    let data = api
+        .get_validators(id.parse()?, validator_ids, statuses)
+        .await?;
+    Ok(Json(Value { data }))
+}
+
+async fn get_validator<T: BeaconApi>(
+    Extension(api): Extension<Arc<T>>,
+    ApiPath((id, validator_id)): ApiPath<(String, String)>,
+) -> ApiResult<Value<ValidatorSummary>> {
+    let data = api
+        .get_validator(id.parse()?, validator_id.parse()?)
+        .await?;
+    Ok(Json(Value { data }))
+}
+
+async fn get_balances<T: BeaconApi>(
+    Extension(api): Extension<Arc<T>>,
+    ApiPath(id): ApiPath<String>,
+    ApiQuery(query): ApiQuery<ValidatorsQuery>,
+) -> ApiResult<Value<Vec<BalanceSummary>>> {
+    let validator_ids = parse_list(query.id.as_ref())?;
+    let data = api.get_balances(id.parse()?, validator_ids).await?;
+    Ok(Json(Value { data }))
+}
+
+async fn get_committees<T: BeaconApi>(
+    Extension(api): Extension<Arc<T>>,
+    ApiPath(id): ApiPath<String>,
+    ApiQuery(filter): ApiQuery<CommitteeFilter>,
+) -> ApiResult<Value<Vec<CommitteeSummary>>> {
+    let data = api.get_committees(id.parse()?, filter).await?;
+    Ok(Json(Value { data }))
+}
+
+async fn get_beacon_header<T: BeaconApi>(
+    Extension(api): Extension<Arc<T>>,
+    ApiPath(id): ApiPath<String>,
+) -> ApiResult<Value<BeaconHeaderSummary>> {
+    let data = api.get_beacon_header(id.parse()?).await?;
+    Ok(Json(Value { data }))
+}
+
+async fn get_beacon_block<T: BeaconApi>(
+    Extension(api): Extension<Arc<T>>,
+    ApiPath(id): ApiPath<String>,
+) -> ApiResult<SignedBeaconBlock> {
+    let block = api.get_beacon_block(id.parse()?).await?;
+    Ok(Json(block))
+}
+
+async fn get_beacon_block_root<T: BeaconApi>(
+    Extension(api): Extension<Arc<T>>,
+    ApiPath(id): ApiPath<String>,
+) -> ApiResult<Value<RootData>> {
+    let root = api.get_beacon_block_root(id.parse()?).await?;
+    Ok(Json(Value {
+        data: RootData { root },
+    }))
+}
+
+async fn get_spec<T: BeaconApi>(
+    Extension(api): Extension<Arc<T>>,
+) -> ApiResult<Value<HashMap<String, String>>> {
+    let data = api.get_spec().await?;
+    Ok(Json(Value { data }))
+}
+
+async fn get_node_identity<T: BeaconApi>(
+    Extension(api): Extension<Arc<T>>,
+) -> ApiResult<Value<NodeIdentity>> {
+    let data = api.get_node_identity().await?;
+    Ok(Json(Value { data }))
+}
+
+async fn get_node_peers<T: BeaconApi>(
+    Extension(api): Extension<Arc<T>>,
+    ApiQuery(query): ApiQuery<PeersQuery>,
+) -> ApiResult<Value<Vec<PeerDescription>>> {
+    let states = parse_list(query.state.as_ref())?;
+    let directions = parse_list(query.direction.as_ref())?;
+    let data = api.get_node_peers(states, directions).await?;
+    Ok(Json(Value { data }))
+}
+
+async fn get_peer_description<T: BeaconApi>(
+    Extension(api): Extension<Arc<T>>,
+    ApiPath(peer_id): ApiPath<String>,
+) -> ApiResult<Value<PeerDescription>> {
+    let peer_id = peer_id
+        .parse()
+        .map_err(|_| ApiError::from(InvalidIdentifier(peer_id.clone())))?;
+    let data = api.get_peer_description(peer_id).await?;
+    Ok(Json(Value { data }))
+}
+
+async fn get_peer_count<T: BeaconApi>(
+    Extension(api): Extension<Arc<T>>,
+) -> ApiResult<Value<PeerSummary>> {
+    let data = api.get_peer_count().await?;
+    Ok(Json(Value { data }))
+}
+
+async fn get_node_version<T: BeaconApi>(
+    Extension(api): Extension<Arc<T>>,
+) -> ApiResult<Value<VersionData>> {
+    let version = api.get_node_version().await?;
+    Ok(Json(Value {
+        data: VersionData { version },
+    }))
+}
+
+async fn get_sync_status<T: BeaconApi>(
+    Extension(api): Extension<Arc<T>>,
+) -> ApiResult<Value<SyncStatus>> {
+    let data = api.get_sync_status().await?;
+    Ok(Json(Value { data }))
+}
+
+async fn get_health<T: BeaconApi>(Extension(api): Extension<Arc<T>>) -> StatusCode {
+    match api.get_health().await {
+        HealthStatus::Ready => StatusCode::OK,
+        HealthStatus::Syncing => StatusCode::PARTIAL_CONTENT,
+        HealthStatus::NotInitialized => StatusCode::SERVICE_UNAVAILABLE,
+        HealthStatus::Unknown => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+fn parse_epoch(epoch: &str) -> Result<Epoch, ApiError> {
+    epoch
+        .parse()
+        .map_err(|_| ApiError::from(InvalidIdentifier(epoch.to_string())))
+}
+
+async fn get_attester_duties<T: BeaconApi>(
+    Extension(api): Extension<Arc<T>>,
+    ApiPath(epoch): ApiPath<String>,
+    ApiJson(indices): ApiJson<Vec<String>>,
+) -> ApiResult<DependentValue<Vec<AttesterDuty>>> {
+    let indices = parse_indices(&indices)?;
+    let (dependent_root, data) = api
+        .get_attester_duties(parse_epoch(&epoch)?, indices)
+        .await?;
+    Ok(Json(DependentValue {
+        dependent_root,
+        data,
+    }))
+}
+
+async fn get_proposer_duties<T: BeaconApi>(
+    Extension(api): Extension<Arc<T>>,
+    ApiPath(epoch): ApiPath<String>,
+) -> ApiResult<DependentValue<Vec<ProposerDuty>>> {
+    let (dependent_root, data) = api.get_proposer_duties(parse_epoch(&epoch)?).await?;
+    Ok(Json(DependentValue {
+        dependent_root,
+        data,
+    }))
+}
+
+async fn get_sync_committee_duties<T: BeaconApi>(
+    Extension(api): Extension<Arc<T>>,
+    ApiPath(epoch): ApiPath<String>,
+    ApiJson(indices): ApiJson<Vec<String>>,
+) -> ApiResult<Value<Vec<SyncCommitteeDuty>>> {
+    let indices = parse_indices(&indices)?;
+    let data = api
+        .get_sync_committee_duties(parse_epoch(&epoch)?, indices)
+        .await?;
+    Ok(Json(Value { data }))
+}
+
+/// Build a `Router` serving the Beacon API endpoints backed by `api`.
+pub fn router<T: BeaconApi>(api: T) -> Router {
+    Router::new()
+        .route("/eth/v1/beacon/genesis", get(get_genesis_details::<T>))
+        .route(
+            "/eth/v1/beacon/states/:state_id/root",
+            get(get_state_root::<T>),
+        )
+        .route("/eth/v1/beacon/states/:state_id/fork", get(get_fork::<T>))
+        .route(
+            "/eth/v1/beacon/states/:state_id/finality_checkpoints",
+            get(get_finality_checkpoints::<T>),
+        )
+        .route(
+            "/eth/v1/beacon/states/:state_id/validators",
+            get(get_validators::<T>),
+        )
+        .route(
+            "/eth/v1/beacon/states/:state_id/validators/:validator_id",
+            get(get_validator::<T>),
+        )
+        .route(
+            "/eth/v1/beacon/states/:state_id/validator_balances",
+            get(get_balances::<T>),
+        )
+        .route(
+            "/eth/v1/beacon/states/:state_id/committees",
+            get(get_committees::<T>),
+        )
+        .route(
+            "/eth/v1/beacon/headers/:block_id",
+            get(get_beacon_header::<T>),
+        )
+        .route(
+            "/eth/v2/beacon/blocks/:block_id",
+            get(get_beacon_block::<T>),
+        )
+        .route(
+            "/eth/v1/beacon/blocks/:block_id/root",
+            get(get_beacon_block_root::<T>),
+        )
+        .route("/eth/v1/config/spec", get(get_spec::<T>))
+        .route("/eth/v1/node/identity", get(get_node_identity::<T>))
+        .route("/eth/v1/node/peers", get(get_node_peers::<T>))
+        .route(
+            "/eth/v1/node/peers/:peer_id",
+            get(get_peer_description::<T>),
+        )
+        .route("/eth/v1/node/peer_count", get(get_peer_count::<T>))
+        .route("/eth/v1/node/version", get(get_node_version::<T>))
+        .route("/eth/v1/node/syncing", get(get_sync_status::<T>))
+        .route("/eth/v1/node/health", get(get_health::<T>))
+        .route(
+            "/eth/v1/validator/duties/attester/:epoch",
+            post(get_attester_duties::<T>),
+        )
+        .route(
+            "/eth/v1/validator/duties/proposer/:epoch",
+            get(get_proposer_duties::<T>),
+        )
+        .route(
+            "/eth/v1/validator/duties/sync/:epoch",
+            post(get_sync_committee_duties::<T>),
+        )
+        .fallback(not_found.into_service())
+        .layer(Extension(Arc::new(api)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::beacon_api::{Client, Error};
+    use crate::phase0::mainnet::BeaconState;
+    use reqwest::Url;
+    use ssz_rs::prelude::*;
+    use std::net::TcpListener;
+
+    struct StateBackedApi {
+        state: BeaconState,
+        peers: Vec<PeerDescription>,
+    }
+
+    #[async_trait]
+    impl BeaconApi for StateBackedApi {
+        async fn get_genesis_details(&self) -> Result<GenesisDetails, ApiError> {
+            Ok(GenesisDetails {
+                genesis_time: self.state.genesis_time,
+                genesis_validators_root: self.state.genesis_validators_root,
+                genesis_fork_version: self.state.fork.previous_version,
+            })
+        }
+
+        async fn get_state_root(&self, id: StateId) -> Result<Root, ApiError> {
+            match id {
+                StateId::Head => {
+                    let mut state = self.state.clone();
+                    state
+                        .hash_tree_root()
+                        .map_err(|err| ApiError::new(500, err.to_string()))
+                }
+                _ => Err(ApiError::new(404, "state not found")),
+            }
+        }
+
+        async fn get_node_peers(
+            &self,
+            states: Vec<PeerState>,
+            directions: Vec<ConnectionOrientation>,
+        ) -> Result<Vec<PeerDescription>, ApiError> {
+            Ok(self
+                .peers
+                .iter()
+                .filter(|peer| states.is_empty() || states.contains(&peer.state))
+                .filter(|peer| directions.is_empty() || directions.contains(&peer.direction))
+                .cloned()
+                .collect())
+        }
+
+        async fn get_peer_description(&self, peer_id: PeerId) -> Result<PeerDescription, ApiError> {
+            self.peers
+                .iter()
+                .find(|peer| peer.peer_id == peer_id)
+                .cloned()
+                .ok_or_else(|| ApiError::new(404, "peer not found"))
+        }
+    }
+
+    fn peer(state: PeerState, direction: ConnectionOrientation) -> PeerDescription {
+        PeerDescription {
+            peer_id: PeerId::random(),
+            enr: None,
+            last_seen_p2p_address: "/ip4/127.0.0.1/tcp/9000".parse().unwrap(),
+            state,
+            direction,
+        }
+    }
+
+    fn spawn_server(api: StateBackedApi) -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = axum::Server::from_tcp(listener)
+            .unwrap()
+            .serve(router(api).into_make_service());
+        tokio::spawn(server);
+        Url::parse(&format!("http://{addr}")).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_router() {
+        let mut state = BeaconState::default();
+        state.genesis_time = 1606824023;
+        let expected_root = state.clone().hash_tree_root().unwrap();
+        let client = Client::new(spawn_server(StateBackedApi {
+            state,
+            peers: vec![],
+        }));
+
+        let genesis = client.get_genesis_details().await.unwrap();
+        assert_eq!(genesis.genesis_time, 1606824023);
+
+        let root = client.get_state_root(StateId::Head).await.unwrap();
+        assert_eq!(root, expected_root);
+
+        let err = client.get_state_root(StateId::Finalized).await.unwrap_err();
+        assert!(matches!(err, Error::Api(ApiError { code: 404, .. })));
+
+        let response = client
+            .http_get("eth/v1/beacon/states/not-an-id/root")
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 400);
+
+        let err = client.get_proposer_duties(0).await.unwrap_err();
+        assert!(matches!(err, Error::Api(ApiError { code: 501, .. })));
+
+        let err = client
+            .get_beacon_block_root(BlockId::Root(Root::default()))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Api(ApiError { code: 501, .. })));
+    }
+
+    #[tokio::test]
+    async fn test_node_peers() {
+        let peers = vec![
+            peer(PeerState::Connected, ConnectionOrientation::Inbound),
+            peer(PeerState::Connected, ConnectionOrientation::Outbound),
+            peer(PeerState::Disconnected, ConnectionOrientation::Outbound),
+        ];
+        let client = Client::new(spawn_server(StateBackedApi {
+            state: BeaconState::default(),
+            peers: peers.clone(),
+        }));
+
+        let all_peers = client.get_node_peers().await.unwrap();
+        assert_eq!(all_peers.len(), 3);
+
+        let response = client
+            .http_get("eth/v1/node/peers?state=connected&direction=outbound")
+            .await
+            .unwrap();
+        let filtered: Value<Vec<PeerDescription>> = response.json().await.unwrap();
+        assert_eq!(filtered.data.len(), 1);
+        assert_eq!(filtered.data[0].peer_id, peers[1].peer_id);
+
+        let peer = client.get_peer_description(peers[2].peer_id).await.unwrap();
+        assert_eq!(peer.state, PeerState::Disconnected);
+
+        let err = client
+            .get_peer_description(PeerId::random())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Api(ApiError { code: 404, .. })));
+
+        let err = client.get_node_identity().await.unwrap_err();
+        assert!(matches!(err, Error::Api(ApiError { code: 501, .. })));
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_api_errors() {
+        let client = Client::new(spawn_server(StateBackedApi {
+            state: BeaconState::default(),
+            peers: vec![],
+        }));
+
+        let response = client.http_get("eth/v1/not/an/endpoint").await.unwrap();
+        assert_eq!(response.status(), 404);
+        let err: ApiError = response.json().await.unwrap();
+        assert_eq!(err.code, 404);
+
+        let response = client
+            .http_post(
+                "eth/v1/validator/duties/attester/1",
+                &serde_json::json!({ "indices": ["1"] }),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        assert!(status.is_client_error());
+        let err: ApiError = response.json().await.unwrap();
+        assert_eq!(err.code, status.as_u16());
+
+        let response = client
+            .http_get("eth/v1/node/peers?state=lost")
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 400);
+        let err: ApiError = response.json().await.unwrap();
+        assert_eq!(err.code, 400);
+    }
+}
@@ -0,0 +1,407 @@
+use crate::altair::mainnet as altair;
+use crate::bellatrix::mainnet as bellatrix;
+use crate::networking::{Enr, MetaDataV2, Multiaddr, PeerId};
+use crate::phase0::mainnet as phase0;
+use crate::phase0::{Checkpoint, SignedBeaconBlockHeader, Validator};
+use crate::primitives::{
+    BlsPublicKey, CommitteeIndex, Epoch, Gwei, Root, Slot, ValidatorIndex, Version,
+};
+use serde::de::IntoDeserializer;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// The body of an error response from the API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiError {
+    pub code: u16,
+    pub message: String,
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (code {})", self.message, self.code)
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl ApiError {
+    pub fn new(code: u16, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// A path or query parameter that does not name a valid identifier.
+#[derive(Debug, Error)]
+#[error("invalid identifier `{0}`")]
+pub struct InvalidIdentifier(pub String);
+
+fn parse_root(s: &str) -> Option<Root> {
+    let bytes = hex::decode(s.strip_prefix("0x")?).ok()?;
+    Some(Root::from_bytes(bytes.try_into().ok()?))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Value<T> {
+    pub data: T,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DependentValue<T> {
+    pub dependent_root: Root,
+    pub data: T,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConsensusVersion {
+    Phase0,
+    Altair,
+    Bellatrix,
+}
+
+impl fmt::Display for ConsensusVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Phase0 => "phase0",
+            Self::Altair => "altair",
+            Self::Bellatrix => "bellatrix",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VersionedValue<T> {
+    pub version: ConsensusVersion,
+    pub data: T,
+}
+
+/// A `SignedBeaconBlock` of any fork, as returned by `/eth/v2/beacon/blocks/{block_id}`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "version", content = "data", rename_all = "lowercase")]
+pub enum SignedBeaconBlock {
+    Phase0(phase0::SignedBeaconBlock),
+    Altair(altair::SignedBeaconBlock),
+    Bellatrix(bellatrix::SignedBeaconBlock),
+}
+
+impl SignedBeaconBlock {
+    pub fn version(&self) -> ConsensusVersion {
+        match self {
+            Self::Phase0(_) => ConsensusVersion::Phase0,
+            Self::Altair(_) => ConsensusVersion::Altair,
+            Self::Bellatrix(_) => ConsensusVersion::Bellatrix,
+        }
+    }
+
+    pub fn slot(&self) -> Slot {
+        match self {
+            Self::Phase0(block) => block.message.slot,
+            Self::Altair(block) => block.message.slot,
+            Self::Bellatrix(block) => block.message.slot,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateId {
+    Head,
+    Genesis,
+    Finalized,
+    Justified,
+    Slot(Slot),
+    Root(Root),
+}
+
+impl fmt::Display for StateId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Head => write!(f, "head"),
+            Self::Genesis => write!(f, "genesis"),
+            Self::Finalized => write!(f, "finalized"),
+            Self::Justified => write!(f, "justified"),
+            Self::Slot(slot) => write!(f, "{slot}"),
+            Self::Root(root) => write!(f, "0x{}", hex::encode(root.as_bytes())),
+        }
+    }
+}
+
+impl FromStr for StateId {
+    type Err = InvalidIdentifier;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = match s {
+            "head" => Self::Head,
+            "genesis" => Self::Genesis,
+            "finalized" => Self::Finalized,
+            "justified" => Self::Justified,
+            s if s.starts_with("0x") => {
+                Self::Root(parse_root(s).ok_or_else(|| InvalidIdentifier(s.to_string()))?)
+            }
+            s => Self::Slot(s.parse().map_err(|_| InvalidIdentifier(s.to_string()))?),
+        };
+        Ok(id)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockId {
+    Head,
+    Genesis,
+    Finalized,
+    Slot(Slot),
+    Root(Root),
+}
+
+impl fmt::Display for BlockId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Head => write!(f, "head"),
+            Self::Genesis => write!(f, "genesis"),
+            Self::Finalized => write!(f, "finalized"),
+            Self::Slot(slot) => write!(f, "{slot}"),
+            Self::Root(root) => write!(f, "0x{}", hex::encode(root.as_bytes())),
+        }
+    }
+}
+
+impl FromStr for BlockId {
+    type Err = InvalidIdentifier;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = match s {
+            "head" => Self::Head,
+            "genesis" => Self::Genesis,
+            "finalized" => Self::Finalized,
+            s if s.starts_with("0x") => {
+                Self::Root(parse_root(s).ok_or_else(|| InvalidIdentifier(s.to_string()))?)
+            }
+            s => Self::Slot(s.parse().map_err(|_| InvalidIdentifier(s.to_string()))?),
+        };
+        Ok(id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PublicKeyOrIndex {
+    PublicKey(BlsPublicKey),
+    Index(ValidatorIndex),
+}
+
+impl fmt::Display for PublicKeyOrIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PublicKey(public_key) => write!(f, "{public_key}"),
+            Self::Index(index) => write!(f, "{index}"),
+        }
+    }
+}
+
+impl FromStr for PublicKeyOrIndex {
+    type Err = InvalidIdentifier;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with("0x") {
+            let public_key = BlsPublicKey::try_from(s.to_string())
+                .map_err(|_| InvalidIdentifier(s.to_string()))?;
+            Ok(Self::PublicKey(public_key))
+        } else {
+            let index = s.parse().map_err(|_| InvalidIdentifier(s.to_string()))?;
+            Ok(Self::Index(index))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenesisDetails {
+    #[serde(with = "crate::serde::as_string")]
+    pub genesis_time: u64,
+    pub genesis_validators_root: Root,
+    #[serde(with = "crate::serde::as_hex")]
+    pub genesis_fork_version: Version,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RootData {
+    pub root: Root,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FinalityCheckpoints {
+    pub previous_justified: Checkpoint,
+    pub current_justified: Checkpoint,
+    pub finalized: Checkpoint,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidatorStatus {
+    PendingInitialized,
+    PendingQueued,
+    ActiveOngoing,
+    ActiveExiting,
+    ActiveSlashed,
+    ExitedUnslashed,
+    ExitedSlashed,
+    WithdrawalPossible,
+    WithdrawalDone,
+}
+
+impl FromStr for ValidatorStatus {
+    type Err = InvalidIdentifier;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let deserializer: serde::de::value::StrDeserializer<serde::de::value::Error> =
+            s.into_deserializer();
+        Self::deserialize(deserializer).map_err(|_| InvalidIdentifier(s.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidatorSummary {
+    #[serde(with = "crate::serde::as_string")]
+    pub index: ValidatorIndex,
+    #[serde(with = "crate::serde::as_string")]
+    pub balance: Gwei,
+    pub status: ValidatorStatus,
+    pub validator: Validator,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceSummary {
+    #[serde(with = "crate::serde::as_string")]
+    pub index: ValidatorIndex,
+    #[serde(with = "crate::serde::as_string")]
+    pub balance: Gwei,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitteeSummary {
+    #[serde(with = "crate::serde::as_string")]
+    pub index: CommitteeIndex,
+    #[serde(with = "crate::serde::as_string")]
+    pub slot: Slot,
+    #[serde(with = "crate::serde::collection_over_string")]
+    pub validators: Vec<ValidatorIndex>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CommitteeFilter {
+    pub epoch: Option<Epoch>,
+    pub index: Option<CommitteeIndex>,
+    pub slot: Option<Slot>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncCommitteeSummary {
+    #[serde(with = "crate::serde::collection_over_string")]
+    pub validators: Vec<ValidatorIndex>,
+    pub validator_aggregates: Vec<SyncSubcommitteeIndices>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SyncSubcommitteeIndices(
+    #[serde(with = "crate::serde::collection_over_string")] pub Vec<ValidatorIndex>,
+);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BeaconHeaderSummary {
+    pub root: Root,
+    pub canonical: bool,
+    pub header: SignedBeaconBlockHeader,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeIdentity {
+    pub peer_id: PeerId,
+    pub enr: Enr,
+    pub p2p_addresses: Vec<Multiaddr>,
+    pub discovery_addresses: Vec<Multiaddr>,
+    pub metadata: MetaDataV2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PeerState {
+    Disconnected,
+    Connecting,
+    Connected,
+    Disconnecting,
+}
+
+impl FromStr for PeerState {
+    type Err = InvalidIdentifier;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let deserializer: serde::de::value::StrDeserializer<serde::de::value::Error> =
+            s.into_deserializer();
+        Self::deserialize(deserializer).map_err(|_| InvalidIdentifier(s.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionOrientation {
+    Inbound,
+    Outbound,
+}
+
+impl FromStr for ConnectionOrientation {
+    type Err = InvalidIdentifier;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let deserializer: serde::de::value::StrDeserializer<serde::de::value::Error> =
+            s.into_deserializer();
+        Self::deserialize(deserializer).map_err(|_| InvalidIdentifier(s.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeerDescription {
+    pub peer_id: PeerId,
+    pub enr: Option<Enr>,
+    pub last_seen_p2p_address: Multiaddr,
+    pub state: PeerState,
+    pub direction: ConnectionOrientation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeerSummary {
+    #[serde(with = "crate::serde::as_string")]
+    pub disconnected: usize,
+    #[serde(with = "crate::serde::as_string")]
+    pub connecting: usize,
+    #[serde(with = "crate::serde::as_string")]
+    pub connected: usize,
+    #[serde(with = "crate::serde::as_string")]
+    pub disconnecting: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionData {
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncStatus {
+    #[serde(with = "crate::serde::as_string")]
+    pub head_slot: Slot,
+    #[serde(with = "crate::serde::as_string")]
+    pub sync_distance: usize,
+    pub is_syncing: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthStatus {
+    Ready,
+    Syncing,
+    NotInitialized,
+    Unknown,
+}
@@ -6,7 +6,7 @@ use crate::phase0::{
 use crate::primitives::{BlsSignature, Bytes32, Root, Slot, ValidatorIndex};
 use ssz_rs::prelude::*;
 
-#[derive(Default, Debug, SimpleSerialize)]
+#[derive(Default, Debug, Clone, SimpleSerialize)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BeaconBlockBody<
     const MAX_PROPOSER_SLASHINGS: usize,
@@ -39,7 +39,7 @@ pub struct BeaconBlockBody<
     >,
 }
 
-#[derive(Default, Debug, SimpleSerialize)]
+#[derive(Default, Debug, Clone, SimpleSerialize)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BeaconBlock<
     const MAX_PROPOSER_SLASHINGS: usize,
@@ -75,7 +75,7 @@ pub struct BeaconBlock<
     >,
 }
 
-#[derive(Default, Debug, SimpleSerialize)]
+#[derive(Default, Debug, Clone, SimpleSerialize)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SignedBeaconBlock<
     const MAX_PROPOSER_SLASHINGS: usize,
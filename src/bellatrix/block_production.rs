@@ -0,0 +1,812 @@
+use crate::bellatrix as spec;
+
+use crate::altair::SyncAggregate;
+use crate::engine_api::{self, ExecutionEngine, ForkchoiceStateV1, PayloadAttributesV1, PayloadId};
+use crate::phase0::{
+    eth1_vote, get_randao_reveal, Attestation, AttesterSlashing, Deposit, Eth1Block, Eth1Data,
+    ProposerSlashing, SignedVoluntaryExit,
+};
+use crate::primitives::{BlsSignature, Bytes32, ExecutionAddress, Hash32, Slot};
+use crate::signer::{self, Signer};
+use crate::state_transition::{Context, Error};
+use spec::helpers::{get_beacon_proposer_index, get_current_epoch, get_randao_mix};
+use spec::{
+    compute_timestamp_at_slot, is_merge_transition_complete, process_attestation,
+    process_attester_slashing, process_block, process_block_header, process_deposit,
+    process_eth1_data, process_proposer_slashing, process_randao, process_slots,
+    process_sync_aggregate, process_voluntary_exit, BeaconBlock, BeaconState, BlindedBeaconBlock,
+    ExecutionPayload, ExecutionPayloadHeader,
+};
+use ssz_rs::prelude::*;
+
+/// Candidate operations for inclusion in a block.
+///
+/// Each collection may contain more operations than fit in a block and
+/// may contain operations that are invalid against the state the block is
+/// built on; `produce_block` only includes those that can be processed.
+/// The `sync_aggregate` is replaced with an empty aggregate if it is not valid
+/// against the state, while the `execution_payload` is always included and is
+/// typically obtained via `prepare_execution_payload` and `get_execution_payload`.
+#[derive(Default, Debug)]
+pub struct BlockOperations<
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+> {
+    pub proposer_slashings: Vec<ProposerSlashing>,
+    pub attester_slashings: Vec<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>>,
+    pub attestations: Vec<Attestation<MAX_VALIDATORS_PER_COMMITTEE>>,
+    pub deposits: Vec<Deposit>,
+    pub voluntary_exits: Vec<SignedVoluntaryExit>,
+    pub sync_aggregate: SyncAggregate<SYNC_COMMITTEE_SIZE>,
+    pub execution_payload: ExecutionPayload<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+}
+
+/// Candidate operations for inclusion in a blinded block, as `BlockOperations`
+/// but committing to the execution payload of a builder via its header.
+#[derive(Default, Debug)]
+pub struct BlindedBlockOperations<
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+> {
+    pub proposer_slashings: Vec<ProposerSlashing>,
+    pub attester_slashings: Vec<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>>,
+    pub attestations: Vec<Attestation<MAX_VALIDATORS_PER_COMMITTEE>>,
+    pub deposits: Vec<Deposit>,
+    pub voluntary_exits: Vec<SignedVoluntaryExit>,
+    pub sync_aggregate: SyncAggregate<SYNC_COMMITTEE_SIZE>,
+    pub execution_payload_header: ExecutionPayloadHeader<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+}
+
+/// Ask the `engine` to start building an execution payload for a block at the
+/// slot of `state`, returning the id to fetch it with via `get_execution_payload`.
+///
+/// The `state` must already be advanced to the slot of the block. Building on the
+/// terminal proof-of-work block is not supported, so no payload is prepared until
+/// the merge transition is complete and the block carries an empty payload.
+pub async fn prepare_execution_payload<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    engine: &impl ExecutionEngine,
+    safe_block_hash: Hash32,
+    finalized_block_hash: Hash32,
+    suggested_fee_recipient: ExecutionAddress,
+    context: &Context,
+) -> Result<Option<PayloadId>, engine_api::Error> {
+    if !is_merge_transition_complete(state) {
+        return Ok(None);
+    }
+
+    let forkchoice_state = ForkchoiceStateV1 {
+        head_block_hash: state.latest_execution_payload_header.block_hash.clone(),
+        safe_block_hash,
+        finalized_block_hash,
+    };
+    let current_epoch = get_current_epoch(state, context);
+    let payload_attributes = PayloadAttributesV1 {
+        timestamp: compute_timestamp_at_slot(state, state.slot, context),
+        prev_randao: get_randao_mix(state, current_epoch).clone(),
+        suggested_fee_recipient,
+    };
+    let response = engine
+        .forkchoice_updated(forkchoice_state, Some(payload_attributes))
+        .await?;
+    Ok(response.payload_id)
+}
+
+/// Fetch the execution payload built by the `engine` for `payload_id`,
+/// or an empty payload if none was prepared.
+pub async fn get_execution_payload<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+>(
+    payload_id: Option<PayloadId>,
+    engine: &impl ExecutionEngine,
+) -> Result<
+    ExecutionPayload<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    engine_api::Error,
+> {
+    match payload_id {
+        Some(payload_id) => {
+            let payload = engine.get_payload(payload_id).await?;
+            Ok(ExecutionPayload::try_from(payload)?)
+        }
+        None => Ok(ExecutionPayload::default()),
+    }
+}
+
+/// Build an unsigned block for `slot` on top of `state`.
+///
+/// The `state` is not modified; a copy is advanced to `slot` (if needed) and
+/// the resulting block is applied to it to compute the block's `state_root`.
+/// See `phase0::produce_block` for how the randao reveal, the eth1 vote and
+/// the operations are selected.
+pub async fn produce_block<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    slot: Slot,
+    signer: &impl Signer,
+    eth1_chain: &[Eth1Block],
+    graffiti: Bytes32,
+    operations: BlockOperations<
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    context: &Context,
+) -> Result<
+    BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    signer::Error,
+> {
+    let (mut state, randao_reveal, eth1_data) =
+        advance_state_and_vote(state, slot, signer, eth1_chain, context).await?;
+    let block = build_block(
+        &mut state,
+        randao_reveal,
+        eth1_data,
+        graffiti,
+        operations,
+        context,
+    )?;
+    Ok(block)
+}
+
+/// Build an unsigned blinded block for `slot` on top of `state` for the execution
+/// payload committed to by `operations.execution_payload_header`, as obtained from a builder.
+///
+/// Otherwise the block is built as in `produce_block`.
+pub async fn produce_blinded_block<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    slot: Slot,
+    signer: &impl Signer,
+    eth1_chain: &[Eth1Block],
+    graffiti: Bytes32,
+    operations: BlindedBlockOperations<
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    context: &Context,
+) -> Result<
+    BlindedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    signer::Error,
+> {
+    let (mut state, randao_reveal, eth1_data) =
+        advance_state_and_vote(state, slot, signer, eth1_chain, context).await?;
+    let block = build_blinded_block(
+        &mut state,
+        randao_reveal,
+        eth1_data,
+        graffiti,
+        operations,
+        context,
+    )?;
+    Ok(block)
+}
+
+// Advance a copy of `state` to `slot` and compute the randao reveal
+// and the eth1 vote of the block proposed on it.
+async fn advance_state_and_vote<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    slot: Slot,
+    signer: &impl Signer,
+    eth1_chain: &[Eth1Block],
+    context: &Context,
+) -> Result<
+    (
+        BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+        >,
+        BlsSignature,
+        Eth1Data,
+    ),
+    signer::Error,
+> {
+    let mut state = state.clone();
+    if state.slot < slot {
+        process_slots(&mut state, slot, context)?;
+    }
+
+    let proposer_index = get_beacon_proposer_index(&state, context)?;
+    let randao_reveal = get_randao_reveal(
+        signer,
+        &state.validators[proposer_index].public_key,
+        &state.fork,
+        state.genesis_validators_root,
+        slot,
+        context,
+    )
+    .await?;
+    let eth1_data = eth1_vote(
+        state.genesis_time,
+        state.slot,
+        &state.eth1_data,
+        &state.eth1_data_votes,
+        eth1_chain,
+        context,
+    );
+    Ok((state, randao_reveal, eth1_data))
+}
+
+// Assemble the block for the slot of `state` from the given operations
+// and apply it to `state`, recording the resulting `state_root`.
+fn build_block<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    randao_reveal: BlsSignature,
+    eth1_data: Eth1Data,
+    graffiti: Bytes32,
+    operations: BlockOperations<
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    context: &Context,
+) -> Result<
+    BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    Error,
+> {
+    let BlockOperations {
+        proposer_slashings,
+        attester_slashings,
+        attestations,
+        deposits,
+        voluntary_exits,
+        sync_aggregate,
+        execution_payload,
+    } = operations;
+
+    let mut block = BeaconBlock {
+        slot: state.slot,
+        proposer_index: get_beacon_proposer_index(state, context)?,
+        parent_root: state.latest_block_header.hash_tree_root()?,
+        ..Default::default()
+    };
+    block.body.randao_reveal = randao_reveal;
+    block.body.eth1_data = eth1_data;
+    block.body.graffiti = graffiti;
+    block.body.execution_payload = execution_payload;
+
+    // NOTE: operations are screened against a scratch copy of the state
+    // in the same order `process_block` applies them so that the
+    // effects of earlier operations are visible to later ones;
+    // the execution payload does not affect them and is only
+    // validated when the block is processed
+    let mut scratch_state = state.clone();
+    process_block_header(&mut scratch_state, &mut block, context)?;
+    process_randao(&mut scratch_state, &block.body, context)?;
+    process_eth1_data(&mut scratch_state, &block.body, context);
+
+    for mut proposer_slashing in proposer_slashings {
+        if block.body.proposer_slashings.len() == context.max_proposer_slashings {
+            break;
+        }
+        if process_proposer_slashing(&mut scratch_state, &mut proposer_slashing, context).is_ok() {
+            block.body.proposer_slashings.push(proposer_slashing);
+        }
+    }
+
+    for mut attester_slashing in attester_slashings {
+        if block.body.attester_slashings.len() == context.max_attester_slashings {
+            break;
+        }
+        if process_attester_slashing(&mut scratch_state, &mut attester_slashing, context).is_ok() {
+            block.body.attester_slashings.push(attester_slashing);
+        }
+    }
+
+    for attestation in attestations {
+        if block.body.attestations.len() == context.max_attestations {
+            break;
+        }
+        if process_attestation(&mut scratch_state, &attestation, context).is_ok() {
+            block.body.attestations.push(attestation);
+        }
+    }
+
+    let pending_deposit_count =
+        scratch_state.eth1_data.deposit_count - scratch_state.eth1_deposit_index;
+    let deposit_count = usize::min(context.max_deposits, pending_deposit_count as usize);
+    for mut deposit in deposits.into_iter().take(deposit_count) {
+        if process_deposit(&mut scratch_state, &mut deposit, context).is_err() {
+            break;
+        }
+        block.body.deposits.push(deposit);
+    }
+
+    for mut voluntary_exit in voluntary_exits {
+        if block.body.voluntary_exits.len() == context.max_voluntary_exits {
+            break;
+        }
+        if process_voluntary_exit(&mut scratch_state, &mut voluntary_exit, context).is_ok() {
+            block.body.voluntary_exits.push(voluntary_exit);
+        }
+    }
+
+    if process_sync_aggregate(&mut scratch_state, &sync_aggregate, context).is_ok() {
+        block.body.sync_aggregate = sync_aggregate;
+    }
+
+    process_block(state, &mut block, context)?;
+    block.state_root = state.hash_tree_root()?;
+
+    Ok(block)
+}
+
+// Assemble the blinded block for the slot of `state` from the given operations
+// and apply it to `state`, recording the resulting `state_root`.
+fn build_blinded_block<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    randao_reveal: BlsSignature,
+    eth1_data: Eth1Data,
+    graffiti: Bytes32,
+    operations: BlindedBlockOperations<
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    context: &Context,
+) -> Result<
+    BlindedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    Error,
+> {
+    let header = operations.execution_payload_header;
+    // NOTE: the block is processed with a payload carrying the fields of the `header`
+    // but none of the transactions, which the `header` only commits to; the commitments
+    // of the state to the payload are then replaced with those of the `header`
+    let execution_payload = ExecutionPayload {
+        parent_hash: header.parent_hash.clone(),
+        fee_recipient: header.fee_recipient.clone(),
+        state_root: header.state_root.clone(),
+        receipts_root: header.receipts_root.clone(),
+        logs_bloom: header.logs_bloom.clone(),
+        prev_randao: header.prev_randao.clone(),
+        block_number: header.block_number,
+        gas_limit: header.gas_limit,
+        gas_used: header.gas_used,
+        timestamp: header.timestamp,
+        extra_data: header.extra_data.clone(),
+        base_fee_per_gas: header.base_fee_per_gas.clone(),
+        block_hash: header.block_hash.clone(),
+        transactions: Default::default(),
+    };
+    let operations = BlockOperations {
+        proposer_slashings: operations.proposer_slashings,
+        attester_slashings: operations.attester_slashings,
+        attestations: operations.attestations,
+        deposits: operations.deposits,
+        voluntary_exits: operations.voluntary_exits,
+        sync_aggregate: operations.sync_aggregate,
+        execution_payload,
+    };
+    let block = build_block(
+        state,
+        randao_reveal,
+        eth1_data,
+        graffiti,
+        operations,
+        context,
+    )?;
+
+    let mut body = block.body.blind()?;
+    state.latest_execution_payload_header = header.clone();
+    body.execution_payload_header = header;
+    state.latest_block_header.body_root = body.hash_tree_root()?;
+
+    Ok(BlindedBeaconBlock {
+        slot: block.slot,
+        proposer_index: block.proposer_index,
+        parent_root: block.parent_root,
+        state_root: state.hash_tree_root()?,
+        body,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bellatrix::helpers::get_domain;
+    use crate::bellatrix::{mainnet, state_transition};
+    use crate::engine_api::{ExecutionPayloadV1, MockExecutionEngine, PayloadStatus};
+    use crate::interop::bellatrix::create_mainnet_genesis_state;
+    use crate::interop::interop_secret_key;
+    use crate::phase0::sign_with_domain;
+    use crate::primitives::DomainType;
+    use crate::signer::LocalSigner;
+    use crate::state_transition::Validation;
+
+    #[tokio::test]
+    async fn test_produced_blocks_pass_state_transition() {
+        let context = Context::for_mainnet();
+        let genesis_block_hash = Hash32::try_from([1u8; 32].as_ref()).unwrap();
+        let execution_payload_header = mainnet::ExecutionPayloadHeader {
+            block_hash: genesis_block_hash.clone(),
+            ..Default::default()
+        };
+        let genesis_state = create_mainnet_genesis_state(64, execution_payload_header, &context);
+        let slot = 1;
+
+        let engine = MockExecutionEngine::default();
+        let genesis_block = ExecutionPayloadV1 {
+            block_hash: genesis_block_hash,
+            ..Default::default()
+        };
+        let status = engine.new_payload(genesis_block).await.unwrap();
+        assert_eq!(status.status, PayloadStatus::Valid);
+
+        let mut state = genesis_state.clone();
+        process_slots(&mut state, slot, &context).unwrap();
+        let proposer_index = get_beacon_proposer_index(&state, &context).unwrap();
+        let signing_key = interop_secret_key(proposer_index).unwrap();
+        let signer = LocalSigner::new([signing_key.clone()]);
+
+        let payload_id = prepare_execution_payload(
+            &state,
+            &engine,
+            Hash32::default(),
+            Hash32::default(),
+            ExecutionAddress::default(),
+            &context,
+        )
+        .await
+        .unwrap();
+        assert!(payload_id.is_some());
+        let execution_payload: mainnet::ExecutionPayload =
+            get_execution_payload(payload_id, &engine).await.unwrap();
+
+        let operations = mainnet::BlockOperations {
+            execution_payload: execution_payload.clone(),
+            ..Default::default()
+        };
+        let mut block: mainnet::BeaconBlock = produce_block(
+            &genesis_state,
+            slot,
+            &signer,
+            &[],
+            Bytes32::default(),
+            operations,
+            &context,
+        )
+        .await
+        .unwrap();
+
+        let domain = get_domain(&state, DomainType::BeaconProposer, None, &context).unwrap();
+        let signature = sign_with_domain(&mut block, &signing_key, domain).unwrap();
+        let mut signed_block = mainnet::SignedBeaconBlock {
+            message: block,
+            signature,
+        };
+        let mut post_state = genesis_state.clone();
+        state_transition(
+            &mut post_state,
+            &mut signed_block,
+            Validation::Enabled,
+            &context,
+        )
+        .unwrap();
+        assert_eq!(
+            post_state.hash_tree_root().unwrap(),
+            signed_block.message.state_root
+        );
+        assert_eq!(
+            post_state.latest_execution_payload_header.block_hash,
+            execution_payload.block_hash
+        );
+
+        // a builder commits to a payload with transactions via its header
+        let mut execution_payload = execution_payload;
+        execution_payload
+            .transactions
+            .push(mainnet::Transaction::try_from([2u8].as_ref()).unwrap());
+        let execution_payload_header =
+            ExecutionPayloadHeader::try_from(&mut execution_payload).unwrap();
+        let operations = mainnet::BlindedBlockOperations {
+            execution_payload_header,
+            ..Default::default()
+        };
+        let blinded_block: mainnet::BlindedBeaconBlock = produce_blinded_block(
+            &genesis_state,
+            slot,
+            &signer,
+            &[],
+            Bytes32::default(),
+            operations,
+            &context,
+        )
+        .await
+        .unwrap();
+
+        let mut block = mainnet::BeaconBlock {
+            slot: blinded_block.slot,
+            proposer_index: blinded_block.proposer_index,
+            parent_root: blinded_block.parent_root,
+            state_root: blinded_block.state_root,
+            body: blinded_block.body.unblind(execution_payload).unwrap(),
+        };
+        let signature = sign_with_domain(&mut block, &signing_key, domain).unwrap();
+        let mut signed_block = mainnet::SignedBeaconBlock {
+            message: block,
+            signature,
+        };
+        let mut post_state = genesis_state;
+        state_transition(
+            &mut post_state,
+            &mut signed_block,
+            Validation::Enabled,
+            &context,
+        )
+        .unwrap();
+        assert_eq!(
+            post_state.hash_tree_root().unwrap(),
+            signed_block.message.state_root
+        );
+    }
+}
@@ -4,7 +4,7 @@ use ssz_rs::prelude::*;
 
 pub type Transaction<const MAX_BYTES_PER_TRANSACTION: usize> = ByteList<MAX_BYTES_PER_TRANSACTION>;
 
-#[derive(Default, Debug, SimpleSerialize)]
+#[derive(Default, Debug, Clone, PartialEq, Eq, SimpleSerialize)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExecutionPayload<
     const BYTES_PER_LOGS_BLOOM: usize,
@@ -32,7 +32,7 @@ pub struct ExecutionPayload<
     pub transactions: List<Transaction<MAX_BYTES_PER_TRANSACTION>, MAX_TRANSACTIONS_PER_PAYLOAD>,
 }
 
-#[derive(Default, Debug, Clone, SimpleSerialize)]
+#[derive(Default, Debug, Clone, PartialEq, Eq, SimpleSerialize)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExecutionPayloadHeader<
     const BYTES_PER_LOGS_BLOOM: usize,
@@ -59,3 +59,54 @@ pub struct ExecutionPayloadHeader<
     pub block_hash: Hash32,
     pub transactions_root: Root,
 }
+
+impl<
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    >
+    TryFrom<
+        &mut ExecutionPayload<
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+        >,
+    >
+    for ExecutionPayloadHeader<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >
+{
+    type Error = MerkleizationError;
+
+    fn try_from(
+        payload: &mut ExecutionPayload<
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+        >,
+    ) -> Result<Self, Self::Error> {
+        let transactions_root = payload.transactions.hash_tree_root()?;
+        Ok(Self {
+            parent_hash: payload.parent_hash.clone(),
+            fee_recipient: payload.fee_recipient.clone(),
+            state_root: payload.state_root.clone(),
+            receipts_root: payload.receipts_root.clone(),
+            logs_bloom: payload.logs_bloom.clone(),
+            prev_randao: payload.prev_randao.clone(),
+            block_number: payload.block_number,
+            gas_limit: payload.gas_limit,
+            gas_used: payload.gas_used,
+            timestamp: payload.timestamp,
+            extra_data: payload.extra_data.clone(),
+            base_fee_per_gas: payload.base_fee_per_gas.clone(),
+            block_hash: payload.block_hash.clone(),
+            transactions_root,
+        })
+    }
+}
@@ -0,0 +1,113 @@
+use crate::altair;
+use crate::bellatrix as spec;
+
+use crate::phase0::{compute_epoch_at_slot, Fork};
+use crate::state_transition::Context;
+use spec::BeaconState;
+
+pub fn upgrade_to_bellatrix<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+>(
+    state: &altair::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+    >,
+    context: &Context,
+) -> BeaconState<
+    SLOTS_PER_HISTORICAL_ROOT,
+    HISTORICAL_ROOTS_LIMIT,
+    ETH1_DATA_VOTES_BOUND,
+    VALIDATOR_REGISTRY_LIMIT,
+    EPOCHS_PER_HISTORICAL_VECTOR,
+    EPOCHS_PER_SLASHINGS_VECTOR,
+    MAX_VALIDATORS_PER_COMMITTEE,
+    SYNC_COMMITTEE_SIZE,
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    MAX_BYTES_PER_TRANSACTION,
+    MAX_TRANSACTIONS_PER_PAYLOAD,
+> {
+    let epoch = compute_epoch_at_slot(state.slot, context);
+    BeaconState {
+        genesis_time: state.genesis_time,
+        genesis_validators_root: state.genesis_validators_root,
+        slot: state.slot,
+        fork: Fork {
+            previous_version: state.fork.current_version,
+            current_version: context.bellatrix_fork_version,
+            epoch,
+        },
+        latest_block_header: state.latest_block_header.clone(),
+        block_roots: state.block_roots.clone(),
+        state_roots: state.state_roots.clone(),
+        historical_roots: state.historical_roots.clone(),
+        eth1_data: state.eth1_data.clone(),
+        eth1_data_votes: state.eth1_data_votes.clone(),
+        eth1_deposit_index: state.eth1_deposit_index,
+        validators: state.validators.clone(),
+        balances: state.balances.clone(),
+        randao_mixes: state.randao_mixes.clone(),
+        slashings: state.slashings.clone(),
+        previous_epoch_participation: state.previous_epoch_participation.clone(),
+        current_epoch_participation: state.current_epoch_participation.clone(),
+        justification_bits: state.justification_bits.clone(),
+        previous_justified_checkpoint: state.previous_justified_checkpoint.clone(),
+        current_justified_checkpoint: state.current_justified_checkpoint.clone(),
+        finalized_checkpoint: state.finalized_checkpoint.clone(),
+        inactivity_scores: state.inactivity_scores.clone(),
+        current_sync_committee: state.current_sync_committee.clone(),
+        next_sync_committee: state.next_sync_committee.clone(),
+        latest_execution_payload_header: Default::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bellatrix::{is_merge_transition_complete, mainnet};
+    use crate::interop::altair::create_mainnet_genesis_state;
+    use ssz_rs::prelude::*;
+
+    #[test]
+    fn test_upgrade_to_bellatrix() {
+        let context = Context::for_mainnet();
+        let mut state = create_mainnet_genesis_state(64, &context);
+        state.slot = 5 * context.slots_per_epoch + 2;
+
+        let mut post_state: mainnet::BeaconState = upgrade_to_bellatrix(&state, &context);
+        assert_eq!(post_state.slot, state.slot);
+        assert_eq!(post_state.fork.previous_version, state.fork.current_version);
+        assert_eq!(
+            post_state.fork.current_version,
+            context.bellatrix_fork_version
+        );
+        assert_eq!(post_state.fork.epoch, 5);
+        assert_eq!(
+            post_state.inactivity_scores.len(),
+            state.inactivity_scores.len()
+        );
+        assert_eq!(
+            post_state.current_sync_committee.hash_tree_root().unwrap(),
+            state.current_sync_committee.hash_tree_root().unwrap()
+        );
+        // the merge happens with the first block carrying an execution payload
+        assert!(!is_merge_transition_complete(&post_state));
+    }
+}
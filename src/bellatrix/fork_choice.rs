@@ -0,0 +1,337 @@
+use crate::bellatrix as spec;
+
+use crate::phase0::compute_epoch_at_slot;
+use crate::primitives::{Hash32, U256};
+use crate::state_transition::{Context, Error, InvalidMergeBlock};
+use spec::BeaconBlock;
+use std::collections::HashMap;
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct PowBlock {
+    pub block_hash: Hash32,
+    pub parent_hash: Hash32,
+    pub total_difficulty: U256,
+}
+
+/// A view of the proof-of-work chain, as followed by the execution client.
+pub trait PowChain {
+    /// Return the block with the given `block_hash`, if known.
+    fn get_pow_block(&self, block_hash: &Hash32) -> Option<PowBlock>;
+
+    /// Return the most recent block of the chain, if any.
+    fn head(&self) -> Option<PowBlock>;
+}
+
+/// A `PowChain` held entirely in memory, e.g. for testing.
+#[derive(Default, Debug)]
+pub struct InMemoryPowChain {
+    blocks: HashMap<Hash32, PowBlock>,
+    head: Option<Hash32>,
+}
+
+impl InMemoryPowChain {
+    /// Blocks are expected to be inserted in chain order; the last one inserted becomes the head.
+    pub fn insert_block(&mut self, block: PowBlock) {
+        self.head = Some(block.block_hash.clone());
+        self.blocks.insert(block.block_hash.clone(), block);
+    }
+}
+
+impl PowChain for InMemoryPowChain {
+    fn get_pow_block(&self, block_hash: &Hash32) -> Option<PowBlock> {
+        self.blocks.get(block_hash).cloned()
+    }
+
+    fn head(&self) -> Option<PowBlock> {
+        self.head
+            .as_ref()
+            .and_then(|block_hash| self.get_pow_block(block_hash))
+    }
+}
+
+pub fn is_valid_terminal_pow_block(block: &PowBlock, parent: &PowBlock, context: &Context) -> bool {
+    let is_total_difficulty_reached = block.total_difficulty >= context.terminal_total_difficulty;
+    let is_parent_total_difficulty_valid =
+        parent.total_difficulty < context.terminal_total_difficulty;
+    is_total_difficulty_reached && is_parent_total_difficulty_valid
+}
+
+pub fn validate_merge_block<
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+>(
+    block: &BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    pow_chain: &impl PowChain,
+    context: &Context,
+) -> Result<(), Error> {
+    let parent_hash = &block.body.execution_payload.parent_hash;
+
+    if context.terminal_block_hash != Hash32::default() {
+        let epoch = compute_epoch_at_slot(block.slot, context);
+        if epoch < context.terminal_block_hash_activation_epoch {
+            return Err(Error::InvalidMergeBlock(
+                InvalidMergeBlock::TerminalBlockHashNotActive {
+                    epoch,
+                    activation_epoch: context.terminal_block_hash_activation_epoch,
+                },
+            ));
+        }
+        if *parent_hash != context.terminal_block_hash {
+            return Err(Error::InvalidMergeBlock(
+                InvalidMergeBlock::TerminalBlockHashMismatch {
+                    expected: context.terminal_block_hash.clone(),
+                    provided: parent_hash.clone(),
+                },
+            ));
+        }
+        return Ok(());
+    }
+
+    let pow_block = pow_chain.get_pow_block(parent_hash).ok_or_else(|| {
+        Error::InvalidMergeBlock(InvalidMergeBlock::UnknownPowBlock(parent_hash.clone()))
+    })?;
+    let pow_parent = pow_chain
+        .get_pow_block(&pow_block.parent_hash)
+        .ok_or_else(|| {
+            Error::InvalidMergeBlock(InvalidMergeBlock::UnknownPowBlock(
+                pow_block.parent_hash.clone(),
+            ))
+        })?;
+    if !is_valid_terminal_pow_block(&pow_block, &pow_parent, context) {
+        return Err(Error::InvalidMergeBlock(
+            InvalidMergeBlock::InvalidTerminalPowBlock(pow_block.block_hash),
+        ));
+    }
+    Ok(())
+}
+
+/// Find the first block of `pow_chain` to reach the terminal total difficulty,
+/// walking back from the head.
+pub fn get_pow_block_at_terminal_total_difficulty(
+    pow_chain: &impl PowChain,
+    context: &Context,
+) -> Option<PowBlock> {
+    let mut block = pow_chain.head()?;
+    if block.total_difficulty < context.terminal_total_difficulty {
+        return None;
+    }
+    loop {
+        // NOTE: the genesis block has no parent, so reaching the terminal
+        // total difficulty alone qualifies it as the terminal block
+        if block.parent_hash == Hash32::default() {
+            return Some(block);
+        }
+        let parent = pow_chain.get_pow_block(&block.parent_hash)?;
+        if parent.total_difficulty < context.terminal_total_difficulty {
+            return Some(block);
+        }
+        block = parent;
+    }
+}
+
+pub fn get_terminal_pow_block(pow_chain: &impl PowChain, context: &Context) -> Option<PowBlock> {
+    if context.terminal_block_hash != Hash32::default() {
+        // NOTE: the terminal block hash override takes precedence over the terminal total difficulty
+        return pow_chain.get_pow_block(&context.terminal_block_hash);
+    }
+    get_pow_block_at_terminal_total_difficulty(pow_chain, context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bellatrix::mainnet::BeaconBlock;
+    use crate::execution::u256_from_be_bytes;
+
+    fn hash(i: u8) -> Hash32 {
+        Hash32::try_from([i; 32].as_ref()).unwrap()
+    }
+
+    fn total_difficulty(value: u64) -> U256 {
+        u256_from_be_bytes(&value.to_be_bytes()).unwrap()
+    }
+
+    fn pow_block(i: u8, parent_hash: Hash32, difficulty: u64) -> PowBlock {
+        PowBlock {
+            block_hash: hash(i),
+            parent_hash,
+            total_difficulty: total_difficulty(difficulty),
+        }
+    }
+
+    // chain `1 <- 2 <- 3 <- 4` where block 3 is the first to reach a TTD of 100
+    fn pow_chain() -> (InMemoryPowChain, Vec<PowBlock>) {
+        let blocks = vec![
+            pow_block(1, Hash32::default(), 50),
+            pow_block(2, hash(1), 90),
+            pow_block(3, hash(2), 110),
+            pow_block(4, hash(3), 130),
+        ];
+        let mut chain = InMemoryPowChain::default();
+        for block in &blocks {
+            chain.insert_block(block.clone());
+        }
+        (chain, blocks)
+    }
+
+    fn context() -> Context {
+        let mut context = Context::for_mainnet();
+        context.terminal_total_difficulty = total_difficulty(100);
+        context.terminal_block_hash = Hash32::default();
+        context
+    }
+
+    fn merge_block(slot: u64, parent_hash: Hash32) -> BeaconBlock {
+        let mut block = BeaconBlock {
+            slot,
+            ..Default::default()
+        };
+        block.body.execution_payload.parent_hash = parent_hash;
+        block
+    }
+
+    #[test]
+    fn test_is_valid_terminal_pow_block() {
+        let context = context();
+        let (_, blocks) = pow_chain();
+
+        // crossing the TTD at the block
+        assert!(is_valid_terminal_pow_block(
+            &blocks[2], &blocks[1], &context
+        ));
+        // parent already above the TTD
+        assert!(!is_valid_terminal_pow_block(
+            &blocks[3], &blocks[2], &context
+        ));
+        // TTD not yet reached
+        assert!(!is_valid_terminal_pow_block(
+            &blocks[1], &blocks[0], &context
+        ));
+    }
+
+    #[test]
+    fn test_get_pow_block_at_terminal_total_difficulty() {
+        let mut context = context();
+        let (chain, blocks) = pow_chain();
+        assert_eq!(
+            get_pow_block_at_terminal_total_difficulty(&chain, &context),
+            Some(blocks[2].clone())
+        );
+        assert_eq!(
+            get_terminal_pow_block(&chain, &context),
+            Some(blocks[2].clone())
+        );
+
+        // the parent of the terminal block is missing
+        let mut partial_chain = InMemoryPowChain::default();
+        partial_chain.insert_block(blocks[2].clone());
+        partial_chain.insert_block(blocks[3].clone());
+        assert_eq!(
+            get_pow_block_at_terminal_total_difficulty(&partial_chain, &context),
+            None
+        );
+
+        // the head has not reached the TTD
+        context.terminal_total_difficulty = total_difficulty(200);
+        assert_eq!(
+            get_pow_block_at_terminal_total_difficulty(&chain, &context),
+            None
+        );
+    }
+
+    #[test]
+    fn test_validate_merge_block() {
+        let context = context();
+        let (chain, blocks) = pow_chain();
+
+        let block = merge_block(1, hash(3));
+        assert!(validate_merge_block(&block, &chain, &context).is_ok());
+
+        // parent of the pow block is already above the TTD
+        let block = merge_block(1, hash(4));
+        assert!(matches!(
+            validate_merge_block(&block, &chain, &context),
+            Err(Error::InvalidMergeBlock(
+                InvalidMergeBlock::InvalidTerminalPowBlock(block_hash)
+            )) if block_hash == hash(4)
+        ));
+
+        // unknown pow block
+        let block = merge_block(1, hash(5));
+        assert!(matches!(
+            validate_merge_block(&block, &chain, &context),
+            Err(Error::InvalidMergeBlock(
+                InvalidMergeBlock::UnknownPowBlock(block_hash)
+            )) if block_hash == hash(5)
+        ));
+
+        // missing parent of the pow block
+        let mut partial_chain = InMemoryPowChain::default();
+        partial_chain.insert_block(blocks[2].clone());
+        let block = merge_block(1, hash(3));
+        assert!(matches!(
+            validate_merge_block(&block, &partial_chain, &context),
+            Err(Error::InvalidMergeBlock(
+                InvalidMergeBlock::UnknownPowBlock(block_hash)
+            )) if block_hash == hash(2)
+        ));
+    }
+
+    #[test]
+    fn test_terminal_block_hash_override() {
+        let mut context = context();
+        let (chain, blocks) = pow_chain();
+        // block 2 has not reached the TTD but the override takes precedence
+        context.terminal_block_hash = hash(2);
+        context.terminal_block_hash_activation_epoch = 2;
+        assert_eq!(
+            get_terminal_pow_block(&chain, &context),
+            Some(blocks[1].clone())
+        );
+
+        let activation_slot = 2 * context.slots_per_epoch;
+        let block = merge_block(activation_slot - 1, hash(2));
+        assert!(matches!(
+            validate_merge_block(&block, &chain, &context),
+            Err(Error::InvalidMergeBlock(
+                InvalidMergeBlock::TerminalBlockHashNotActive {
+                    epoch: 1,
+                    activation_epoch: 2,
+                }
+            ))
+        ));
+
+        let block = merge_block(activation_slot, hash(2));
+        assert!(validate_merge_block(&block, &chain, &context).is_ok());
+
+        // the TTD block does not satisfy the override
+        let block = merge_block(activation_slot, hash(3));
+        assert!(matches!(
+            validate_merge_block(&block, &chain, &context),
+            Err(Error::InvalidMergeBlock(
+                InvalidMergeBlock::TerminalBlockHashMismatch { .. }
+            ))
+        ));
+    }
+}
@@ -0,0 +1,258 @@
+use crate::bellatrix as spec;
+
+use crate::phase0::genesis::activate_genesis_validators;
+use crate::phase0::{
+    active_validator_indices, BeaconBlockHeader, Deposit, DepositData, Eth1Data, Fork,
+    DEPOSIT_CONTRACT_TREE_DEPTH,
+};
+use crate::primitives::{Hash32, GENESIS_EPOCH};
+use crate::state_transition::{Context, Error};
+use spec::{
+    get_next_sync_committee, process_deposit, BeaconBlockBody, BeaconState, ExecutionPayloadHeader,
+};
+use ssz_rs::prelude::*;
+
+const DEPOSIT_DATA_LIST_BOUND: usize = 2usize.pow(DEPOSIT_CONTRACT_TREE_DEPTH as u32);
+
+/// Initialize a genesis state at the `bellatrix` fork. Pass the header of the
+/// execution genesis block to start post-merge, or a default header to start pre-merge.
+pub fn initialize_beacon_state_from_eth1<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    eth1_block_hash: Hash32,
+    eth1_timestamp: u64,
+    deposits: &mut [Deposit],
+    execution_payload_header: ExecutionPayloadHeader<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    context: &Context,
+) -> Result<
+    BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    Error,
+> {
+    let fork = Fork {
+        previous_version: context.bellatrix_fork_version,
+        current_version: context.bellatrix_fork_version,
+        epoch: GENESIS_EPOCH,
+    };
+    let eth1_data = Eth1Data {
+        block_hash: eth1_block_hash.clone(),
+        deposit_count: deposits.len() as u64,
+        ..Default::default()
+    };
+    let mut latest_block_body = BeaconBlockBody::<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >::default();
+    let body_root = latest_block_body.hash_tree_root()?;
+    let latest_block_header = BeaconBlockHeader {
+        body_root,
+        ..Default::default()
+    };
+    let randao_mixes = Vector::from_iter(
+        std::iter::repeat(eth1_block_hash).take(context.epochs_per_historical_vector as usize),
+    );
+    let mut state = BeaconState {
+        genesis_time: eth1_timestamp + context.genesis_delay,
+        fork,
+        eth1_data,
+        latest_block_header,
+        randao_mixes,
+        latest_execution_payload_header: execution_payload_header,
+        ..Default::default()
+    };
+
+    let mut leaves = List::<DepositData, DEPOSIT_DATA_LIST_BOUND>::default();
+    for deposit in deposits.iter_mut() {
+        leaves.push(deposit.data.clone());
+        state.eth1_data.deposit_root = leaves.hash_tree_root()?;
+        process_deposit(&mut state, deposit, context)?;
+    }
+
+    activate_genesis_validators(&mut state.validators, &state.balances, context);
+
+    state.genesis_validators_root = state.validators.hash_tree_root()?;
+
+    // NOTE: the same committee is assigned as the current and next committee at genesis
+    let sync_committee = get_next_sync_committee(&state, context)?;
+    state.current_sync_committee = sync_committee.clone();
+    state.next_sync_committee = sync_committee;
+
+    Ok(state)
+}
+
+pub fn is_valid_genesis_state<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    context: &Context,
+) -> bool {
+    if state.genesis_time < context.min_genesis_time {
+        return false;
+    }
+
+    if active_validator_indices(&state.validators, GENESIS_EPOCH).len()
+        < context.min_genesis_active_validator_count
+    {
+        return false;
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::initialize_beacon_state_from_eth1;
+    use crate::bellatrix::get_next_sync_committee_indices;
+    use crate::bellatrix::mainnet::*;
+    use crate::interop::{create_deposits, interop_secret_keys, mock_eth1_block_hash};
+    use crate::primitives::{Hash32, GENESIS_EPOCH};
+    use crate::state_transition::Context;
+    use ssz_rs::prelude::*;
+
+    #[test]
+    fn test_initialize_beacon_state_from_eth1() {
+        let context = Context::for_mainnet();
+        let validator_count = 16;
+        let eth1_timestamp = 1_600_000_000;
+        // the header of a post-merge execution genesis block
+        let mut execution_payload_header = ExecutionPayloadHeader {
+            block_hash: Hash32::try_from([0xab; 32].as_ref()).unwrap(),
+            block_number: 1,
+            gas_limit: 30_000_000,
+            timestamp: eth1_timestamp,
+            ..Default::default()
+        };
+        let secret_keys = interop_secret_keys(validator_count).unwrap();
+        let mut deposits = create_deposits(&secret_keys, &context).unwrap();
+        let state = initialize_beacon_state_from_eth1::<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_PROPOSER_SLASHINGS,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+        >(
+            mock_eth1_block_hash(),
+            eth1_timestamp,
+            &mut deposits,
+            execution_payload_header.clone(),
+            &context,
+        )
+        .unwrap();
+
+        assert_eq!(state.genesis_time, eth1_timestamp + context.genesis_delay);
+        assert_eq!(state.fork.previous_version, context.bellatrix_fork_version);
+        assert_eq!(state.fork.current_version, context.bellatrix_fork_version);
+        assert_eq!(state.validators.len(), validator_count);
+        assert_eq!(state.inactivity_scores.len(), validator_count);
+        assert!(state
+            .validators
+            .iter()
+            .all(|validator| validator.activation_epoch == GENESIS_EPOCH));
+
+        let mut latest_execution_payload_header = state.latest_execution_payload_header.clone();
+        assert_eq!(
+            latest_execution_payload_header.block_hash,
+            execution_payload_header.block_hash
+        );
+        assert_eq!(
+            latest_execution_payload_header.hash_tree_root().unwrap(),
+            execution_payload_header.hash_tree_root().unwrap()
+        );
+        assert_ne!(
+            latest_execution_payload_header.hash_tree_root().unwrap(),
+            ExecutionPayloadHeader::default().hash_tree_root().unwrap()
+        );
+
+        let indices = get_next_sync_committee_indices(&state, &context).unwrap();
+        let public_keys = indices
+            .iter()
+            .map(|&index| state.validators[index].public_key.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            state.current_sync_committee.public_keys.to_vec(),
+            public_keys
+        );
+        assert_eq!(
+            state.current_sync_committee.public_keys,
+            state.next_sync_committee.public_keys
+        );
+    }
+}
@@ -4,15 +4,46 @@
 mod beacon_block;
 mod beacon_state;
 mod blinded_beacon_block;
+mod block_processing;
+#[cfg(all(feature = "signer", feature = "engine-api"))]
+mod block_production;
+mod epoch_processing;
 mod execution;
+mod fork;
+mod fork_choice;
+pub mod genesis;
+mod helpers;
 mod presets;
+mod signing;
+mod slot_processing;
+mod state_transition;
 
 pub use beacon_block::*;
 pub use beacon_state::*;
 pub use blinded_beacon_block::*;
+pub use block_processing::*;
+#[cfg(all(feature = "signer", feature = "engine-api"))]
+pub use block_production::*;
+pub use epoch_processing::*;
 pub use execution::*;
+pub use fork::*;
+pub use fork_choice::*;
+pub use helpers::{
+    compute_timestamp_at_slot, get_attestation_participation_flag_indices, get_base_reward,
+    get_base_reward_per_increment, get_beacon_committee, get_beacon_proposer_index,
+    get_committee_count_per_slot, get_next_sync_committee, get_next_sync_committee_indices,
+    get_unslashed_participating_indices, is_execution_enabled, is_merge_transition_complete,
+    slash_validator,
+};
 pub use presets::Preset;
+pub use signing::*;
+pub use slot_processing::process_slots;
+pub use state_transition::state_transition;
 
 pub mod mainnet {
     pub use super::presets::mainnet::*;
 }
+
+pub mod minimal {
+    pub use super::presets::minimal::*;
+}
@@ -0,0 +1,956 @@
+use clap::{Parser, Subcommand, ValueEnum};
+use ethereum_consensus::phase0::compute_epoch_at_slot;
+use ethereum_consensus::phase0::mainnet::{
+    Attestation, AttesterSlashing, Deposit, ProposerSlashing, SignedAggregateAndProof,
+    SignedVoluntaryExit,
+};
+use ethereum_consensus::primitives::{Root, Slot, ValidatorIndex};
+use ethereum_consensus::ssz::io::{
+    read_block, read_ssz_file, read_state, write_state, BeaconState, Encoding, Fork, Preset,
+    SignedBeaconBlock,
+};
+use ethereum_consensus::state_transition::{Context, Error};
+use ethereum_consensus::{altair, bellatrix, phase0};
+use serde::Serialize;
+use ssz_rs::prelude::*;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Parser)]
+#[clap(
+    name = "ethereum-consensus",
+    version,
+    about = "Tools for working with consensus data"
+)]
+struct Cli {
+    /// The network whose configuration and presets are used
+    #[clap(long, value_parser = Network::from_str, default_value = "mainnet", global = true)]
+    network: Network,
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Network {
+    Mainnet,
+    Minimal,
+}
+
+impl FromStr for Network {
+    type Err = String;
+
+    fn from_str(name: &str) -> std::result::Result<Self, Self::Err> {
+        match name {
+            "mainnet" => Ok(Self::Mainnet),
+            "minimal" => Ok(Self::Minimal),
+            name => Err(format!(
+                "network `{name}` is not supported, only `mainnet` and `minimal` are"
+            )),
+        }
+    }
+}
+
+impl Network {
+    fn context(&self) -> Context {
+        match self {
+            Self::Mainnet => Context::for_mainnet(),
+            Self::Minimal => Context::for_minimal(),
+        }
+    }
+
+    fn run(&self, command: Command) -> Result<()> {
+        let context = self.context();
+        match self {
+            Self::Mainnet => presets::mainnet::Commands::new().run(command, &context),
+            Self::Minimal => presets::minimal::Commands::new().run(command, &context),
+        }
+    }
+}
+
+/// The type of the container in a SSZ file.
+#[derive(Clone, Copy, ValueEnum)]
+enum Kind {
+    State,
+    Block,
+    Attestation,
+    AggregateAndProof,
+    AttesterSlashing,
+    ProposerSlashing,
+    Deposit,
+    VoluntaryExit,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Json,
+    Yaml,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Apply a sequence of blocks to a pre-state
+    Transition {
+        #[clap(long, value_parser)]
+        pre: PathBuf,
+        #[clap(long, value_parser, multiple_values = true, required = true)]
+        blocks: Vec<PathBuf>,
+        #[clap(long, value_parser)]
+        post: PathBuf,
+        /// Skip verification of block signatures and state roots
+        #[clap(long)]
+        no_verify: bool,
+    },
+    /// Process empty slots of a pre-state up to the given slot
+    AdvanceSlots {
+        #[clap(long, value_parser)]
+        pre: PathBuf,
+        #[clap(long, value_parser)]
+        slot: Slot,
+        #[clap(long, value_parser)]
+        post: PathBuf,
+    },
+    /// Print the `hash_tree_root` of a container
+    HashTreeRoot {
+        #[clap(long, value_enum)]
+        kind: Kind,
+        #[clap(value_parser)]
+        path: PathBuf,
+    },
+    /// Print a container as JSON or YAML
+    Pretty {
+        #[clap(long, value_enum)]
+        kind: Kind,
+        #[clap(long, value_enum, default_value = "json")]
+        format: Format,
+        #[clap(value_parser)]
+        path: PathBuf,
+    },
+    /// Print the beacon committees and proposer of a slot
+    Duties {
+        #[clap(long, value_parser)]
+        state: PathBuf,
+        /// Defaults to the slot of the state
+        #[clap(long, value_parser)]
+        slot: Option<Slot>,
+    },
+}
+
+// NOTE: the operations have the same bounds under the mainnet and minimal presets
+#[derive(Debug, Serialize)]
+#[serde(untagged, bound = "")]
+enum Container<P: Preset> {
+    State(BeaconState<P>),
+    Block(SignedBeaconBlock<P>),
+    Attestation(Box<Attestation>),
+    AggregateAndProof(Box<SignedAggregateAndProof>),
+    AttesterSlashing(Box<AttesterSlashing>),
+    ProposerSlashing(Box<ProposerSlashing>),
+    Deposit(Box<Deposit>),
+    VoluntaryExit(Box<SignedVoluntaryExit>),
+}
+
+fn read_container<T: SimpleSerialize>(path: &Path) -> Result<Box<T>> {
+    let bytes = read_ssz_file(path)?;
+    Ok(Box::new(T::deserialize(&bytes)?))
+}
+
+impl<P: Preset> Container<P> {
+    fn read(kind: Kind, path: &Path, context: &Context) -> Result<Self> {
+        let container = match kind {
+            Kind::State => Self::State(read_state(path, context)?),
+            Kind::Block => Self::Block(read_block(path, context)?),
+            Kind::Attestation => Self::Attestation(read_container(path)?),
+            Kind::AggregateAndProof => Self::AggregateAndProof(read_container(path)?),
+            Kind::AttesterSlashing => Self::AttesterSlashing(read_container(path)?),
+            Kind::ProposerSlashing => Self::ProposerSlashing(read_container(path)?),
+            Kind::Deposit => Self::Deposit(read_container(path)?),
+            Kind::VoluntaryExit => Self::VoluntaryExit(read_container(path)?),
+        };
+        Ok(container)
+    }
+
+    fn hash_tree_root(&mut self) -> Result<Root> {
+        let root = match self {
+            Self::State(state) => state.hash_tree_root()?,
+            Self::Block(block) => block.hash_tree_root()?,
+            Self::Attestation(attestation) => attestation.hash_tree_root()?,
+            Self::AggregateAndProof(aggregate) => aggregate.hash_tree_root()?,
+            Self::AttesterSlashing(slashing) => slashing.hash_tree_root()?,
+            Self::ProposerSlashing(slashing) => slashing.hash_tree_root()?,
+            Self::Deposit(deposit) => deposit.hash_tree_root()?,
+            Self::VoluntaryExit(exit) => exit.hash_tree_root()?,
+        };
+        Ok(root)
+    }
+}
+
+#[derive(Serialize)]
+struct Duties {
+    slot: Slot,
+    proposer_index: Option<ValidatorIndex>,
+    committees: Vec<Vec<ValidatorIndex>>,
+}
+
+// NOTE: `.ssz_snappy` dumps use the block format, following the consensus spec tests
+fn encoding_for(path: &Path) -> Encoding {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("ssz_snappy") => Encoding::Snappy,
+        _ => Encoding::Ssz,
+    }
+}
+
+fn unsupported_fork<T>(fork: impl std::fmt::Debug) -> Result<T> {
+    Err(format!("the {fork:?} fork is not supported by this command").into())
+}
+
+/// The commands over states and blocks of the phase0, altair and bellatrix forks of
+/// the preset `P`, whose containers are bounded by the remaining parameters.
+struct Commands<
+    P,
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(PhantomData<P>);
+
+impl<
+        P,
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+    >
+    Commands<
+        P,
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_PROPOSER_SLASHINGS,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+    >
+where
+    P: Preset<
+        Phase0BeaconState = phase0::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+        >,
+        AltairBeaconState = altair::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+        >,
+        BellatrixBeaconState = bellatrix::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+        >,
+        Phase0SignedBeaconBlock = phase0::SignedBeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+        >,
+        AltairSignedBeaconBlock = altair::SignedBeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+        >,
+        BellatrixSignedBeaconBlock = bellatrix::SignedBeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+        >,
+    >,
+{
+    fn new() -> Self {
+        Self(PhantomData)
+    }
+
+    fn run(&self, command: Command, context: &Context) -> Result<()> {
+        match command {
+            Command::Transition {
+                pre,
+                blocks,
+                post,
+                no_verify,
+            } => self.transition(&pre, &blocks, &post, no_verify, context),
+            Command::AdvanceSlots { pre, slot, post } => {
+                self.advance_slots(&pre, slot, &post, context)
+            }
+            Command::HashTreeRoot { kind, path } => {
+                let root = Container::<P>::read(kind, &path, context)?.hash_tree_root()?;
+                println!("0x{}", hex::encode(root.as_bytes()));
+                Ok(())
+            }
+            Command::Pretty { kind, format, path } => {
+                let container = Container::<P>::read(kind, &path, context)?;
+                print_as(&container, format)
+            }
+            Command::Duties { state, slot } => {
+                let duties = self.duties(&state, slot, context)?;
+                print_as(&duties, Format::Json)
+            }
+        }
+    }
+
+    fn slot(&self, state: &BeaconState<P>) -> Result<Slot> {
+        match state {
+            BeaconState::Phase0(state) => Ok(state.slot),
+            BeaconState::Altair(state) => Ok(state.slot),
+            BeaconState::Bellatrix(state) => Ok(state.slot),
+            state => unsupported_fork(state.fork()),
+        }
+    }
+
+    /// Process the slots of `state` up to `slot`, upgrading it at each fork on the way.
+    fn process_slots(
+        &self,
+        mut state: BeaconState<P>,
+        slot: Slot,
+        context: &Context,
+    ) -> Result<BeaconState<P>> {
+        let current = self.slot(&state)?;
+        if current >= slot {
+            let error = Error::TransitionToPreviousSlot {
+                current,
+                requested: slot,
+            };
+            return Err(error.into());
+        }
+        // NOTE: a state reaching the first slot of a fork is upgraded before any block
+        // of that slot is applied
+        let start_slot = |epoch: u64| epoch.saturating_mul(context.slots_per_epoch);
+        loop {
+            state = match state {
+                BeaconState::Phase0(mut state) => {
+                    let fork_slot = start_slot(context.altair_fork_epoch);
+                    if state.slot < slot.min(fork_slot) {
+                        phase0::process_slots(&mut state, slot.min(fork_slot), context)?;
+                    }
+                    if slot < fork_slot {
+                        return Ok(BeaconState::Phase0(state));
+                    }
+                    let state = altair::upgrade_to_altair(&state, context)?;
+                    BeaconState::Altair(Box::new(state))
+                }
+                BeaconState::Altair(mut state) => {
+                    let fork_slot = start_slot(context.bellatrix_fork_epoch);
+                    if state.slot < slot.min(fork_slot) {
+                        altair::process_slots(&mut state, slot.min(fork_slot), context)?;
+                    }
+                    if slot < fork_slot {
+                        return Ok(BeaconState::Altair(state));
+                    }
+                    let state = bellatrix::upgrade_to_bellatrix(&state, context);
+                    BeaconState::Bellatrix(Box::new(state))
+                }
+                BeaconState::Bellatrix(mut state) => {
+                    let fork_slot = start_slot(context.capella_fork_epoch);
+                    if slot >= fork_slot {
+                        return unsupported_fork(Fork::Capella);
+                    }
+                    if state.slot < slot {
+                        bellatrix::process_slots(&mut state, slot, context)?;
+                    }
+                    return Ok(BeaconState::Bellatrix(state));
+                }
+                state => return unsupported_fork(state.fork()),
+            };
+        }
+    }
+
+    /// Apply `block` to `state` after processing the slots up to that of the block,
+    /// verifying the block signature and the resulting state root if `verify`.
+    fn apply_block(
+        &self,
+        state: BeaconState<P>,
+        block: &mut SignedBeaconBlock<P>,
+        verify: bool,
+        context: &Context,
+    ) -> Result<BeaconState<P>> {
+        let slot = match block {
+            SignedBeaconBlock::Phase0(block) => block.message.slot,
+            SignedBeaconBlock::Altair(block) => block.message.slot,
+            SignedBeaconBlock::Bellatrix(block) => block.message.slot,
+            block => return unsupported_fork(block.fork()),
+        };
+        let mut state = self.process_slots(state, slot, context)?;
+        let state_root = match (&mut state, block) {
+            (BeaconState::Phase0(state), SignedBeaconBlock::Phase0(block)) => {
+                if verify {
+                    phase0::verify_block_signature(state, block, context)?;
+                }
+                phase0::process_block(state, &mut block.message, context)?;
+                block.message.state_root
+            }
+            (BeaconState::Altair(state), SignedBeaconBlock::Altair(block)) => {
+                if verify {
+                    altair::verify_block_signature(state, block, context)?;
+                }
+                altair::process_block(state, &mut block.message, context)?;
+                block.message.state_root
+            }
+            (BeaconState::Bellatrix(state), SignedBeaconBlock::Bellatrix(block)) => {
+                if verify {
+                    bellatrix::verify_block_signature(state, block, context)?;
+                }
+                bellatrix::process_block(state, &mut block.message, context)?;
+                block.message.state_root
+            }
+            (state, block) => {
+                let (state, block) = (state.fork(), block.fork());
+                return Err(
+                    format!("a {block:?} block does not apply to a {state:?} state").into(),
+                );
+            }
+        };
+        if verify && state_root != state.hash_tree_root()? {
+            return Err(Error::InvalidStateRoot.into());
+        }
+        Ok(state)
+    }
+
+    fn transition(
+        &self,
+        pre: &Path,
+        blocks: &[PathBuf],
+        post: &Path,
+        no_verify: bool,
+        context: &Context,
+    ) -> Result<()> {
+        let mut state: BeaconState<P> = read_state(pre, context)?;
+        for path in blocks {
+            let mut block: SignedBeaconBlock<P> = read_block(path, context)?;
+            state = self.apply_block(state, &mut block, !no_verify, context)?;
+        }
+        write_state(post, &state, encoding_for(post))?;
+        Ok(())
+    }
+
+    fn advance_slots(&self, pre: &Path, slot: Slot, post: &Path, context: &Context) -> Result<()> {
+        let state: BeaconState<P> = read_state(pre, context)?;
+        let state = self.process_slots(state, slot, context)?;
+        write_state(post, &state, encoding_for(post))?;
+        Ok(())
+    }
+
+    fn duties(&self, path: &Path, slot: Option<Slot>, context: &Context) -> Result<Duties> {
+        let mut state: BeaconState<P> = read_state(path, context)?;
+        let slot = slot.unwrap_or(self.slot(&state)?);
+        if slot > self.slot(&state)? {
+            state = self.process_slots(state, slot, context)?;
+        }
+        let epoch = compute_epoch_at_slot(slot, context);
+        // the proposer can only be computed from a state at the requested slot
+        let (proposer_index, committees) = match &state {
+            BeaconState::Phase0(state) => {
+                let proposer_index = if state.slot == slot {
+                    Some(phase0::get_beacon_proposer_index(state, context)?)
+                } else {
+                    None
+                };
+                let committees = (0..phase0::get_committee_count_per_slot(state, epoch, context))
+                    .map(|index| phase0::get_beacon_committee(state, slot, index, context))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                (proposer_index, committees)
+            }
+            BeaconState::Altair(state) => {
+                let proposer_index = if state.slot == slot {
+                    Some(altair::get_beacon_proposer_index(state, context)?)
+                } else {
+                    None
+                };
+                let committees = (0..altair::get_committee_count_per_slot(state, epoch, context))
+                    .map(|index| altair::get_beacon_committee(state, slot, index, context))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                (proposer_index, committees)
+            }
+            BeaconState::Bellatrix(state) => {
+                let proposer_index = if state.slot == slot {
+                    Some(bellatrix::get_beacon_proposer_index(state, context)?)
+                } else {
+                    None
+                };
+                let committees =
+                    (0..bellatrix::get_committee_count_per_slot(state, epoch, context))
+                        .map(|index| bellatrix::get_beacon_committee(state, slot, index, context))
+                        .collect::<std::result::Result<Vec<_>, _>>()?;
+                (proposer_index, committees)
+            }
+            state => return unsupported_fork(state.fork()),
+        };
+        Ok(Duties {
+            slot,
+            proposer_index,
+            committees,
+        })
+    }
+}
+
+mod presets {
+    pub mod mainnet {
+        use ethereum_consensus::bellatrix::mainnet::*;
+        use ethereum_consensus::ssz::io::Mainnet;
+
+        const PENDING_ATTESTATIONS_BOUND: usize = MAX_ATTESTATIONS * SLOTS_PER_EPOCH as usize;
+
+        pub type Commands = super::super::Commands<
+            Mainnet,
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_PROPOSER_SLASHINGS,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+        >;
+    }
+
+    pub mod minimal {
+        use ethereum_consensus::bellatrix::minimal::*;
+        use ethereum_consensus::ssz::io::Minimal;
+
+        const PENDING_ATTESTATIONS_BOUND: usize = MAX_ATTESTATIONS * SLOTS_PER_EPOCH as usize;
+
+        pub type Commands = super::super::Commands<
+            Minimal,
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_PROPOSER_SLASHINGS,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+        >;
+    }
+}
+
+fn print_as<T: Serialize>(value: &T, format: Format) -> Result<()> {
+    let output = match format {
+        Format::Json => serde_json::to_string_pretty(value)?,
+        Format::Yaml => serde_yaml::to_string(value)?,
+    };
+    println!("{output}");
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    cli.network.run(cli.command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+    use ethereum_consensus::interop::interop_secret_key;
+    use ethereum_consensus::interop::phase0::create_genesis_state;
+    use ethereum_consensus::phase0::{
+        get_beacon_proposer_index, get_block_signature, process_slots, produce_block,
+    };
+    use ethereum_consensus::phase0::{mainnet, minimal};
+    use ethereum_consensus::primitives::Bytes32;
+    use ethereum_consensus::signer::LocalSigner;
+    use ethereum_consensus::ssz::io::{write_block, Mainnet, Minimal};
+
+    fn create_mainnet_genesis_state(
+        validator_count: usize,
+        context: &Context,
+    ) -> mainnet::BeaconState {
+        use ethereum_consensus::phase0::mainnet::*;
+
+        const PENDING_ATTESTATIONS_BOUND: usize = MAX_ATTESTATIONS * SLOTS_PER_EPOCH as usize;
+
+        create_genesis_state::<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            MAX_PROPOSER_SLASHINGS,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+        >(validator_count, 0, context)
+        .unwrap()
+    }
+
+    fn create_minimal_genesis_state(
+        validator_count: usize,
+        context: &Context,
+    ) -> minimal::BeaconState {
+        use ethereum_consensus::phase0::minimal::*;
+
+        const PENDING_ATTESTATIONS_BOUND: usize = MAX_ATTESTATIONS * SLOTS_PER_EPOCH as usize;
+
+        create_genesis_state::<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            MAX_PROPOSER_SLASHINGS,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+        >(validator_count, 0, context)
+        .unwrap()
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "ethereum-consensus-cli-{}-{name}",
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_transition() {
+        let cli = Cli::try_parse_from([
+            "ethereum-consensus",
+            "transition",
+            "--pre",
+            "pre.ssz",
+            "--blocks",
+            "block_1.ssz",
+            "block_2.ssz_snappy",
+            "--post",
+            "post.ssz",
+        ])
+        .unwrap();
+        assert_eq!(cli.network, Network::Mainnet);
+        match cli.command {
+            Command::Transition {
+                pre,
+                blocks,
+                post,
+                no_verify,
+            } => {
+                assert_eq!(pre, PathBuf::from("pre.ssz"));
+                assert_eq!(
+                    blocks,
+                    vec![
+                        PathBuf::from("block_1.ssz"),
+                        PathBuf::from("block_2.ssz_snappy")
+                    ]
+                );
+                assert_eq!(post, PathBuf::from("post.ssz"));
+                assert!(!no_verify);
+            }
+            _ => panic!("expected the transition command"),
+        }
+
+        // at least one block is required
+        assert!(Cli::try_parse_from([
+            "ethereum-consensus",
+            "transition",
+            "--pre",
+            "pre.ssz",
+            "--post",
+            "post.ssz",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        let cli = Cli::try_parse_from([
+            "ethereum-consensus",
+            "advance-slots",
+            "--pre",
+            "pre.ssz",
+            "--slot",
+            "32",
+            "--post",
+            "post.ssz",
+            "--network",
+            "mainnet",
+        ])
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::AdvanceSlots { slot: 32, .. }
+        ));
+
+        let cli = Cli::try_parse_from([
+            "ethereum-consensus",
+            "pretty",
+            "--kind",
+            "aggregate-and-proof",
+            "--format",
+            "yaml",
+            "aggregate.ssz",
+        ])
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Command::Pretty {
+                kind: Kind::AggregateAndProof,
+                format: Format::Yaml,
+                ..
+            }
+        ));
+
+        let cli =
+            Cli::try_parse_from(["ethereum-consensus", "duties", "--state", "state.ssz"]).unwrap();
+        assert!(matches!(cli.command, Command::Duties { slot: None, .. }));
+
+        assert!(Cli::try_parse_from([
+            "ethereum-consensus",
+            "hash-tree-root",
+            "--kind",
+            "receipt",
+            "receipt.ssz",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_unsupported_network() {
+        let cli = Cli::try_parse_from([
+            "ethereum-consensus",
+            "duties",
+            "--state",
+            "state.ssz",
+            "--network",
+            "minimal",
+        ])
+        .unwrap();
+        assert_eq!(cli.network, Network::Minimal);
+
+        for network in ["goerli", "sepolia"] {
+            let err = Cli::try_parse_from([
+                "ethereum-consensus",
+                "duties",
+                "--state",
+                "state.ssz",
+                "--network",
+                network,
+            ])
+            .err()
+            .unwrap();
+            let message = err.to_string();
+            assert!(message.contains(&format!("network `{network}` is not supported")));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transition() {
+        let context = Context::for_mainnet();
+        let genesis_state = create_mainnet_genesis_state(64, &context);
+        let slot = 1;
+
+        let mut state = genesis_state.clone();
+        process_slots(&mut state, slot, &context).unwrap();
+        let proposer_index = get_beacon_proposer_index(&state, &context).unwrap();
+        let signing_key = interop_secret_key(proposer_index).unwrap();
+        let signer = LocalSigner::new([signing_key.clone()]);
+        let mut block: mainnet::BeaconBlock = produce_block(
+            &genesis_state,
+            slot,
+            &signer,
+            &[],
+            Bytes32::default(),
+            Default::default(),
+            &context,
+        )
+        .await
+        .unwrap();
+        let signature = get_block_signature(&state, &mut block, &signing_key, &context).unwrap();
+        let expected_state_root = block.state_root;
+        let block = mainnet::SignedBeaconBlock {
+            message: block,
+            signature,
+        };
+
+        let pre = temp_path("transition-pre.ssz");
+        let block_path = temp_path("transition-block.ssz");
+        let post = temp_path("transition-post.ssz_snappy");
+        write_state(
+            &pre,
+            &BeaconState::<Mainnet>::Phase0(Box::new(genesis_state)),
+            Encoding::Ssz,
+        )
+        .unwrap();
+        write_block(
+            &block_path,
+            &SignedBeaconBlock::<Mainnet>::Phase0(Box::new(block)),
+            Encoding::Ssz,
+        )
+        .unwrap();
+
+        let result = presets::mainnet::Commands::new().transition(
+            &pre,
+            &[block_path.clone()],
+            &post,
+            false,
+            &context,
+        );
+        let post_state = result.and_then(|_| Ok(read_state::<Mainnet, _>(&post, &context)?));
+        for path in [&pre, &block_path, &post] {
+            let _ = std::fs::remove_file(path);
+        }
+        let mut post_state = post_state.unwrap();
+        assert_eq!(post_state.fork(), Fork::Phase0);
+        assert_eq!(post_state.hash_tree_root().unwrap(), expected_state_root);
+    }
+
+    #[test]
+    fn test_advance_slots_across_forks() {
+        let mut context = Context::for_minimal();
+        context.altair_fork_epoch = 1;
+        context.bellatrix_fork_epoch = 2;
+        context.capella_fork_epoch = 3;
+        let genesis_state = create_minimal_genesis_state(64, &context);
+        let commands = presets::minimal::Commands::new();
+        let slot = 2 * context.slots_per_epoch + 1;
+
+        let pre = temp_path("forks-pre.ssz");
+        let post = temp_path("forks-post.ssz");
+        write_state(
+            &pre,
+            &BeaconState::<Minimal>::Phase0(Box::new(genesis_state)),
+            Encoding::Ssz,
+        )
+        .unwrap();
+        let result = commands.advance_slots(&pre, slot, &post, &context);
+        let post_state = result.and_then(|_| Ok(read_state::<Minimal, _>(&post, &context)?));
+        let duties = commands.duties(&post, None, &context);
+        let _ = std::fs::remove_file(&pre);
+        let post_state = post_state.unwrap();
+        assert_eq!(post_state.fork(), Fork::Bellatrix);
+        match post_state {
+            BeaconState::Bellatrix(state) => {
+                assert_eq!(state.slot, slot);
+                assert_eq!(state.fork.previous_version, context.altair_fork_version);
+                assert_eq!(state.fork.current_version, context.bellatrix_fork_version);
+            }
+            state => panic!("expected a bellatrix state, got {:?}", state.fork()),
+        }
+        let duties = duties.unwrap();
+        assert_eq!(duties.slot, slot);
+        assert!(duties.proposer_index.is_some());
+        assert!(!duties.committees.is_empty());
+
+        // the command stops short of processing capella
+        let capella_slot = 3 * context.slots_per_epoch;
+        let result = commands.advance_slots(&post, capella_slot, &pre, &context);
+        let _ = std::fs::remove_file(&post);
+        let err = result.err().unwrap();
+        assert!(err.to_string().contains("Capella"));
+        assert!(!pre.exists());
+    }
+}
@@ -0,0 +1,262 @@
+use crate::beacon_api::{ApiError, Client as ApiClient, Error, VersionedValue};
+use crate::bellatrix::{ExecutionPayload, SignedBlindedBeaconBlock};
+use crate::builder::{SignedBuilderBid, SignedValidatorRegistration};
+use crate::primitives::{BlsPublicKey, Hash32, Slot};
+use reqwest::{StatusCode, Url};
+
+/// A client for the builder API exposed by relays and `mev-boost`.
+///
+/// Methods exchanging payloads or blocks are generic over the preset.
+#[derive(Clone)]
+pub struct Client {
+    api: ApiClient,
+}
+
+impl Client {
+    pub fn new(endpoint: Url) -> Self {
+        Self {
+            api: ApiClient::new(endpoint),
+        }
+    }
+
+    pub async fn check_status(&self) -> Result<(), Error> {
+        let response = self.api.http_get("eth/v1/builder/status").await?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(Error::Api(response.json::<ApiError>().await?))
+        }
+    }
+
+    pub async fn register_validators(
+        &self,
+        registrations: &[SignedValidatorRegistration],
+    ) -> Result<(), Error> {
+        self.api
+            .post("eth/v1/builder/validators", registrations)
+            .await
+    }
+
+    /// Request the best bid for the given `slot` building on `parent_hash`;
+    /// `None` signals that the builder has no bid to offer.
+    pub async fn fetch_best_bid<
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    >(
+        &self,
+        slot: Slot,
+        parent_hash: &Hash32,
+        public_key: &BlsPublicKey,
+    ) -> Result<
+        Option<
+            SignedBuilderBid<
+                BYTES_PER_LOGS_BLOOM,
+                MAX_EXTRA_DATA_BYTES,
+                MAX_BYTES_PER_TRANSACTION,
+                MAX_TRANSACTIONS_PER_PAYLOAD,
+            >,
+        >,
+        Error,
+    > {
+        let path = format!("eth/v1/builder/header/{slot}/{parent_hash}/{public_key}");
+        let response = self.api.http_get(&path).await?;
+        match response.status() {
+            StatusCode::NO_CONTENT => Ok(None),
+            status if status.is_success() => {
+                let bid: VersionedValue<
+                    SignedBuilderBid<
+                        BYTES_PER_LOGS_BLOOM,
+                        MAX_EXTRA_DATA_BYTES,
+                        MAX_BYTES_PER_TRANSACTION,
+                        MAX_TRANSACTIONS_PER_PAYLOAD,
+                    >,
+                > = response.json().await?;
+                Ok(Some(bid.data))
+            }
+            _ => Err(Error::Api(response.json::<ApiError>().await?)),
+        }
+    }
+
+    /// Submit the signed blinded block to reveal the full `ExecutionPayload`.
+    pub async fn open_bid<
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    >(
+        &self,
+        signed_block: &SignedBlindedBeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+        >,
+    ) -> Result<
+        ExecutionPayload<
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+        >,
+        Error,
+    > {
+        let response = self
+            .api
+            .http_post("eth/v1/builder/blinded_blocks", signed_block)
+            .await?;
+        if response.status().is_success() {
+            let payload: VersionedValue<
+                ExecutionPayload<
+                    BYTES_PER_LOGS_BLOOM,
+                    MAX_EXTRA_DATA_BYTES,
+                    MAX_BYTES_PER_TRANSACTION,
+                    MAX_TRANSACTIONS_PER_PAYLOAD,
+                >,
+            > = response.json().await?;
+            Ok(payload.data)
+        } else {
+            Err(Error::Api(response.json::<ApiError>().await?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::beacon_api::ConsensusVersion;
+    use crate::bellatrix::mainnet::{
+        ExecutionPayload as Payload, SignedBlindedBeaconBlock as SignedBlindedBlock,
+    };
+    use crate::builder::mainnet::{BuilderBid, SignedBuilderBid};
+    use crate::builder::ValidatorRegistration;
+    use crate::crypto::SecretKey;
+    use axum::{
+        extract::Path,
+        http::StatusCode as HttpStatusCode,
+        response::IntoResponse,
+        routing::{get, post},
+        Json, Router,
+    };
+    use serde_json::json;
+    use std::net::TcpListener;
+
+    fn spawn_stub_relay() -> Url {
+        let api = Router::new()
+            .route(
+                "/eth/v1/builder/status",
+                get(|| async { HttpStatusCode::OK }),
+            )
+            .route(
+                "/eth/v1/builder/header/:slot/:parent_hash/:public_key",
+                get(
+                    |Path((slot, _, _)): Path<(u64, String, String)>| async move {
+                        if slot == 0 {
+                            return HttpStatusCode::NO_CONTENT.into_response();
+                        }
+                        let bid = VersionedValue {
+                            version: ConsensusVersion::Bellatrix,
+                            data: SignedBuilderBid {
+                                message: BuilderBid {
+                                    public_key: SecretKey::random(&mut rand::thread_rng())
+                                        .unwrap()
+                                        .public_key(),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                        };
+                        Json(serde_json::to_value(bid).unwrap()).into_response()
+                    },
+                ),
+            )
+            .route(
+                "/eth/v1/builder/validators",
+                post(|Json(registrations): Json<serde_json::Value>| async move {
+                    assert_eq!(registrations[0]["message"]["gas_limit"], "30000000");
+                    HttpStatusCode::OK
+                }),
+            )
+            .route(
+                "/eth/v1/builder/blinded_blocks",
+                post(|Json(block): Json<SignedBlindedBlock>| async move {
+                    let slot = block.message.slot;
+                    if slot == 0 {
+                        let error = json!({ "code": 400, "message": "no bid for slot 0" });
+                        return (HttpStatusCode::BAD_REQUEST, Json(error)).into_response();
+                    }
+                    let payload = VersionedValue {
+                        version: ConsensusVersion::Bellatrix,
+                        data: Payload {
+                            block_number: slot,
+                            ..Default::default()
+                        },
+                    };
+                    Json(serde_json::to_value(payload).unwrap()).into_response()
+                }),
+            );
+        let app = Router::new().nest("/relay", api);
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = axum::Server::from_tcp(listener)
+            .unwrap()
+            .serve(app.into_make_service());
+        tokio::spawn(server);
+        // NOTE: serve under a base path to check it is preserved
+        Url::parse(&format!("http://{addr}/relay")).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_client() {
+        let client = Client::new(spawn_stub_relay());
+        client.check_status().await.unwrap();
+
+        let parent_hash = Hash32::default();
+        let public_key = BlsPublicKey::default();
+        let bid: Option<SignedBuilderBid> = client
+            .fetch_best_bid(0, &parent_hash, &public_key)
+            .await
+            .unwrap();
+        assert!(bid.is_none());
+
+        let bid: Option<SignedBuilderBid> = client
+            .fetch_best_bid(1, &parent_hash, &public_key)
+            .await
+            .unwrap();
+        assert!(bid.is_some());
+
+        let registration = SignedValidatorRegistration {
+            message: ValidatorRegistration {
+                gas_limit: 30_000_000,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        client.register_validators(&[registration]).await.unwrap();
+
+        let mut signed_block = SignedBlindedBlock::default();
+        signed_block.message.slot = 10;
+        let payload: Payload = client.open_bid(&signed_block).await.unwrap();
+        assert_eq!(payload.block_number, 10);
+
+        signed_block.message.slot = 0;
+        let error = client.open_bid(&signed_block).await.unwrap_err();
+        assert!(matches!(error, Error::Api(ApiError { code: 400, .. })));
+    }
+}
@@ -1,8 +1,18 @@
-use crate::phase0::compute_domain;
-use crate::primitives::{BlsPublicKey, BlsSignature, Domain, DomainType, ExecutionAddress};
+#[cfg(feature = "builder")]
+mod client;
+
+use crate::bellatrix::{
+    ExecutionPayload, ExecutionPayloadHeader, SignedBeaconBlock, SignedBlindedBeaconBlock,
+};
+use crate::crypto::SecretKey;
+use crate::phase0::{compute_domain, sign_with_domain, verify_signed_data};
+use crate::primitives::{BlsPublicKey, BlsSignature, Domain, DomainType, ExecutionAddress, U256};
 use crate::state_transition::{Context, Error};
 use ssz_rs::prelude::*;
 
+#[cfg(feature = "builder")]
+pub use client::*;
+
 #[derive(Debug, Clone, Default, SimpleSerialize)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ValidatorRegistration {
@@ -22,7 +32,214 @@ pub struct SignedValidatorRegistration {
     pub signature: BlsSignature,
 }
 
+#[derive(Debug, Clone, Default, SimpleSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BuilderBid<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+> {
+    pub header: ExecutionPayloadHeader<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    pub value: U256,
+    #[serde(rename = "pubkey")]
+    pub public_key: BlsPublicKey,
+}
+
+#[derive(Debug, Clone, Default, SimpleSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SignedBuilderBid<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+> {
+    pub message: BuilderBid<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    pub signature: BlsSignature,
+}
+
+pub mod mainnet {
+    use crate::bellatrix::mainnet::{
+        BYTES_PER_LOGS_BLOOM, MAX_BYTES_PER_TRANSACTION, MAX_EXTRA_DATA_BYTES,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    };
+
+    pub type BuilderBid = super::BuilderBid<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >;
+
+    pub type SignedBuilderBid = super::SignedBuilderBid<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >;
+}
+
 pub fn compute_builder_domain(context: &Context) -> Result<Domain, Error> {
     let domain_type = DomainType::ApplicationBuilder;
     compute_domain(domain_type, None, None, context)
 }
+
+pub fn sign_builder_message<T: SimpleSerialize>(
+    message: &mut T,
+    signing_key: &SecretKey,
+    context: &Context,
+) -> Result<BlsSignature, Error> {
+    let domain = compute_builder_domain(context)?;
+    sign_with_domain(message, signing_key, domain)
+}
+
+pub fn verify_signed_builder_message<T: SimpleSerialize>(
+    message: &mut T,
+    signature: &BlsSignature,
+    public_key: &BlsPublicKey,
+    context: &Context,
+) -> Result<(), Error> {
+    let domain = compute_builder_domain(context)?;
+    verify_signed_data(message, signature, public_key, domain)
+}
+
+/// Verify that `signed_bid` was signed by the builder it names, under the builder domain.
+pub fn verify_signed_builder_bid<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+>(
+    signed_bid: &mut SignedBuilderBid<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    context: &Context,
+) -> Result<(), Error> {
+    let public_key = signed_bid.message.public_key.clone();
+    verify_signed_builder_message(
+        &mut signed_bid.message,
+        &signed_bid.signature,
+        &public_key,
+        context,
+    )
+}
+
+/// Reconstruct the full `SignedBeaconBlock` from a `signed_blinded_block` and the
+/// `execution_payload` revealed by the builder, provided the payload matches the
+/// header the proposer signed over.
+pub fn unblind_block<
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+>(
+    signed_blinded_block: SignedBlindedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    execution_payload: ExecutionPayload<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+) -> Result<
+    SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    Error,
+> {
+    signed_blinded_block.unblind(execution_payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bellatrix::mainnet::{
+        ExecutionPayload as Payload, SignedBlindedBeaconBlock as SignedBlindedBlock,
+    };
+    use crate::builder::mainnet::{BuilderBid as Bid, SignedBuilderBid as SignedBid};
+
+    #[test]
+    fn test_unblind_block() {
+        let mut execution_payload = Payload {
+            gas_limit: 30_000_000,
+            ..Default::default()
+        };
+        let mut signed_blinded_block = SignedBlindedBlock::default();
+        signed_blinded_block.message.slot = 10;
+        signed_blinded_block.message.body.execution_payload_header =
+            ExecutionPayloadHeader::try_from(&mut execution_payload).unwrap();
+
+        let signed_block =
+            unblind_block(signed_blinded_block.clone(), execution_payload.clone()).unwrap();
+        assert_eq!(signed_block.message.slot, 10);
+        assert_eq!(
+            signed_block.message.body.execution_payload.gas_limit,
+            30_000_000
+        );
+
+        execution_payload.gas_limit += 1;
+        let result = unblind_block(signed_blinded_block, execution_payload);
+        assert!(matches!(
+            result,
+            Err(Error::ExecutionPayloadMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_signed_builder_bid() {
+        let context = Context::for_mainnet();
+        let mut rng = rand::thread_rng();
+        let signing_key = SecretKey::random(&mut rng).unwrap();
+        let mut message = Bid {
+            public_key: signing_key.public_key(),
+            ..Default::default()
+        };
+        let signature = sign_builder_message(&mut message, &signing_key, &context).unwrap();
+        let mut signed_bid = SignedBid { message, signature };
+        verify_signed_builder_bid(&mut signed_bid, &context).unwrap();
+
+        signed_bid.message.value = U256::from_bytes_le([1u8; 32]);
+        assert!(verify_signed_builder_bid(&mut signed_bid, &context).is_err());
+    }
+}
@@ -0,0 +1,507 @@
+use crate::capella as spec;
+
+use crate::crypto::hash;
+use crate::phase0::{compute_domain, compute_signing_root, Validator};
+use crate::primitives::{
+    Bytes32, DomainType, Epoch, ExecutionAddress, Gwei, BLS_WITHDRAWAL_PREFIX,
+    ETH1_ADDRESS_WITHDRAWAL_PREFIX,
+};
+use crate::state_transition::{
+    invalid_operation_error, Context, Error, InvalidBlsToExecutionChange, InvalidOperation,
+    InvalidWithdrawals,
+};
+use spec::helpers::{decrease_balance, get_current_epoch};
+use spec::{BeaconState, ExecutionPayload, SignedBlsToExecutionChange, Withdrawal};
+
+pub fn has_eth1_withdrawal_credential(validator: &Validator) -> bool {
+    validator.withdrawal_credentials.as_ref()[0] == ETH1_ADDRESS_WITHDRAWAL_PREFIX
+}
+
+pub fn is_fully_withdrawable_validator(validator: &Validator, balance: Gwei, epoch: Epoch) -> bool {
+    has_eth1_withdrawal_credential(validator)
+        && validator.withdrawable_epoch <= epoch
+        && balance > 0
+}
+
+pub fn is_partially_withdrawable_validator(
+    validator: &Validator,
+    balance: Gwei,
+    context: &Context,
+) -> bool {
+    let has_max_effective_balance = validator.effective_balance == context.max_effective_balance;
+    let has_excess_balance = balance > context.max_effective_balance;
+    has_eth1_withdrawal_credential(validator) && has_max_effective_balance && has_excess_balance
+}
+
+pub fn get_expected_withdrawals<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    context: &Context,
+) -> Vec<Withdrawal> {
+    let epoch = get_current_epoch(state, context);
+    let mut withdrawal_index = state.next_withdrawal_index;
+    let mut validator_index = state.next_withdrawal_validator_index;
+    let mut withdrawals = vec![];
+    let bound = state
+        .validators
+        .len()
+        .min(context.max_validators_per_withdrawals_sweep);
+    for _ in 0..bound {
+        let validator = &state.validators[validator_index];
+        let balance = state.balances[validator_index];
+        let address = || {
+            ExecutionAddress::try_from(&validator.withdrawal_credentials.as_ref()[12..])
+                .expect("correct input")
+        };
+        if is_fully_withdrawable_validator(validator, balance, epoch) {
+            withdrawals.push(Withdrawal {
+                index: withdrawal_index,
+                validator_index,
+                address: address(),
+                amount: balance,
+            });
+            withdrawal_index += 1;
+        } else if is_partially_withdrawable_validator(validator, balance, context) {
+            withdrawals.push(Withdrawal {
+                index: withdrawal_index,
+                validator_index,
+                address: address(),
+                amount: balance - context.max_effective_balance,
+            });
+            withdrawal_index += 1;
+        }
+        if withdrawals.len() == context.max_withdrawals_per_payload {
+            break;
+        }
+        validator_index = (validator_index + 1) % state.validators.len();
+    }
+    withdrawals
+}
+
+pub fn process_withdrawals<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    execution_payload: &ExecutionPayload<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+    >,
+    context: &Context,
+) -> Result<(), Error> {
+    let expected_withdrawals = get_expected_withdrawals(state, context);
+    let withdrawals = &execution_payload.withdrawals;
+
+    if withdrawals.len() != expected_withdrawals.len() {
+        return Err(invalid_operation_error(InvalidOperation::Withdrawal(
+            InvalidWithdrawals::IncorrectCount {
+                expected: expected_withdrawals.len(),
+                provided: withdrawals.len(),
+            },
+        )));
+    }
+
+    for (expected, withdrawal) in expected_withdrawals.iter().zip(withdrawals.iter()) {
+        if expected != withdrawal {
+            return Err(invalid_operation_error(InvalidOperation::Withdrawal(
+                InvalidWithdrawals::IncorrectWithdrawal {
+                    expected: Box::new(expected.clone()),
+                    provided: Box::new(withdrawal.clone()),
+                },
+            )));
+        }
+        decrease_balance(state, withdrawal.validator_index, withdrawal.amount);
+    }
+
+    if let Some(latest_withdrawal) = expected_withdrawals.last() {
+        state.next_withdrawal_index = latest_withdrawal.index + 1;
+    }
+
+    // NOTE: if the sweep filled the payload, resume after the last withdrawn validator;
+    // otherwise advance by the full sweep
+    let validator_count = state.validators.len();
+    if validator_count == 0 {
+        return Ok(());
+    }
+    if expected_withdrawals.len() == context.max_withdrawals_per_payload {
+        let latest_withdrawal = expected_withdrawals.last().expect("payload is not empty");
+        state.next_withdrawal_validator_index =
+            (latest_withdrawal.validator_index + 1) % validator_count;
+    } else {
+        let next_validator_index =
+            state.next_withdrawal_validator_index + context.max_validators_per_withdrawals_sweep;
+        state.next_withdrawal_validator_index = next_validator_index % validator_count;
+    }
+
+    Ok(())
+}
+
+pub fn process_bls_to_execution_change<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    signed_address_change: &mut SignedBlsToExecutionChange,
+    context: &Context,
+) -> Result<(), Error> {
+    let address_change = &mut signed_address_change.message;
+    let validator_index = address_change.validator_index;
+    if validator_index >= state.validators.len() {
+        return Err(invalid_operation_error(
+            InvalidOperation::BlsToExecutionChange(
+                InvalidBlsToExecutionChange::ValidatorIndexOutOfBounds(validator_index),
+            ),
+        ));
+    }
+
+    let withdrawal_credentials = state.validators[validator_index]
+        .withdrawal_credentials
+        .as_ref();
+    if withdrawal_credentials[0] != BLS_WITHDRAWAL_PREFIX {
+        return Err(invalid_operation_error(
+            InvalidOperation::BlsToExecutionChange(
+                InvalidBlsToExecutionChange::NonBlsWithdrawalCredentials(withdrawal_credentials[0]),
+            ),
+        ));
+    }
+
+    let public_key = &address_change.from_bls_public_key;
+    if withdrawal_credentials[1..] != hash(public_key.as_bytes()).as_ref()[1..] {
+        return Err(invalid_operation_error(
+            InvalidOperation::BlsToExecutionChange(InvalidBlsToExecutionChange::PublicKeyMismatch(
+                public_key.clone(),
+            )),
+        ));
+    }
+
+    // NOTE: the domain is fixed to the genesis fork so signed changes remain valid across forks
+    let domain = compute_domain(
+        DomainType::BlsToExecutionChange,
+        None,
+        Some(state.genesis_validators_root),
+        context,
+    )?;
+    let signing_root = compute_signing_root(address_change, domain)?;
+    let public_key = &address_change.from_bls_public_key;
+    if !public_key.verify_signature(signing_root.as_bytes(), &signed_address_change.signature) {
+        return Err(invalid_operation_error(
+            InvalidOperation::BlsToExecutionChange(InvalidBlsToExecutionChange::InvalidSignature(
+                signed_address_change.signature.clone(),
+            )),
+        ));
+    }
+
+    let mut withdrawal_credentials = [0u8; 32];
+    withdrawal_credentials[0] = ETH1_ADDRESS_WITHDRAWAL_PREFIX;
+    withdrawal_credentials[12..].copy_from_slice(address_change.to_execution_address.as_ref());
+    state.validators[validator_index].withdrawal_credentials =
+        Bytes32::try_from(withdrawal_credentials.as_ref()).expect("correct input");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capella::mainnet::{BeaconState, ExecutionPayload};
+    use crate::capella::BlsToExecutionChange;
+    use crate::crypto::SecretKey;
+    use crate::phase0::sign_with_domain;
+    use crate::primitives::FAR_FUTURE_EPOCH;
+    use crate::state_transition::InvalidBlock;
+
+    const EPOCH: Epoch = 10;
+
+    // NOTE: every byte of the credentials is `prefix` so the withdrawal address is `[prefix; 20]`
+    fn validator(prefix: u8, withdrawable_epoch: Epoch, context: &Context) -> Validator {
+        Validator {
+            withdrawal_credentials: Bytes32::try_from([prefix; 32].as_ref()).unwrap(),
+            effective_balance: context.max_effective_balance,
+            withdrawable_epoch,
+            exit_epoch: FAR_FUTURE_EPOCH,
+            ..Default::default()
+        }
+    }
+
+    // validators with BLS credentials, a full withdrawal, a partial withdrawal and no excess
+    fn state(context: &Context) -> BeaconState {
+        let mut state = BeaconState {
+            slot: EPOCH * context.slots_per_epoch,
+            ..Default::default()
+        };
+        let max_effective_balance = context.max_effective_balance;
+        let validators = [
+            (
+                validator(BLS_WITHDRAWAL_PREFIX, EPOCH, context),
+                max_effective_balance,
+            ),
+            (
+                validator(ETH1_ADDRESS_WITHDRAWAL_PREFIX, EPOCH, context),
+                max_effective_balance,
+            ),
+            (
+                validator(ETH1_ADDRESS_WITHDRAWAL_PREFIX, FAR_FUTURE_EPOCH, context),
+                max_effective_balance + 10u64.pow(9),
+            ),
+            (
+                validator(ETH1_ADDRESS_WITHDRAWAL_PREFIX, FAR_FUTURE_EPOCH, context),
+                max_effective_balance,
+            ),
+        ];
+        for (validator, balance) in validators {
+            state.validators.push(validator);
+            state.balances.push(balance);
+        }
+        state
+    }
+
+    fn withdrawal(index: u64, validator_index: usize, amount: Gwei) -> Withdrawal {
+        Withdrawal {
+            index,
+            validator_index,
+            address: ExecutionAddress::try_from([ETH1_ADDRESS_WITHDRAWAL_PREFIX; 20].as_ref())
+                .unwrap(),
+            amount,
+        }
+    }
+
+    fn bls_to_execution_change_error(
+        result: Result<(), Error>,
+    ) -> Option<InvalidBlsToExecutionChange> {
+        match result {
+            Err(Error::InvalidBlock(error)) => match *error {
+                InvalidBlock::InvalidOperation(InvalidOperation::BlsToExecutionChange(error)) => {
+                    Some(error)
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_get_expected_withdrawals() {
+        let context = Context::for_mainnet();
+        let mut state = state(&context);
+        let max_effective_balance = context.max_effective_balance;
+
+        let withdrawals = get_expected_withdrawals(&state, &context);
+        assert_eq!(
+            withdrawals,
+            vec![
+                withdrawal(0, 1, max_effective_balance),
+                withdrawal(1, 2, 10u64.pow(9)),
+            ]
+        );
+
+        // the sweep starts from the next validator and wraps around the registry
+        state.next_withdrawal_index = 5;
+        state.next_withdrawal_validator_index = 2;
+        let withdrawals = get_expected_withdrawals(&state, &context);
+        assert_eq!(
+            withdrawals,
+            vec![
+                withdrawal(5, 2, 10u64.pow(9)),
+                withdrawal(6, 1, max_effective_balance),
+            ]
+        );
+
+        let empty_state = BeaconState::default();
+        assert!(get_expected_withdrawals(&empty_state, &context).is_empty());
+    }
+
+    #[test]
+    fn test_process_withdrawals() {
+        let context = Context::for_mainnet();
+        let mut state = state(&context);
+        let mut payload = ExecutionPayload::default();
+        for withdrawal in get_expected_withdrawals(&state, &context) {
+            payload.withdrawals.push(withdrawal);
+        }
+
+        process_withdrawals(&mut state, &payload, &context).unwrap();
+        assert_eq!(state.balances[1], 0);
+        assert_eq!(state.balances[2], context.max_effective_balance);
+        assert_eq!(state.next_withdrawal_index, 2);
+        let next_validator_index = context.max_validators_per_withdrawals_sweep % 4;
+        assert_eq!(state.next_withdrawal_validator_index, next_validator_index);
+
+        // the payload must carry exactly the expected withdrawals
+        let mut state = self::state(&context);
+        payload.withdrawals[1].amount += 1;
+        assert!(process_withdrawals(&mut state, &payload, &context).is_err());
+        assert!(process_withdrawals(&mut state, &ExecutionPayload::default(), &context).is_err());
+    }
+
+    #[test]
+    fn test_process_withdrawals_full_payload() {
+        let mut context = Context::for_mainnet();
+        context.max_withdrawals_per_payload = 1;
+        let mut state = state(&context);
+        let mut payload = ExecutionPayload::default();
+        payload
+            .withdrawals
+            .push(withdrawal(0, 1, context.max_effective_balance));
+
+        // a full payload resumes the sweep after the last withdrawn validator
+        process_withdrawals(&mut state, &payload, &context).unwrap();
+        assert_eq!(state.next_withdrawal_index, 1);
+        assert_eq!(state.next_withdrawal_validator_index, 2);
+    }
+
+    #[test]
+    fn test_process_withdrawals_empty_registry() {
+        let context = Context::for_mainnet();
+        let mut state = BeaconState::default();
+        process_withdrawals(&mut state, &ExecutionPayload::default(), &context).unwrap();
+        assert_eq!(state.next_withdrawal_validator_index, 0);
+    }
+
+    #[test]
+    fn test_process_bls_to_execution_change() {
+        let context = Context::for_mainnet();
+        let mut state = state(&context);
+        let mut rng = rand::thread_rng();
+        let secret_key = SecretKey::random(&mut rng).unwrap();
+        let public_key = secret_key.public_key();
+        let mut withdrawal_credentials = hash(public_key.as_bytes());
+        withdrawal_credentials[0] = BLS_WITHDRAWAL_PREFIX;
+        state.validators[0].withdrawal_credentials = withdrawal_credentials;
+
+        let to_execution_address = ExecutionAddress::try_from([0xabu8; 20].as_ref()).unwrap();
+        let mut message = BlsToExecutionChange {
+            validator_index: 0,
+            from_bls_public_key: public_key,
+            to_execution_address: to_execution_address.clone(),
+        };
+        let domain = compute_domain(
+            DomainType::BlsToExecutionChange,
+            None,
+            Some(state.genesis_validators_root),
+            &context,
+        )
+        .unwrap();
+        let signature = sign_with_domain(&mut message, &secret_key, domain).unwrap();
+        let signed_change = SignedBlsToExecutionChange { message, signature };
+
+        // signed by another key
+        let other_key = SecretKey::random(&mut rng).unwrap();
+        let mut invalid_change = signed_change.clone();
+        let mut message = invalid_change.message.clone();
+        invalid_change.signature = sign_with_domain(&mut message, &other_key, domain).unwrap();
+        let result = process_bls_to_execution_change(&mut state, &mut invalid_change, &context);
+        assert!(matches!(
+            bls_to_execution_change_error(result),
+            Some(InvalidBlsToExecutionChange::InvalidSignature(_))
+        ));
+
+        // the public key does not match the credentials
+        let mut invalid_change = signed_change.clone();
+        invalid_change.message.from_bls_public_key = other_key.public_key();
+        let result = process_bls_to_execution_change(&mut state, &mut invalid_change, &context);
+        assert!(matches!(
+            bls_to_execution_change_error(result),
+            Some(InvalidBlsToExecutionChange::PublicKeyMismatch(_))
+        ));
+
+        let mut invalid_change = signed_change.clone();
+        invalid_change.message.validator_index = state.validators.len();
+        let result = process_bls_to_execution_change(&mut state, &mut invalid_change, &context);
+        assert!(matches!(
+            bls_to_execution_change_error(result),
+            Some(InvalidBlsToExecutionChange::ValidatorIndexOutOfBounds(4))
+        ));
+
+        let mut change = signed_change.clone();
+        process_bls_to_execution_change(&mut state, &mut change, &context).unwrap();
+        let withdrawal_credentials = state.validators[0].withdrawal_credentials.as_ref();
+        assert_eq!(withdrawal_credentials[0], ETH1_ADDRESS_WITHDRAWAL_PREFIX);
+        assert_eq!(withdrawal_credentials[1..12], [0u8; 11]);
+        assert_eq!(&withdrawal_credentials[12..], to_execution_address.as_ref());
+
+        // the credentials can only be changed once
+        let mut change = signed_change;
+        let result = process_bls_to_execution_change(&mut state, &mut change, &context);
+        assert!(matches!(
+            bls_to_execution_change_error(result),
+            Some(InvalidBlsToExecutionChange::NonBlsWithdrawalCredentials(
+                ETH1_ADDRESS_WITHDRAWAL_PREFIX
+            ))
+        ));
+    }
+}
@@ -0,0 +1,83 @@
+use crate::capella as spec;
+
+use crate::state_transition::{Context, Error};
+use spec::helpers::get_current_epoch;
+use spec::{BeaconState, HistoricalSummary};
+use ssz_rs::prelude::*;
+
+pub fn process_historical_summaries_update<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    context: &Context,
+) -> Result<(), Error> {
+    let next_epoch = get_current_epoch(state, context) + 1;
+    let epochs_per_historical_root = context.slots_per_historical_root / context.slots_per_epoch;
+    if next_epoch % epochs_per_historical_root == 0 {
+        let historical_summary = HistoricalSummary {
+            block_summary_root: state.block_roots.hash_tree_root()?,
+            state_summary_root: state.state_roots.hash_tree_root()?,
+        };
+        state.historical_summaries.push(historical_summary)
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capella::mainnet::BeaconState;
+    use crate::primitives::Root;
+
+    #[test]
+    fn test_process_historical_summaries_update() {
+        let context = Context::for_mainnet();
+        let epochs_per_historical_root =
+            context.slots_per_historical_root / context.slots_per_epoch;
+        let mut state = BeaconState::default();
+        state.block_roots[0] = Root::from_bytes([1u8; 32]);
+        state.state_roots[0] = Root::from_bytes([2u8; 32]);
+
+        // not at the end of a historical root period
+        state.slot = (epochs_per_historical_root - 2) * context.slots_per_epoch;
+        process_historical_summaries_update(&mut state, &context).unwrap();
+        assert!(state.historical_summaries.is_empty());
+
+        state.slot = (epochs_per_historical_root - 1) * context.slots_per_epoch;
+        process_historical_summaries_update(&mut state, &context).unwrap();
+        assert_eq!(state.historical_summaries.len(), 1);
+        let historical_summary = &state.historical_summaries[0];
+        assert_eq!(
+            historical_summary.block_summary_root,
+            state.block_roots.hash_tree_root().unwrap()
+        );
+        assert_eq!(
+            historical_summary.state_summary_root,
+            state.state_roots.hash_tree_root().unwrap()
+        );
+    }
+}
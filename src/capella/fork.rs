@@ -0,0 +1,159 @@
+use crate::bellatrix;
+use crate::capella as spec;
+
+use crate::phase0::{compute_epoch_at_slot, Fork};
+use crate::state_transition::Context;
+use spec::{BeaconState, ExecutionPayloadHeader};
+use ssz_rs::prelude::*;
+
+pub fn upgrade_to_capella<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+>(
+    state: &bellatrix::BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+    context: &Context,
+) -> BeaconState<
+    SLOTS_PER_HISTORICAL_ROOT,
+    HISTORICAL_ROOTS_LIMIT,
+    ETH1_DATA_VOTES_BOUND,
+    VALIDATOR_REGISTRY_LIMIT,
+    EPOCHS_PER_HISTORICAL_VECTOR,
+    EPOCHS_PER_SLASHINGS_VECTOR,
+    MAX_VALIDATORS_PER_COMMITTEE,
+    SYNC_COMMITTEE_SIZE,
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    MAX_BYTES_PER_TRANSACTION,
+    MAX_TRANSACTIONS_PER_PAYLOAD,
+> {
+    let epoch = compute_epoch_at_slot(state.slot, context);
+    let latest_execution_payload_header = &state.latest_execution_payload_header;
+    let latest_execution_payload_header = ExecutionPayloadHeader {
+        parent_hash: latest_execution_payload_header.parent_hash.clone(),
+        fee_recipient: latest_execution_payload_header.fee_recipient.clone(),
+        state_root: latest_execution_payload_header.state_root.clone(),
+        receipts_root: latest_execution_payload_header.receipts_root.clone(),
+        logs_bloom: latest_execution_payload_header.logs_bloom.clone(),
+        prev_randao: latest_execution_payload_header.prev_randao.clone(),
+        block_number: latest_execution_payload_header.block_number,
+        gas_limit: latest_execution_payload_header.gas_limit,
+        gas_used: latest_execution_payload_header.gas_used,
+        timestamp: latest_execution_payload_header.timestamp,
+        extra_data: latest_execution_payload_header.extra_data.clone(),
+        base_fee_per_gas: latest_execution_payload_header.base_fee_per_gas.clone(),
+        block_hash: latest_execution_payload_header.block_hash.clone(),
+        transactions_root: latest_execution_payload_header.transactions_root,
+        withdrawals_root: Default::default(),
+    };
+    BeaconState {
+        genesis_time: state.genesis_time,
+        genesis_validators_root: state.genesis_validators_root,
+        slot: state.slot,
+        fork: Fork {
+            previous_version: state.fork.current_version,
+            current_version: context.capella_fork_version,
+            epoch,
+        },
+        latest_block_header: state.latest_block_header.clone(),
+        block_roots: state.block_roots.clone(),
+        state_roots: state.state_roots.clone(),
+        historical_roots: state.historical_roots.clone(),
+        eth1_data: state.eth1_data.clone(),
+        eth1_data_votes: state.eth1_data_votes.clone(),
+        eth1_deposit_index: state.eth1_deposit_index,
+        validators: state.validators.clone(),
+        balances: state.balances.clone(),
+        randao_mixes: state.randao_mixes.clone(),
+        slashings: state.slashings.clone(),
+        previous_epoch_participation: state.previous_epoch_participation.clone(),
+        current_epoch_participation: state.current_epoch_participation.clone(),
+        justification_bits: state.justification_bits.clone(),
+        previous_justified_checkpoint: state.previous_justified_checkpoint.clone(),
+        current_justified_checkpoint: state.current_justified_checkpoint.clone(),
+        finalized_checkpoint: state.finalized_checkpoint.clone(),
+        inactivity_scores: state.inactivity_scores.clone(),
+        current_sync_committee: state.current_sync_committee.clone(),
+        next_sync_committee: state.next_sync_committee.clone(),
+        latest_execution_payload_header,
+        next_withdrawal_index: 0,
+        next_withdrawal_validator_index: 0,
+        historical_summaries: List::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bellatrix::mainnet::BeaconState;
+    use crate::phase0::Validator;
+    use crate::primitives::{Hash32, Root};
+
+    #[test]
+    fn test_upgrade_to_capella() {
+        let context = Context::for_mainnet();
+        let mut state = BeaconState {
+            slot: 10 * context.slots_per_epoch + 3,
+            ..Default::default()
+        };
+        state.fork.current_version = context.bellatrix_fork_version;
+        state.validators.push(Validator::default());
+        state.balances.push(context.max_effective_balance);
+        let header = &mut state.latest_execution_payload_header;
+        header.block_hash = Hash32::try_from([1u8; 32].as_ref()).unwrap();
+        header.block_number = 42;
+        header.gas_limit = 30_000_000;
+
+        let mut post_state = upgrade_to_capella(&state, &context);
+        assert_eq!(post_state.slot, state.slot);
+        assert_eq!(
+            post_state.fork.previous_version,
+            context.bellatrix_fork_version
+        );
+        assert_eq!(
+            post_state.fork.current_version,
+            context.capella_fork_version
+        );
+        assert_eq!(post_state.fork.epoch, 10);
+        assert_eq!(post_state.balances[0], context.max_effective_balance);
+        assert_eq!(
+            post_state.validators.hash_tree_root().unwrap(),
+            state.validators.hash_tree_root().unwrap()
+        );
+
+        let header = &post_state.latest_execution_payload_header;
+        let pre_header = &state.latest_execution_payload_header;
+        assert_eq!(header.block_hash, pre_header.block_hash);
+        assert_eq!(header.block_number, 42);
+        assert_eq!(header.gas_limit, 30_000_000);
+        assert_eq!(header.transactions_root, pre_header.transactions_root);
+        assert_eq!(header.withdrawals_root, Root::default());
+
+        assert_eq!(post_state.next_withdrawal_index, 0);
+        assert_eq!(post_state.next_withdrawal_validator_index, 0);
+        assert!(post_state.historical_summaries.is_empty());
+        assert!(post_state.hash_tree_root().is_ok());
+    }
+}
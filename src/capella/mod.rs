@@ -0,0 +1,31 @@
+//! This module provides an implementation of the `capella` fork
+//! of the consensus spec. The primary entrypoints should be one of
+//! the "presets" like `mainnet` or `minimal`.
+mod beacon_block;
+mod beacon_state;
+mod block_processing;
+mod bls_to_execution_change;
+mod epoch_processing;
+mod execution;
+mod fork;
+mod helpers;
+mod presets;
+mod withdrawal;
+
+pub use beacon_block::*;
+pub use beacon_state::*;
+pub use block_processing::*;
+pub use bls_to_execution_change::*;
+pub use epoch_processing::*;
+pub use execution::*;
+pub use fork::*;
+pub use presets::Preset;
+pub use withdrawal::*;
+
+pub mod mainnet {
+    pub use super::presets::mainnet::*;
+}
+
+pub mod minimal {
+    pub use super::presets::minimal::*;
+}
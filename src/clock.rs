@@ -2,6 +2,7 @@
 use crate::configs;
 use crate::phase0 as presets;
 use crate::primitives::{Epoch, Slot};
+use std::ops::RangeInclusive;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 pub const MAINNET_GENESIS_TIME: u64 = 1606824023;
@@ -104,6 +105,21 @@ impl<T: TimeProvider> Clock<T> {
         slot / self.slots_per_epoch
     }
 
+    // Return the range of slots the current time falls in when tolerating
+    // up to `disparity` between the local clock and that of a peer.
+    pub fn current_slot_range(&self, disparity: Duration) -> RangeInclusive<Slot> {
+        let current_time = Duration::from_secs(self.get_current_time());
+        let earliest = self.slot_at_duration(current_time.saturating_sub(disparity));
+        let latest = self.slot_at_duration(current_time + disparity);
+        earliest..=latest
+    }
+
+    #[inline]
+    fn slot_at_duration(&self, time: Duration) -> Slot {
+        let span = time.saturating_sub(Duration::from_secs(self.genesis_time));
+        (span.as_millis() / Duration::from_secs(self.seconds_per_slot).as_millis()) as Slot
+    }
+
     pub fn duration_until_next_slot(&self) -> Duration {
         let current_time = self.get_current_time();
         if self.before_genesis_at(current_time) {
@@ -194,6 +210,25 @@ mod tests {
         assert_eq!(clock.duration_until_next_slot().as_secs(), 9);
     }
 
+    #[test]
+    fn test_current_slot_range() {
+        let seconds_per_slot = 12;
+        let disparity = Duration::from_millis(500);
+        let time_provider = new_ticker(seconds_per_slot);
+        let clock = Clock::new(0, seconds_per_slot, 32, time_provider.clone());
+        assert_eq!(clock.current_slot_range(disparity), 0..=0);
+        for _ in 0..12 {
+            time_provider.tick();
+        }
+        assert_eq!(clock.current_slot_range(disparity), 0..=1);
+        time_provider.tick();
+        assert_eq!(clock.current_slot_range(disparity), 1..=1);
+        for _ in 0..11 {
+            time_provider.tick();
+        }
+        assert_eq!(clock.current_slot_range(disparity), 1..=2);
+    }
+
     #[cfg(feature = "async")]
     #[tokio::test]
     async fn test_slot_stream() {
@@ -20,6 +20,8 @@ pub const BELLATRIX_FORK_VERSION: Version = [2, 0, 0, 0];
 pub const BELLATRIX_FORK_EPOCH: Epoch = 18446744073709551615;
 pub const CAPELLA_FORK_VERSION: Version = [3, 0, 0, 0];
 pub const CAPELLA_FORK_EPOCH: Epoch = 18446744073709551615;
+pub const DENEB_FORK_VERSION: Version = [4, 0, 0, 0];
+pub const DENEB_FORK_EPOCH: Epoch = 18446744073709551615;
 pub const SHARDING_FORK_VERSION: Version = [4, 0, 0, 0];
 pub const SHARDING_FORK_EPOCH: Epoch = 18446744073709551615;
 pub const INACTIVITY_SCORE_BIAS: u64 = 4;
@@ -55,6 +57,8 @@ pub fn config() -> Config {
         bellatrix_fork_epoch: BELLATRIX_FORK_EPOCH,
         capella_fork_version: CAPELLA_FORK_VERSION,
         capella_fork_epoch: CAPELLA_FORK_EPOCH,
+        deneb_fork_version: DENEB_FORK_VERSION,
+        deneb_fork_epoch: DENEB_FORK_EPOCH,
         sharding_fork_version: SHARDING_FORK_VERSION,
         sharding_fork_epoch: SHARDING_FORK_EPOCH,
         seconds_per_slot: SECONDS_PER_SLOT,
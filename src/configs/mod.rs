@@ -21,6 +21,8 @@ pub struct Config {
     pub bellatrix_fork_epoch: Epoch,
     pub capella_fork_version: Version,
     pub capella_fork_epoch: Epoch,
+    pub deneb_fork_version: Version,
+    pub deneb_fork_epoch: Epoch,
     pub sharding_fork_version: Version,
     pub sharding_fork_epoch: Epoch,
 
@@ -74,6 +74,17 @@ pub fn aggregate(signatures: &[Signature]) -> Result<Signature, Error> {
         .map_err(|e| BLSTError::from(e).into())
 }
 
+pub fn eth_aggregate_public_keys(public_keys: &[PublicKey]) -> Result<PublicKey, Error> {
+    if public_keys.is_empty() {
+        return Err(Error::EmptyInput);
+    }
+    let vs: Vec<&blst_core::PublicKey> = public_keys.iter().map(|pk| &pk.0).collect();
+
+    blst_core::AggregatePublicKey::aggregate(&vs, true)
+        .map(|pk| PublicKey(pk.to_public_key()))
+        .map_err(|e| BLSTError::from(e).into())
+}
+
 pub fn aggregate_verify(pks: &[PublicKey], msgs: &[&[u8]], signature: &Signature) -> bool {
     let v: Vec<&blst_core::PublicKey> = pks.iter().map(|pk| &pk.0).collect();
     let res = signature.0.aggregate_verify(true, msgs, BLS_DST, &v, true);
@@ -396,7 +407,9 @@ impl SimpleSerialize for Signature {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::crypto::{aggregate, aggregate_verify, fast_aggregate_verify};
+    use crate::crypto::{
+        aggregate, aggregate_verify, eth_aggregate_public_keys, fast_aggregate_verify,
+    };
     use rand::prelude::*;
 
     const INFINITY_COMPRESSED_PUBLIC_KEY: [u8; BLS_PUBLIC_KEY_BYTES_LEN] = [
@@ -585,6 +598,25 @@ mod tests {
         assert!(v);
     }
 
+    #[test]
+    fn test_eth_aggregate_public_keys() {
+        let n = 20;
+        let mut rng = thread_rng();
+        let sks: Vec<_> = (0..n)
+            .map(|_| SecretKey::random(&mut rng).unwrap())
+            .collect();
+        let pks: Vec<_> = sks.iter().map(|sk| sk.public_key()).collect();
+        let msg = "message".as_bytes();
+
+        let signatures: Vec<_> = sks.iter().map(|sk| sk.sign(msg)).collect();
+
+        let aggregate_public_key = eth_aggregate_public_keys(&pks).unwrap();
+        let sig = aggregate(signatures.as_slice()).unwrap();
+
+        assert!(aggregate_public_key.verify_signature(msg, &sig));
+        assert!(eth_aggregate_public_keys(&[]).is_err());
+    }
+
     #[test]
     fn test_can_make_default_signature() {
         let _ = Signature::default();
@@ -0,0 +1,73 @@
+use crate::crypto::hash;
+use crate::deneb::VERSIONED_HASH_VERSION_KZG;
+use crate::phase0::SignedBeaconBlockHeader;
+use crate::primitives::{Bytes32, Root};
+use crate::ssz::ByteVector;
+use ssz_rs::prelude::*;
+
+pub type BlobIndex = u64;
+pub type VersionedHash = Bytes32;
+pub type KzgCommitment = ByteVector<48>;
+pub type KzgProof = ByteVector<48>;
+pub type Blob<const BYTES_PER_BLOB: usize> = ByteVector<BYTES_PER_BLOB>;
+
+#[derive(Default, Debug, Clone, SimpleSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlobSidecar<
+    const BYTES_PER_BLOB: usize,
+    const KZG_COMMITMENT_INCLUSION_PROOF_DEPTH: usize,
+> {
+    #[serde(with = "crate::serde::as_string")]
+    pub index: BlobIndex,
+    pub blob: Blob<BYTES_PER_BLOB>,
+    pub kzg_commitment: KzgCommitment,
+    pub kzg_proof: KzgProof,
+    pub signed_block_header: SignedBeaconBlockHeader,
+    pub kzg_commitment_inclusion_proof: Vector<Bytes32, KZG_COMMITMENT_INCLUSION_PROOF_DEPTH>,
+}
+
+#[derive(Default, Debug, Clone, SimpleSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlobIdentifier {
+    pub block_root: Root,
+    #[serde(with = "crate::serde::as_string")]
+    pub index: BlobIndex,
+}
+
+/// The blobs accompanying an execution payload, along with their commitments and proofs,
+/// as returned by the execution layer and builders.
+#[derive(Default, Debug, Clone, SimpleSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlobsBundle<const BYTES_PER_BLOB: usize, const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize> {
+    pub commitments: List<KzgCommitment, MAX_BLOB_COMMITMENTS_PER_BLOCK>,
+    pub proofs: List<KzgProof, MAX_BLOB_COMMITMENTS_PER_BLOCK>,
+    pub blobs: List<Blob<BYTES_PER_BLOB>, MAX_BLOB_COMMITMENTS_PER_BLOCK>,
+}
+
+pub fn kzg_commitment_to_versioned_hash(kzg_commitment: &KzgCommitment) -> VersionedHash {
+    let mut versioned_hash = hash(kzg_commitment.as_ref()).as_ref().to_vec();
+    versioned_hash[0] = VERSIONED_HASH_VERSION_KZG;
+    VersionedHash::try_from(versioned_hash.as_ref()).expect("correct input")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kzg_commitment_to_versioned_hash() {
+        // the commitment to the empty blob, i.e. the point at infinity
+        let mut commitment = [0u8; 48];
+        commitment[0] = 0xc0;
+        let commitment = KzgCommitment::try_from(commitment.as_ref()).unwrap();
+
+        let versioned_hash = kzg_commitment_to_versioned_hash(&commitment);
+        assert_eq!(
+            hex::encode(versioned_hash.as_ref()),
+            "010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014"
+        );
+        let digest = hash(commitment.as_ref());
+        assert_eq!(versioned_hash.as_ref()[0], VERSIONED_HASH_VERSION_KZG);
+        assert_eq!(versioned_hash.as_ref()[1..], digest.as_ref()[1..]);
+    }
+}
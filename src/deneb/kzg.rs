@@ -0,0 +1,150 @@
+use crate::deneb::{Blob, KzgCommitment, KzgProof};
+use c_kzg::Bytes48;
+pub use c_kzg::KzgSettings;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("{0}")]
+    Kzg(#[from] c_kzg::Error),
+    #[error("expected the same number of blobs ({blobs}), commitments ({commitments}) and proofs ({proofs})")]
+    LengthMismatch {
+        blobs: usize,
+        commitments: usize,
+        proofs: usize,
+    },
+    #[error("KZG proofs do not verify against the given blobs and commitments")]
+    InvalidProof,
+}
+
+/// Load the parameters of the KZG trusted setup from a file in the format of the
+/// consensus specs' `trusted_setup.txt`.
+pub fn load_trusted_setup<P: AsRef<Path>>(path: P) -> Result<KzgSettings, Error> {
+    let settings = KzgSettings::load_trusted_setup_file(path.as_ref())?;
+    Ok(settings)
+}
+
+fn to_bytes48(bytes: &[u8]) -> Result<Bytes48, Error> {
+    Ok(Bytes48::from_bytes(bytes)?)
+}
+
+pub fn verify_blob_kzg_proof_batch<const BYTES_PER_BLOB: usize>(
+    blobs: &[Blob<BYTES_PER_BLOB>],
+    commitments: &[KzgCommitment],
+    proofs: &[KzgProof],
+    kzg_settings: &KzgSettings,
+) -> Result<(), Error> {
+    if blobs.len() != commitments.len() || blobs.len() != proofs.len() {
+        return Err(Error::LengthMismatch {
+            blobs: blobs.len(),
+            commitments: commitments.len(),
+            proofs: proofs.len(),
+        });
+    }
+
+    let blobs = blobs
+        .iter()
+        .map(|blob| c_kzg::Blob::from_bytes(blob.as_ref()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let commitments = commitments
+        .iter()
+        .map(|commitment| to_bytes48(commitment.as_ref()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let proofs = proofs
+        .iter()
+        .map(|proof| to_bytes48(proof.as_ref()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let is_valid =
+        c_kzg::KzgProof::verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs, kzg_settings)?;
+    if !is_valid {
+        return Err(Error::InvalidProof);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deneb::mainnet::{BYTES_PER_BLOB, FIELD_ELEMENTS_PER_BLOB};
+    use std::fmt::Write;
+
+    const G1_GENERATOR: &str = "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+    const G2_GENERATOR: &str = "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8";
+    const G2_POINTS: usize = 65;
+
+    // NOTE: an insecure setup with the secret `1`, which is the first root of unity, so
+    // the Lagrange basis is the generator followed by points at infinity; this keeps the
+    // fixture small while still exercising the verification against a loaded setup
+    fn write_trusted_setup_fixture() -> std::path::PathBuf {
+        let g1_infinity = format!("c0{}", "0".repeat(94));
+        let mut fixture = format!("{FIELD_ELEMENTS_PER_BLOB}\n{G2_POINTS}\n{G1_GENERATOR}\n");
+        for _ in 1..FIELD_ELEMENTS_PER_BLOB {
+            writeln!(fixture, "{g1_infinity}").unwrap();
+        }
+        for _ in 0..G2_POINTS {
+            writeln!(fixture, "{G2_GENERATOR}").unwrap();
+        }
+        let path = std::env::temp_dir().join(format!(
+            "ethereum-consensus-trusted-setup-{}.txt",
+            std::process::id()
+        ));
+        std::fs::write(&path, fixture).unwrap();
+        path
+    }
+
+    fn blob(seed: u8) -> Blob<BYTES_PER_BLOB> {
+        let mut bytes = vec![0u8; BYTES_PER_BLOB];
+        // keep each field element below the modulus by leaving the leading byte empty
+        for (i, field_element) in bytes.chunks_exact_mut(32).enumerate() {
+            field_element[31] = seed.wrapping_add(i as u8);
+        }
+        Blob::try_from(bytes.as_ref()).unwrap()
+    }
+
+    fn commit_and_prove(
+        blob: &Blob<BYTES_PER_BLOB>,
+        kzg_settings: &KzgSettings,
+    ) -> (KzgCommitment, KzgProof) {
+        let c_kzg_blob = c_kzg::Blob::from_bytes(blob.as_ref()).unwrap();
+        let commitment =
+            c_kzg::KzgCommitment::blob_to_kzg_commitment(&c_kzg_blob, kzg_settings).unwrap();
+        let commitment = commitment.to_bytes();
+        let proof = c_kzg::KzgProof::compute_blob_kzg_proof(&c_kzg_blob, &commitment, kzg_settings)
+            .unwrap();
+        (
+            KzgCommitment::try_from(commitment.as_slice()).unwrap(),
+            KzgProof::try_from(proof.to_bytes().as_slice()).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_verify_blob_kzg_proof_batch() {
+        let path = write_trusted_setup_fixture();
+        let kzg_settings = load_trusted_setup(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let blobs = [blob(1), blob(2)];
+        let (commitments, proofs): (Vec<_>, Vec<_>) = blobs
+            .iter()
+            .map(|blob| commit_and_prove(blob, &kzg_settings))
+            .unzip();
+        verify_blob_kzg_proof_batch(&blobs, &commitments, &proofs, &kzg_settings).unwrap();
+
+        let swapped_proofs = [proofs[1].clone(), proofs[0].clone()];
+        assert!(
+            verify_blob_kzg_proof_batch(&blobs, &commitments, &swapped_proofs, &kzg_settings)
+                .is_err()
+        );
+
+        assert!(matches!(
+            verify_blob_kzg_proof_batch(&blobs, &commitments[..1], &proofs, &kzg_settings),
+            Err(Error::LengthMismatch {
+                blobs: 2,
+                commitments: 1,
+                proofs: 2,
+            })
+        ));
+    }
+}
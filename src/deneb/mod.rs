@@ -0,0 +1,27 @@
+//! This module provides an implementation of the `deneb` fork
+//! of the consensus spec. The primary entrypoints should be one of
+//! the "presets" like `mainnet` or `minimal`.
+mod beacon_block;
+mod beacon_state;
+mod blob_sidecar;
+mod execution;
+#[cfg(feature = "kzg")]
+pub mod kzg;
+mod presets;
+
+pub use beacon_block::*;
+pub use beacon_state::*;
+pub use blob_sidecar::*;
+pub use execution::*;
+pub use presets::Preset;
+
+pub const BYTES_PER_FIELD_ELEMENT: usize = 32;
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+pub mod mainnet {
+    pub use super::presets::mainnet::*;
+}
+
+pub mod minimal {
+    pub use super::presets::minimal::*;
+}
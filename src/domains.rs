@@ -1,4 +1,6 @@
-#[derive(Clone, Copy)]
+use thiserror::Error;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DomainType {
     BeaconProposer,
     BeaconAttester,
@@ -7,19 +9,95 @@ pub enum DomainType {
     VoluntaryExit,
     SelectionProof,
     AggregateAndProof,
+    SyncCommittee,
+    SyncCommitteeSelectionProof,
+    ContributionAndProof,
+    BlsToExecutionChange,
+    BlobSidecar,
     ApplicationMask,
     ApplicationBuilder,
 }
 
+#[derive(Debug, Error)]
+#[error("unknown domain type {0:?}")]
+pub struct UnknownDomainType(pub [u8; 4]);
+
 impl DomainType {
     pub fn as_bytes(&self) -> [u8; 4] {
         match self {
+            Self::BeaconProposer => [0, 0, 0, 0],
+            Self::BeaconAttester => [1, 0, 0, 0],
+            Self::Randao => [2, 0, 0, 0],
+            Self::Deposit => [3, 0, 0, 0],
+            Self::VoluntaryExit => [4, 0, 0, 0],
+            Self::SelectionProof => [5, 0, 0, 0],
+            Self::AggregateAndProof => [6, 0, 0, 0],
+            Self::SyncCommittee => [7, 0, 0, 0],
+            Self::SyncCommitteeSelectionProof => [8, 0, 0, 0],
+            Self::ContributionAndProof => [9, 0, 0, 0],
+            Self::BlsToExecutionChange => [10, 0, 0, 0],
+            Self::BlobSidecar => [11, 0, 0, 0],
             Self::ApplicationMask => [0, 0, 0, 1],
             Self::ApplicationBuilder => [0, 0, 0, 1],
-            _ => {
-                let data = *self as u32;
-                data.to_le_bytes()
-            }
         }
     }
 }
+
+impl TryFrom<[u8; 4]> for DomainType {
+    type Error = UnknownDomainType;
+
+    // NOTE: `ApplicationMask` and `ApplicationBuilder` share the same bytes;
+    // they are recovered as `ApplicationBuilder`, the only application domain in use
+    fn try_from(bytes: [u8; 4]) -> Result<Self, Self::Error> {
+        let domain_type = match bytes {
+            [0, 0, 0, 0] => Self::BeaconProposer,
+            [1, 0, 0, 0] => Self::BeaconAttester,
+            [2, 0, 0, 0] => Self::Randao,
+            [3, 0, 0, 0] => Self::Deposit,
+            [4, 0, 0, 0] => Self::VoluntaryExit,
+            [5, 0, 0, 0] => Self::SelectionProof,
+            [6, 0, 0, 0] => Self::AggregateAndProof,
+            [7, 0, 0, 0] => Self::SyncCommittee,
+            [8, 0, 0, 0] => Self::SyncCommitteeSelectionProof,
+            [9, 0, 0, 0] => Self::ContributionAndProof,
+            [10, 0, 0, 0] => Self::BlsToExecutionChange,
+            [11, 0, 0, 0] => Self::BlobSidecar,
+            [0, 0, 0, 1] => Self::ApplicationBuilder,
+            bytes => return Err(UnknownDomainType(bytes)),
+        };
+        Ok(domain_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_type_round_trip() {
+        let domain_types = [
+            DomainType::BeaconProposer,
+            DomainType::BeaconAttester,
+            DomainType::Randao,
+            DomainType::Deposit,
+            DomainType::VoluntaryExit,
+            DomainType::SelectionProof,
+            DomainType::AggregateAndProof,
+            DomainType::SyncCommittee,
+            DomainType::SyncCommitteeSelectionProof,
+            DomainType::ContributionAndProof,
+            DomainType::BlsToExecutionChange,
+            DomainType::BlobSidecar,
+            DomainType::ApplicationBuilder,
+        ];
+        for domain_type in domain_types {
+            let recovered = DomainType::try_from(domain_type.as_bytes()).unwrap();
+            assert_eq!(recovered, domain_type);
+        }
+        assert_eq!(
+            DomainType::try_from(DomainType::ApplicationMask.as_bytes()).unwrap(),
+            DomainType::ApplicationBuilder
+        );
+        assert!(DomainType::try_from([12, 0, 0, 0]).is_err());
+    }
+}
@@ -0,0 +1,90 @@
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+pub const JWT_SECRET_LENGTH: usize = 32;
+// NOTE: tokens are accepted if issued within this many seconds of the local time
+pub const JWT_IAT_TOLERANCE_SECS: u64 = 60;
+
+#[derive(Debug, Error)]
+pub enum AuthError {
+    #[error("JWT secret must be {JWT_SECRET_LENGTH} hex-encoded bytes")]
+    InvalidSecret,
+    #[error("{0}")]
+    Jwt(#[from] jsonwebtoken::errors::Error),
+    #[error("token issued at {iat} is outside the tolerated window around {now}")]
+    StaleToken { iat: u64, now: u64 },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Claims {
+    pub iat: u64,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("after unix epoch")
+        .as_secs()
+}
+
+/// The secret shared with the execution client to authenticate Engine API requests.
+#[derive(Clone)]
+pub struct JwtSecret([u8; JWT_SECRET_LENGTH]);
+
+impl fmt::Debug for JwtSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "JwtSecret(..)")
+    }
+}
+
+impl JwtSecret {
+    pub fn random() -> Self {
+        let mut secret = [0u8; JWT_SECRET_LENGTH];
+        rand::thread_rng().fill_bytes(&mut secret);
+        Self(secret)
+    }
+
+    /// Parse a secret in the format of the shared `jwt.hex` file, with or without `0x` prefix.
+    pub fn try_from_hex(s: &str) -> Result<Self, AuthError> {
+        let s = s.trim();
+        let s = s.strip_prefix("0x").unwrap_or(s);
+        let mut secret = [0u8; JWT_SECRET_LENGTH];
+        hex::decode_to_slice(s, &mut secret).map_err(|_| AuthError::InvalidSecret)?;
+        Ok(Self(secret))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn generate_token(&self) -> Result<String, AuthError> {
+        let claims = Claims { iat: now() };
+        let token = encode(
+            &Header::new(Algorithm::HS256),
+            &claims,
+            &EncodingKey::from_secret(&self.0),
+        )?;
+        Ok(token)
+    }
+
+    pub fn validate_token(&self, token: &str) -> Result<Claims, AuthError> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.required_spec_claims = HashSet::new();
+        validation.validate_exp = false;
+        let claims =
+            decode::<Claims>(token, &DecodingKey::from_secret(&self.0), &validation)?.claims;
+        let now = now();
+        if claims.iat.abs_diff(now) > JWT_IAT_TOLERANCE_SECS {
+            return Err(AuthError::StaleToken {
+                iat: claims.iat,
+                now,
+            });
+        }
+        Ok(claims)
+    }
+}
@@ -0,0 +1,300 @@
+use crate::engine_api::auth::{AuthError, JwtSecret};
+use crate::engine_api::types::{
+    ConversionError, ExecutionPayloadV1, ForkchoiceStateV1, ForkchoiceUpdatedV1Response,
+    PayloadAttributesV1, PayloadId, PayloadStatusV1, TransitionConfigurationV1,
+};
+use async_trait::async_trait;
+use reqwest::Url;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use thiserror::Error;
+
+pub const ENGINE_NEW_PAYLOAD_V1: &str = "engine_newPayloadV1";
+pub const ENGINE_FORKCHOICE_UPDATED_V1: &str = "engine_forkchoiceUpdatedV1";
+pub const ENGINE_GET_PAYLOAD_V1: &str = "engine_getPayloadV1";
+pub const ENGINE_EXCHANGE_TRANSITION_CONFIGURATION_V1: &str =
+    "engine_exchangeTransitionConfigurationV1";
+
+/// An error object returned by the execution client.
+#[derive(Debug, Clone, Error, Serialize, Deserialize)]
+#[error("{message} (code {code})")]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("{0}")]
+    Url(#[from] url::ParseError),
+    #[error("{0}")]
+    Http(#[from] reqwest::Error),
+    #[error("{0}")]
+    Auth(#[from] AuthError),
+    #[error("{0}")]
+    Rpc(#[from] RpcError),
+    #[error("{0}")]
+    Conversion(#[from] ConversionError),
+    #[error("response is missing both a result and an error")]
+    MissingResult,
+    #[error("unknown payload with id {0:?}")]
+    UnknownPayload(PayloadId),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsonRpcRequest<P> {
+    pub jsonrpc: String,
+    pub id: u64,
+    pub method: String,
+    pub params: P,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JsonRpcResponse<T> {
+    pub jsonrpc: String,
+    pub id: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+/// The subset of the Engine API a consensus client drives during and after the merge.
+#[async_trait]
+pub trait ExecutionEngine: Send + Sync {
+    async fn new_payload(&self, payload: ExecutionPayloadV1) -> Result<PayloadStatusV1, Error>;
+
+    async fn forkchoice_updated(
+        &self,
+        forkchoice_state: ForkchoiceStateV1,
+        payload_attributes: Option<PayloadAttributesV1>,
+    ) -> Result<ForkchoiceUpdatedV1Response, Error>;
+
+    async fn get_payload(&self, payload_id: PayloadId) -> Result<ExecutionPayloadV1, Error>;
+
+    async fn exchange_transition_configuration(
+        &self,
+        configuration: TransitionConfigurationV1,
+    ) -> Result<TransitionConfigurationV1, Error>;
+}
+
+/// A JSON-RPC client for the authenticated Engine API of an execution client.
+pub struct Client {
+    http: reqwest::Client,
+    endpoint: Url,
+    jwt_secret: JwtSecret,
+    next_id: AtomicU64,
+}
+
+impl Client {
+    pub fn new(endpoint: Url, jwt_secret: JwtSecret) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            endpoint,
+            jwt_secret,
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    pub async fn call<P: Serialize + Send, T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<T, Error> {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            method: method.to_string(),
+            params,
+        };
+        let token = self.jwt_secret.generate_token()?;
+        let response: JsonRpcResponse<T> = self
+            .http
+            .post(self.endpoint.clone())
+            .bearer_auth(token)
+            .json(&request)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        match (response.result, response.error) {
+            (_, Some(error)) => Err(Error::Rpc(error)),
+            (Some(result), None) => Ok(result),
+            (None, None) => Err(Error::MissingResult),
+        }
+    }
+}
+
+#[async_trait]
+impl ExecutionEngine for Client {
+    async fn new_payload(&self, payload: ExecutionPayloadV1) -> Result<PayloadStatusV1, Error> {
+        self.call(ENGINE_NEW_PAYLOAD_V1, [payload]).await
+    }
+
+    async fn forkchoice_updated(
+        &self,
+        forkchoice_state: ForkchoiceStateV1,
+        payload_attributes: Option<PayloadAttributesV1>,
+    ) -> Result<ForkchoiceUpdatedV1Response, Error> {
+        self.call(
+            ENGINE_FORKCHOICE_UPDATED_V1,
+            (forkchoice_state, payload_attributes),
+        )
+        .await
+    }
+
+    async fn get_payload(&self, payload_id: PayloadId) -> Result<ExecutionPayloadV1, Error> {
+        self.call(ENGINE_GET_PAYLOAD_V1, [payload_id]).await
+    }
+
+    async fn exchange_transition_configuration(
+        &self,
+        configuration: TransitionConfigurationV1,
+    ) -> Result<TransitionConfigurationV1, Error> {
+        self.call(ENGINE_EXCHANGE_TRANSITION_CONFIGURATION_V1, [configuration])
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine_api::{MockExecutionEngine, PayloadStatus};
+    use axum::{
+        http::{HeaderMap, StatusCode as HttpStatusCode},
+        response::{IntoResponse, Response},
+        routing::post,
+        Extension, Json, Router,
+    };
+    use serde_json::{from_value, json, to_value, Value};
+    use std::net::TcpListener;
+    use std::sync::Arc;
+
+    // serves the Engine API over a `MockExecutionEngine`
+    async fn handle_request(
+        Extension(secret): Extension<JwtSecret>,
+        Extension(engine): Extension<Arc<MockExecutionEngine>>,
+        headers: HeaderMap,
+        Json(request): Json<JsonRpcRequest<Vec<Value>>>,
+    ) -> Response {
+        let token = headers
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .unwrap_or_default();
+        if secret.validate_token(token).is_err() {
+            return HttpStatusCode::UNAUTHORIZED.into_response();
+        }
+        let params = request.params;
+        let result = match request.method.as_str() {
+            ENGINE_NEW_PAYLOAD_V1 => {
+                let payload = from_value(params[0].clone()).unwrap();
+                to_value(engine.new_payload(payload).await.unwrap())
+            }
+            ENGINE_FORKCHOICE_UPDATED_V1 => {
+                let forkchoice_state = from_value(params[0].clone()).unwrap();
+                let payload_attributes = from_value(params[1].clone()).unwrap();
+                let response = engine
+                    .forkchoice_updated(forkchoice_state, payload_attributes)
+                    .await;
+                to_value(response.unwrap())
+            }
+            ENGINE_GET_PAYLOAD_V1 => {
+                let payload_id = from_value(params[0].clone()).unwrap();
+                match engine.get_payload(payload_id).await {
+                    Ok(payload) => to_value(payload),
+                    Err(_) => {
+                        let response = json!({
+                            "jsonrpc": "2.0",
+                            "id": request.id,
+                            "error": { "code": -38001, "message": "Unknown payload" },
+                        });
+                        return Json(response).into_response();
+                    }
+                }
+            }
+            ENGINE_EXCHANGE_TRANSITION_CONFIGURATION_V1 => {
+                let configuration = from_value(params[0].clone()).unwrap();
+                let response = engine
+                    .exchange_transition_configuration(configuration)
+                    .await;
+                to_value(response.unwrap())
+            }
+            method => unreachable!("unexpected method {method}"),
+        };
+        let response = json!({
+            "jsonrpc": "2.0",
+            "id": request.id,
+            "result": result.unwrap(),
+        });
+        Json(response).into_response()
+    }
+
+    fn spawn_stub_engine(secret: JwtSecret, engine: MockExecutionEngine) -> Url {
+        let app = Router::new()
+            .route("/", post(handle_request))
+            .layer(Extension(secret))
+            .layer(Extension(Arc::new(engine)));
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = axum::Server::from_tcp(listener)
+            .unwrap()
+            .serve(app.into_make_service());
+        tokio::spawn(server);
+        Url::parse(&format!("http://{addr}")).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_client() {
+        let secret = JwtSecret::random();
+        let configuration = TransitionConfigurationV1 {
+            terminal_block_number: 15537393,
+            ..Default::default()
+        };
+        let engine = MockExecutionEngine::new(configuration.clone());
+        let endpoint = spawn_stub_engine(secret.clone(), engine);
+
+        let client = Client::new(endpoint.clone(), secret);
+        let response = client
+            .exchange_transition_configuration(configuration.clone())
+            .await
+            .unwrap();
+        assert_eq!(response, configuration);
+
+        let attributes = PayloadAttributesV1 {
+            timestamp: 1663224179,
+            ..Default::default()
+        };
+        let response = client
+            .forkchoice_updated(ForkchoiceStateV1::default(), Some(attributes))
+            .await
+            .unwrap();
+        assert_eq!(response.payload_status.status, PayloadStatus::Valid);
+
+        let payload = client
+            .get_payload(response.payload_id.unwrap())
+            .await
+            .unwrap();
+        assert_eq!(payload.timestamp, 1663224179);
+
+        let block_hash = payload.block_hash.clone();
+        let status = client.new_payload(payload).await.unwrap();
+        assert_eq!(status.status, PayloadStatus::Valid);
+        assert_eq!(status.latest_valid_hash, Some(block_hash));
+
+        let err = client.get_payload(PayloadId([9u8; 8])).await.unwrap_err();
+        assert!(matches!(err, Error::Rpc(RpcError { code: -38001, .. })));
+
+        // the engine rejects requests that are not authenticated before any JSON-RPC handling
+        let client = Client::new(endpoint, JwtSecret::random());
+        let err = client
+            .exchange_transition_configuration(configuration)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Http(error) if error.status() == Some(reqwest::StatusCode::UNAUTHORIZED)
+        ));
+    }
+}
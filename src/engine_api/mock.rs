@@ -0,0 +1,181 @@
+use crate::bellatrix::mainnet::BYTES_PER_LOGS_BLOOM;
+use crate::crypto::hash;
+use crate::engine_api::client::{Error, ExecutionEngine};
+use crate::engine_api::types::{
+    ExecutionPayloadV1, ForkchoiceStateV1, ForkchoiceUpdatedV1Response, PayloadAttributesV1,
+    PayloadId, PayloadStatus, PayloadStatusV1, TransitionConfigurationV1,
+};
+use crate::primitives::Hash32;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+#[derive(Default)]
+struct State {
+    blocks: HashMap<Hash32, ExecutionPayloadV1>,
+    pending_payloads: HashMap<PayloadId, ExecutionPayloadV1>,
+    head: Hash32,
+    next_payload_id: u64,
+}
+
+/// An in-memory `ExecutionEngine` that accepts any payload extending a known block
+/// and builds empty payloads on request.
+#[derive(Default)]
+pub struct MockExecutionEngine {
+    transition_configuration: TransitionConfigurationV1,
+    state: Mutex<State>,
+}
+
+impl MockExecutionEngine {
+    pub fn new(transition_configuration: TransitionConfigurationV1) -> Self {
+        Self {
+            transition_configuration,
+            state: Default::default(),
+        }
+    }
+
+    pub fn head(&self) -> Hash32 {
+        self.state.lock().unwrap().head.clone()
+    }
+
+    fn status(status: PayloadStatus, latest_valid_hash: Option<Hash32>) -> PayloadStatusV1 {
+        PayloadStatusV1 {
+            status,
+            latest_valid_hash,
+            validation_error: None,
+        }
+    }
+}
+
+#[async_trait]
+impl ExecutionEngine for MockExecutionEngine {
+    async fn new_payload(&self, payload: ExecutionPayloadV1) -> Result<PayloadStatusV1, Error> {
+        let mut state = self.state.lock().unwrap();
+        let block_hash = payload.block_hash.clone();
+        if state.blocks.contains_key(&block_hash) {
+            return Ok(Self::status(PayloadStatus::Valid, Some(block_hash)));
+        }
+        // NOTE: blocks building on the zero hash are treated as extending genesis
+        let is_known_parent = payload.parent_hash == Hash32::default()
+            || state.blocks.contains_key(&payload.parent_hash);
+        if !is_known_parent {
+            return Ok(Self::status(PayloadStatus::Syncing, None));
+        }
+        state.blocks.insert(block_hash.clone(), payload);
+        Ok(Self::status(PayloadStatus::Valid, Some(block_hash)))
+    }
+
+    async fn forkchoice_updated(
+        &self,
+        forkchoice_state: ForkchoiceStateV1,
+        payload_attributes: Option<PayloadAttributesV1>,
+    ) -> Result<ForkchoiceUpdatedV1Response, Error> {
+        let mut state = self.state.lock().unwrap();
+        let head = forkchoice_state.head_block_hash;
+        let parent_number = match state.blocks.get(&head) {
+            Some(parent) => Some(parent.block_number),
+            None if head == Hash32::default() => None,
+            None => {
+                return Ok(ForkchoiceUpdatedV1Response {
+                    payload_status: Self::status(PayloadStatus::Syncing, None),
+                    payload_id: None,
+                })
+            }
+        };
+        state.head = head.clone();
+
+        let payload_id = payload_attributes.map(|attributes| {
+            let id = PayloadId(state.next_payload_id.to_be_bytes());
+            state.next_payload_id += 1;
+
+            let mut preimage = head.as_ref().to_vec();
+            preimage.extend_from_slice(&attributes.timestamp.to_le_bytes());
+            preimage.extend_from_slice(attributes.prev_randao.as_ref());
+            let payload = ExecutionPayloadV1 {
+                parent_hash: head.clone(),
+                fee_recipient: attributes.suggested_fee_recipient,
+                prev_randao: attributes.prev_randao,
+                block_number: parent_number.map(|number| number + 1).unwrap_or_default(),
+                timestamp: attributes.timestamp,
+                block_hash: hash(preimage),
+                logs_bloom: vec![0u8; BYTES_PER_LOGS_BLOOM],
+                ..Default::default()
+            };
+            state.pending_payloads.insert(id, payload);
+            id
+        });
+
+        Ok(ForkchoiceUpdatedV1Response {
+            payload_status: Self::status(PayloadStatus::Valid, Some(head)),
+            payload_id,
+        })
+    }
+
+    async fn get_payload(&self, payload_id: PayloadId) -> Result<ExecutionPayloadV1, Error> {
+        let state = self.state.lock().unwrap();
+        state
+            .pending_payloads
+            .get(&payload_id)
+            .cloned()
+            .ok_or(Error::UnknownPayload(payload_id))
+    }
+
+    async fn exchange_transition_configuration(
+        &self,
+        _configuration: TransitionConfigurationV1,
+    ) -> Result<TransitionConfigurationV1, Error> {
+        Ok(self.transition_configuration.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bellatrix::mainnet::ExecutionPayload;
+
+    #[tokio::test]
+    async fn test_build_and_import_payload() {
+        let engine = MockExecutionEngine::default();
+
+        let forkchoice_state = ForkchoiceStateV1::default();
+        let attributes = PayloadAttributesV1 {
+            timestamp: 1663224179,
+            ..Default::default()
+        };
+        let response = engine
+            .forkchoice_updated(forkchoice_state, Some(attributes))
+            .await
+            .unwrap();
+        assert_eq!(response.payload_status.status, PayloadStatus::Valid);
+        let payload_id = response.payload_id.unwrap();
+
+        let payload = engine.get_payload(payload_id).await.unwrap();
+        assert_eq!(payload.timestamp, 1663224179);
+        // the built payload must be representable in the consensus types
+        ExecutionPayload::try_from(payload.clone()).unwrap();
+
+        let block_hash = payload.block_hash.clone();
+        let status = engine.new_payload(payload).await.unwrap();
+        assert_eq!(status.status, PayloadStatus::Valid);
+
+        let forkchoice_state = ForkchoiceStateV1 {
+            head_block_hash: block_hash.clone(),
+            ..Default::default()
+        };
+        engine
+            .forkchoice_updated(forkchoice_state, None)
+            .await
+            .unwrap();
+        assert_eq!(engine.head(), block_hash);
+
+        let orphan = ExecutionPayloadV1 {
+            parent_hash: hash([1u8]),
+            block_hash: hash([2u8]),
+            ..Default::default()
+        };
+        let status = engine.new_payload(orphan).await.unwrap();
+        assert_eq!(status.status, PayloadStatus::Syncing);
+
+        assert!(engine.get_payload(PayloadId([9u8; 8])).await.is_err());
+    }
+}
@@ -0,0 +1,11 @@
+//! Types and a JSON-RPC client for the Engine API between the consensus and
+//! execution layers, along with an in-memory engine for tests.
+mod auth;
+mod client;
+mod mock;
+mod types;
+
+pub use auth::*;
+pub use client::*;
+pub use mock::*;
+pub use types::*;
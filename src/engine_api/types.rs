@@ -0,0 +1,309 @@
+use crate::bellatrix::ExecutionPayload;
+use crate::primitives::{Bytes32, ExecutionAddress, Hash32, U256};
+use crate::ssz::{ByteList, ByteVector};
+use serde::{Deserialize, Serialize};
+use ssz_rs::prelude::List;
+use thiserror::Error;
+
+/// Serde support for the `QUANTITY` encoding of the JSON-RPC API: a `0x`-prefixed,
+/// big-endian hex string without leading zeroes.
+mod as_quantity {
+    use serde::de::Deserialize;
+
+    pub fn serialize<S>(data: &u64, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(&format!("{data:#x}"))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<u64, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = <String>::deserialize(deserializer)?;
+        let digits = s
+            .strip_prefix("0x")
+            .ok_or_else(|| serde::de::Error::custom("missing prefix `0x` for quantity"))?;
+        u64::from_str_radix(digits, 16).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Serde support for the `QUANTITY` encoding of a `U256`, sharing the big-endian
+/// conversions with the RLP encoding in `crate::execution`.
+mod as_u256_quantity {
+    use crate::execution::{u256_from_be_bytes, u256_to_be_bytes};
+    use crate::primitives::U256;
+    use serde::de::Deserialize;
+
+    pub fn serialize<S>(data: &U256, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let encoding = hex::encode(u256_to_be_bytes(data));
+        let digits = encoding.trim_start_matches('0');
+        let digits = if digits.is_empty() { "0" } else { digits };
+        serializer.collect_str(&format!("0x{digits}"))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<U256, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = <String>::deserialize(deserializer)?;
+        let digits = s
+            .strip_prefix("0x")
+            .ok_or_else(|| serde::de::Error::custom("missing prefix `0x` for quantity"))?;
+        let digits = if digits.len() % 2 == 1 {
+            format!("0{digits}")
+        } else {
+            digits.to_string()
+        };
+        let bytes = hex::decode(digits).map_err(serde::de::Error::custom)?;
+        u256_from_be_bytes(&bytes)
+            .ok_or_else(|| serde::de::Error::custom("quantity exceeds 256 bits"))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ConversionError {
+    #[error("field `{field}` has {length} bytes, exceeding the bound of {bound}")]
+    InvalidLength {
+        field: &'static str,
+        length: usize,
+        bound: usize,
+    },
+    #[error("payload has {count} transactions, exceeding the bound of {bound}")]
+    TooManyTransactions { count: usize, bound: usize },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Transaction(#[serde(with = "crate::serde::as_hex")] pub Vec<u8>);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionPayloadV1 {
+    pub parent_hash: Hash32,
+    pub fee_recipient: ExecutionAddress,
+    pub state_root: Bytes32,
+    pub receipts_root: Bytes32,
+    #[serde(with = "crate::serde::as_hex")]
+    pub logs_bloom: Vec<u8>,
+    pub prev_randao: Bytes32,
+    #[serde(with = "as_quantity")]
+    pub block_number: u64,
+    #[serde(with = "as_quantity")]
+    pub gas_limit: u64,
+    #[serde(with = "as_quantity")]
+    pub gas_used: u64,
+    #[serde(with = "as_quantity")]
+    pub timestamp: u64,
+    #[serde(with = "crate::serde::as_hex")]
+    pub extra_data: Vec<u8>,
+    #[serde(with = "as_u256_quantity")]
+    pub base_fee_per_gas: U256,
+    pub block_hash: Hash32,
+    pub transactions: Vec<Transaction>,
+}
+
+impl<
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    >
+    From<
+        &ExecutionPayload<
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+        >,
+    > for ExecutionPayloadV1
+{
+    fn from(
+        payload: &ExecutionPayload<
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+        >,
+    ) -> Self {
+        Self {
+            parent_hash: payload.parent_hash.clone(),
+            fee_recipient: payload.fee_recipient.clone(),
+            state_root: payload.state_root.clone(),
+            receipts_root: payload.receipts_root.clone(),
+            logs_bloom: payload.logs_bloom.as_ref().to_vec(),
+            prev_randao: payload.prev_randao.clone(),
+            block_number: payload.block_number,
+            gas_limit: payload.gas_limit,
+            gas_used: payload.gas_used,
+            timestamp: payload.timestamp,
+            extra_data: payload.extra_data.as_ref().to_vec(),
+            base_fee_per_gas: payload.base_fee_per_gas.clone(),
+            block_hash: payload.block_hash.clone(),
+            transactions: payload
+                .transactions
+                .iter()
+                .map(|transaction| Transaction(transaction.as_ref().to_vec()))
+                .collect(),
+        }
+    }
+}
+
+impl<
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    > TryFrom<ExecutionPayloadV1>
+    for ExecutionPayload<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >
+{
+    type Error = ConversionError;
+
+    fn try_from(payload: ExecutionPayloadV1) -> Result<Self, Self::Error> {
+        let logs_bloom = ByteVector::try_from(payload.logs_bloom.as_ref()).map_err(|_| {
+            ConversionError::InvalidLength {
+                field: "logs_bloom",
+                length: payload.logs_bloom.len(),
+                bound: BYTES_PER_LOGS_BLOOM,
+            }
+        })?;
+        let extra_data = ByteList::try_from(payload.extra_data.as_ref()).map_err(|_| {
+            ConversionError::InvalidLength {
+                field: "extra_data",
+                length: payload.extra_data.len(),
+                bound: MAX_EXTRA_DATA_BYTES,
+            }
+        })?;
+        let count = payload.transactions.len();
+        if count > MAX_TRANSACTIONS_PER_PAYLOAD {
+            return Err(ConversionError::TooManyTransactions {
+                count,
+                bound: MAX_TRANSACTIONS_PER_PAYLOAD,
+            });
+        }
+        let mut transactions = List::default();
+        for transaction in &payload.transactions {
+            let transaction = ByteList::try_from(transaction.0.as_ref()).map_err(|_| {
+                ConversionError::InvalidLength {
+                    field: "transactions",
+                    length: transaction.0.len(),
+                    bound: MAX_BYTES_PER_TRANSACTION,
+                }
+            })?;
+            transactions.push(transaction);
+        }
+        Ok(Self {
+            parent_hash: payload.parent_hash,
+            fee_recipient: payload.fee_recipient,
+            state_root: payload.state_root,
+            receipts_root: payload.receipts_root,
+            logs_bloom,
+            prev_randao: payload.prev_randao,
+            block_number: payload.block_number,
+            gas_limit: payload.gas_limit,
+            gas_used: payload.gas_used,
+            timestamp: payload.timestamp,
+            extra_data,
+            base_fee_per_gas: payload.base_fee_per_gas,
+            block_hash: payload.block_hash,
+            transactions,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PayloadStatus {
+    Valid,
+    Invalid,
+    Syncing,
+    Accepted,
+    InvalidBlockHash,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadStatusV1 {
+    pub status: PayloadStatus,
+    pub latest_valid_hash: Option<Hash32>,
+    pub validation_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkchoiceStateV1 {
+    pub head_block_hash: Hash32,
+    pub safe_block_hash: Hash32,
+    pub finalized_block_hash: Hash32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadAttributesV1 {
+    #[serde(with = "as_quantity")]
+    pub timestamp: u64,
+    pub prev_randao: Bytes32,
+    pub suggested_fee_recipient: ExecutionAddress,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PayloadId(#[serde(with = "crate::serde::as_hex")] pub [u8; 8]);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForkchoiceUpdatedV1Response {
+    pub payload_status: PayloadStatusV1,
+    pub payload_id: Option<PayloadId>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransitionConfigurationV1 {
+    #[serde(with = "as_u256_quantity")]
+    pub terminal_total_difficulty: U256,
+    pub terminal_block_hash: Hash32,
+    #[serde(with = "as_quantity")]
+    pub terminal_block_number: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bellatrix::mainnet;
+
+    #[test]
+    fn test_execution_payload_round_trip() {
+        let mut payload = mainnet::ExecutionPayload {
+            block_number: 15537394,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: crate::execution::u256_from_be_bytes(&[0x01, 0x2a]).unwrap(),
+            ..Default::default()
+        };
+        payload
+            .transactions
+            .push(mainnet::Transaction::try_from([0x02u8, 0xf8, 0x72].as_ref()).unwrap());
+
+        let encoding = ExecutionPayloadV1::from(&payload);
+        let json = serde_json::to_value(&encoding).unwrap();
+        assert_eq!(json["blockNumber"], "0xed14f2");
+        assert_eq!(json["gasLimit"], "0x1c9c380");
+        assert_eq!(json["baseFeePerGas"], "0x12a");
+        assert_eq!(json["transactions"][0], "0x02f872");
+
+        let decoding: ExecutionPayloadV1 = serde_json::from_value(json).unwrap();
+        let recovered = mainnet::ExecutionPayload::try_from(decoding).unwrap();
+        assert_eq!(recovered.block_number, payload.block_number);
+        assert_eq!(recovered.base_fee_per_gas, payload.base_fee_per_gas);
+        assert_eq!(recovered.transactions.len(), 1);
+    }
+}
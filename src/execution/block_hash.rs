@@ -0,0 +1,213 @@
+use crate::bellatrix::ExecutionPayload;
+use crate::execution::{hash32_from, keccak256, ordered_trie_root, u256_to_be_bytes, Error};
+use crate::primitives::{Bytes32, ExecutionAddress, Hash32, U256};
+use rlp::RlpStream;
+
+/// The hash of the empty list of ommers, i.e. `keccak256(rlp([]))`.
+pub const EMPTY_OMMERS_HASH: [u8; 32] = [
+    0x1d, 0xcc, 0x4d, 0xe8, 0xde, 0xc7, 0x5d, 0x7a, 0xab, 0x85, 0xb5, 0x67, 0xb6, 0xcc, 0xd4, 0x1a,
+    0xd3, 0x12, 0x45, 0x1b, 0x94, 0x8a, 0x74, 0x13, 0xf0, 0xa1, 0x42, 0xfd, 0x40, 0xd4, 0x93, 0x47,
+];
+
+/// The header of the execution block corresponding to an `ExecutionPayload`, with the
+/// fields deprecated by the merge set to their constant values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionBlockHeader {
+    pub parent_hash: Hash32,
+    pub ommers_hash: Hash32,
+    pub beneficiary: ExecutionAddress,
+    pub state_root: Bytes32,
+    pub transactions_root: Bytes32,
+    pub receipts_root: Bytes32,
+    pub logs_bloom: Vec<u8>,
+    pub difficulty: u64,
+    pub number: u64,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub timestamp: u64,
+    pub extra_data: Vec<u8>,
+    pub mix_hash: Bytes32,
+    pub nonce: [u8; 8],
+    pub base_fee_per_gas: U256,
+}
+
+impl ExecutionBlockHeader {
+    pub fn from_payload<
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    >(
+        payload: &ExecutionPayload<
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+        >,
+    ) -> Self {
+        Self {
+            parent_hash: payload.parent_hash.clone(),
+            ommers_hash: hash32_from(EMPTY_OMMERS_HASH),
+            beneficiary: payload.fee_recipient.clone(),
+            state_root: payload.state_root.clone(),
+            transactions_root: compute_transactions_root(payload),
+            receipts_root: payload.receipts_root.clone(),
+            logs_bloom: payload.logs_bloom.as_ref().to_vec(),
+            difficulty: 0,
+            number: payload.block_number,
+            gas_limit: payload.gas_limit,
+            gas_used: payload.gas_used,
+            timestamp: payload.timestamp,
+            extra_data: payload.extra_data.as_ref().to_vec(),
+            mix_hash: payload.prev_randao.clone(),
+            nonce: [0u8; 8],
+            base_fee_per_gas: payload.base_fee_per_gas.clone(),
+        }
+    }
+
+    pub fn rlp_encode(&self) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(16);
+        stream.append(&self.parent_hash.as_ref());
+        stream.append(&self.ommers_hash.as_ref());
+        stream.append(&self.beneficiary.as_ref());
+        stream.append(&self.state_root.as_ref());
+        stream.append(&self.transactions_root.as_ref());
+        stream.append(&self.receipts_root.as_ref());
+        stream.append(&self.logs_bloom);
+        stream.append(&self.difficulty);
+        stream.append(&self.number);
+        stream.append(&self.gas_limit);
+        stream.append(&self.gas_used);
+        stream.append(&self.timestamp);
+        stream.append(&self.extra_data);
+        stream.append(&self.mix_hash.as_ref());
+        stream.append(&self.nonce.as_ref());
+        stream.append(&u256_to_be_bytes(&self.base_fee_per_gas));
+        stream.out().to_vec()
+    }
+
+    pub fn hash(&self) -> Hash32 {
+        hash32_from(keccak256(self.rlp_encode()))
+    }
+}
+
+pub fn compute_transactions_root<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+>(
+    payload: &ExecutionPayload<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+) -> Bytes32 {
+    ordered_trie_root(payload.transactions.iter())
+}
+
+pub fn compute_block_hash<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+>(
+    payload: &ExecutionPayload<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+) -> Hash32 {
+    ExecutionBlockHeader::from_payload(payload).hash()
+}
+
+pub fn verify_block_hash<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+>(
+    payload: &ExecutionPayload<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+    >,
+) -> Result<(), Error> {
+    let computed = compute_block_hash(payload);
+    if computed != payload.block_hash {
+        return Err(Error::BlockHashMismatch {
+            provided: payload.block_hash.clone(),
+            computed,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bellatrix::mainnet;
+    use crate::execution::{u256_from_be_bytes, EMPTY_TRIE_ROOT};
+
+    #[test]
+    fn test_empty_ommers_hash() {
+        let empty_list = RlpStream::new_list(0).out().to_vec();
+        assert_eq!(keccak256(empty_list), EMPTY_OMMERS_HASH);
+    }
+
+    fn hash32_from_hex(value: &str) -> Hash32 {
+        Hash32::try_from(hex::decode(value).unwrap().as_ref()).unwrap()
+    }
+
+    // the genesis block of the Holesky testnet, which carries a base fee
+    fn holesky_genesis_payload() -> mainnet::ExecutionPayload {
+        mainnet::ExecutionPayload {
+            state_root: hash32_from_hex(
+                "69d8c9d72f6fa4ad42d4702b433707212f90db395eb54dc20bc85de253788783",
+            ),
+            receipts_root: hash32_from(EMPTY_TRIE_ROOT),
+            gas_limit: 25_000_000,
+            timestamp: 1695902100,
+            base_fee_per_gas: u256_from_be_bytes(&1_000_000_000u64.to_be_bytes()).unwrap(),
+            block_hash: hash32_from_hex(
+                "b5f7f912443c940f21fd611f12828d75b534364ed9e95ca4e307729a4661bde4",
+            ),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_known_block_hash() {
+        let payload = holesky_genesis_payload();
+        let mut header = ExecutionBlockHeader::from_payload(&payload);
+        assert_eq!(header.transactions_root.as_ref(), EMPTY_TRIE_ROOT);
+        assert_eq!(header.ommers_hash.as_ref(), EMPTY_OMMERS_HASH);
+
+        // NOTE: the genesis predates the merge so it still sets the proof-of-work fields
+        header.difficulty = 1;
+        header.nonce = [0, 0, 0, 0, 0, 0, 0x12, 0x34];
+        assert_eq!(header.hash(), payload.block_hash);
+    }
+
+    #[test]
+    fn test_verify_block_hash() {
+        // with the proof-of-work fields zeroed the known hash no longer matches
+        let mut payload = holesky_genesis_payload();
+        assert!(matches!(
+            verify_block_hash(&payload),
+            Err(Error::BlockHashMismatch { .. })
+        ));
+
+        payload.block_hash = compute_block_hash(&payload);
+        verify_block_hash(&payload).unwrap();
+
+        payload.gas_used = 21_000;
+        assert!(matches!(
+            verify_block_hash(&payload),
+            Err(Error::BlockHashMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,57 @@
+//! Utilities for inspecting execution payloads with the encodings of the execution layer:
+//! recomputing the block hash of a payload from its RLP-encoded header, computing
+//! Merkle-Patricia trie roots and decoding the typed transactions it carries.
+mod block_hash;
+mod transaction;
+mod trie;
+
+pub use block_hash::*;
+pub use transaction::*;
+pub use trie::*;
+
+use crate::primitives::{Hash32, U256};
+use sha3::{Digest, Keccak256};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("{0}")]
+    Rlp(#[from] rlp::DecoderError),
+    #[error("payload has block hash {provided} but its header hashes to {computed}")]
+    BlockHashMismatch { provided: Hash32, computed: Hash32 },
+    #[error("transaction is empty")]
+    EmptyTransaction,
+    #[error("unsupported transaction type {0:#04x}")]
+    UnsupportedTransactionType(u8),
+    #[error("field `{field}` has invalid length {length}")]
+    InvalidLength { field: &'static str, length: usize },
+}
+
+pub fn keccak256<D: AsRef<[u8]>>(data: D) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+pub(crate) fn hash32_from(bytes: [u8; 32]) -> Hash32 {
+    Hash32::try_from(bytes.as_ref()).expect("correct input")
+}
+
+/// Return the minimal big-endian encoding of `value`, as used for integers in RLP.
+pub(crate) fn u256_to_be_bytes(value: &U256) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(32);
+    ssz_rs::prelude::Serialize::serialize(value, &mut buffer).expect("can serialize U256");
+    buffer.reverse();
+    let leading_zeros = buffer.iter().take_while(|&&byte| byte == 0).count();
+    buffer.split_off(leading_zeros)
+}
+
+pub(crate) fn u256_from_be_bytes(bytes: &[u8]) -> Option<U256> {
+    if bytes.len() > 32 {
+        return None;
+    }
+    let mut buffer = [0u8; 32];
+    buffer[32 - bytes.len()..].copy_from_slice(bytes);
+    buffer.reverse();
+    Some(U256::from_bytes_le(buffer))
+}
@@ -0,0 +1,378 @@
+use crate::execution::{hash32_from, keccak256, u256_from_be_bytes, Error};
+use crate::primitives::{Bytes32, ExecutionAddress, Hash32, U256};
+use rlp::{DecoderError, Rlp};
+
+pub const ACCESS_LIST_TRANSACTION_TYPE: u8 = 0x01;
+pub const DYNAMIC_FEE_TRANSACTION_TYPE: u8 = 0x02;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessListItem {
+    pub address: ExecutionAddress,
+    pub storage_keys: Vec<Bytes32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegacyTransaction {
+    pub nonce: u64,
+    pub gas_price: U256,
+    pub gas_limit: u64,
+    pub to: Option<ExecutionAddress>,
+    pub value: U256,
+    pub data: Vec<u8>,
+    pub v: u64,
+    pub r: U256,
+    pub s: U256,
+}
+
+/// An EIP-2930 transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessListTransaction {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub gas_price: U256,
+    pub gas_limit: u64,
+    pub to: Option<ExecutionAddress>,
+    pub value: U256,
+    pub data: Vec<u8>,
+    pub access_list: Vec<AccessListItem>,
+    pub y_parity: bool,
+    pub r: U256,
+    pub s: U256,
+}
+
+/// An EIP-1559 transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynamicFeeTransaction {
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub max_priority_fee_per_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub gas_limit: u64,
+    pub to: Option<ExecutionAddress>,
+    pub value: U256,
+    pub data: Vec<u8>,
+    pub access_list: Vec<AccessListItem>,
+    pub y_parity: bool,
+    pub r: U256,
+    pub s: U256,
+}
+
+/// A signed transaction decoded from the opaque encoding carried in an `ExecutionPayload`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodedTransaction {
+    Legacy(LegacyTransaction),
+    AccessList(AccessListTransaction),
+    DynamicFee(DynamicFeeTransaction),
+}
+
+fn decode_u256(rlp: &Rlp, index: usize, field: &'static str) -> Result<U256, Error> {
+    let bytes: Vec<u8> = rlp.val_at(index)?;
+    u256_from_be_bytes(&bytes).ok_or(Error::InvalidLength {
+        field,
+        length: bytes.len(),
+    })
+}
+
+fn decode_to(rlp: &Rlp, index: usize) -> Result<Option<ExecutionAddress>, Error> {
+    let bytes: Vec<u8> = rlp.val_at(index)?;
+    // NOTE: an empty recipient denotes contract creation
+    if bytes.is_empty() {
+        return Ok(None);
+    }
+    let address = ExecutionAddress::try_from(bytes.as_ref()).map_err(|_| Error::InvalidLength {
+        field: "to",
+        length: bytes.len(),
+    })?;
+    Ok(Some(address))
+}
+
+fn decode_access_list(rlp: &Rlp) -> Result<Vec<AccessListItem>, Error> {
+    rlp.iter()
+        .map(|item| -> Result<AccessListItem, Error> {
+            if item.item_count()? != 2 {
+                return Err(DecoderError::RlpIncorrectListLen.into());
+            }
+            let address: Vec<u8> = item.val_at(0)?;
+            let address =
+                ExecutionAddress::try_from(address.as_ref()).map_err(|_| Error::InvalidLength {
+                    field: "access_list.address",
+                    length: address.len(),
+                })?;
+            let storage_keys = item
+                .at(1)?
+                .iter()
+                .map(|key| {
+                    let key = key.data()?;
+                    Bytes32::try_from(key).map_err(|_| Error::InvalidLength {
+                        field: "access_list.storage_keys",
+                        length: key.len(),
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(AccessListItem {
+                address,
+                storage_keys,
+            })
+        })
+        .collect()
+}
+
+fn decode_list(bytes: &[u8], expected_count: usize) -> Result<Rlp, Error> {
+    let rlp = Rlp::new(bytes);
+    if !rlp.is_list() {
+        return Err(DecoderError::RlpExpectedToBeList.into());
+    }
+    if rlp.payload_info()?.total() != bytes.len() {
+        return Err(DecoderError::RlpInconsistentLengthAndData.into());
+    }
+    if rlp.item_count()? != expected_count {
+        return Err(DecoderError::RlpIncorrectListLen.into());
+    }
+    Ok(rlp)
+}
+
+impl DecodedTransaction {
+    pub fn decode(bytes: &[u8]) -> Result<Self, Error> {
+        let transaction_type = *bytes.first().ok_or(Error::EmptyTransaction)?;
+        match transaction_type {
+            // NOTE: legacy transactions are bare RLP lists, whose encoding starts at `0xc0`
+            0xc0..=0xff => Self::decode_legacy(bytes).map(Self::Legacy),
+            ACCESS_LIST_TRANSACTION_TYPE => {
+                Self::decode_access_list(&bytes[1..]).map(Self::AccessList)
+            }
+            DYNAMIC_FEE_TRANSACTION_TYPE => {
+                Self::decode_dynamic_fee(&bytes[1..]).map(Self::DynamicFee)
+            }
+            other => Err(Error::UnsupportedTransactionType(other)),
+        }
+    }
+
+    fn decode_legacy(bytes: &[u8]) -> Result<LegacyTransaction, Error> {
+        let rlp = decode_list(bytes, 9)?;
+        Ok(LegacyTransaction {
+            nonce: rlp.val_at(0)?,
+            gas_price: decode_u256(&rlp, 1, "gas_price")?,
+            gas_limit: rlp.val_at(2)?,
+            to: decode_to(&rlp, 3)?,
+            value: decode_u256(&rlp, 4, "value")?,
+            data: rlp.val_at(5)?,
+            v: rlp.val_at(6)?,
+            r: decode_u256(&rlp, 7, "r")?,
+            s: decode_u256(&rlp, 8, "s")?,
+        })
+    }
+
+    fn decode_access_list(bytes: &[u8]) -> Result<AccessListTransaction, Error> {
+        let rlp = decode_list(bytes, 11)?;
+        Ok(AccessListTransaction {
+            chain_id: rlp.val_at(0)?,
+            nonce: rlp.val_at(1)?,
+            gas_price: decode_u256(&rlp, 2, "gas_price")?,
+            gas_limit: rlp.val_at(3)?,
+            to: decode_to(&rlp, 4)?,
+            value: decode_u256(&rlp, 5, "value")?,
+            data: rlp.val_at(6)?,
+            access_list: decode_access_list(&rlp.at(7)?)?,
+            y_parity: rlp.val_at(8)?,
+            r: decode_u256(&rlp, 9, "r")?,
+            s: decode_u256(&rlp, 10, "s")?,
+        })
+    }
+
+    fn decode_dynamic_fee(bytes: &[u8]) -> Result<DynamicFeeTransaction, Error> {
+        let rlp = decode_list(bytes, 12)?;
+        Ok(DynamicFeeTransaction {
+            chain_id: rlp.val_at(0)?,
+            nonce: rlp.val_at(1)?,
+            max_priority_fee_per_gas: decode_u256(&rlp, 2, "max_priority_fee_per_gas")?,
+            max_fee_per_gas: decode_u256(&rlp, 3, "max_fee_per_gas")?,
+            gas_limit: rlp.val_at(4)?,
+            to: decode_to(&rlp, 5)?,
+            value: decode_u256(&rlp, 6, "value")?,
+            data: rlp.val_at(7)?,
+            access_list: decode_access_list(&rlp.at(8)?)?,
+            y_parity: rlp.val_at(9)?,
+            r: decode_u256(&rlp, 10, "r")?,
+            s: decode_u256(&rlp, 11, "s")?,
+        })
+    }
+
+    /// The chain id the transaction is bound to, if any; legacy transactions only
+    /// carry one if signed according to EIP-155.
+    pub fn chain_id(&self) -> Option<u64> {
+        match self {
+            Self::Legacy(transaction) if transaction.v >= 35 => Some((transaction.v - 35) / 2),
+            Self::Legacy(_) => None,
+            Self::AccessList(transaction) => Some(transaction.chain_id),
+            Self::DynamicFee(transaction) => Some(transaction.chain_id),
+        }
+    }
+
+    pub fn nonce(&self) -> u64 {
+        match self {
+            Self::Legacy(transaction) => transaction.nonce,
+            Self::AccessList(transaction) => transaction.nonce,
+            Self::DynamicFee(transaction) => transaction.nonce,
+        }
+    }
+
+    pub fn gas_limit(&self) -> u64 {
+        match self {
+            Self::Legacy(transaction) => transaction.gas_limit,
+            Self::AccessList(transaction) => transaction.gas_limit,
+            Self::DynamicFee(transaction) => transaction.gas_limit,
+        }
+    }
+
+    pub fn to(&self) -> Option<&ExecutionAddress> {
+        match self {
+            Self::Legacy(transaction) => transaction.to.as_ref(),
+            Self::AccessList(transaction) => transaction.to.as_ref(),
+            Self::DynamicFee(transaction) => transaction.to.as_ref(),
+        }
+    }
+
+    pub fn value(&self) -> &U256 {
+        match self {
+            Self::Legacy(transaction) => &transaction.value,
+            Self::AccessList(transaction) => &transaction.value,
+            Self::DynamicFee(transaction) => &transaction.value,
+        }
+    }
+}
+
+/// Compute the hash identifying a transaction from its opaque encoding.
+pub fn compute_transaction_hash<T: AsRef<[u8]>>(transaction: T) -> Hash32 {
+    hash32_from(keccak256(transaction))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rlp::RlpStream;
+
+    #[test]
+    fn test_decode_legacy_transaction() {
+        // signed example transaction from EIP-155
+        let encoding = hex::decode("f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83").unwrap();
+        let transaction = DecodedTransaction::decode(&encoding).unwrap();
+        assert_eq!(transaction.nonce(), 9);
+        assert_eq!(transaction.gas_limit(), 21000);
+        assert_eq!(transaction.chain_id(), Some(1));
+        assert_eq!(transaction.to().unwrap().as_ref(), [0x35u8; 20]);
+        assert_eq!(
+            transaction.value(),
+            &u256_from_be_bytes(&1_000_000_000_000_000_000u64.to_be_bytes()).unwrap()
+        );
+        match transaction {
+            DecodedTransaction::Legacy(transaction) => {
+                assert_eq!(
+                    transaction.gas_price,
+                    u256_from_be_bytes(&20_000_000_000u64.to_be_bytes()).unwrap()
+                );
+                assert_eq!(transaction.v, 37);
+            }
+            other => panic!("unexpected transaction type: {other:?}"),
+        }
+
+        let mut truncated = encoding.clone();
+        truncated.pop();
+        assert!(DecodedTransaction::decode(&truncated).is_err());
+    }
+
+    #[test]
+    fn test_decode_access_list_transaction() {
+        // an EIP-2930 transaction on chain 1 warming two storage slots of its recipient
+        let encoding = hex::decode("01f8c10180843b9aca0082753094095e7baea6a6c7c4c2dfeb977efac326af552d878080f85bf85994095e7baea6a6c7c4c2dfeb977efac326af552d87f842a00000000000000000000000000000000000000000000000000000000000000000a0000000000000000000000000000000000000000000000000000000000000000101a0b935047bf9b8464afec5bda917281610b2aaabd8de4b01d2eba6e876c934ca7aa0431b406eb13aefca05a0320c3595700b9375df6fac8cc8ec5603ac2e42af4894").unwrap();
+        let transaction = DecodedTransaction::decode(&encoding).unwrap();
+        assert_eq!(transaction.chain_id(), Some(1));
+        assert_eq!(transaction.nonce(), 0);
+        assert_eq!(transaction.gas_limit(), 30_000);
+        let recipient = hex::decode("095e7baea6a6c7c4c2dfeb977efac326af552d87").unwrap();
+        assert_eq!(transaction.to().unwrap().as_ref(), recipient);
+        assert_eq!(transaction.value(), &U256::default());
+        match transaction {
+            DecodedTransaction::AccessList(transaction) => {
+                assert_eq!(
+                    transaction.gas_price,
+                    u256_from_be_bytes(&1_000_000_000u64.to_be_bytes()).unwrap()
+                );
+                assert!(transaction.data.is_empty());
+                assert_eq!(transaction.access_list.len(), 1);
+                let item = &transaction.access_list[0];
+                assert_eq!(item.address.as_ref(), recipient);
+                assert_eq!(item.storage_keys.len(), 2);
+                assert_eq!(item.storage_keys[0].as_ref(), [0u8; 32]);
+                let mut key = [0u8; 32];
+                key[31] = 1;
+                assert_eq!(item.storage_keys[1].as_ref(), key);
+                assert!(transaction.y_parity);
+            }
+            other => panic!("unexpected transaction type: {other:?}"),
+        }
+
+        // the access list carries the wrong number of fields per item
+        let mut stream = RlpStream::new_list(11);
+        for _ in 0..7 {
+            stream.append_empty_data();
+        }
+        stream.begin_list(1);
+        stream.begin_list(1);
+        stream.append(&[0x11u8; 20].as_ref());
+        for _ in 0..3 {
+            stream.append_empty_data();
+        }
+        let mut encoding = vec![ACCESS_LIST_TRANSACTION_TYPE];
+        encoding.extend_from_slice(&stream.out());
+        assert!(DecodedTransaction::decode(&encoding).is_err());
+    }
+
+    #[test]
+    fn test_decode_dynamic_fee_transaction() {
+        let mut stream = RlpStream::new_list(12);
+        stream.append(&1u64);
+        stream.append(&3u64);
+        stream.append(&2_000_000_000u64);
+        stream.append(&30_000_000_000u64);
+        stream.append(&50_000u64);
+        stream.append_empty_data();
+        stream.append(&0u64);
+        stream.append(&vec![0x60u8, 0x80]);
+        stream.begin_list(1);
+        stream.begin_list(2);
+        stream.append(&[0x11u8; 20].as_ref());
+        stream.begin_list(1);
+        stream.append(&[0x22u8; 32].as_ref());
+        stream.append(&true);
+        stream.append(&vec![0x01u8; 32]);
+        stream.append(&vec![0x02u8; 32]);
+        let mut encoding = vec![DYNAMIC_FEE_TRANSACTION_TYPE];
+        encoding.extend_from_slice(&stream.out());
+
+        let transaction = DecodedTransaction::decode(&encoding).unwrap();
+        assert_eq!(transaction.chain_id(), Some(1));
+        assert!(transaction.to().is_none());
+        match transaction {
+            DecodedTransaction::DynamicFee(transaction) => {
+                assert_eq!(transaction.data, vec![0x60, 0x80]);
+                assert_eq!(transaction.access_list.len(), 1);
+                assert_eq!(transaction.access_list[0].address.as_ref(), [0x11u8; 20]);
+                assert_eq!(
+                    transaction.access_list[0].storage_keys[0].as_ref(),
+                    [0x22u8; 32]
+                );
+                assert!(transaction.y_parity);
+            }
+            other => panic!("unexpected transaction type: {other:?}"),
+        }
+
+        assert!(matches!(
+            DecodedTransaction::decode(&[0x03, 0xc0]),
+            Err(Error::UnsupportedTransactionType(0x03))
+        ));
+        assert!(matches!(
+            DecodedTransaction::decode(&[]),
+            Err(Error::EmptyTransaction)
+        ));
+    }
+}
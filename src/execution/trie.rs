@@ -0,0 +1,219 @@
+use crate::execution::keccak256;
+use crate::primitives::Bytes32;
+use rlp::RlpStream;
+
+/// The root of a trie with no entries, i.e. `keccak256(rlp(b""))`.
+pub const EMPTY_TRIE_ROOT: [u8; 32] = [
+    0x56, 0xe8, 0x1f, 0x17, 0x1b, 0xcc, 0x55, 0xa6, 0xff, 0x83, 0x45, 0xe6, 0x92, 0xc0, 0xf8, 0x6e,
+    0x5b, 0x48, 0xe0, 0x1b, 0x99, 0x6c, 0xad, 0xc0, 0x01, 0x62, 0x2f, 0xb5, 0xe3, 0x63, 0xb4, 0x21,
+];
+
+fn to_nibbles(bytes: &[u8]) -> Vec<u8> {
+    bytes
+        .iter()
+        .flat_map(|byte| [byte >> 4, byte & 0x0f])
+        .collect()
+}
+
+/// Compact encoding of a path of `nibbles`, flagging whether it terminates in a leaf.
+fn hex_prefix(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+    let flag = if is_leaf { 2 } else { 0 };
+    let mut encoding = Vec::with_capacity(nibbles.len() / 2 + 1);
+    let rest = if nibbles.len() % 2 == 1 {
+        encoding.push(((flag + 1) << 4) | nibbles[0]);
+        &nibbles[1..]
+    } else {
+        encoding.push(flag << 4);
+        nibbles
+    };
+    for pair in rest.chunks(2) {
+        encoding.push((pair[0] << 4) | pair[1]);
+    }
+    encoding
+}
+
+// NOTE: nodes shorter than a hash are embedded in their parent rather than referenced
+fn append_node_reference(stream: &mut RlpStream, encoded_node: &[u8]) {
+    if encoded_node.len() < 32 {
+        stream.append_raw(encoded_node, 1);
+    } else {
+        stream.append(&keccak256(encoded_node).to_vec());
+    }
+}
+
+fn shared_prefix_length(items: &[(Vec<u8>, &[u8])], depth: usize) -> usize {
+    let first = &items[0].0[depth..];
+    items[1..].iter().fold(first.len(), |length, (key, _)| {
+        let key = &key[depth..];
+        first[..length]
+            .iter()
+            .zip(key)
+            .take_while(|(a, b)| a == b)
+            .count()
+    })
+}
+
+/// Encode the node holding `items`, which must be sorted by key and share their first
+/// `depth` nibbles.
+fn encode_node(items: &[(Vec<u8>, &[u8])], depth: usize) -> Vec<u8> {
+    if items.is_empty() {
+        return rlp::NULL_RLP.to_vec();
+    }
+
+    if items.len() == 1 {
+        let (key, value) = &items[0];
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&hex_prefix(&key[depth..], true));
+        stream.append(value);
+        return stream.out().to_vec();
+    }
+
+    let shared = shared_prefix_length(items, depth);
+    if shared > 0 {
+        let child = encode_node(items, depth + shared);
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&hex_prefix(&items[0].0[depth..depth + shared], false));
+        append_node_reference(&mut stream, &child);
+        return stream.out().to_vec();
+    }
+
+    let mut stream = RlpStream::new_list(17);
+    let mut rest = items;
+    let mut value = None;
+    if rest[0].0.len() == depth {
+        value = Some(rest[0].1);
+        rest = &rest[1..];
+    }
+    for nibble in 0..16 {
+        let count = rest
+            .iter()
+            .take_while(|(key, _)| key[depth] == nibble)
+            .count();
+        let (children, remaining) = rest.split_at(count);
+        if children.is_empty() {
+            stream.append_empty_data();
+        } else {
+            append_node_reference(&mut stream, &encode_node(children, depth + 1));
+        }
+        rest = remaining;
+    }
+    match value {
+        Some(value) => stream.append(&value),
+        None => stream.append_empty_data(),
+    };
+    stream.out().to_vec()
+}
+
+/// Compute the root of the Merkle-Patricia trie mapping `rlp(key)` to `value`
+/// for each `(key, value)` pair.
+///
+/// If a key appears more than once, its last value is the one in the trie.
+pub fn trie_root<K: rlp::Encodable, V: AsRef<[u8]>>(
+    entries: impl IntoIterator<Item = (K, V)>,
+) -> Bytes32 {
+    let entries = entries
+        .into_iter()
+        .map(|(key, value)| (rlp::encode(&key).to_vec(), value))
+        .collect::<Vec<_>>();
+    raw_trie_root(&entries)
+}
+
+/// Compute the root of the trie mapping each key of `entries`, taken as is, to its value.
+fn raw_trie_root<K: AsRef<[u8]>, V: AsRef<[u8]>>(entries: &[(K, V)]) -> Bytes32 {
+    let mut items = entries
+        .iter()
+        .map(|(key, value)| (to_nibbles(key.as_ref()), value.as_ref()))
+        .collect::<Vec<_>>();
+    // NOTE: later entries overwrite earlier ones, as if inserted one after the other;
+    // the sort is stable so the last value of a key comes first once reversed
+    items.reverse();
+    items.sort_by(|a, b| a.0.cmp(&b.0));
+    items.dedup_by(|a, b| a.0 == b.0);
+    let root = keccak256(encode_node(&items, 0));
+    Bytes32::try_from(root.as_ref()).expect("correct input")
+}
+
+/// Compute the root of the trie keyed by the index of each of `values`, as used for
+/// the `transactions_root` and `receipts_root` of an execution block.
+pub fn ordered_trie_root<V: AsRef<[u8]>>(values: impl IntoIterator<Item = V>) -> Bytes32 {
+    trie_root(values.into_iter().enumerate())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_trie_root() {
+        let root = ordered_trie_root(Vec::<Vec<u8>>::new());
+        assert_eq!(root.as_ref(), EMPTY_TRIE_ROOT);
+        assert_eq!(keccak256(rlp::NULL_RLP), EMPTY_TRIE_ROOT);
+    }
+
+    #[test]
+    fn test_hex_prefix() {
+        assert_eq!(hex_prefix(&[1, 2, 3, 4, 5], false), vec![0x11, 0x23, 0x45]);
+        assert_eq!(
+            hex_prefix(&[0, 1, 2, 3, 4, 5], false),
+            vec![0x00, 0x01, 0x23, 0x45]
+        );
+        assert_eq!(
+            hex_prefix(&[0, 15, 1, 12, 11, 8], true),
+            vec![0x20, 0x0f, 0x1c, 0xb8]
+        );
+        assert_eq!(
+            hex_prefix(&[15, 1, 12, 11, 8], true),
+            vec![0x3f, 0x1c, 0xb8]
+        );
+    }
+
+    #[test]
+    fn test_single_entry_trie_root() {
+        // a lone leaf is hashed directly as the root node
+        let value = vec![0xabu8; 40];
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&hex_prefix(&to_nibbles(&rlp::encode(&0usize)), true));
+        stream.append(&value);
+        let expected = keccak256(stream.out());
+        assert_eq!(ordered_trie_root([value]).as_ref(), expected);
+    }
+
+    #[test]
+    fn test_duplicate_keys() {
+        let entries = [("dog", "puppy"), ("doe", "reindeer"), ("dog", "hound")];
+        let expected = raw_trie_root(&[("doe", "reindeer"), ("dog", "hound")]);
+        assert_eq!(raw_trie_root(&entries), expected);
+        assert_eq!(
+            trie_root([(1u64, "a"), (2, "b"), (1, "c")]),
+            trie_root([(1u64, "c"), (2, "b")])
+        );
+    }
+
+    #[test]
+    fn test_known_trie_roots() {
+        // an extension `646f6` into a branch splitting `doe` from `dog`, whose
+        // value sits in a branch above `dogglesworth`
+        let entries = [
+            ("doe", "reindeer"),
+            ("dog", "puppy"),
+            ("dogglesworth", "cat"),
+        ];
+        assert_eq!(
+            hex::encode(raw_trie_root(&entries)),
+            "8aad789dff2f538bca5d8ea56e8abe10f4c7ba3a5dea95fea4cd6e7c3a1168d3"
+        );
+
+        // an extension `6` into a branch splitting `horse` from the `do` subtrie,
+        // where `do` and `dog` are values of nested branches
+        let entries = [
+            ("do", "verb"),
+            ("horse", "stallion"),
+            ("doge", "coin"),
+            ("dog", "puppy"),
+        ];
+        assert_eq!(
+            hex::encode(raw_trie_root(&entries)),
+            "5991bb8c6514148a29db676a14ac506cd2cd5775ace63c30a4fe457715e9ac84"
+        );
+    }
+}
@@ -0,0 +1,891 @@
+//! Validation of `phase0` messages received over gossip, following the conditions
+//! of the p2p interface that decide whether a message is forwarded to peers.
+//!
+//! Conditions that depend on fork choice (e.g. whether the parent of a block or the
+//! block an attestation votes for has been seen) are left to the caller.
+use crate::clock::{Clock, TimeProvider};
+use crate::networking::{ATTESTATION_PROPAGATION_SLOT_RANGE, MAXIMUM_GOSSIP_CLOCK_DISPARITY};
+use crate::phase0::{
+    compute_epoch_at_slot, compute_start_slot_at_epoch, compute_subnet_for_attestation,
+    get_beacon_committee, get_beacon_proposer_index, get_committee_count_per_slot,
+    get_indexed_attestation, is_aggregator, is_valid_indexed_attestation,
+    verify_aggregate_and_proof_signature, verify_block_signature, Attestation, BeaconState,
+    SignedAggregateAndProof, SignedBeaconBlock,
+};
+use crate::primitives::{CommitteeIndex, Epoch, Root, Slot, ValidatorIndex};
+use crate::state_transition::{Context, Error};
+use ssz_rs::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
+
+#[derive(Debug, thiserror::Error)]
+pub enum IgnoreReason {
+    #[error("slot {slot} is later than the latest current slot {latest_slot}")]
+    FutureSlot { slot: Slot, latest_slot: Slot },
+    #[error("slot {slot} is earlier than the earliest slot {earliest_slot} still propagated")]
+    PastSlot { slot: Slot, earliest_slot: Slot },
+    #[error("slot {slot} is not later than the finalized slot {finalized_slot}")]
+    NotLaterThanFinalizedSlot { slot: Slot, finalized_slot: Slot },
+    #[error("already seen a block from proposer {proposer_index} in slot {slot}")]
+    DuplicateBlock {
+        slot: Slot,
+        proposer_index: ValidatorIndex,
+    },
+    #[error("already seen an attestation from validator {validator_index} for target epoch {target_epoch}")]
+    DuplicateAttestation {
+        validator_index: ValidatorIndex,
+        target_epoch: Epoch,
+    },
+    #[error("already seen an aggregate from aggregator {aggregator_index} for target epoch {target_epoch}")]
+    DuplicateAggregator {
+        aggregator_index: ValidatorIndex,
+        target_epoch: Epoch,
+    },
+    #[error("already seen a superset of the aggregate for attestation data with root {0:?}")]
+    DuplicateAggregate(Root),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RejectReason {
+    #[error("block proposed by validator {provided} instead of the expected proposer {expected}")]
+    IncorrectProposer {
+        expected: ValidatorIndex,
+        provided: ValidatorIndex,
+    },
+    #[error(
+        "committee index {index} is out of range for {committees_per_slot} committees per slot"
+    )]
+    CommitteeIndexOutOfRange {
+        index: CommitteeIndex,
+        committees_per_slot: usize,
+    },
+    #[error("attestation received on subnet {provided} instead of subnet {expected}")]
+    IncorrectSubnet { expected: u64, provided: u64 },
+    #[error(
+        "target epoch {target_epoch} does not match the epoch {slot_epoch} of the attestation slot"
+    )]
+    TargetEpochMismatch {
+        target_epoch: Epoch,
+        slot_epoch: Epoch,
+    },
+    #[error("aggregation bits have length {provided} instead of the committee size {expected}")]
+    AggregationBitsLengthMismatch { expected: usize, provided: usize },
+    #[error("unaggregated attestation has {0} participants instead of one")]
+    NotSingleParticipant(usize),
+    #[error("aggregate has no participants")]
+    NoParticipants,
+    #[error("aggregator {0} is not a member of the committee")]
+    AggregatorNotInCommittee(ValidatorIndex),
+    #[error("validator {0} is not selected as an aggregator")]
+    NotAggregator(ValidatorIndex),
+    #[error("invalid signature: {0}")]
+    InvalidSignature(Error),
+}
+
+/// The outcome of validating a gossip message: only accepted messages are forwarded,
+/// while rejected messages additionally penalize the peer that sent them.
+#[derive(Debug)]
+pub enum ValidationResult {
+    Accept,
+    Ignore(IgnoreReason),
+    Reject(RejectReason),
+}
+
+impl ValidationResult {
+    pub fn is_accept(&self) -> bool {
+        matches!(self, Self::Accept)
+    }
+}
+
+fn check_propagation_slot_range(
+    slot: Slot,
+    current_slots: &RangeInclusive<Slot>,
+) -> Option<IgnoreReason> {
+    let latest_slot = *current_slots.end();
+    let earliest_slot = current_slots
+        .start()
+        .saturating_sub(ATTESTATION_PROPAGATION_SLOT_RANGE as Slot);
+    if slot > latest_slot {
+        Some(IgnoreReason::FutureSlot { slot, latest_slot })
+    } else if slot < earliest_slot {
+        Some(IgnoreReason::PastSlot {
+            slot,
+            earliest_slot,
+        })
+    } else {
+        None
+    }
+}
+
+// Indicates if `bits` has every bit of `other` set, for bits of the same length.
+fn is_superset(bits: &[bool], other: &[bool]) -> bool {
+    bits.len() == other.len() && bits.iter().zip(other).all(|(&bit, &other)| bit || !other)
+}
+
+/// Validates gossip messages against the time given by a `Clock`, remembering the
+/// messages it has accepted to ignore later duplicates.
+pub struct GossipValidator<T: TimeProvider> {
+    clock: Clock<T>,
+    // `(slot, proposer_index)` of accepted blocks
+    seen_block_proposers: HashSet<(Slot, ValidatorIndex)>,
+    // `(validator_index, target_epoch)` of accepted unaggregated attestations
+    seen_attesters: HashSet<(ValidatorIndex, Epoch)>,
+    // `(aggregator_index, target_epoch)` of accepted aggregates
+    seen_aggregators: HashSet<(ValidatorIndex, Epoch)>,
+    // aggregation bits of accepted aggregates by the root of their `AttestationData`,
+    // along with their target epoch
+    seen_aggregates: HashMap<Root, (Epoch, Vec<Vec<bool>>)>,
+}
+
+impl<T: TimeProvider> GossipValidator<T> {
+    pub fn new(clock: Clock<T>) -> Self {
+        Self {
+            clock,
+            seen_block_proposers: Default::default(),
+            seen_attesters: Default::default(),
+            seen_aggregators: Default::default(),
+            seen_aggregates: Default::default(),
+        }
+    }
+
+    fn current_slots(&self) -> RangeInclusive<Slot> {
+        self.clock
+            .current_slot_range(MAXIMUM_GOSSIP_CLOCK_DISPARITY)
+    }
+
+    /// Forget messages that are too old to pass validation again.
+    pub fn prune(&mut self, context: &Context) {
+        let earliest_slot = self
+            .current_slots()
+            .start()
+            .saturating_sub(ATTESTATION_PROPAGATION_SLOT_RANGE as Slot);
+        let earliest_epoch = compute_epoch_at_slot(earliest_slot, context);
+
+        self.seen_block_proposers
+            .retain(|(slot, _)| *slot >= earliest_slot);
+        self.seen_attesters
+            .retain(|(_, epoch)| *epoch >= earliest_epoch);
+        self.seen_aggregators
+            .retain(|(_, epoch)| *epoch >= earliest_epoch);
+        self.seen_aggregates
+            .retain(|_, (epoch, _)| *epoch >= earliest_epoch);
+    }
+
+    /// Validate a block received on the `beacon_block` topic.
+    ///
+    /// The `state` must be the state of the parent of the block, advanced to the slot
+    /// of the block.
+    pub fn validate_beacon_block<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+    >(
+        &mut self,
+        signed_block: &mut SignedBeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+        >,
+        state: &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+        >,
+        context: &Context,
+    ) -> Result<ValidationResult, Error> {
+        let block = &signed_block.message;
+        let slot = block.slot;
+        let proposer_index = block.proposer_index;
+
+        let latest_slot = *self.current_slots().end();
+        if slot > latest_slot {
+            return Ok(ValidationResult::Ignore(IgnoreReason::FutureSlot {
+                slot,
+                latest_slot,
+            }));
+        }
+
+        let finalized_slot = compute_start_slot_at_epoch(state.finalized_checkpoint.epoch, context);
+        if slot <= finalized_slot {
+            return Ok(ValidationResult::Ignore(
+                IgnoreReason::NotLaterThanFinalizedSlot {
+                    slot,
+                    finalized_slot,
+                },
+            ));
+        }
+
+        if self.seen_block_proposers.contains(&(slot, proposer_index)) {
+            return Ok(ValidationResult::Ignore(IgnoreReason::DuplicateBlock {
+                slot,
+                proposer_index,
+            }));
+        }
+
+        let expected_proposer_index = get_beacon_proposer_index(state, context)?;
+        if proposer_index != expected_proposer_index {
+            return Ok(ValidationResult::Reject(RejectReason::IncorrectProposer {
+                expected: expected_proposer_index,
+                provided: proposer_index,
+            }));
+        }
+
+        if let Err(err) = verify_block_signature(state, signed_block, context) {
+            return Ok(ValidationResult::Reject(RejectReason::InvalidSignature(
+                err,
+            )));
+        }
+
+        self.seen_block_proposers.insert((slot, proposer_index));
+        Ok(ValidationResult::Accept)
+    }
+
+    /// Validate an unaggregated attestation received on the `beacon_attestation_{subnet_id}`
+    /// topic.
+    pub fn validate_attestation<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+    >(
+        &mut self,
+        attestation: &Attestation<MAX_VALIDATORS_PER_COMMITTEE>,
+        subnet_id: u64,
+        state: &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+        >,
+        context: &Context,
+    ) -> Result<ValidationResult, Error> {
+        let data = &attestation.data;
+        let target_epoch = data.target.epoch;
+
+        let committees_per_slot = get_committee_count_per_slot(state, target_epoch, context);
+        if data.index >= committees_per_slot {
+            return Ok(ValidationResult::Reject(
+                RejectReason::CommitteeIndexOutOfRange {
+                    index: data.index,
+                    committees_per_slot,
+                },
+            ));
+        }
+
+        let expected_subnet_id =
+            compute_subnet_for_attestation(committees_per_slot, data.slot, data.index, context);
+        if subnet_id != expected_subnet_id {
+            return Ok(ValidationResult::Reject(RejectReason::IncorrectSubnet {
+                expected: expected_subnet_id,
+                provided: subnet_id,
+            }));
+        }
+
+        if let Some(reason) = check_propagation_slot_range(data.slot, &self.current_slots()) {
+            return Ok(ValidationResult::Ignore(reason));
+        }
+
+        let slot_epoch = compute_epoch_at_slot(data.slot, context);
+        if target_epoch != slot_epoch {
+            return Ok(ValidationResult::Reject(
+                RejectReason::TargetEpochMismatch {
+                    target_epoch,
+                    slot_epoch,
+                },
+            ));
+        }
+
+        let bits = &attestation.aggregation_bits;
+        let participants = (0..bits.len()).filter(|&i| bits[i]).collect::<Vec<_>>();
+        if participants.len() != 1 {
+            return Ok(ValidationResult::Reject(
+                RejectReason::NotSingleParticipant(participants.len()),
+            ));
+        }
+
+        let committee = get_beacon_committee(state, data.slot, data.index, context)?;
+        if bits.len() != committee.len() {
+            return Ok(ValidationResult::Reject(
+                RejectReason::AggregationBitsLengthMismatch {
+                    expected: committee.len(),
+                    provided: bits.len(),
+                },
+            ));
+        }
+
+        let validator_index = committee[participants[0]];
+        if self
+            .seen_attesters
+            .contains(&(validator_index, target_epoch))
+        {
+            return Ok(ValidationResult::Ignore(
+                IgnoreReason::DuplicateAttestation {
+                    validator_index,
+                    target_epoch,
+                },
+            ));
+        }
+
+        let mut indexed_attestation = get_indexed_attestation(state, attestation, context)?;
+        if let Err(err) = is_valid_indexed_attestation(state, &mut indexed_attestation, context) {
+            return Ok(ValidationResult::Reject(RejectReason::InvalidSignature(
+                err,
+            )));
+        }
+
+        self.seen_attesters.insert((validator_index, target_epoch));
+        Ok(ValidationResult::Accept)
+    }
+
+    /// Validate an aggregate received on the `beacon_aggregate_and_proof` topic.
+    pub fn validate_aggregate_and_proof<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+    >(
+        &mut self,
+        signed_aggregate_and_proof: &mut SignedAggregateAndProof<MAX_VALIDATORS_PER_COMMITTEE>,
+        state: &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+        >,
+        context: &Context,
+    ) -> Result<ValidationResult, Error> {
+        let aggregate_and_proof = &mut signed_aggregate_and_proof.message;
+        let aggregator_index = aggregate_and_proof.aggregator_index;
+        let aggregate = &mut aggregate_and_proof.aggregate;
+        let slot = aggregate.data.slot;
+        let index = aggregate.data.index;
+        let target_epoch = aggregate.data.target.epoch;
+
+        let committees_per_slot = get_committee_count_per_slot(state, target_epoch, context);
+        if index >= committees_per_slot {
+            return Ok(ValidationResult::Reject(
+                RejectReason::CommitteeIndexOutOfRange {
+                    index,
+                    committees_per_slot,
+                },
+            ));
+        }
+
+        if let Some(reason) = check_propagation_slot_range(slot, &self.current_slots()) {
+            return Ok(ValidationResult::Ignore(reason));
+        }
+
+        let slot_epoch = compute_epoch_at_slot(slot, context);
+        if target_epoch != slot_epoch {
+            return Ok(ValidationResult::Reject(
+                RejectReason::TargetEpochMismatch {
+                    target_epoch,
+                    slot_epoch,
+                },
+            ));
+        }
+
+        let data_root = aggregate.data.hash_tree_root()?;
+        let bits = &aggregate.aggregation_bits;
+        let participation = (0..bits.len()).map(|i| bits[i]).collect::<Vec<_>>();
+        if let Some((_, seen_participations)) = self.seen_aggregates.get(&data_root) {
+            if seen_participations
+                .iter()
+                .any(|seen| is_superset(seen, &participation))
+            {
+                return Ok(ValidationResult::Ignore(IgnoreReason::DuplicateAggregate(
+                    data_root,
+                )));
+            }
+        }
+
+        if self
+            .seen_aggregators
+            .contains(&(aggregator_index, target_epoch))
+        {
+            return Ok(ValidationResult::Ignore(
+                IgnoreReason::DuplicateAggregator {
+                    aggregator_index,
+                    target_epoch,
+                },
+            ));
+        }
+
+        if !participation.iter().any(|&bit| bit) {
+            return Ok(ValidationResult::Reject(RejectReason::NoParticipants));
+        }
+
+        let committee = get_beacon_committee(state, slot, index, context)?;
+        if !committee.contains(&aggregator_index) {
+            return Ok(ValidationResult::Reject(
+                RejectReason::AggregatorNotInCommittee(aggregator_index),
+            ));
+        }
+
+        if !is_aggregator(
+            state,
+            slot,
+            index,
+            &aggregate_and_proof.selection_proof,
+            context,
+        )? {
+            return Ok(ValidationResult::Reject(RejectReason::NotAggregator(
+                aggregator_index,
+            )));
+        }
+
+        if let Err(err) =
+            verify_aggregate_and_proof_signature(state, signed_aggregate_and_proof, context)
+        {
+            return Ok(ValidationResult::Reject(RejectReason::InvalidSignature(
+                err,
+            )));
+        }
+
+        let aggregate = &signed_aggregate_and_proof.message.aggregate;
+        let mut indexed_attestation = get_indexed_attestation(state, aggregate, context)?;
+        if let Err(err) = is_valid_indexed_attestation(state, &mut indexed_attestation, context) {
+            return Ok(ValidationResult::Reject(RejectReason::InvalidSignature(
+                err,
+            )));
+        }
+
+        self.seen_aggregators
+            .insert((aggregator_index, target_epoch));
+        self.seen_aggregates
+            .entry(data_root)
+            .or_insert_with(|| (target_epoch, vec![]))
+            .1
+            .push(participation);
+        Ok(ValidationResult::Accept)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::aggregate;
+    use crate::interop::interop_secret_key;
+    use crate::interop::phase0::create_mainnet_genesis_state;
+    use crate::phase0::{
+        get_aggregate_and_proof, get_aggregate_and_proof_signature, get_block_signature,
+        get_domain, mainnet, process_slots, sign_with_domain, AttestationData, Checkpoint,
+        Validator,
+    };
+    use crate::primitives::{BlsSignature, DomainType, FAR_FUTURE_EPOCH};
+
+    const VALIDATOR_COUNT: usize = 64;
+    const SLOT: Slot = 1;
+    // the compressed generator of G2, whose digest is not `0 mod 4`
+    const G2_GENERATOR: &str = "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8";
+
+    struct FixedTime(u64);
+
+    impl TimeProvider for FixedTime {
+        fn get_current_time(&self) -> u64 {
+            self.0
+        }
+    }
+
+    // a validator whose clock is in the middle of `slot`, far from any slot boundary
+    fn validator_at(
+        slot: Slot,
+        genesis_time: u64,
+        context: &Context,
+    ) -> GossipValidator<FixedTime> {
+        let current_time =
+            genesis_time + slot * context.seconds_per_slot + context.seconds_per_slot / 2;
+        let clock = Clock::new(
+            genesis_time,
+            context.seconds_per_slot,
+            context.slots_per_epoch,
+            FixedTime(current_time),
+        );
+        GossipValidator::new(clock)
+    }
+
+    // NOTE: with 64 validators there is a single committee of 2 per slot, whose members
+    // are all aggregators
+    fn state(context: &Context) -> mainnet::BeaconState {
+        let mut state = create_mainnet_genesis_state(VALIDATOR_COUNT, context);
+        process_slots(&mut state, SLOT, context).unwrap();
+        state
+    }
+
+    fn signed_block(
+        state: &mainnet::BeaconState,
+        slot: Slot,
+        proposer_index: ValidatorIndex,
+        signer_index: ValidatorIndex,
+        context: &Context,
+    ) -> mainnet::SignedBeaconBlock {
+        let mut block = mainnet::BeaconBlock {
+            slot,
+            proposer_index,
+            ..Default::default()
+        };
+        let signing_key = interop_secret_key(signer_index).unwrap();
+        let signature = get_block_signature(state, &mut block, &signing_key, context).unwrap();
+        mainnet::SignedBeaconBlock {
+            message: block,
+            signature,
+        }
+    }
+
+    // an attestation to committee `0` of `slot` by the committee members at `participants`
+    fn attestation(
+        state: &mainnet::BeaconState,
+        slot: Slot,
+        participants: &[usize],
+        context: &Context,
+    ) -> mainnet::Attestation {
+        let committee = get_beacon_committee(state, slot, 0, context).unwrap();
+        let epoch = compute_epoch_at_slot(slot, context);
+        let mut data = AttestationData {
+            slot,
+            index: 0,
+            target: Checkpoint {
+                epoch,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let domain = get_domain(state, DomainType::BeaconAttester, Some(epoch), context).unwrap();
+        let signatures = participants
+            .iter()
+            .map(|&member| {
+                let signing_key = interop_secret_key(committee[member]).unwrap();
+                sign_with_domain(&mut data, &signing_key, domain).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let mut aggregation_bits = Bitlist::default();
+        for member in 0..committee.len() {
+            aggregation_bits.push(participants.contains(&member));
+        }
+        mainnet::Attestation {
+            aggregation_bits,
+            data,
+            signature: aggregate(&signatures).unwrap(),
+        }
+    }
+
+    fn signed_aggregate_and_proof(
+        state: &mainnet::BeaconState,
+        slot: Slot,
+        aggregator: usize,
+        participants: &[usize],
+        context: &Context,
+    ) -> mainnet::SignedAggregateAndProof {
+        let committee = get_beacon_committee(state, slot, 0, context).unwrap();
+        let aggregator_index = committee[aggregator];
+        let signing_key = interop_secret_key(aggregator_index).unwrap();
+        let aggregate = attestation(state, slot, participants, context);
+        let mut message =
+            get_aggregate_and_proof(state, aggregator_index, aggregate, &signing_key, context)
+                .unwrap();
+        let signature =
+            get_aggregate_and_proof_signature(state, &mut message, &signing_key, context).unwrap();
+        mainnet::SignedAggregateAndProof { message, signature }
+    }
+
+    #[test]
+    fn test_propagation_slot_range() {
+        let range = ATTESTATION_PROPAGATION_SLOT_RANGE as Slot;
+        let current_slots = 100..=101;
+        assert!(check_propagation_slot_range(101, &current_slots).is_none());
+        assert!(check_propagation_slot_range(100 - range, &current_slots).is_none());
+        assert!(matches!(
+            check_propagation_slot_range(102, &current_slots),
+            Some(IgnoreReason::FutureSlot { .. })
+        ));
+        assert!(matches!(
+            check_propagation_slot_range(99 - range, &current_slots),
+            Some(IgnoreReason::PastSlot { .. })
+        ));
+    }
+
+    #[test]
+    fn test_is_superset() {
+        assert!(is_superset(&[true, true], &[true, false]));
+        assert!(is_superset(&[true, false], &[true, false]));
+        assert!(!is_superset(&[true, false], &[true, true]));
+        assert!(!is_superset(&[true, true], &[true, true, false]));
+    }
+
+    #[test]
+    fn test_validate_beacon_block() {
+        let context = Context::for_mainnet();
+        let state = state(&context);
+        let mut validator = validator_at(SLOT, state.genesis_time, &context);
+        let proposer_index = get_beacon_proposer_index(&state, &context).unwrap();
+        let other_index = (proposer_index + 1) % VALIDATOR_COUNT;
+
+        let mut block = signed_block(&state, SLOT + 1, proposer_index, proposer_index, &context);
+        let result = validator.validate_beacon_block(&mut block, &state, &context);
+        assert!(matches!(
+            result.unwrap(),
+            ValidationResult::Ignore(IgnoreReason::FutureSlot {
+                slot: 2,
+                latest_slot: SLOT,
+            })
+        ));
+
+        let mut block = signed_block(&state, 0, proposer_index, proposer_index, &context);
+        let result = validator.validate_beacon_block(&mut block, &state, &context);
+        assert!(matches!(
+            result.unwrap(),
+            ValidationResult::Ignore(IgnoreReason::NotLaterThanFinalizedSlot { .. })
+        ));
+
+        let mut block = signed_block(&state, SLOT, other_index, other_index, &context);
+        let result = validator.validate_beacon_block(&mut block, &state, &context);
+        assert!(matches!(
+            result.unwrap(),
+            ValidationResult::Reject(RejectReason::IncorrectProposer { expected, .. })
+                if expected == proposer_index
+        ));
+
+        let mut block = signed_block(&state, SLOT, proposer_index, other_index, &context);
+        let result = validator.validate_beacon_block(&mut block, &state, &context);
+        assert!(matches!(
+            result.unwrap(),
+            ValidationResult::Reject(RejectReason::InvalidSignature(_))
+        ));
+
+        let mut block = signed_block(&state, SLOT, proposer_index, proposer_index, &context);
+        let result = validator.validate_beacon_block(&mut block, &state, &context);
+        assert!(result.unwrap().is_accept());
+
+        let result = validator.validate_beacon_block(&mut block, &state, &context);
+        assert!(matches!(
+            result.unwrap(),
+            ValidationResult::Ignore(IgnoreReason::DuplicateBlock { slot: SLOT, .. })
+        ));
+    }
+
+    #[test]
+    fn test_validate_attestation() {
+        let context = Context::for_mainnet();
+        let state = state(&context);
+        let mut validator = validator_at(SLOT, state.genesis_time, &context);
+        let subnet_id = compute_subnet_for_attestation(1, SLOT, 0, &context);
+
+        let attestation = attestation(&state, SLOT, &[0], &context);
+
+        let mut invalid_attestation = attestation.clone();
+        invalid_attestation.data.index = 1;
+        let result =
+            validator.validate_attestation(&invalid_attestation, subnet_id, &state, &context);
+        assert!(matches!(
+            result.unwrap(),
+            ValidationResult::Reject(RejectReason::CommitteeIndexOutOfRange {
+                index: 1,
+                committees_per_slot: 1,
+            })
+        ));
+
+        let result = validator.validate_attestation(&attestation, subnet_id + 1, &state, &context);
+        assert!(matches!(
+            result.unwrap(),
+            ValidationResult::Reject(RejectReason::IncorrectSubnet { .. })
+        ));
+
+        let future_attestation = self::attestation(&state, SLOT + 1, &[0], &context);
+        let future_subnet_id = compute_subnet_for_attestation(1, SLOT + 1, 0, &context);
+        let result =
+            validator.validate_attestation(&future_attestation, future_subnet_id, &state, &context);
+        assert!(matches!(
+            result.unwrap(),
+            ValidationResult::Ignore(IgnoreReason::FutureSlot { .. })
+        ));
+
+        let aggregate = self::attestation(&state, SLOT, &[0, 1], &context);
+        let result = validator.validate_attestation(&aggregate, subnet_id, &state, &context);
+        assert!(matches!(
+            result.unwrap(),
+            ValidationResult::Reject(RejectReason::NotSingleParticipant(2))
+        ));
+
+        // signed by the other member of the committee
+        let mut invalid_attestation = attestation.clone();
+        invalid_attestation.signature = self::attestation(&state, SLOT, &[1], &context).signature;
+        let result =
+            validator.validate_attestation(&invalid_attestation, subnet_id, &state, &context);
+        assert!(matches!(
+            result.unwrap(),
+            ValidationResult::Reject(RejectReason::InvalidSignature(_))
+        ));
+
+        let result = validator.validate_attestation(&attestation, subnet_id, &state, &context);
+        assert!(result.unwrap().is_accept());
+
+        let result = validator.validate_attestation(&attestation, subnet_id, &state, &context);
+        assert!(matches!(
+            result.unwrap(),
+            ValidationResult::Ignore(IgnoreReason::DuplicateAttestation {
+                target_epoch: 0,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_validate_aggregate_and_proof() {
+        let context = Context::for_mainnet();
+        let state = state(&context);
+        let mut validator = validator_at(SLOT, state.genesis_time, &context);
+
+        let mut aggregate = signed_aggregate_and_proof(&state, SLOT + 1, 0, &[0], &context);
+        let result = validator.validate_aggregate_and_proof(&mut aggregate, &state, &context);
+        assert!(matches!(
+            result.unwrap(),
+            ValidationResult::Ignore(IgnoreReason::FutureSlot { .. })
+        ));
+
+        let mut aggregate = signed_aggregate_and_proof(&state, SLOT, 0, &[0], &context);
+        let mut bits = Bitlist::default();
+        for _ in 0..aggregate.message.aggregate.aggregation_bits.len() {
+            bits.push(false);
+        }
+        aggregate.message.aggregate.aggregation_bits = bits;
+        let result = validator.validate_aggregate_and_proof(&mut aggregate, &state, &context);
+        assert!(matches!(
+            result.unwrap(),
+            ValidationResult::Reject(RejectReason::NoParticipants)
+        ));
+
+        let committee = get_beacon_committee(&state, SLOT, 0, &context).unwrap();
+        let outsider_index = (0..VALIDATOR_COUNT)
+            .find(|index| !committee.contains(index))
+            .unwrap();
+        let mut aggregate = signed_aggregate_and_proof(&state, SLOT, 0, &[0], &context);
+        aggregate.message.aggregator_index = outsider_index;
+        let result = validator.validate_aggregate_and_proof(&mut aggregate, &state, &context);
+        assert!(matches!(
+            result.unwrap(),
+            ValidationResult::Reject(RejectReason::AggregatorNotInCommittee(index))
+                if index == outsider_index
+        ));
+
+        let mut aggregate = signed_aggregate_and_proof(&state, SLOT, 0, &[0], &context);
+        aggregate.signature = signed_aggregate_and_proof(&state, SLOT, 1, &[0], &context).signature;
+        let result = validator.validate_aggregate_and_proof(&mut aggregate, &state, &context);
+        assert!(matches!(
+            result.unwrap(),
+            ValidationResult::Reject(RejectReason::InvalidSignature(_))
+        ));
+
+        let mut aggregate = signed_aggregate_and_proof(&state, SLOT, 0, &[0], &context);
+        let result = validator.validate_aggregate_and_proof(&mut aggregate, &state, &context);
+        assert!(result.unwrap().is_accept());
+
+        // the same attestation data with a strict superset of the participants is new
+        let mut aggregate = signed_aggregate_and_proof(&state, SLOT, 1, &[0, 1], &context);
+        let result = validator.validate_aggregate_and_proof(&mut aggregate, &state, &context);
+        assert!(result.unwrap().is_accept());
+
+        // a subset of the participants already seen for the same attestation data
+        let mut aggregate = signed_aggregate_and_proof(&state, SLOT, 0, &[1], &context);
+        let data_root = aggregate.message.aggregate.data.hash_tree_root().unwrap();
+        let result = validator.validate_aggregate_and_proof(&mut aggregate, &state, &context);
+        assert!(matches!(
+            result.unwrap(),
+            ValidationResult::Ignore(IgnoreReason::DuplicateAggregate(root)) if root == data_root
+        ));
+    }
+
+    #[test]
+    fn test_validate_aggregate_from_non_aggregator() {
+        let context = Context::for_mainnet();
+        // a committee of 64 has an aggregator modulo of 4
+        let mut state = mainnet::BeaconState::default();
+        for _ in 0..2048 {
+            state.validators.push(Validator {
+                effective_balance: context.max_effective_balance,
+                exit_epoch: FAR_FUTURE_EPOCH,
+                withdrawable_epoch: FAR_FUTURE_EPOCH,
+                ..Default::default()
+            });
+            state.balances.push(context.max_effective_balance);
+        }
+        let mut validator = validator_at(0, state.genesis_time, &context);
+        let committee = get_beacon_committee(&state, 0, 0, &context).unwrap();
+        assert_eq!(committee.len(), 64);
+
+        let mut aggregation_bits = Bitlist::default();
+        for member in 0..committee.len() {
+            aggregation_bits.push(member == 0);
+        }
+        let mut aggregate = mainnet::SignedAggregateAndProof::default();
+        aggregate.message.aggregator_index = committee[0];
+        aggregate.message.aggregate.aggregation_bits = aggregation_bits;
+
+        // the digest of the generator is `3 mod 4`
+        aggregate.message.selection_proof =
+            BlsSignature::try_from(hex::decode(G2_GENERATOR).unwrap().as_ref()).unwrap();
+        let result = validator.validate_aggregate_and_proof(&mut aggregate, &state, &context);
+        assert!(matches!(
+            result.unwrap(),
+            ValidationResult::Reject(RejectReason::NotAggregator(index)) if index == committee[0]
+        ));
+
+        // the digest of the point at infinity is `0 mod 4`, so only the signatures fail
+        aggregate.message.selection_proof = BlsSignature::default();
+        let result = validator.validate_aggregate_and_proof(&mut aggregate, &state, &context);
+        assert!(matches!(
+            result.unwrap(),
+            ValidationResult::Reject(RejectReason::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_reason_messages() {
+        let reason = IgnoreReason::FutureSlot {
+            slot: 3,
+            latest_slot: 2,
+        };
+        assert_eq!(
+            reason.to_string(),
+            "slot 3 is later than the latest current slot 2"
+        );
+        let reason = RejectReason::NotAggregator(7);
+        assert_eq!(
+            reason.to_string(),
+            "validator 7 is not selected as an aggregator"
+        );
+    }
+}
@@ -0,0 +1,249 @@
+//! Helpers to start a chain without an eth1 deposit contract, following the
+//! interop conventions shared by the consensus clients for local devnets.
+//! Keys are derived deterministically from validator indices so that every
+//! client computes the same genesis state from a validator count and a time.
+pub mod altair;
+pub mod bellatrix;
+pub mod phase0;
+
+use crate::crypto::{self, hash, SecretKey};
+use crate::phase0::{
+    compute_domain, sign_with_domain, Deposit, DepositData, DepositMessage, DepositTree,
+};
+use crate::primitives::{Bytes32, DomainType, Hash32, BLS_WITHDRAWAL_PREFIX};
+use crate::state_transition::{self, Context};
+use ssz_rs::prelude::*;
+use thiserror::Error;
+
+/// The timestamp of the mocked eth1 block used to initialize interop genesis states.
+pub const MOCK_ETH1_TIMESTAMP: u64 = 2u64.pow(40);
+
+/// The order `r` of the BLS12-381 curve, as little-endian 64-bit limbs.
+const CURVE_ORDER: [u64; 4] = [
+    0xffffffff00000001,
+    0x53bda402fffe5bfe,
+    0x3339d80809a1d805,
+    0x73eda753299d7d48,
+];
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("{0}")]
+    Crypto(#[from] crypto::Error),
+    #[error("{0}")]
+    StateTransition(#[from] state_transition::Error),
+}
+
+/// The block hash of the mocked eth1 block used to initialize interop genesis states.
+pub fn mock_eth1_block_hash() -> Hash32 {
+    Hash32::try_from([0x42u8; 32].as_ref()).expect("correct input")
+}
+
+fn is_at_least_curve_order(limbs: &[u64; 4]) -> bool {
+    for (limb, order_limb) in limbs.iter().zip(CURVE_ORDER.iter()).rev() {
+        if limb != order_limb {
+            return limb > order_limb;
+        }
+    }
+    true
+}
+
+fn subtract_curve_order(limbs: &mut [u64; 4]) {
+    let mut borrow = false;
+    for (limb, order_limb) in limbs.iter_mut().zip(CURVE_ORDER.iter()) {
+        let (difference, underflow) = limb.overflowing_sub(*order_limb);
+        let (difference, borrow_underflow) = difference.overflowing_sub(borrow as u64);
+        *limb = difference;
+        borrow = underflow || borrow_underflow;
+    }
+}
+
+/// Return the interop secret key of the validator at `index`, i.e. the little-endian
+/// integer `sha256(uint256(index))` reduced modulo the curve order.
+pub fn interop_secret_key(index: usize) -> Result<SecretKey, Error> {
+    let mut preimage = [0u8; 32];
+    preimage[..8].copy_from_slice(&(index as u64).to_le_bytes());
+    let digest = hash(preimage);
+
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(digest.as_ref().chunks_exact(8)) {
+        *limb = u64::from_le_bytes(chunk.try_into().expect("correct input"));
+    }
+    // NOTE: `2**256 < 3 * r` so at most two subtractions are required
+    while is_at_least_curve_order(&limbs) {
+        subtract_curve_order(&mut limbs);
+    }
+
+    // `SecretKey` expects the big-endian encoding of the scalar
+    let mut bytes = [0u8; 32];
+    for (chunk, limb) in bytes.chunks_exact_mut(8).zip(limbs.iter().rev()) {
+        chunk.copy_from_slice(&limb.to_be_bytes());
+    }
+    Ok(SecretKey::try_from(bytes.as_ref())?)
+}
+
+/// Return the interop secret keys of the first `validator_count` validators.
+pub fn interop_secret_keys(validator_count: usize) -> Result<Vec<SecretKey>, Error> {
+    (0..validator_count).map(interop_secret_key).collect()
+}
+
+/// Create a deposit of `max_effective_balance` for each of the `secret_keys`, with BLS
+/// withdrawal credentials and proofs against the deposit root after each deposit,
+/// as expected when processing the deposits in order at genesis.
+pub fn create_deposits(
+    secret_keys: &[SecretKey],
+    context: &Context,
+) -> Result<Vec<Deposit>, Error> {
+    let domain = compute_domain(DomainType::Deposit, None, None, context)?;
+    let mut tree = DepositTree::default();
+    let mut deposits = Vec::with_capacity(secret_keys.len());
+    for (index, secret_key) in secret_keys.iter().enumerate() {
+        let public_key = secret_key.public_key();
+        let mut withdrawal_credentials = hash(public_key.as_bytes());
+        withdrawal_credentials[0] = BLS_WITHDRAWAL_PREFIX;
+        let mut message = DepositMessage {
+            public_key,
+            withdrawal_credentials,
+            amount: context.max_effective_balance,
+        };
+        let signature = sign_with_domain(&mut message, secret_key, domain)?;
+        let data = DepositData {
+            public_key: message.public_key,
+            withdrawal_credentials: message.withdrawal_credentials,
+            amount: message.amount,
+            signature,
+        };
+        tree.push(&data)?;
+
+        // NOTE: against the latest deposit, the siblings are the complete nodes to the
+        // left, kept by the tree as deposits are pushed, or zero hashes to the right,
+        // so each proof is read off the tree's branch without hashing
+        let proof = tree
+            .proof(index, index + 1)?
+            .iter()
+            .map(|node| Bytes32::try_from(&node.as_bytes()[..]).expect("correct input"))
+            .collect::<Vec<_>>();
+        deposits.push(Deposit {
+            proof: Vector::from_iter(proof),
+            data,
+        });
+    }
+    Ok(deposits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::phase0::DEPOSIT_CONTRACT_TREE_DEPTH;
+    use crate::primitives::GENESIS_EPOCH;
+
+    #[test]
+    fn test_interop_secret_keys() {
+        // from the interop keygen test vectors
+        let expected = [
+            "25295f0d1d592a90b333e26e85149708208e9f8e8bc18f6c77bd62f8ad7a6866",
+            "51d0b65185db6989ab0b560d6deed19c7ead0e24b9b6372cbecb1f26bdfad000",
+        ];
+        for (index, expected) in expected.iter().enumerate() {
+            let secret_key = interop_secret_key(index).unwrap();
+            assert_eq!(hex::encode(secret_key.as_bytes()), *expected);
+        }
+    }
+
+    #[test]
+    fn test_create_deposits() {
+        let context = Context::for_mainnet();
+        let secret_keys = interop_secret_keys(8).unwrap();
+        let deposits = create_deposits(&secret_keys, &context).unwrap();
+        assert_eq!(deposits.len(), secret_keys.len());
+
+        let mut tree = DepositTree::default();
+        for (index, deposit) in deposits.iter().enumerate() {
+            assert_eq!(deposit.data.amount, context.max_effective_balance);
+            assert_eq!(
+                deposit.data.withdrawal_credentials[0],
+                BLS_WITHDRAWAL_PREFIX
+            );
+            tree.push(&deposit.data).unwrap();
+
+            // each proof is against the deposit root including the deposit itself
+            let leaf = deposit.data.clone().hash_tree_root().unwrap();
+            let branch = deposit
+                .proof
+                .iter()
+                .map(|node| Node::from_bytes(node.as_ref().try_into().unwrap()))
+                .collect::<Vec<_>>();
+            let root = tree.root(index + 1).unwrap();
+            assert!(is_valid_merkle_branch(
+                &leaf,
+                branch.iter(),
+                DEPOSIT_CONTRACT_TREE_DEPTH + 1,
+                index,
+                &root
+            ));
+        }
+    }
+
+    #[test]
+    fn test_create_phase0_genesis_state() {
+        use crate::phase0::mainnet::*;
+
+        const PENDING_ATTESTATIONS_BOUND: usize = MAX_ATTESTATIONS * SLOTS_PER_EPOCH as usize;
+
+        let context = Context::for_mainnet();
+        let validator_count = 16;
+        let genesis_time = 1_600_000_000;
+        let state = phase0::create_genesis_state::<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            MAX_PROPOSER_SLASHINGS,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+        >(validator_count, genesis_time, &context)
+        .unwrap();
+
+        assert_eq!(state.genesis_time, genesis_time);
+        assert_eq!(state.fork.current_version, context.genesis_fork_version);
+        assert_eq!(state.validators.len(), validator_count);
+        assert_eq!(state.eth1_deposit_index, validator_count as u64);
+        assert_eq!(state.eth1_data.deposit_count, validator_count as u64);
+        assert_eq!(state.eth1_data.block_hash, mock_eth1_block_hash());
+
+        let deposits =
+            create_deposits(&interop_secret_keys(validator_count).unwrap(), &context).unwrap();
+        let mut tree = DepositTree::default();
+        for deposit in &deposits {
+            tree.push(&deposit.data).unwrap();
+        }
+        assert_eq!(
+            state.eth1_data.deposit_root,
+            tree.root(validator_count).unwrap()
+        );
+        assert!(state
+            .validators
+            .iter()
+            .all(|validator| validator.activation_epoch == GENESIS_EPOCH));
+    }
+
+    #[test]
+    fn test_reduction_modulo_curve_order() {
+        let mut limbs = CURVE_ORDER;
+        assert!(is_at_least_curve_order(&limbs));
+        subtract_curve_order(&mut limbs);
+        assert_eq!(limbs, [0; 4]);
+        assert!(!is_at_least_curve_order(&limbs));
+
+        let mut limbs = [u64::MAX; 4];
+        subtract_curve_order(&mut limbs);
+        subtract_curve_order(&mut limbs);
+        assert!(!is_at_least_curve_order(&limbs));
+    }
+}
@@ -0,0 +1,93 @@
+use crate::interop::{
+    create_deposits, interop_secret_keys, mock_eth1_block_hash, Error, MOCK_ETH1_TIMESTAMP,
+};
+use crate::phase0::genesis::initialize_beacon_state_from_eth1;
+use crate::phase0::BeaconState;
+use crate::state_transition::Context;
+
+/// Create a `phase0` genesis state with `validator_count` interop validators,
+/// all active from genesis, starting at `genesis_time`.
+pub fn create_genesis_state<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    validator_count: usize,
+    genesis_time: u64,
+    context: &Context,
+) -> Result<
+    BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    Error,
+> {
+    let secret_keys = interop_secret_keys(validator_count)?;
+    let mut deposits = create_deposits(&secret_keys, context)?;
+    let mut state = initialize_beacon_state_from_eth1::<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        MAX_PROPOSER_SLASHINGS,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+    >(
+        mock_eth1_block_hash(),
+        MOCK_ETH1_TIMESTAMP,
+        &mut deposits,
+        context,
+    )?;
+    state.genesis_time = genesis_time;
+    Ok(state)
+}
+
+/// Create a mainnet `phase0` genesis state with `validator_count` interop validators.
+#[cfg(test)]
+pub(crate) fn create_mainnet_genesis_state(
+    validator_count: usize,
+    context: &Context,
+) -> crate::phase0::mainnet::BeaconState {
+    use crate::phase0::mainnet::*;
+
+    const PENDING_ATTESTATIONS_BOUND: usize = MAX_ATTESTATIONS * SLOTS_PER_EPOCH as usize;
+
+    create_genesis_state::<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        MAX_PROPOSER_SLASHINGS,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+    >(validator_count, MOCK_ETH1_TIMESTAMP, context)
+    .expect("can create genesis state")
+}
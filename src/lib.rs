@@ -1,15 +1,27 @@
 pub mod altair;
+#[cfg(feature = "beacon-api")]
+pub mod beacon_api;
 pub mod bellatrix;
 pub mod builder;
 pub(crate) mod bytes;
+pub mod capella;
 pub mod clock;
 pub mod configs;
 pub mod crypto;
+pub mod deneb;
 pub mod domains;
+#[cfg(feature = "engine-api")]
+pub mod engine_api;
+pub mod execution;
+pub mod gossip_validation;
+pub mod interop;
 pub mod networking;
+pub mod operation_pool;
 pub mod phase0;
 pub mod primitives;
 #[cfg(feature = "serde")]
 pub mod serde;
+#[cfg(feature = "signer")]
+pub mod signer;
 pub mod ssz;
 pub mod state_transition;
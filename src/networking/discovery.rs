@@ -0,0 +1,232 @@
+//! The consensus-specific fields of a node's ENR, used to discover peers on the
+//! same fork and subscribed to the subnets we are interested in.
+use crate::altair::SYNC_COMMITTEE_SUBNET_COUNT;
+use crate::networking::{Enr, ATTESTATION_SUBNET_COUNT};
+use crate::phase0::compute_fork_digest;
+use crate::primitives::{Epoch, ForkDigest, Root, Version, FAR_FUTURE_EPOCH};
+use crate::state_transition::{self, Context};
+use enr::k256::ecdsa::SigningKey;
+use ssz_rs::prelude::*;
+use thiserror::Error;
+
+pub const ETH2_ENR_KEY: &str = "eth2";
+pub const ATTESTATION_BITFIELD_ENR_KEY: &str = "attnets";
+pub const SYNC_COMMITTEE_BITFIELD_ENR_KEY: &str = "syncnets";
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("ENR is missing the `{0}` field")]
+    MissingField(&'static str),
+    #[error("{0}")]
+    Serialize(#[from] SerializeError),
+    #[error("{0}")]
+    Deserialize(#[from] DeserializeError),
+    #[error("could not update ENR: {0:?}")]
+    Enr(enr::EnrError),
+    #[error("{0}")]
+    StateTransition(#[from] state_transition::Error),
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, SimpleSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ENRForkID {
+    #[serde(with = "crate::serde::as_hex")]
+    pub fork_digest: ForkDigest,
+    #[serde(with = "crate::serde::as_hex")]
+    pub next_fork_version: Version,
+    #[serde(with = "crate::serde::as_string")]
+    pub next_fork_epoch: Epoch,
+}
+
+impl ENRForkID {
+    /// Compute the fork id advertised at `epoch` given the fork schedule of the `context`.
+    pub fn new(
+        epoch: Epoch,
+        genesis_validators_root: Root,
+        context: &Context,
+    ) -> Result<Self, Error> {
+        let schedule = context.fork_schedule();
+        let forks = [
+            (context.genesis_fork_version, 0),
+            (context.altair_fork_version, schedule.altair),
+            (context.bellatrix_fork_version, schedule.bellatrix),
+            (context.capella_fork_version, schedule.capella),
+            (context.deneb_fork_version, schedule.deneb),
+        ];
+        let current_fork_version = forks
+            .iter()
+            .take_while(|(_, fork_epoch)| *fork_epoch <= epoch)
+            .last()
+            .map(|(version, _)| *version)
+            .unwrap_or(context.genesis_fork_version);
+        let (next_fork_version, next_fork_epoch) = forks
+            .iter()
+            .find(|(_, fork_epoch)| *fork_epoch > epoch && *fork_epoch != FAR_FUTURE_EPOCH)
+            .copied()
+            .unwrap_or((current_fork_version, FAR_FUTURE_EPOCH));
+        let fork_digest = compute_fork_digest(current_fork_version, genesis_validators_root)?;
+        Ok(Self {
+            fork_digest,
+            next_fork_version,
+            next_fork_epoch,
+        })
+    }
+
+    /// Indicates if a peer advertising `other` is on the same fork as us.
+    ///
+    /// Peers that agree on the current fork but disagree on the next fork are still
+    /// considered compatible until that fork is reached.
+    pub fn is_compatible(&self, other: &Self) -> bool {
+        self.fork_digest == other.fork_digest
+    }
+}
+
+fn read_field<T: SimpleSerialize>(enr: &Enr, key: &'static str) -> Result<T, Error> {
+    let value = enr.get(key).ok_or(Error::MissingField(key))?;
+    Ok(T::deserialize(value)?)
+}
+
+fn write_field<T: SimpleSerialize>(
+    enr: &mut Enr,
+    key: &'static str,
+    value: &T,
+    signing_key: &SigningKey,
+) -> Result<(), Error> {
+    let mut encoding = vec![];
+    value.serialize(&mut encoding)?;
+    enr.insert(key, &encoding, signing_key)
+        .map_err(Error::Enr)?;
+    Ok(())
+}
+
+pub fn get_fork_id(enr: &Enr) -> Result<ENRForkID, Error> {
+    read_field(enr, ETH2_ENR_KEY)
+}
+
+pub fn set_fork_id(
+    enr: &mut Enr,
+    fork_id: &ENRForkID,
+    signing_key: &SigningKey,
+) -> Result<(), Error> {
+    write_field(enr, ETH2_ENR_KEY, fork_id, signing_key)
+}
+
+pub fn get_attestation_subnets(enr: &Enr) -> Result<Bitvector<ATTESTATION_SUBNET_COUNT>, Error> {
+    read_field(enr, ATTESTATION_BITFIELD_ENR_KEY)
+}
+
+pub fn set_attestation_subnets(
+    enr: &mut Enr,
+    subnets: &Bitvector<ATTESTATION_SUBNET_COUNT>,
+    signing_key: &SigningKey,
+) -> Result<(), Error> {
+    write_field(enr, ATTESTATION_BITFIELD_ENR_KEY, subnets, signing_key)
+}
+
+pub fn get_sync_committee_subnets(
+    enr: &Enr,
+) -> Result<Bitvector<SYNC_COMMITTEE_SUBNET_COUNT>, Error> {
+    read_field(enr, SYNC_COMMITTEE_BITFIELD_ENR_KEY)
+}
+
+pub fn set_sync_committee_subnets(
+    enr: &mut Enr,
+    subnets: &Bitvector<SYNC_COMMITTEE_SUBNET_COUNT>,
+    signing_key: &SigningKey,
+) -> Result<(), Error> {
+    write_field(enr, SYNC_COMMITTEE_BITFIELD_ENR_KEY, subnets, signing_key)
+}
+
+/// Indicates if the peer behind `enr` advertises a fork compatible with `fork_id`.
+///
+/// Peers without a (well-formed) `eth2` field are not considered on our fork.
+pub fn is_on_fork(enr: &Enr, fork_id: &ENRForkID) -> bool {
+    get_fork_id(enr)
+        .map(|peer_fork_id| fork_id.is_compatible(&peer_fork_id))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use enr::EnrBuilder;
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[1u8; 32]).unwrap()
+    }
+
+    #[test]
+    fn test_fork_id_before_and_after_altair() {
+        let context = Context::for_mainnet();
+        let genesis_validators_root = Root::default();
+        let altair_epoch = context.altair_fork_epoch;
+
+        let fork_id = ENRForkID::new(0, genesis_validators_root, &context).unwrap();
+        assert_eq!(
+            fork_id.fork_digest,
+            compute_fork_digest(context.genesis_fork_version, genesis_validators_root).unwrap()
+        );
+        assert_eq!(fork_id.next_fork_version, context.altair_fork_version);
+        assert_eq!(fork_id.next_fork_epoch, altair_epoch);
+
+        let fork_id = ENRForkID::new(altair_epoch, genesis_validators_root, &context).unwrap();
+        assert_eq!(
+            fork_id.fork_digest,
+            compute_fork_digest(context.altair_fork_version, genesis_validators_root).unwrap()
+        );
+        // no further fork is scheduled on mainnet
+        assert_eq!(fork_id.next_fork_version, context.altair_fork_version);
+        assert_eq!(fork_id.next_fork_epoch, FAR_FUTURE_EPOCH);
+    }
+
+    #[test]
+    fn test_fork_id_round_trip() {
+        let context = Context::for_mainnet();
+        let fork_id = ENRForkID::new(0, Root::default(), &context).unwrap();
+        let mut encoding = vec![];
+        fork_id.serialize(&mut encoding).unwrap();
+        let recovered = ENRForkID::deserialize(&encoding).unwrap();
+        assert_eq!(recovered, fork_id);
+        assert!(fork_id.is_compatible(&recovered));
+
+        let other = ENRForkID::new(context.altair_fork_epoch, Root::default(), &context).unwrap();
+        assert!(!fork_id.is_compatible(&other));
+    }
+
+    #[test]
+    fn test_enr_fields() {
+        let context = Context::for_mainnet();
+        let signing_key = signing_key();
+        let mut enr = EnrBuilder::new("v4").build(&signing_key).unwrap();
+
+        assert!(matches!(
+            get_fork_id(&enr),
+            Err(Error::MissingField(ETH2_ENR_KEY))
+        ));
+        let fork_id = ENRForkID::new(0, Root::default(), &context).unwrap();
+        assert!(!is_on_fork(&enr, &fork_id));
+
+        set_fork_id(&mut enr, &fork_id, &signing_key).unwrap();
+        assert_eq!(get_fork_id(&enr).unwrap(), fork_id);
+
+        let mut attnets = Bitvector::<ATTESTATION_SUBNET_COUNT>::default();
+        attnets.set(3, true);
+        attnets.set(ATTESTATION_SUBNET_COUNT - 1, true);
+        set_attestation_subnets(&mut enr, &attnets, &signing_key).unwrap();
+        assert_eq!(get_attestation_subnets(&enr).unwrap(), attnets);
+
+        let mut syncnets = Bitvector::<SYNC_COMMITTEE_SUBNET_COUNT>::default();
+        syncnets.set(1, true);
+        set_sync_committee_subnets(&mut enr, &syncnets, &signing_key).unwrap();
+        assert_eq!(get_sync_committee_subnets(&enr).unwrap(), syncnets);
+
+        // every update is signed with the key of the node
+        assert!(enr.verify());
+        assert_eq!(get_fork_id(&enr).unwrap(), fork_id);
+
+        assert!(is_on_fork(&enr, &fork_id));
+        let altair_fork_id =
+            ENRForkID::new(context.altair_fork_epoch, Root::default(), &context).unwrap();
+        assert!(!is_on_fork(&enr, &altair_fork_id));
+    }
+}
@@ -0,0 +1,301 @@
+//! Gossipsub topics of the form `/eth2/{fork_digest}/{name}/ssz_snappy` and the
+//! message ids used to deduplicate messages published to them.
+use crate::altair::SYNC_COMMITTEE_SUBNET_COUNT;
+use crate::crypto::hash;
+use crate::networking::{MessageDomain, ATTESTATION_SUBNET_COUNT, GOSSIP_MAX_SIZE};
+use crate::primitives::ForkDigest;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+const TOPIC_PREFIX: &str = "eth2";
+const ENCODING_POSTFIX: &str = "ssz_snappy";
+
+const BEACON_BLOCK_TOPIC: &str = "beacon_block";
+const BEACON_AGGREGATE_AND_PROOF_TOPIC: &str = "beacon_aggregate_and_proof";
+const BEACON_ATTESTATION_TOPIC_PREFIX: &str = "beacon_attestation_";
+const VOLUNTARY_EXIT_TOPIC: &str = "voluntary_exit";
+const PROPOSER_SLASHING_TOPIC: &str = "proposer_slashing";
+const ATTESTER_SLASHING_TOPIC: &str = "attester_slashing";
+const SYNC_COMMITTEE_CONTRIBUTION_AND_PROOF_TOPIC: &str = "sync_committee_contribution_and_proof";
+const SYNC_COMMITTEE_TOPIC_PREFIX: &str = "sync_committee_";
+
+pub const MESSAGE_ID_LENGTH: usize = 20;
+
+pub type MessageId = [u8; MESSAGE_ID_LENGTH];
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("invalid gossip topic `{0}`")]
+pub struct InvalidTopic(pub String);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GossipTopicKind {
+    BeaconBlock,
+    BeaconAggregateAndProof,
+    BeaconAttestation(u64),
+    VoluntaryExit,
+    ProposerSlashing,
+    AttesterSlashing,
+    SyncCommitteeContributionAndProof,
+    SyncCommittee(u64),
+}
+
+impl fmt::Display for GossipTopicKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BeaconBlock => write!(f, "{}", BEACON_BLOCK_TOPIC),
+            Self::BeaconAggregateAndProof => write!(f, "{}", BEACON_AGGREGATE_AND_PROOF_TOPIC),
+            Self::BeaconAttestation(subnet_id) => {
+                write!(f, "{}{}", BEACON_ATTESTATION_TOPIC_PREFIX, subnet_id)
+            }
+            Self::VoluntaryExit => write!(f, "{}", VOLUNTARY_EXIT_TOPIC),
+            Self::ProposerSlashing => write!(f, "{}", PROPOSER_SLASHING_TOPIC),
+            Self::AttesterSlashing => write!(f, "{}", ATTESTER_SLASHING_TOPIC),
+            Self::SyncCommitteeContributionAndProof => {
+                write!(f, "{}", SYNC_COMMITTEE_CONTRIBUTION_AND_PROOF_TOPIC)
+            }
+            Self::SyncCommittee(subnet_id) => {
+                write!(f, "{}{}", SYNC_COMMITTEE_TOPIC_PREFIX, subnet_id)
+            }
+        }
+    }
+}
+
+fn parse_subnet_id(s: &str, subnet_count: usize) -> Option<u64> {
+    // NOTE: reject non-canonical forms like `+1` or `01` so parsing round-trips
+    if s.is_empty() || (s.len() > 1 && s.starts_with('0')) || !s.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let subnet_id = s.parse::<u64>().ok()?;
+    (subnet_id < subnet_count as u64).then_some(subnet_id)
+}
+
+impl FromStr for GossipTopicKind {
+    type Err = InvalidTopic;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let kind = match s {
+            BEACON_BLOCK_TOPIC => Some(Self::BeaconBlock),
+            BEACON_AGGREGATE_AND_PROOF_TOPIC => Some(Self::BeaconAggregateAndProof),
+            VOLUNTARY_EXIT_TOPIC => Some(Self::VoluntaryExit),
+            PROPOSER_SLASHING_TOPIC => Some(Self::ProposerSlashing),
+            ATTESTER_SLASHING_TOPIC => Some(Self::AttesterSlashing),
+            SYNC_COMMITTEE_CONTRIBUTION_AND_PROOF_TOPIC => {
+                Some(Self::SyncCommitteeContributionAndProof)
+            }
+            s => {
+                if let Some(subnet_id) = s.strip_prefix(BEACON_ATTESTATION_TOPIC_PREFIX) {
+                    parse_subnet_id(subnet_id, ATTESTATION_SUBNET_COUNT)
+                        .map(Self::BeaconAttestation)
+                } else if let Some(subnet_id) = s.strip_prefix(SYNC_COMMITTEE_TOPIC_PREFIX) {
+                    parse_subnet_id(subnet_id, SYNC_COMMITTEE_SUBNET_COUNT).map(Self::SyncCommittee)
+                } else {
+                    None
+                }
+            }
+        };
+        kind.ok_or_else(|| InvalidTopic(s.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GossipTopic {
+    pub fork_digest: ForkDigest,
+    pub kind: GossipTopicKind,
+}
+
+impl GossipTopic {
+    pub fn new(fork_digest: ForkDigest, kind: GossipTopicKind) -> Self {
+        Self { fork_digest, kind }
+    }
+}
+
+impl fmt::Display for GossipTopic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "/{}/", TOPIC_PREFIX)?;
+        for byte in self.fork_digest {
+            write!(f, "{:02x}", byte)?;
+        }
+        write!(f, "/{}/{}", self.kind, ENCODING_POSTFIX)
+    }
+}
+
+fn parse_fork_digest(s: &str) -> Option<ForkDigest> {
+    if s.len() != 2 * std::mem::size_of::<ForkDigest>() || !s.is_ascii() {
+        return None;
+    }
+    let mut fork_digest = ForkDigest::default();
+    for (byte, chunk) in fork_digest.iter_mut().zip(s.as_bytes().chunks(2)) {
+        let chunk = std::str::from_utf8(chunk).ok()?;
+        *byte = u8::from_str_radix(chunk, 16).ok()?;
+    }
+    Some(fork_digest)
+}
+
+impl FromStr for GossipTopic {
+    type Err = InvalidTopic;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidTopic(s.to_string());
+        let mut parts = s.split('/');
+        if parts.next() != Some("") || parts.next() != Some(TOPIC_PREFIX) {
+            return Err(invalid());
+        }
+        let fork_digest = parts
+            .next()
+            .and_then(parse_fork_digest)
+            .ok_or_else(invalid)?;
+        let kind = parts
+            .next()
+            .ok_or_else(invalid)?
+            .parse()
+            .map_err(|_| invalid())?;
+        if parts.next() != Some(ENCODING_POSTFIX) || parts.next().is_some() {
+            return Err(invalid());
+        }
+        Ok(Self { fork_digest, kind })
+    }
+}
+
+/// Decompress `data` with the raw snappy block format used on gossip, returning the
+/// domain the message id should be computed under along with the bytes to hash.
+fn message_domain_and_data(data: &[u8]) -> (MessageDomain, std::borrow::Cow<'_, [u8]>) {
+    let decompressed = snap::raw::decompress_len(data)
+        .ok()
+        .filter(|&length| length <= GOSSIP_MAX_SIZE)
+        .and_then(|_| snap::raw::Decoder::new().decompress_vec(data).ok());
+    match decompressed {
+        Some(decompressed) => (MessageDomain::ValidSnappy, decompressed.into()),
+        None => (MessageDomain::InvalidSnappy, data.into()),
+    }
+}
+
+fn truncate_to_message_id(digest: &[u8]) -> MessageId {
+    let mut message_id = MessageId::default();
+    message_id.copy_from_slice(&digest[..MESSAGE_ID_LENGTH]);
+    message_id
+}
+
+/// Compute the phase0 gossip message id of the (snappy compressed) message `data`.
+pub fn compute_message_id(data: &[u8]) -> MessageId {
+    let (domain, data) = message_domain_and_data(data);
+    let mut preimage = Vec::with_capacity(4 + data.len());
+    preimage.extend_from_slice(&domain.as_bytes());
+    preimage.extend_from_slice(&data);
+    truncate_to_message_id(hash(preimage).as_ref())
+}
+
+/// Compute the gossip message id of the (snappy compressed) message `data` published
+/// to `topic`, which from Altair onwards commits to the topic as well.
+pub fn compute_message_id_with_topic(topic: &str, data: &[u8]) -> MessageId {
+    let (domain, data) = message_domain_and_data(data);
+    let topic = topic.as_bytes();
+    let mut preimage = Vec::with_capacity(4 + 8 + topic.len() + data.len());
+    preimage.extend_from_slice(&domain.as_bytes());
+    preimage.extend_from_slice(&(topic.len() as u64).to_le_bytes());
+    preimage.extend_from_slice(topic);
+    preimage.extend_from_slice(&data);
+    truncate_to_message_id(hash(preimage).as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORK_DIGEST: ForkDigest = [0xb5, 0x30, 0x3f, 0x2a];
+
+    #[test]
+    fn test_topic_round_trip() {
+        let kinds = [
+            GossipTopicKind::BeaconBlock,
+            GossipTopicKind::BeaconAggregateAndProof,
+            GossipTopicKind::BeaconAttestation(0),
+            GossipTopicKind::BeaconAttestation(ATTESTATION_SUBNET_COUNT as u64 - 1),
+            GossipTopicKind::VoluntaryExit,
+            GossipTopicKind::ProposerSlashing,
+            GossipTopicKind::AttesterSlashing,
+            GossipTopicKind::SyncCommitteeContributionAndProof,
+            GossipTopicKind::SyncCommittee(SYNC_COMMITTEE_SUBNET_COUNT as u64 - 1),
+        ];
+        for kind in kinds {
+            let topic = GossipTopic::new(FORK_DIGEST, kind);
+            let recovered: GossipTopic = topic.to_string().parse().unwrap();
+            assert_eq!(recovered, topic);
+        }
+    }
+
+    #[test]
+    fn test_topic_format() {
+        let topic = GossipTopic::new(FORK_DIGEST, GossipTopicKind::BeaconAttestation(12));
+        assert_eq!(
+            topic.to_string(),
+            "/eth2/b5303f2a/beacon_attestation_12/ssz_snappy"
+        );
+    }
+
+    #[test]
+    fn test_invalid_topics() {
+        let topics = [
+            "",
+            "/eth2/b5303f2a/beacon_block",
+            "/eth2/b5303f2a/beacon_block/ssz",
+            "/eth2/b5303f2a/beacon_block/ssz_snappy/extra",
+            "/eth1/b5303f2a/beacon_block/ssz_snappy",
+            "/eth2/b5303f/beacon_block/ssz_snappy",
+            "/eth2/b5303fzz/beacon_block/ssz_snappy",
+            "/eth2/b5303f2a/unknown/ssz_snappy",
+            "/eth2/b5303f2a/beacon_attestation_64/ssz_snappy",
+            "/eth2/b5303f2a/beacon_attestation_01/ssz_snappy",
+            "/eth2/b5303f2a/beacon_attestation_/ssz_snappy",
+            "/eth2/b5303f2a/sync_committee_4/ssz_snappy",
+        ];
+        for topic in topics {
+            assert!(topic.parse::<GossipTopic>().is_err(), "{}", topic);
+        }
+    }
+
+    #[test]
+    fn test_message_id_valid_snappy() {
+        let data = b"some gossip message".to_vec();
+        let compressed = snap::raw::Encoder::new().compress_vec(&data).unwrap();
+
+        let mut preimage = MessageDomain::ValidSnappy.as_bytes().to_vec();
+        preimage.extend_from_slice(&data);
+        let expected = hash(&preimage);
+        assert_eq!(compute_message_id(&compressed), expected.as_ref()[..20]);
+
+        let topic = GossipTopic::new(FORK_DIGEST, GossipTopicKind::BeaconBlock).to_string();
+        let mut preimage = MessageDomain::ValidSnappy.as_bytes().to_vec();
+        preimage.extend_from_slice(&(topic.len() as u64).to_le_bytes());
+        preimage.extend_from_slice(topic.as_bytes());
+        preimage.extend_from_slice(&data);
+        let expected = hash(&preimage);
+        assert_eq!(
+            compute_message_id_with_topic(&topic, &compressed),
+            expected.as_ref()[..20]
+        );
+    }
+
+    #[test]
+    fn test_message_id_invalid_snappy() {
+        let data = [0xffu8; 8];
+        assert!(snap::raw::Decoder::new().decompress_vec(&data).is_err());
+
+        let mut preimage = MessageDomain::InvalidSnappy.as_bytes().to_vec();
+        preimage.extend_from_slice(&data);
+        let expected = hash(&preimage);
+        assert_eq!(compute_message_id(&data), expected.as_ref()[..20]);
+    }
+
+    #[test]
+    fn test_message_id_depends_on_topic() {
+        let compressed = snap::raw::Encoder::new().compress_vec(b"message").unwrap();
+        let a = GossipTopic::new(FORK_DIGEST, GossipTopicKind::VoluntaryExit).to_string();
+        let b = GossipTopic::new(FORK_DIGEST, GossipTopicKind::ProposerSlashing).to_string();
+        assert_ne!(
+            compute_message_id_with_topic(&a, &compressed),
+            compute_message_id_with_topic(&b, &compressed)
+        );
+    }
+}
@@ -0,0 +1,68 @@
+pub mod discovery;
+pub mod gossip;
+pub mod reqresp;
+pub mod subnets;
+
+use crate::altair::SYNC_COMMITTEE_SUBNET_COUNT;
+use crate::primitives::Epoch;
+use enr;
+use ssz_rs::prelude::*;
+use std::time::Duration;
+
+pub const ATTESTATION_SUBNET_COUNT: usize = 64;
+pub const GOSSIP_MAX_SIZE: usize = 2usize.pow(20);
+pub const MAX_REQUEST_BLOCKS: usize = 2usize.pow(10);
+pub const MIN_EPOCHS_FOR_BLOCK_REQUESTS: Epoch = 33024;
+pub const MAX_CHUNK_SIZE: usize = 2usize.pow(20);
+pub const TTFB_TIMEOUT: Duration = Duration::from_secs(5);
+pub const RESP_TIMEOUT: Duration = Duration::from_secs(10);
+pub const ATTESTATION_PROPAGATION_SLOT_RANGE: usize = 32;
+pub const MAXIMUM_GOSSIP_CLOCK_DISPARITY: Duration = Duration::from_millis(500);
+pub const SUBNETS_PER_NODE: usize = 2;
+pub const EPOCHS_PER_SUBNET_SUBSCRIPTION: Epoch = 256;
+pub const ATTESTATION_SUBNET_EXTRA_BITS: usize = 0;
+// NOTE: `ceillog2(ATTESTATION_SUBNET_COUNT) + ATTESTATION_SUBNET_EXTRA_BITS`
+pub const ATTESTATION_SUBNET_PREFIX_BITS: usize =
+    (usize::BITS - (ATTESTATION_SUBNET_COUNT - 1).leading_zeros()) as usize
+        + ATTESTATION_SUBNET_EXTRA_BITS;
+pub const NODE_ID_BITS: usize = 256;
+
+pub use libp2p_core::PeerId;
+pub use multiaddr::Multiaddr;
+
+pub type Enr = enr::Enr<enr::k256::ecdsa::SigningKey>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageDomain {
+    InvalidSnappy,
+    ValidSnappy,
+}
+
+impl MessageDomain {
+    pub fn as_bytes(&self) -> [u8; 4] {
+        match self {
+            Self::InvalidSnappy => [0, 0, 0, 0],
+            Self::ValidSnappy => [1, 0, 0, 0],
+        }
+    }
+}
+
+/// The `MetaData` of the phase0 networking spec, served over `METADATA_V1_PROTOCOL_ID`.
+#[derive(Default, Debug, Clone, SimpleSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetaData {
+    #[serde(with = "crate::serde::as_string")]
+    pub seq_number: u64,
+    pub attnets: Bitvector<ATTESTATION_SUBNET_COUNT>,
+}
+
+/// The `MetaData` of the altair networking spec, which adds the sync committee subnets,
+/// served over `METADATA_PROTOCOL_ID`.
+#[derive(Default, Debug, Clone, SimpleSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetaDataV2 {
+    #[serde(with = "crate::serde::as_string")]
+    pub seq_number: u64,
+    pub attnets: Bitvector<ATTESTATION_SUBNET_COUNT>,
+    pub syncnets: Bitvector<SYNC_COMMITTEE_SUBNET_COUNT>,
+}
@@ -0,0 +1,435 @@
+//! The `ssz_snappy` encoding of the req/resp domain: each payload is prefixed by its
+//! uncompressed length as an unsigned varint and compressed with the snappy frame format.
+//! Response chunks are additionally prefixed by a result code and, for some protocols,
+//! the fork digest of the context the payload belongs to.
+use crate::networking::{MAX_CHUNK_SIZE, MAX_REQUEST_BLOCKS};
+use crate::primitives::{Epoch, ForkDigest, Root, Slot};
+use ssz_rs::prelude::*;
+use std::io::{self, Read, Write};
+use thiserror::Error;
+
+pub const STATUS_PROTOCOL_ID: &str = "/eth2/beacon_chain/req/status/1/ssz_snappy";
+pub const GOODBYE_PROTOCOL_ID: &str = "/eth2/beacon_chain/req/goodbye/1/ssz_snappy";
+pub const PING_PROTOCOL_ID: &str = "/eth2/beacon_chain/req/ping/1/ssz_snappy";
+pub const METADATA_V1_PROTOCOL_ID: &str = "/eth2/beacon_chain/req/metadata/1/ssz_snappy";
+pub const METADATA_PROTOCOL_ID: &str = "/eth2/beacon_chain/req/metadata/2/ssz_snappy";
+pub const BEACON_BLOCKS_BY_RANGE_PROTOCOL_ID: &str =
+    "/eth2/beacon_chain/req/beacon_blocks_by_range/2/ssz_snappy";
+pub const BEACON_BLOCKS_BY_ROOT_PROTOCOL_ID: &str =
+    "/eth2/beacon_chain/req/beacon_blocks_by_root/2/ssz_snappy";
+
+pub const MAX_ERROR_MESSAGE_LENGTH: usize = 256;
+// NOTE: a `u64` needs at most 10 bytes as an unsigned LEB128 varint
+const MAX_VARINT_LENGTH: usize = 10;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("{0}")]
+    Io(#[from] io::Error),
+    #[error("{0}")]
+    Serialize(#[from] SerializeError),
+    #[error("{0}")]
+    Deserialize(#[from] DeserializeError),
+    #[error("invalid varint length prefix")]
+    InvalidVarint,
+    #[error("payload of {length} bytes exceeds the maximum of {max} bytes")]
+    PayloadTooLarge { length: usize, max: usize },
+    #[error("expected payload of {expected} bytes but only decompressed {provided} bytes")]
+    TruncatedPayload { expected: usize, provided: usize },
+    #[error("unknown response code {0}")]
+    UnknownResponseCode(u8),
+    #[error("peer responded with {code:?}: {message}")]
+    ErrorResponse { code: ResponseCode, message: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseCode {
+    Success,
+    InvalidRequest,
+    ServerError,
+    ResourceUnavailable,
+}
+
+impl From<ResponseCode> for u8 {
+    fn from(code: ResponseCode) -> Self {
+        match code {
+            ResponseCode::Success => 0,
+            ResponseCode::InvalidRequest => 1,
+            ResponseCode::ServerError => 2,
+            ResponseCode::ResourceUnavailable => 3,
+        }
+    }
+}
+
+impl TryFrom<u8> for ResponseCode {
+    type Error = Error;
+
+    fn try_from(code: u8) -> Result<Self, Self::Error> {
+        match code {
+            0 => Ok(Self::Success),
+            1 => Ok(Self::InvalidRequest),
+            2 => Ok(Self::ServerError),
+            3 => Ok(Self::ResourceUnavailable),
+            code => Err(Error::UnknownResponseCode(code)),
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, SimpleSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Status {
+    #[serde(with = "crate::serde::as_hex")]
+    pub fork_digest: ForkDigest,
+    pub finalized_root: Root,
+    #[serde(with = "crate::serde::as_string")]
+    pub finalized_epoch: Epoch,
+    pub head_root: Root,
+    #[serde(with = "crate::serde::as_string")]
+    pub head_slot: Slot,
+}
+
+/// The reason given by a peer when disconnecting; see the `GOODBYE_*` codes.
+pub type Goodbye = u64;
+
+pub const GOODBYE_CLIENT_SHUTDOWN: Goodbye = 1;
+pub const GOODBYE_IRRELEVANT_NETWORK: Goodbye = 2;
+pub const GOODBYE_FAULT_OR_ERROR: Goodbye = 3;
+
+/// The sequence number of the sender's `MetaData`.
+pub type Ping = u64;
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, SimpleSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BeaconBlocksByRangeRequest {
+    #[serde(with = "crate::serde::as_string")]
+    pub start_slot: Slot,
+    #[serde(with = "crate::serde::as_string")]
+    pub count: u64,
+    // NOTE: deprecated, must be set to 1
+    #[serde(with = "crate::serde::as_string")]
+    pub step: u64,
+}
+
+pub type BeaconBlocksByRootRequest = List<Root, MAX_REQUEST_BLOCKS>;
+
+pub type ErrorMessage = List<u8, MAX_ERROR_MESSAGE_LENGTH>;
+
+fn write_varint<W: Write>(writer: &mut W, mut value: u64) -> Result<(), Error> {
+    let mut buffer = [0u8; MAX_VARINT_LENGTH];
+    let mut length = 0;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buffer[length] = byte;
+            length += 1;
+            break;
+        }
+        buffer[length] = byte | 0x80;
+        length += 1;
+    }
+    writer.write_all(&buffer[..length])?;
+    Ok(())
+}
+
+fn read_varint<R: Read>(reader: &mut R) -> Result<u64, Error> {
+    let mut value = 0u64;
+    for i in 0..MAX_VARINT_LENGTH {
+        let mut byte = [0u8; 1];
+        reader.read_exact(&mut byte)?;
+        let byte = byte[0];
+        let bits = (byte & 0x7f) as u64;
+        let shift = 7 * i as u32;
+        // NOTE: reject encodings overflowing a `u64` or carrying redundant trailing bytes
+        if (shift == 63 && bits > 1) || (i > 0 && byte == 0) {
+            return Err(Error::InvalidVarint);
+        }
+        value |= bits << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(Error::InvalidVarint)
+}
+
+/// Write `payload` with its length prefix, compressed with the snappy frame format.
+pub fn write_payload<W: Write>(writer: &mut W, payload: &[u8]) -> Result<(), Error> {
+    if payload.len() > MAX_CHUNK_SIZE {
+        return Err(Error::PayloadTooLarge {
+            length: payload.len(),
+            max: MAX_CHUNK_SIZE,
+        });
+    }
+    write_varint(writer, payload.len() as u64)?;
+    let mut encoder = snap::write::FrameEncoder::new(writer);
+    encoder.write_all(payload)?;
+    encoder.flush()?;
+    Ok(())
+}
+
+/// Read a length-prefixed, snappy-compressed payload of at most `max_length` bytes.
+pub fn read_payload<R: Read>(reader: &mut R, max_length: usize) -> Result<Vec<u8>, Error> {
+    let length = read_varint(reader)?;
+    let length = usize::try_from(length).map_err(|_| Error::InvalidVarint)?;
+    if length > max_length {
+        return Err(Error::PayloadTooLarge {
+            length,
+            max: max_length,
+        });
+    }
+    let mut payload = Vec::with_capacity(length);
+    let decoder = snap::read::FrameDecoder::new(reader);
+    decoder.take(length as u64).read_to_end(&mut payload)?;
+    if payload.len() != length {
+        return Err(Error::TruncatedPayload {
+            expected: length,
+            provided: payload.len(),
+        });
+    }
+    Ok(payload)
+}
+
+pub fn write_request<T: SimpleSerialize, W: Write>(
+    writer: &mut W,
+    request: &T,
+) -> Result<(), Error> {
+    let mut buffer = vec![];
+    request.serialize(&mut buffer)?;
+    write_payload(writer, &buffer)
+}
+
+pub fn read_request<T: SimpleSerialize, R: Read>(reader: &mut R) -> Result<T, Error> {
+    let payload = read_payload(reader, MAX_CHUNK_SIZE)?;
+    let request = T::deserialize(&payload)?;
+    Ok(request)
+}
+
+/// Write a successful response chunk, prefixed by the `context` bytes if the protocol uses them.
+pub fn write_response_chunk<T: SimpleSerialize, W: Write>(
+    writer: &mut W,
+    context: Option<ForkDigest>,
+    response: &T,
+) -> Result<(), Error> {
+    writer.write_all(&[u8::from(ResponseCode::Success)])?;
+    if let Some(fork_digest) = context {
+        writer.write_all(&fork_digest)?;
+    }
+    let mut buffer = vec![];
+    response.serialize(&mut buffer)?;
+    write_payload(writer, &buffer)
+}
+
+pub fn write_error_response<W: Write>(
+    writer: &mut W,
+    code: ResponseCode,
+    message: &str,
+) -> Result<(), Error> {
+    writer.write_all(&[u8::from(code)])?;
+    // NOTE: overlong messages are truncated to fit the `ErrorMessage` bound
+    let message = &message.as_bytes()[..message.len().min(MAX_ERROR_MESSAGE_LENGTH)];
+    let mut error_message = ErrorMessage::default();
+    for byte in message {
+        error_message.push(*byte);
+    }
+    let mut buffer = vec![];
+    error_message.serialize(&mut buffer)?;
+    write_payload(writer, &buffer)
+}
+
+/// A single chunk of a response, with its payload still SSZ-encoded so the caller can
+/// pick the type to decode based on the `context` bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseChunk {
+    pub code: ResponseCode,
+    pub context: Option<ForkDigest>,
+    pub payload: Vec<u8>,
+}
+
+impl ResponseChunk {
+    pub fn decode<T: SimpleSerialize>(&self) -> Result<T, Error> {
+        if self.code != ResponseCode::Success {
+            return Err(Error::ErrorResponse {
+                code: self.code,
+                message: String::from_utf8_lossy(&self.payload).into_owned(),
+            });
+        }
+        let response = T::deserialize(&self.payload)?;
+        Ok(response)
+    }
+}
+
+/// Read the next response chunk, returning `None` once the stream is exhausted.
+pub fn read_response_chunk<R: Read>(
+    reader: &mut R,
+    has_context_bytes: bool,
+) -> Result<Option<ResponseChunk>, Error> {
+    let mut code = [0u8; 1];
+    if reader.read(&mut code)? == 0 {
+        return Ok(None);
+    }
+    let code = ResponseCode::try_from(code[0])?;
+
+    if code != ResponseCode::Success {
+        let payload = read_payload(reader, MAX_ERROR_MESSAGE_LENGTH)?;
+        return Ok(Some(ResponseChunk {
+            code,
+            context: None,
+            payload,
+        }));
+    }
+
+    let context = if has_context_bytes {
+        let mut fork_digest = ForkDigest::default();
+        reader.read_exact(&mut fork_digest)?;
+        Some(fork_digest)
+    } else {
+        None
+    };
+    let payload = read_payload(reader, MAX_CHUNK_SIZE)?;
+    Ok(Some(ResponseChunk {
+        code,
+        context,
+        payload,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::networking::{MetaData, MetaDataV2};
+    use std::io::Cursor;
+
+    #[test]
+    fn test_varint_round_trip() {
+        for value in [0, 1, 127, 128, 300, MAX_CHUNK_SIZE as u64, u64::MAX] {
+            let mut buffer = vec![];
+            write_varint(&mut buffer, value).unwrap();
+            assert_eq!(read_varint(&mut Cursor::new(buffer)).unwrap(), value);
+        }
+
+        let mut buffer = vec![];
+        write_varint(&mut buffer, 300).unwrap();
+        assert_eq!(buffer, vec![0xac, 0x02]);
+
+        assert!(read_varint(&mut Cursor::new(vec![0x80, 0x00])).is_err());
+        assert!(read_varint(&mut Cursor::new(vec![0xff; 11])).is_err());
+    }
+
+    #[test]
+    fn test_request_round_trip() {
+        let status = Status {
+            fork_digest: [0x4a, 0x26, 0xc5, 0x8b],
+            finalized_epoch: 146,
+            head_slot: 4700,
+            ..Default::default()
+        };
+        let mut stream = vec![];
+        write_request(&mut stream, &status).unwrap();
+        // the length prefix is the size of the uncompressed SSZ encoding
+        assert_eq!(stream[0], 84);
+
+        let mut reader = Cursor::new(stream);
+        let recovered: Status = read_request(&mut reader).unwrap();
+        assert_eq!(recovered, status);
+
+        let request = BeaconBlocksByRangeRequest {
+            start_slot: 4096,
+            count: 64,
+            step: 1,
+        };
+        let mut stream = vec![];
+        write_request(&mut stream, &request).unwrap();
+        let recovered: BeaconBlocksByRangeRequest = read_request(&mut Cursor::new(stream)).unwrap();
+        assert_eq!(recovered, request);
+
+        let mut request = BeaconBlocksByRootRequest::default();
+        request.push(Root::default());
+        let mut stream = vec![];
+        write_request(&mut stream, &request).unwrap();
+        let recovered: BeaconBlocksByRootRequest = read_request(&mut Cursor::new(stream)).unwrap();
+        assert_eq!(recovered.len(), 1);
+
+        let goodbye: Goodbye = GOODBYE_CLIENT_SHUTDOWN;
+        let mut stream = vec![];
+        write_request(&mut stream, &goodbye).unwrap();
+        let recovered: Goodbye = read_request(&mut Cursor::new(stream)).unwrap();
+        assert_eq!(recovered, GOODBYE_CLIENT_SHUTDOWN);
+    }
+
+    #[test]
+    fn test_response_stream() {
+        let fork_digest = [0xaf, 0xca, 0xab, 0xa0];
+        let mut metadata = MetaDataV2 {
+            seq_number: 7,
+            ..Default::default()
+        };
+        metadata.syncnets.set(1, true);
+        let ping: Ping = 7;
+
+        let mut stream = vec![];
+        write_response_chunk(&mut stream, None, &ping).unwrap();
+        write_response_chunk(&mut stream, Some(fork_digest), &metadata).unwrap();
+        write_error_response(&mut stream, ResponseCode::ResourceUnavailable, "pruned").unwrap();
+
+        let mut reader = Cursor::new(stream);
+        let chunk = read_response_chunk(&mut reader, false).unwrap().unwrap();
+        assert_eq!(chunk.decode::<Ping>().unwrap(), ping);
+
+        let chunk = read_response_chunk(&mut reader, true).unwrap().unwrap();
+        assert_eq!(chunk.context, Some(fork_digest));
+        let recovered = chunk.decode::<MetaDataV2>().unwrap();
+        assert_eq!(recovered.seq_number, 7);
+        assert!(recovered.syncnets[1]);
+
+        let chunk = read_response_chunk(&mut reader, true).unwrap().unwrap();
+        assert_eq!(chunk.code, ResponseCode::ResourceUnavailable);
+        match chunk.decode::<Ping>() {
+            Err(Error::ErrorResponse { code, message }) => {
+                assert_eq!(code, ResponseCode::ResourceUnavailable);
+                assert_eq!(message, "pruned");
+            }
+            other => panic!("expected error response but got {other:?}"),
+        }
+
+        assert!(read_response_chunk(&mut reader, true).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_payload_bounds() {
+        let mut stream = vec![];
+        write_varint(&mut stream, (MAX_CHUNK_SIZE + 1) as u64).unwrap();
+        assert!(matches!(
+            read_payload(&mut Cursor::new(stream), MAX_CHUNK_SIZE),
+            Err(Error::PayloadTooLarge { .. })
+        ));
+
+        let mut stream = vec![];
+        write_payload(&mut stream, &[1u8; 64]).unwrap();
+        stream.truncate(stream.len() - 8);
+        assert!(read_payload(&mut Cursor::new(stream), MAX_CHUNK_SIZE).is_err());
+    }
+
+    #[test]
+    fn test_metadata_encodings() {
+        let mut metadata = MetaDataV2 {
+            seq_number: 3,
+            ..Default::default()
+        };
+        metadata.attnets.set(5, true);
+        metadata.syncnets.set(2, true);
+        let mut encoding = vec![];
+        metadata.serialize(&mut encoding).unwrap();
+        // `seq_number`, then 64 attestation subnet bits and 4 sync committee subnet bits
+        assert_eq!(encoding.len(), 8 + 8 + 1);
+        assert_eq!(encoding[encoding.len() - 1], 0b0100);
+
+        // the v1 encoding is the v2 encoding without `syncnets`
+        let metadata_v1 = MetaData {
+            seq_number: metadata.seq_number,
+            attnets: metadata.attnets.clone(),
+        };
+        let mut encoding_v1 = vec![];
+        metadata_v1.serialize(&mut encoding_v1).unwrap();
+        assert_eq!(encoding_v1, encoding[..16]);
+    }
+}
@@ -0,0 +1,124 @@
+//! The attestation subnets a node subscribes to for the long term, derived from its
+//! node id so that peers can verify the subscriptions advertised in its ENR.
+use crate::crypto::hash;
+use crate::networking::{
+    ATTESTATION_SUBNET_COUNT, ATTESTATION_SUBNET_PREFIX_BITS, EPOCHS_PER_SUBNET_SUBSCRIPTION,
+    NODE_ID_BITS, SUBNETS_PER_NODE,
+};
+use crate::phase0::compute_shuffled_index;
+use crate::primitives::Epoch;
+use crate::state_transition::{Context, Error};
+use enr::NodeId;
+use ssz_rs::prelude::*;
+
+pub fn compute_subscribed_subnet(
+    node_id: &NodeId,
+    epoch: Epoch,
+    index: usize,
+    context: &Context,
+) -> Result<u64, Error> {
+    // NOTE: the node id is a big-endian `uint256`
+    let node_id = node_id.raw();
+    let high_bits = u64::from_be_bytes(node_id[..8].try_into().expect("correct input"));
+    let node_id_prefix = high_bits >> (64 - ATTESTATION_SUBNET_PREFIX_BITS);
+    let node_offset = node_id[NODE_ID_BITS / 8 - 1] as u64 % EPOCHS_PER_SUBNET_SUBSCRIPTION;
+    let permutation_seed =
+        hash(((epoch + node_offset) / EPOCHS_PER_SUBNET_SUBSCRIPTION).to_le_bytes());
+    let permutated_prefix = compute_shuffled_index(
+        node_id_prefix as usize,
+        1 << ATTESTATION_SUBNET_PREFIX_BITS,
+        &permutation_seed,
+        context,
+    )?;
+    Ok(((permutated_prefix + index) % ATTESTATION_SUBNET_COUNT) as u64)
+}
+
+/// Return the attestation subnets the node with `node_id` must subscribe to during `epoch`,
+/// in the form advertised by `MetaData::attnets`.
+pub fn compute_subscribed_subnets(
+    node_id: &NodeId,
+    epoch: Epoch,
+    context: &Context,
+) -> Result<Bitvector<ATTESTATION_SUBNET_COUNT>, Error> {
+    let mut subnets = Bitvector::default();
+    for index in 0..SUBNETS_PER_NODE {
+        let subnet_id = compute_subscribed_subnet(node_id, epoch, index, context)?;
+        subnets.set(subnet_id as usize, true);
+    }
+    Ok(subnets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node_id(first_byte: u8, last_byte: u8) -> NodeId {
+        let mut raw = [0u8; 32];
+        raw[0] = first_byte;
+        raw[31] = last_byte;
+        NodeId::new(&raw)
+    }
+
+    #[test]
+    fn test_subscribed_subnets_are_consecutive() {
+        let context = Context::for_mainnet();
+        let node_id = node_id(0xab, 0x12);
+        let first = compute_subscribed_subnet(&node_id, 0, 0, &context).unwrap();
+        let second = compute_subscribed_subnet(&node_id, 0, 1, &context).unwrap();
+        assert_eq!(second, (first + 1) % ATTESTATION_SUBNET_COUNT as u64);
+
+        let subnets = compute_subscribed_subnets(&node_id, 0, &context).unwrap();
+        let subscribed = (0..ATTESTATION_SUBNET_COUNT)
+            .filter(|&i| subnets[i])
+            .map(|i| i as u64)
+            .collect::<Vec<_>>();
+        assert_eq!(subscribed.len(), SUBNETS_PER_NODE);
+        assert!(subscribed.contains(&first));
+        assert!(subscribed.contains(&second));
+    }
+
+    #[test]
+    fn test_subscription_rotates_at_node_offset() {
+        let context = Context::for_mainnet();
+        let node_offset = 0x12;
+        let node_id = node_id(0xab, node_offset as u8);
+        // the subscription period of this node ends `node_offset` epochs early
+        let boundary = EPOCHS_PER_SUBNET_SUBSCRIPTION - node_offset;
+        let subnets = compute_subscribed_subnets(&node_id, 0, &context).unwrap();
+        assert_eq!(
+            compute_subscribed_subnets(&node_id, boundary - 1, &context).unwrap(),
+            subnets
+        );
+        let next_subnets = compute_subscribed_subnets(&node_id, boundary, &context).unwrap();
+        assert_ne!(next_subnets, subnets);
+        let next_period_subnets = compute_subscribed_subnets(
+            &node_id,
+            boundary + EPOCHS_PER_SUBNET_SUBSCRIPTION - 1,
+            &context,
+        )
+        .unwrap();
+        assert_eq!(next_subnets, next_period_subnets);
+    }
+
+    #[test]
+    fn test_known_subscribed_subnets() {
+        let context = Context::for_mainnet();
+        // node id prefix 42 and node offset 18, rotating at epochs 238 and 494
+        let node_id = node_id(0xab, 0x12);
+        let expected = [(0, [3, 4]), (237, [3, 4]), (238, [0, 1]), (494, [58, 59])];
+        for (epoch, subnet_ids) in expected {
+            for (index, subnet_id) in subnet_ids.into_iter().enumerate() {
+                assert_eq!(
+                    compute_subscribed_subnet(&node_id, epoch, index, &context).unwrap(),
+                    subnet_id
+                );
+            }
+            let subnets = compute_subscribed_subnets(&node_id, epoch, &context).unwrap();
+            let subscribed = (0..ATTESTATION_SUBNET_COUNT)
+                .filter(|&i| subnets[i])
+                .map(|i| i as u64)
+                .collect::<Vec<_>>();
+            assert_eq!(subscribed, subnet_ids);
+        }
+    }
+}
@@ -0,0 +1,786 @@
+//! A pool of operations awaiting inclusion in a block.
+//!
+//! Attestations and sync committee contributions are aggregated as they arrive
+//! and attestations are selected for a block by (greedy) maximum coverage of the
+//! validators they reward.
+use crate::altair::{
+    get_sync_subcommittee_size, SyncAggregate, SyncCommitteeContribution,
+    SYNC_COMMITTEE_SUBNET_COUNT,
+};
+use crate::crypto::{self, aggregate};
+use crate::phase0::{
+    get_attesting_indices, get_current_epoch, get_deposits_for_block, get_previous_epoch,
+    get_total_active_balance, is_slashable_validator, process_attester_slashing,
+    process_proposer_slashing, process_voluntary_exit, Attestation, AttesterSlashing, BeaconState,
+    BlockOperations, Eth1Cache, ProposerSlashing, SignedVoluntaryExit, BASE_REWARDS_PER_EPOCH,
+};
+use crate::primitives::{BlsSignature, Epoch, Gwei, Root, Slot, ValidatorIndex, FAR_FUTURE_EPOCH};
+use crate::state_transition::{self, Context};
+use integer_sqrt::IntegerSquareRoot;
+use ssz_rs::prelude::*;
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("{0}")]
+    Merkleization(#[from] MerkleizationError),
+    #[error("{0}")]
+    Crypto(#[from] crypto::Error),
+    #[error("{0}")]
+    StateTransition(#[from] state_transition::Error),
+    #[error("sync committee of size {sync_committee_size} does not have subcommittees of size {sync_subcommittee_size}")]
+    SyncSubcommitteeSizeMismatch {
+        sync_committee_size: usize,
+        sync_subcommittee_size: usize,
+    },
+}
+
+/// An operation carrying a participation bitfield and the aggregate signature of the
+/// participants, which can be combined with other operations over the same data as long
+/// as their participants do not overlap.
+trait Aggregate {
+    fn participation(&self) -> Vec<bool>;
+
+    fn merge(&mut self, other: &Self) -> Result<(), Error>;
+}
+
+impl<const MAX_VALIDATORS_PER_COMMITTEE: usize> Aggregate
+    for Attestation<MAX_VALIDATORS_PER_COMMITTEE>
+{
+    fn participation(&self) -> Vec<bool> {
+        (0..self.aggregation_bits.len())
+            .map(|i| self.aggregation_bits[i])
+            .collect()
+    }
+
+    fn merge(&mut self, other: &Self) -> Result<(), Error> {
+        for (i, participated) in other.participation().into_iter().enumerate() {
+            if participated {
+                self.aggregation_bits.set(i, true);
+            }
+        }
+        self.signature = aggregate(&[self.signature.clone(), other.signature.clone()])?;
+        Ok(())
+    }
+}
+
+impl<const SYNC_SUBCOMMITTEE_SIZE: usize> Aggregate
+    for SyncCommitteeContribution<SYNC_SUBCOMMITTEE_SIZE>
+{
+    fn participation(&self) -> Vec<bool> {
+        (0..self.aggregation_bits.len())
+            .map(|i| self.aggregation_bits[i])
+            .collect()
+    }
+
+    fn merge(&mut self, other: &Self) -> Result<(), Error> {
+        for (i, participated) in other.participation().into_iter().enumerate() {
+            if participated {
+                self.aggregation_bits.set(i, true);
+            }
+        }
+        self.signature = aggregate(&[self.signature.clone(), other.signature.clone()])?;
+        Ok(())
+    }
+}
+
+fn is_subset(a: &[bool], b: &[bool]) -> bool {
+    a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| !a || *b)
+}
+
+fn is_disjoint(a: &[bool], b: &[bool]) -> bool {
+    a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| !(a & b))
+}
+
+fn insert_aggregate<T: Aggregate>(aggregates: &mut Vec<T>, item: T) -> Result<(), Error> {
+    let participation = item.participation();
+    if aggregates
+        .iter()
+        .any(|existing| is_subset(&participation, &existing.participation()))
+    {
+        return Ok(());
+    }
+    for existing in aggregates.iter_mut() {
+        if is_disjoint(&participation, &existing.participation()) {
+            return existing.merge(&item);
+        }
+    }
+    aggregates.push(item);
+    Ok(())
+}
+
+type ContributionKey = (Slot, Root, u64);
+
+#[derive(Default, Debug)]
+pub struct OperationPool<
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_SUBCOMMITTEE_SIZE: usize,
+> {
+    // aggregates keyed by the root of their `AttestationData`
+    attestations: HashMap<Root, Vec<Attestation<MAX_VALIDATORS_PER_COMMITTEE>>>,
+    // keyed by the index of the proposer to be slashed
+    proposer_slashings: HashMap<ValidatorIndex, ProposerSlashing>,
+    // keyed by the root of the slashing
+    attester_slashings: HashMap<Root, AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>>,
+    // keyed by the index of the exiting validator
+    voluntary_exits: HashMap<ValidatorIndex, SignedVoluntaryExit>,
+    sync_contributions:
+        HashMap<ContributionKey, Vec<SyncCommitteeContribution<SYNC_SUBCOMMITTEE_SIZE>>>,
+}
+
+impl<const MAX_VALIDATORS_PER_COMMITTEE: usize, const SYNC_SUBCOMMITTEE_SIZE: usize>
+    OperationPool<MAX_VALIDATORS_PER_COMMITTEE, SYNC_SUBCOMMITTEE_SIZE>
+{
+    pub fn insert_attestation(
+        &mut self,
+        attestation: Attestation<MAX_VALIDATORS_PER_COMMITTEE>,
+    ) -> Result<(), Error> {
+        let root = attestation.data.clone().hash_tree_root()?;
+        let aggregates = self.attestations.entry(root).or_default();
+        insert_aggregate(aggregates, attestation)
+    }
+
+    pub fn insert_proposer_slashing(&mut self, proposer_slashing: ProposerSlashing) {
+        let proposer_index = proposer_slashing.signed_header_1.message.proposer_index;
+        self.proposer_slashings
+            .entry(proposer_index)
+            .or_insert(proposer_slashing);
+    }
+
+    pub fn insert_attester_slashing(
+        &mut self,
+        mut attester_slashing: AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>,
+    ) -> Result<(), Error> {
+        let root = attester_slashing.hash_tree_root()?;
+        self.attester_slashings
+            .entry(root)
+            .or_insert(attester_slashing);
+        Ok(())
+    }
+
+    pub fn insert_voluntary_exit(&mut self, voluntary_exit: SignedVoluntaryExit) {
+        let validator_index = voluntary_exit.message.validator_index;
+        self.voluntary_exits
+            .entry(validator_index)
+            .or_insert(voluntary_exit);
+    }
+
+    pub fn insert_sync_contribution(
+        &mut self,
+        contribution: SyncCommitteeContribution<SYNC_SUBCOMMITTEE_SIZE>,
+    ) -> Result<(), Error> {
+        let key = (
+            contribution.slot,
+            contribution.beacon_block_root,
+            contribution.subcommittee_index,
+        );
+        let aggregates = self.sync_contributions.entry(key).or_default();
+        insert_aggregate(aggregates, contribution)
+    }
+
+    pub fn attestations(&self) -> impl Iterator<Item = &Attestation<MAX_VALIDATORS_PER_COMMITTEE>> {
+        self.attestations.values().flatten()
+    }
+
+    /// Drop all operations that can no longer be included in a block built on `state`.
+    pub fn prune<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+    >(
+        &mut self,
+        state: &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+        >,
+        context: &Context,
+    ) {
+        let current_epoch = get_current_epoch(state, context);
+
+        self.attestations.retain(|_, aggregates| {
+            aggregates
+                .first()
+                .map(|a| state.slot <= a.data.slot + context.slots_per_epoch)
+                .unwrap_or_default()
+        });
+
+        // NOTE: slashings and exits are rechecked against a scratch copy of the state
+        // so that operations made redundant by earlier ones are also dropped
+        let mut scratch_state = state.clone();
+
+        self.proposer_slashings.retain(|_, proposer_slashing| {
+            process_proposer_slashing(&mut scratch_state, proposer_slashing, context).is_ok()
+        });
+
+        self.attester_slashings.retain(|_, attester_slashing| {
+            process_attester_slashing(&mut scratch_state, attester_slashing, context).is_ok()
+        });
+
+        self.voluntary_exits.retain(|&index, voluntary_exit| {
+            let (activation_epoch, exit_epoch) = match scratch_state.validators.get(index) {
+                Some(validator) => (validator.activation_epoch, validator.exit_epoch),
+                None => return false,
+            };
+            if exit_epoch != FAR_FUTURE_EPOCH {
+                return false;
+            }
+            // an exit that is not valid yet may become valid in a later epoch
+            let is_premature = voluntary_exit.message.epoch > current_epoch
+                || current_epoch < activation_epoch.saturating_add(context.shard_committee_period);
+            is_premature
+                || process_voluntary_exit(&mut scratch_state, voluntary_exit, context).is_ok()
+        });
+
+        // a contribution for `slot` is included in the block at `slot + 1`
+        self.sync_contributions
+            .retain(|(slot, _, _), _| state.slot <= *slot + 1);
+    }
+
+    /// Select the attestations to include in a block built on `state`.
+    ///
+    /// Attestations are chosen greedily by the total base reward of the validators
+    /// they newly cover, where validators already credited in the `state` are
+    /// considered covered. This matches weighting by `get_base_reward` while only
+    /// computing the total active balance once.
+    pub fn get_attestations_for_block<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+    >(
+        &self,
+        state: &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+        >,
+        context: &Context,
+    ) -> Result<Vec<Attestation<MAX_VALIDATORS_PER_COMMITTEE>>, Error> {
+        let previous_epoch = get_previous_epoch(state, context);
+        let current_epoch = get_current_epoch(state, context);
+
+        let mut covered: HashSet<(Epoch, ValidatorIndex)> = HashSet::new();
+        for (epoch, pending_attestations) in [
+            (previous_epoch, &state.previous_epoch_attestations),
+            (current_epoch, &state.current_epoch_attestations),
+        ] {
+            for a in pending_attestations.iter() {
+                let indices = get_attesting_indices(state, &a.data, &a.aggregation_bits, context)?;
+                covered.extend(indices.into_iter().map(|i| (epoch, i)));
+            }
+        }
+
+        let mut candidates = vec![];
+        for attestation in self.attestations() {
+            let data = &attestation.data;
+            let is_timely = data.slot + context.min_attestation_inclusion_delay <= state.slot
+                && state.slot <= data.slot + context.slots_per_epoch;
+            if !is_timely {
+                continue;
+            }
+            let expected_source = if data.target.epoch == current_epoch {
+                &state.current_justified_checkpoint
+            } else if data.target.epoch == previous_epoch {
+                &state.previous_justified_checkpoint
+            } else {
+                continue;
+            };
+            if data.source != *expected_source {
+                continue;
+            }
+            // NOTE: skip attestations that do not match the committees in `state`
+            if let Ok(indices) =
+                get_attesting_indices(state, data, &attestation.aggregation_bits, context)
+            {
+                candidates.push((attestation, data.target.epoch, indices));
+            }
+        }
+
+        let total_active_balance = get_total_active_balance(state, context)?;
+        let base_reward = |index: ValidatorIndex| -> Gwei {
+            let validator = &state.validators[index];
+            if validator.slashed {
+                return 0;
+            }
+            validator.effective_balance * context.base_reward_factor
+                / total_active_balance.integer_sqrt()
+                / BASE_REWARDS_PER_EPOCH
+        };
+
+        let mut selected = vec![];
+        while selected.len() < context.max_attestations {
+            let best = candidates
+                .iter()
+                .enumerate()
+                .map(|(i, (_, epoch, indices))| {
+                    let weight = indices
+                        .iter()
+                        .filter(|&&index| !covered.contains(&(*epoch, index)))
+                        .map(|&index| base_reward(index))
+                        .sum::<Gwei>();
+                    (i, weight)
+                })
+                .max_by_key(|(_, weight)| *weight);
+            match best {
+                Some((i, weight)) if weight > 0 => {
+                    let (attestation, epoch, indices) = candidates.swap_remove(i);
+                    covered.extend(indices.into_iter().map(|index| (epoch, index)));
+                    selected.push(attestation.clone());
+                }
+                _ => break,
+            }
+        }
+
+        Ok(selected)
+    }
+
+    /// Gather the operations in the pool for a block built on `state`.
+    ///
+    /// The pending deposits are taken from the `eth1_cache`. The returned operations
+    /// are suitable candidates for `produce_block`, which performs the full validation
+    /// against the state.
+    pub fn get_block_operations<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+    >(
+        &self,
+        state: &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+        >,
+        eth1_cache: &impl Eth1Cache,
+        context: &Context,
+    ) -> Result<BlockOperations<MAX_VALIDATORS_PER_COMMITTEE>, Error> {
+        let current_epoch = get_current_epoch(state, context);
+        let is_slashable = |index: ValidatorIndex| {
+            state
+                .validators
+                .get(index)
+                .map(|v| is_slashable_validator(v, current_epoch))
+                .unwrap_or_default()
+        };
+
+        let proposer_slashings = self
+            .proposer_slashings
+            .iter()
+            .filter(|(&index, _)| is_slashable(index))
+            .map(|(_, proposer_slashing)| proposer_slashing.clone())
+            .take(context.max_proposer_slashings)
+            .collect();
+
+        let attester_slashings = self
+            .attester_slashings
+            .values()
+            .filter(|attester_slashing| {
+                slashable_indices(attester_slashing)
+                    .into_iter()
+                    .any(is_slashable)
+            })
+            .cloned()
+            .take(context.max_attester_slashings)
+            .collect();
+
+        let voluntary_exits = self
+            .voluntary_exits
+            .iter()
+            .filter(|(&index, _)| {
+                state
+                    .validators
+                    .get(index)
+                    .map(|v| v.exit_epoch == FAR_FUTURE_EPOCH)
+                    .unwrap_or_default()
+            })
+            .map(|(_, voluntary_exit)| voluntary_exit.clone())
+            .take(context.max_voluntary_exits)
+            .collect();
+
+        Ok(BlockOperations {
+            proposer_slashings,
+            attester_slashings,
+            attestations: self.get_attestations_for_block(state, context)?,
+            deposits: get_deposits_for_block(state, eth1_cache, context)?,
+            voluntary_exits,
+        })
+    }
+
+    /// Build the `SyncAggregate` for a block at `slot + 1` from the best
+    /// contribution of each sync subcommittee for `beacon_block_root` at `slot`.
+    ///
+    /// The `SYNC_COMMITTEE_SIZE` must be the one the subcommittees of the pool are
+    /// derived from.
+    pub fn get_sync_aggregate<const SYNC_COMMITTEE_SIZE: usize>(
+        &self,
+        slot: Slot,
+        beacon_block_root: Root,
+    ) -> Result<SyncAggregate<SYNC_COMMITTEE_SIZE>, Error> {
+        if get_sync_subcommittee_size(SYNC_COMMITTEE_SIZE) != SYNC_SUBCOMMITTEE_SIZE {
+            return Err(Error::SyncSubcommitteeSizeMismatch {
+                sync_committee_size: SYNC_COMMITTEE_SIZE,
+                sync_subcommittee_size: SYNC_SUBCOMMITTEE_SIZE,
+            });
+        }
+        let mut sync_aggregate = SyncAggregate::<SYNC_COMMITTEE_SIZE>::default();
+        let mut signatures = vec![];
+        for subcommittee_index in 0..SYNC_COMMITTEE_SUBNET_COUNT {
+            let key = (slot, beacon_block_root, subcommittee_index as u64);
+            let best = self.sync_contributions.get(&key).and_then(|contributions| {
+                contributions.iter().max_by_key(|contribution| {
+                    contribution
+                        .participation()
+                        .into_iter()
+                        .filter(|&participated| participated)
+                        .count()
+                })
+            });
+            if let Some(contribution) = best {
+                for (i, participated) in contribution.participation().into_iter().enumerate() {
+                    if participated {
+                        let index = subcommittee_index * SYNC_SUBCOMMITTEE_SIZE + i;
+                        sync_aggregate.sync_committee_bits.set(index, true);
+                    }
+                }
+                signatures.push(contribution.signature.clone());
+            }
+        }
+        // NOTE: the default signature is the point at infinity,
+        // as required for an empty aggregate
+        if !signatures.is_empty() {
+            sync_aggregate.sync_committee_signature = aggregate(&signatures)?;
+        }
+        Ok(sync_aggregate)
+    }
+}
+
+fn slashable_indices<const MAX_VALIDATORS_PER_COMMITTEE: usize>(
+    attester_slashing: &AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>,
+) -> Vec<ValidatorIndex> {
+    let indices_1: HashSet<ValidatorIndex> = HashSet::from_iter(
+        attester_slashing
+            .attestation_1
+            .attesting_indices
+            .iter()
+            .cloned(),
+    );
+    attester_slashing
+        .attestation_2
+        .attesting_indices
+        .iter()
+        .filter(|i| indices_1.contains(i))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::SecretKey;
+    use crate::interop::interop_secret_key;
+    use crate::interop::phase0::create_mainnet_genesis_state;
+    use crate::phase0::{
+        get_beacon_committee, get_domain, mainnet, process_slots, sign_with_domain,
+        AttestationData, BeaconBlockHeader, SignedBeaconBlockHeader, VoluntaryExit,
+    };
+    use crate::primitives::DomainType;
+    use rand::prelude::*;
+
+    const COMMITTEE_SIZE: usize = 4;
+
+    type Pool = OperationPool<2048, 128>;
+
+    fn attestation_with(participants: &[usize], data: &AttestationData) -> Attestation<2048> {
+        let mut rng = thread_rng();
+        let sk = SecretKey::random(&mut rng).unwrap();
+        let mut aggregation_bits = Bitlist::default();
+        for i in 0..COMMITTEE_SIZE {
+            aggregation_bits.push(participants.contains(&i));
+        }
+        Attestation {
+            aggregation_bits,
+            data: data.clone(),
+            signature: sk.sign(b"attestation"),
+        }
+    }
+
+    #[test]
+    fn test_aggregates_disjoint_attestations() {
+        let mut pool = Pool::default();
+        let data = AttestationData::default();
+        pool.insert_attestation(attestation_with(&[0], &data))
+            .unwrap();
+        pool.insert_attestation(attestation_with(&[1, 2], &data))
+            .unwrap();
+
+        let attestations = pool.attestations().collect::<Vec<_>>();
+        assert_eq!(attestations.len(), 1);
+        assert_eq!(
+            attestations[0].participation(),
+            vec![true, true, true, false]
+        );
+    }
+
+    #[test]
+    fn test_keeps_overlapping_attestations_apart() {
+        let mut pool = Pool::default();
+        let data = AttestationData::default();
+        pool.insert_attestation(attestation_with(&[0, 1], &data))
+            .unwrap();
+        pool.insert_attestation(attestation_with(&[1, 2], &data))
+            .unwrap();
+        // covered by an existing aggregate
+        pool.insert_attestation(attestation_with(&[0], &data))
+            .unwrap();
+
+        assert_eq!(pool.attestations().count(), 2);
+    }
+
+    #[test]
+    fn test_separates_attestations_by_data() {
+        let mut pool = Pool::default();
+        let data = AttestationData::default();
+        let other_data = AttestationData {
+            slot: 1,
+            ..Default::default()
+        };
+        pool.insert_attestation(attestation_with(&[0], &data))
+            .unwrap();
+        pool.insert_attestation(attestation_with(&[1], &other_data))
+            .unwrap();
+
+        assert_eq!(pool.attestations().count(), 2);
+    }
+
+    fn attestation_for_committee(
+        committee: &[ValidatorIndex],
+        participants: &[ValidatorIndex],
+        data: &AttestationData,
+    ) -> Attestation<2048> {
+        let mut aggregation_bits = Bitlist::default();
+        for index in committee {
+            aggregation_bits.push(participants.contains(index));
+        }
+        Attestation {
+            aggregation_bits,
+            data: data.clone(),
+            ..Default::default()
+        }
+    }
+
+    fn proposer_slashing(
+        state: &mainnet::BeaconState,
+        proposer_index: ValidatorIndex,
+        context: &Context,
+    ) -> ProposerSlashing {
+        let signing_key = interop_secret_key(proposer_index).unwrap();
+        let domain = get_domain(state, DomainType::BeaconProposer, None, context).unwrap();
+        let [signed_header_1, signed_header_2] = [0u8, 1].map(|byte| {
+            let mut header = BeaconBlockHeader {
+                proposer_index,
+                body_root: Node::from_bytes([byte; 32]),
+                ..Default::default()
+            };
+            let signature = sign_with_domain(&mut header, &signing_key, domain).unwrap();
+            SignedBeaconBlockHeader {
+                message: header,
+                signature,
+            }
+        });
+        ProposerSlashing {
+            signed_header_1,
+            signed_header_2,
+        }
+    }
+
+    #[test]
+    fn test_attestations_for_block_by_max_cover() {
+        let mut context = Context::for_mainnet();
+        let mut state = create_mainnet_genesis_state(256, &context);
+        process_slots(&mut state, 2, &context).unwrap();
+        let committee = get_beacon_committee(&state, 1, 0, &context).unwrap();
+        assert_eq!(committee.len(), 8);
+
+        // attestations over distinct data are not aggregated in the pool
+        let data_with_root = |byte: u8| AttestationData {
+            slot: 1,
+            beacon_block_root: Node::from_bytes([byte; 32]),
+            ..Default::default()
+        };
+        let largest = attestation_for_committee(&committee, &committee[..5], &data_with_root(1));
+        let covered = attestation_for_committee(&committee, &committee[..3], &data_with_root(2));
+        let disjoint = attestation_for_committee(&committee, &committee[5..7], &data_with_root(3));
+        let mut pool = Pool::default();
+        for attestation in [&covered, &disjoint, &largest] {
+            pool.insert_attestation(attestation.clone()).unwrap();
+        }
+
+        // the attestation adding no new validators is left out
+        let selected = pool.get_attestations_for_block(&state, &context).unwrap();
+        assert_eq!(selected.len(), 2);
+        assert_eq!(selected[0].data, largest.data);
+        assert_eq!(selected[1].data, disjoint.data);
+
+        context.max_attestations = 1;
+        let selected = pool.get_attestations_for_block(&state, &context).unwrap();
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].data, largest.data);
+    }
+
+    #[test]
+    fn test_prune() {
+        let context = Context::for_mainnet();
+        let mut state = create_mainnet_genesis_state(64, &context);
+
+        let mut pool = Pool::default();
+        pool.insert_proposer_slashing(proposer_slashing(&state, 3, &context));
+        pool.insert_proposer_slashing(proposer_slashing(&state, 5, &context));
+        for validator_index in [7, 8] {
+            pool.insert_voluntary_exit(SignedVoluntaryExit {
+                message: VoluntaryExit {
+                    epoch: 0,
+                    validator_index,
+                },
+                ..Default::default()
+            });
+        }
+        for slot in [0, 10] {
+            let data = AttestationData {
+                slot,
+                ..Default::default()
+            };
+            pool.insert_attestation(attestation_with(&[0], &data))
+                .unwrap();
+        }
+
+        state.slot = 40;
+        state.validators[5].slashed = true;
+        state.validators[7].exit_epoch = 10;
+        pool.prune(&state, &context);
+
+        assert_eq!(pool.proposer_slashings.keys().collect::<Vec<_>>(), vec![&3]);
+        // the exit of validator 8 is not valid yet but may be included later
+        assert_eq!(pool.voluntary_exits.keys().collect::<Vec<_>>(), vec![&8]);
+        let attestations = pool.attestations().collect::<Vec<_>>();
+        assert_eq!(attestations.len(), 1);
+        assert_eq!(attestations[0].data.slot, 10);
+    }
+
+    #[test]
+    fn test_deduplicates_attester_slashings() {
+        let mut pool = Pool::default();
+        let attester_slashing = AttesterSlashing::<2048>::default();
+        pool.insert_attester_slashing(attester_slashing.clone())
+            .unwrap();
+        pool.insert_attester_slashing(attester_slashing).unwrap();
+        let mut other_attester_slashing = AttesterSlashing::<2048>::default();
+        other_attester_slashing.attestation_1.data.slot = 1;
+        pool.insert_attester_slashing(other_attester_slashing)
+            .unwrap();
+
+        assert_eq!(pool.attester_slashings.len(), 2);
+    }
+
+    fn contribution_with(
+        subcommittee_index: u64,
+        participants: &[usize],
+        secret_key: &SecretKey,
+    ) -> SyncCommitteeContribution<128> {
+        let mut aggregation_bits = Bitvector::default();
+        for &i in participants {
+            aggregation_bits.set(i, true);
+        }
+        SyncCommitteeContribution {
+            slot: 1,
+            beacon_block_root: Root::default(),
+            subcommittee_index,
+            aggregation_bits,
+            signature: secret_key.sign(b"contribution"),
+        }
+    }
+
+    #[test]
+    fn test_sync_aggregate_from_contributions() {
+        let mut rng = thread_rng();
+        let secret_keys = (0..3)
+            .map(|_| SecretKey::random(&mut rng).unwrap())
+            .collect::<Vec<_>>();
+        let first = contribution_with(0, &[0, 1], &secret_keys[0]);
+        let second = contribution_with(0, &[5], &secret_keys[1]);
+        let third = contribution_with(2, &[127], &secret_keys[2]);
+        let mut pool = Pool::default();
+        for contribution in [&first, &second, &third] {
+            pool.insert_sync_contribution(contribution.clone()).unwrap();
+        }
+        // covered by an existing contribution
+        pool.insert_sync_contribution(contribution_with(0, &[1], &secret_keys[0]))
+            .unwrap();
+
+        // disjoint contributions to the same subcommittee are aggregated
+        let contributions = &pool.sync_contributions[&(1, Root::default(), 0)];
+        assert_eq!(contributions.len(), 1);
+        assert_eq!(
+            contributions[0]
+                .participation()
+                .into_iter()
+                .filter(|&participated| participated)
+                .count(),
+            3
+        );
+
+        let sync_aggregate = pool.get_sync_aggregate::<512>(1, Root::default()).unwrap();
+        let participants = (0..512)
+            .filter(|&i| sync_aggregate.sync_committee_bits[i])
+            .collect::<Vec<_>>();
+        assert_eq!(participants, vec![0, 1, 5, 2 * 128 + 127]);
+        let expected_signature =
+            aggregate(&[first.signature, second.signature, third.signature]).unwrap();
+        assert_eq!(sync_aggregate.sync_committee_signature, expected_signature);
+
+        // no contributions for another block
+        let sync_aggregate = pool
+            .get_sync_aggregate::<512>(1, Node::from_bytes([1; 32]))
+            .unwrap();
+        assert!((0..512).all(|i| !sync_aggregate.sync_committee_bits[i]));
+        assert_eq!(
+            sync_aggregate.sync_committee_signature,
+            BlsSignature::default()
+        );
+
+        // the subcommittees of the pool do not divide a committee of 32
+        assert!(matches!(
+            pool.get_sync_aggregate::<32>(1, Root::default()),
+            Err(Error::SyncSubcommitteeSizeMismatch {
+                sync_committee_size: 32,
+                sync_subcommittee_size: 128,
+            })
+        ));
+    }
+}
@@ -0,0 +1,333 @@
+use crate::phase0 as spec;
+
+use crate::crypto::{hash, SecretKey};
+use crate::primitives::{
+    BlsPublicKey, BlsSignature, CommitteeIndex, DomainType, Slot, ValidatorIndex,
+};
+use crate::state_transition::{Context, Error};
+use spec::{
+    compute_epoch_at_slot, get_beacon_committee, get_domain, sign_with_domain, verify_signed_data,
+    AggregateAndProof, Attestation, BeaconState, SignedAggregateAndProof,
+    TARGET_AGGREGATORS_PER_COMMITTEE,
+};
+
+pub fn get_slot_signature<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    mut slot: Slot,
+    signing_key: &SecretKey,
+    context: &Context,
+) -> Result<BlsSignature, Error> {
+    let epoch = compute_epoch_at_slot(slot, context);
+    let domain = get_domain(state, DomainType::SelectionProof, Some(epoch), context)?;
+    sign_with_domain(&mut slot, signing_key, domain)
+}
+
+pub fn verify_slot_signature<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    mut slot: Slot,
+    slot_signature: &BlsSignature,
+    public_key: &BlsPublicKey,
+    context: &Context,
+) -> Result<(), Error> {
+    let epoch = compute_epoch_at_slot(slot, context);
+    let domain = get_domain(state, DomainType::SelectionProof, Some(epoch), context)?;
+    verify_signed_data(&mut slot, slot_signature, public_key, domain)
+}
+
+pub(crate) fn is_selected(signature: &BlsSignature, modulo: u64) -> bool {
+    let digest = hash(signature.as_bytes());
+    let value = u64::from_le_bytes(digest.as_ref()[..8].try_into().unwrap());
+    value % modulo == 0
+}
+
+pub fn is_aggregator<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    slot: Slot,
+    index: CommitteeIndex,
+    slot_signature: &BlsSignature,
+    context: &Context,
+) -> Result<bool, Error> {
+    let committee = get_beacon_committee(state, slot, index, context)?;
+    let modulo = usize::max(1, committee.len() / TARGET_AGGREGATORS_PER_COMMITTEE);
+    Ok(is_selected(slot_signature, modulo as u64))
+}
+
+pub fn get_aggregate_and_proof<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    aggregator_index: ValidatorIndex,
+    aggregate: Attestation<MAX_VALIDATORS_PER_COMMITTEE>,
+    signing_key: &SecretKey,
+    context: &Context,
+) -> Result<AggregateAndProof<MAX_VALIDATORS_PER_COMMITTEE>, Error> {
+    let selection_proof = get_slot_signature(state, aggregate.data.slot, signing_key, context)?;
+    Ok(AggregateAndProof {
+        aggregator_index,
+        aggregate,
+        selection_proof,
+    })
+}
+
+pub fn get_aggregate_and_proof_signature<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    aggregate_and_proof: &mut AggregateAndProof<MAX_VALIDATORS_PER_COMMITTEE>,
+    signing_key: &SecretKey,
+    context: &Context,
+) -> Result<BlsSignature, Error> {
+    let epoch = compute_epoch_at_slot(aggregate_and_proof.aggregate.data.slot, context);
+    let domain = get_domain(state, DomainType::AggregateAndProof, Some(epoch), context)?;
+    sign_with_domain(aggregate_and_proof, signing_key, domain)
+}
+
+/// Verify both the selection proof and the signature of the aggregator
+/// in `signed_aggregate_and_proof`.
+pub fn verify_aggregate_and_proof_signature<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    signed_aggregate_and_proof: &mut SignedAggregateAndProof<MAX_VALIDATORS_PER_COMMITTEE>,
+    context: &Context,
+) -> Result<(), Error> {
+    let aggregate_and_proof = &mut signed_aggregate_and_proof.message;
+    let aggregator_index = aggregate_and_proof.aggregator_index;
+    let aggregator = state
+        .validators
+        .get(aggregator_index)
+        .ok_or(Error::OutOfBounds {
+            requested: aggregator_index,
+            bound: state.validators.len(),
+        })?;
+
+    let slot = aggregate_and_proof.aggregate.data.slot;
+    verify_slot_signature(
+        state,
+        slot,
+        &aggregate_and_proof.selection_proof,
+        &aggregator.public_key,
+        context,
+    )?;
+
+    let epoch = compute_epoch_at_slot(slot, context);
+    let domain = get_domain(state, DomainType::AggregateAndProof, Some(epoch), context)?;
+    verify_signed_data(
+        aggregate_and_proof,
+        &signed_aggregate_and_proof.signature,
+        &aggregator.public_key,
+        domain,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interop::interop_secret_key;
+    use crate::phase0::{mainnet, AttestationData, Validator};
+    use crate::primitives::FAR_FUTURE_EPOCH;
+
+    // the compressed generator of G2, whose digest is not `0 mod 4`
+    const G2_GENERATOR: &str = "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8";
+
+    fn state_with_validators(validator_count: usize, context: &Context) -> mainnet::BeaconState {
+        let mut state = mainnet::BeaconState::default();
+        for _ in 0..validator_count {
+            state.validators.push(Validator {
+                effective_balance: context.max_effective_balance,
+                exit_epoch: FAR_FUTURE_EPOCH,
+                withdrawable_epoch: FAR_FUTURE_EPOCH,
+                ..Default::default()
+            });
+            state.balances.push(context.max_effective_balance);
+        }
+        state
+    }
+
+    #[test]
+    fn test_is_aggregator() {
+        let context = Context::for_mainnet();
+        // the digest of the point at infinity is `0 mod 8`
+        let infinity = BlsSignature::default();
+        let generator =
+            BlsSignature::try_from(hex::decode(G2_GENERATOR).unwrap().as_ref()).unwrap();
+
+        // a committee of 64 has an aggregator modulo of 4
+        let state = state_with_validators(2048, &context);
+        assert_eq!(
+            get_beacon_committee(&state, 0, 0, &context).unwrap().len(),
+            64
+        );
+        assert!(is_aggregator(&state, 0, 0, &infinity, &context).unwrap());
+        assert!(!is_aggregator(&state, 0, 0, &generator, &context).unwrap());
+
+        // every member of a committee smaller than the target is an aggregator
+        let state = state_with_validators(256, &context);
+        assert!(is_aggregator(&state, 0, 0, &infinity, &context).unwrap());
+        assert!(is_aggregator(&state, 0, 0, &generator, &context).unwrap());
+    }
+
+    #[test]
+    fn test_slot_signature_round_trip() {
+        let context = Context::for_mainnet();
+        let state = state_with_validators(1, &context);
+        let signing_key = interop_secret_key(0).unwrap();
+        let public_key = signing_key.public_key();
+
+        let slot = 5;
+        let slot_signature = get_slot_signature(&state, slot, &signing_key, &context).unwrap();
+        assert!(
+            verify_slot_signature(&state, slot, &slot_signature, &public_key, &context).is_ok()
+        );
+        assert!(
+            verify_slot_signature(&state, slot + 1, &slot_signature, &public_key, &context)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_aggregate_and_proof_round_trip() {
+        let context = Context::for_mainnet();
+        let mut state = state_with_validators(2, &context);
+        let signing_key = interop_secret_key(0).unwrap();
+        state.validators[0].public_key = signing_key.public_key();
+        state.validators[1].public_key = interop_secret_key(1).unwrap().public_key();
+
+        let aggregate = Attestation {
+            data: AttestationData {
+                slot: 5,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut aggregate_and_proof =
+            get_aggregate_and_proof(&state, 0, aggregate, &signing_key, &context).unwrap();
+        let signature = get_aggregate_and_proof_signature(
+            &state,
+            &mut aggregate_and_proof,
+            &signing_key,
+            &context,
+        )
+        .unwrap();
+        let mut signed_aggregate_and_proof = SignedAggregateAndProof {
+            message: aggregate_and_proof,
+            signature,
+        };
+        assert!(verify_aggregate_and_proof_signature(
+            &state,
+            &mut signed_aggregate_and_proof,
+            &context
+        )
+        .is_ok());
+
+        signed_aggregate_and_proof.message.aggregator_index = 1;
+        assert!(verify_aggregate_and_proof_signature(
+            &state,
+            &mut signed_aggregate_and_proof,
+            &context
+        )
+        .is_err());
+    }
+}
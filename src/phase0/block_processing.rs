@@ -2,7 +2,8 @@ use crate::phase0 as spec;
 
 use crate::crypto::hash;
 use crate::primitives::{
-    BlsPublicKey, Bytes32, DomainType, Gwei, ValidatorIndex, FAR_FUTURE_EPOCH,
+    BlsSignature, Bytes32, Domain, DomainType, Epoch, Gwei, Root, Slot, ValidatorIndex,
+    FAR_FUTURE_EPOCH,
 };
 use crate::ssz::ByteVector;
 use crate::state_transition::{
@@ -11,14 +12,13 @@ use crate::state_transition::{
     InvalidProposerSlashing, InvalidVoluntaryExit,
 };
 use spec::{
-    compute_domain, compute_epoch_at_slot, compute_signing_root, get_beacon_committee,
-    get_beacon_proposer_index, get_committee_count_per_slot, get_current_epoch, get_domain,
-    get_indexed_attestation, get_previous_epoch, get_randao_mix, increase_balance,
-    initiate_validator_exit, is_active_validator, is_slashable_attestation_data,
-    is_slashable_validator, is_valid_indexed_attestation, slash_validator, Attestation,
-    AttesterSlashing, BeaconBlock, BeaconBlockBody, BeaconBlockHeader, BeaconState, Deposit,
-    DepositMessage, PendingAttestation, ProposerSlashing, SignedVoluntaryExit, Validator,
-    DEPOSIT_CONTRACT_TREE_DEPTH,
+    beacon_committee, beacon_proposer_index, committee_count_per_slot, compute_domain,
+    compute_epoch_at_slot, compute_signing_root, domain, get_beacon_proposer_index,
+    get_current_epoch, get_domain, indexed_attestation, initiate_exit, is_active_validator,
+    is_slashable_attestation_data, is_slashable_validator, slash_validator,
+    verify_indexed_attestation, Attestation, AttesterSlashing, BeaconBlock, BeaconBlockBody,
+    BeaconBlockHeader, BeaconState, Deposit, DepositMessage, Eth1Data, Fork, PendingAttestation,
+    ProposerSlashing, SignedVoluntaryExit, Validator, DEPOSIT_CONTRACT_TREE_DEPTH,
 };
 use ssz_rs::prelude::*;
 use std::collections::HashSet;
@@ -46,6 +46,26 @@ pub fn process_proposer_slashing<
     proposer_slashing: &mut ProposerSlashing,
     context: &Context,
 ) -> Result<(), Error> {
+    let epoch = compute_epoch_at_slot(proposer_slashing.signed_header_1.message.slot, context);
+    let domain = get_domain(state, DomainType::BeaconProposer, Some(epoch), context)?;
+    let proposer_index = verify_proposer_slashing(
+        &state.validators,
+        get_current_epoch(state, context),
+        proposer_slashing,
+        domain,
+    )?;
+    slash_validator(state, proposer_index, None, context)
+}
+
+// NOTE: the functions below take only the fields of the `BeaconState` they use, like the
+// helpers, so that each fork processes blocks with the same logic for its own `BeaconState`;
+// `domain` is computed by the caller from the epoch of the signed message
+pub(crate) fn verify_proposer_slashing(
+    validators: &[Validator],
+    current_epoch: Epoch,
+    proposer_slashing: &mut ProposerSlashing,
+    domain: Domain,
+) -> Result<ValidatorIndex, Error> {
     let header_1 = &proposer_slashing.signed_header_1.message;
     let header_2 = &proposer_slashing.signed_header_2.message;
 
@@ -71,15 +91,13 @@ pub fn process_proposer_slashing<
     }
 
     let proposer_index = header_1.proposer_index;
-    let proposer = &state.validators[proposer_index];
-    if !is_slashable_validator(proposer, get_current_epoch(state, context)) {
+    let proposer = &validators[proposer_index];
+    if !is_slashable_validator(proposer, current_epoch) {
         return Err(invalid_operation_error(InvalidOperation::ProposerSlashing(
             InvalidProposerSlashing::ProposerIsNotSlashable(header_1.proposer_index),
         )));
     }
 
-    let epoch = compute_epoch_at_slot(header_1.slot, context);
-    let domain = get_domain(state, DomainType::BeaconProposer, Some(epoch), context)?;
     for signed_header in [
         &mut proposer_slashing.signed_header_1,
         &mut proposer_slashing.signed_header_2,
@@ -96,7 +114,7 @@ pub fn process_proposer_slashing<
         }
     }
 
-    slash_validator(state, proposer_index, None, context)
+    Ok(proposer_index)
 }
 
 pub fn process_attester_slashing<
@@ -122,6 +140,30 @@ pub fn process_attester_slashing<
     attester_slashing: &mut AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>,
     context: &Context,
 ) -> Result<(), Error> {
+    let current_epoch = get_current_epoch(state, context);
+    let indices = slashable_attester_indices(
+        &state.validators,
+        &state.fork,
+        state.genesis_validators_root,
+        current_epoch,
+        attester_slashing,
+        context,
+    )?;
+    for index in indices {
+        slash_validator(state, index, None, context)?;
+    }
+    Ok(())
+}
+
+/// Verify `attester_slashing` and return the indices of the validators it slashes, in order.
+pub(crate) fn slashable_attester_indices<const MAX_VALIDATORS_PER_COMMITTEE: usize>(
+    validators: &[Validator],
+    fork: &Fork,
+    genesis_validators_root: Root,
+    current_epoch: Epoch,
+    attester_slashing: &mut AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>,
+    context: &Context,
+) -> Result<Vec<ValidatorIndex>, Error> {
     let attestation_1 = &mut attester_slashing.attestation_1;
     let attestation_2 = &mut attester_slashing.attestation_2;
 
@@ -134,9 +176,21 @@ pub fn process_attester_slashing<
         )));
     }
 
-    let _ = is_valid_indexed_attestation(state, attestation_1, context)?;
+    verify_indexed_attestation(
+        validators,
+        fork,
+        genesis_validators_root,
+        attestation_1,
+        context,
+    )?;
 
-    let _ = is_valid_indexed_attestation(state, attestation_2, context)?;
+    verify_indexed_attestation(
+        validators,
+        fork,
+        genesis_validators_root,
+        attestation_2,
+        context,
+    )?;
 
     let indices_1: HashSet<ValidatorIndex> =
         HashSet::from_iter(attestation_1.attesting_indices.iter().cloned());
@@ -147,21 +201,18 @@ pub fn process_attester_slashing<
         .collect::<Vec<_>>();
     indices.sort_unstable();
 
-    let mut slashed_any = false;
-    let current_epoch = get_current_epoch(state, context);
-    for &index in &indices {
-        if is_slashable_validator(&state.validators[index], current_epoch) {
-            slash_validator(state, index, None, context)?;
-            slashed_any = true;
-        }
-    }
+    let slashable_indices = indices
+        .iter()
+        .cloned()
+        .filter(|&index| is_slashable_validator(&validators[index], current_epoch))
+        .collect::<Vec<_>>();
 
-    if !slashed_any {
+    if slashable_indices.is_empty() {
         Err(invalid_operation_error(InvalidOperation::AttesterSlashing(
             InvalidAttesterSlashing::NoSlashings(indices),
         )))
     } else {
-        Ok(())
+        Ok(slashable_indices)
     }
 }
 
@@ -188,12 +239,68 @@ pub fn process_attestation<
     attestation: &Attestation<MAX_VALIDATORS_PER_COMMITTEE>,
     context: &Context,
 ) -> Result<(), Error> {
-    let data = &attestation.data;
+    verify_attestation(
+        &state.validators,
+        &state.randao_mixes,
+        state.slot,
+        &state.fork,
+        state.genesis_validators_root,
+        attestation,
+        context,
+    )?;
 
-    let is_previous = data.target.epoch == get_previous_epoch(state, context);
+    let data = &attestation.data;
     let current_epoch = get_current_epoch(state, context);
-    let is_current = data.target.epoch == current_epoch;
-    let valid_target_epoch = is_previous || is_current;
+    let pending_attestation = PendingAttestation {
+        aggregation_bits: attestation.aggregation_bits.clone(),
+        data: data.clone(),
+        inclusion_delay: state.slot - data.slot,
+        proposer_index: get_beacon_proposer_index(state, context)?,
+    };
+    if data.target.epoch == current_epoch {
+        if data.source != state.current_justified_checkpoint {
+            return Err(invalid_operation_error(InvalidOperation::Attestation(
+                InvalidAttestation::InvalidSource {
+                    expected: state.current_justified_checkpoint.clone(),
+                    source_checkpoint: data.source.clone(),
+                    current: current_epoch,
+                },
+            )));
+        }
+        state.current_epoch_attestations.push(pending_attestation);
+    } else {
+        if data.source != state.previous_justified_checkpoint {
+            return Err(invalid_operation_error(InvalidOperation::Attestation(
+                InvalidAttestation::InvalidSource {
+                    expected: state.previous_justified_checkpoint.clone(),
+                    source_checkpoint: data.source.clone(),
+                    current: current_epoch,
+                },
+            )));
+        }
+        state.previous_epoch_attestations.push(pending_attestation);
+    }
+
+    Ok(())
+}
+
+/// Verify everything about `attestation` except its source checkpoint, which each fork
+/// checks against its own justified checkpoints.
+pub(crate) fn verify_attestation<const MAX_VALIDATORS_PER_COMMITTEE: usize>(
+    validators: &[Validator],
+    randao_mixes: &[Bytes32],
+    slot: Slot,
+    fork: &Fork,
+    genesis_validators_root: Root,
+    attestation: &Attestation<MAX_VALIDATORS_PER_COMMITTEE>,
+    context: &Context,
+) -> Result<(), Error> {
+    let data = &attestation.data;
+
+    let current_epoch = compute_epoch_at_slot(slot, context);
+    let previous_epoch = current_epoch.saturating_sub(1);
+    let valid_target_epoch =
+        data.target.epoch == previous_epoch || data.target.epoch == current_epoch;
     if !valid_target_epoch {
         return Err(invalid_operation_error(InvalidOperation::Attestation(
             InvalidAttestation::InvalidTargetEpoch {
@@ -214,13 +321,13 @@ pub fn process_attestation<
         )));
     }
 
-    let attestation_has_delay = data.slot + context.min_attestation_inclusion_delay <= state.slot;
-    let attestation_is_recent = state.slot <= data.slot + context.slots_per_epoch;
+    let attestation_has_delay = data.slot + context.min_attestation_inclusion_delay <= slot;
+    let attestation_is_recent = slot <= data.slot + context.slots_per_epoch;
     let attestation_is_timely = attestation_has_delay && attestation_is_recent;
     if !attestation_is_timely {
         return Err(invalid_operation_error(InvalidOperation::Attestation(
             InvalidAttestation::NotTimely {
-                state_slot: state.slot,
+                state_slot: slot,
                 attestation_slot: data.slot,
                 lower_bound: data.slot + context.slots_per_epoch,
                 upper_bound: data.slot + context.min_attestation_inclusion_delay,
@@ -228,7 +335,7 @@ pub fn process_attestation<
         )));
     }
 
-    let committee_count = get_committee_count_per_slot(state, data.target.epoch, context);
+    let committee_count = committee_count_per_slot(validators, data.target.epoch, context);
     if data.index >= committee_count {
         return Err(invalid_operation_error(InvalidOperation::Attestation(
             InvalidAttestation::InvalidIndex {
@@ -238,7 +345,7 @@ pub fn process_attestation<
         )));
     }
 
-    let committee = get_beacon_committee(state, data.slot, data.index, context)?;
+    let committee = beacon_committee(validators, randao_mixes, data.slot, data.index, context)?;
 
     if attestation.aggregation_bits.len() != committee.len() {
         return Err(invalid_operation_error(InvalidOperation::Attestation(
@@ -251,46 +358,16 @@ pub fn process_attestation<
 
     // NOTE: swap order of these wrt the spec to avoid mutation
     // to the state that would need to be undone
-    let _ = is_valid_indexed_attestation(
-        state,
-        &mut get_indexed_attestation(state, attestation, context)?,
+    verify_indexed_attestation(
+        validators,
+        fork,
+        genesis_validators_root,
+        &mut indexed_attestation(validators, randao_mixes, attestation, context)?,
         context,
-    )?;
-
-    let pending_attestation = PendingAttestation {
-        aggregation_bits: attestation.aggregation_bits.clone(),
-        data: data.clone(),
-        inclusion_delay: state.slot - data.slot,
-        proposer_index: get_beacon_proposer_index(state, context)?,
-    };
-    if is_current {
-        if data.source != state.current_justified_checkpoint {
-            return Err(invalid_operation_error(InvalidOperation::Attestation(
-                InvalidAttestation::InvalidSource {
-                    expected: state.current_justified_checkpoint.clone(),
-                    source_checkpoint: data.source.clone(),
-                    current: current_epoch,
-                },
-            )));
-        }
-        state.current_epoch_attestations.push(pending_attestation);
-    } else {
-        if data.source != state.previous_justified_checkpoint {
-            return Err(invalid_operation_error(InvalidOperation::Attestation(
-                InvalidAttestation::InvalidSource {
-                    expected: state.previous_justified_checkpoint.clone(),
-                    source_checkpoint: data.source.clone(),
-                    current: current_epoch,
-                },
-            )));
-        }
-        state.previous_epoch_attestations.push(pending_attestation);
-    }
-
-    Ok(())
+    )
 }
 
-fn get_validator_from_deposit(deposit: &Deposit, context: &Context) -> Validator {
+pub fn get_validator_from_deposit(deposit: &Deposit, context: &Context) -> Validator {
     let amount = deposit.data.amount;
     let effective_balance = Gwei::min(
         amount - amount % context.effective_balance_increment,
@@ -332,6 +409,27 @@ pub fn process_deposit<
     deposit: &mut Deposit,
     context: &Context,
 ) -> Result<(), Error> {
+    apply_deposit(
+        &mut state.eth1_deposit_index,
+        &state.eth1_data,
+        &mut state.validators,
+        &mut state.balances,
+        deposit,
+        context,
+    )?;
+    Ok(())
+}
+
+/// Verify `deposit` against `eth1_data` and credit it to the registry, returning
+/// `true` if it added a new validator so later forks can extend their own per-validator lists.
+pub(crate) fn apply_deposit<const VALIDATOR_REGISTRY_LIMIT: usize>(
+    eth1_deposit_index: &mut u64,
+    eth1_data: &Eth1Data,
+    validators: &mut List<Validator, VALIDATOR_REGISTRY_LIMIT>,
+    balances: &mut List<Gwei, VALIDATOR_REGISTRY_LIMIT>,
+    deposit: &mut Deposit,
+    context: &Context,
+) -> Result<bool, Error> {
     let branch = deposit
         .proof
         .iter()
@@ -339,8 +437,8 @@ pub fn process_deposit<
         .collect::<Vec<_>>();
     let leaf = deposit.data.hash_tree_root()?;
     let depth = DEPOSIT_CONTRACT_TREE_DEPTH + 1;
-    let index = state.eth1_deposit_index as usize;
-    let root = &state.eth1_data.deposit_root;
+    let index = *eth1_deposit_index as usize;
+    let root = &eth1_data.deposit_root;
     if !is_valid_merkle_branch(&leaf, branch.iter(), depth, index, root) {
         return Err(invalid_operation_error(InvalidOperation::Deposit(
             InvalidDeposit::InvalidProof {
@@ -355,40 +453,35 @@ pub fn process_deposit<
 
     // NOTE: deviate from the order of the spec to avoid mutations
     // that would need to be rolled back upon failure
-    let public_key = deposit.data.public_key.clone();
+    let public_key = &deposit.data.public_key;
     let amount = deposit.data.amount;
-    let validator_public_keys: HashSet<&BlsPublicKey> =
-        HashSet::from_iter(state.validators.iter().map(|v| &v.public_key));
-    if !validator_public_keys.contains(&public_key) {
-        let mut deposit_message = DepositMessage {
-            public_key: public_key.clone(),
-            withdrawal_credentials: deposit.data.withdrawal_credentials.clone(),
-            amount,
-        };
-        let domain = compute_domain(DomainType::Deposit, None, None, context)?;
-        let signing_root = compute_signing_root(&mut deposit_message, domain)?;
-        if !public_key.verify_signature(signing_root.as_bytes(), &deposit.data.signature) {
-            return Err(invalid_operation_error(InvalidOperation::Deposit(
-                InvalidDeposit::InvalidSignature(deposit.data.signature.clone()),
-            )));
+    let is_new_validator = match validators.iter().position(|v| &v.public_key == public_key) {
+        Some(index) => {
+            balances[index] += amount;
+            false
         }
+        None => {
+            let mut deposit_message = DepositMessage {
+                public_key: public_key.clone(),
+                withdrawal_credentials: deposit.data.withdrawal_credentials.clone(),
+                amount,
+            };
+            let domain = compute_domain(DomainType::Deposit, None, None, context)?;
+            let signing_root = compute_signing_root(&mut deposit_message, domain)?;
+            if !public_key.verify_signature(signing_root.as_bytes(), &deposit.data.signature) {
+                return Err(invalid_operation_error(InvalidOperation::Deposit(
+                    InvalidDeposit::InvalidSignature(deposit.data.signature.clone()),
+                )));
+            }
+
+            validators.push(get_validator_from_deposit(deposit, context));
+            balances.push(amount);
+            true
+        }
+    };
 
-        state
-            .validators
-            .push(get_validator_from_deposit(deposit, context));
-        state.balances.push(amount);
-    } else {
-        let index = state
-            .validators
-            .iter()
-            .position(|v| v.public_key == public_key)
-            .unwrap();
-
-        increase_balance(state, index, amount);
-    }
-
-    state.eth1_deposit_index += 1;
-    Ok(())
+    *eth1_deposit_index += 1;
+    Ok(is_new_validator)
 }
 
 pub fn process_voluntary_exit<
@@ -414,9 +507,27 @@ pub fn process_voluntary_exit<
     signed_voluntary_exit: &mut SignedVoluntaryExit,
     context: &Context,
 ) -> Result<(), Error> {
-    let voluntary_exit = &mut signed_voluntary_exit.message;
-    let validator = &state.validators[voluntary_exit.validator_index];
     let current_epoch = get_current_epoch(state, context);
+    apply_voluntary_exit(
+        &mut state.validators,
+        &state.fork,
+        state.genesis_validators_root,
+        current_epoch,
+        signed_voluntary_exit,
+        context,
+    )
+}
+
+pub(crate) fn apply_voluntary_exit<const VALIDATOR_REGISTRY_LIMIT: usize>(
+    validators: &mut List<Validator, VALIDATOR_REGISTRY_LIMIT>,
+    fork: &Fork,
+    genesis_validators_root: Root,
+    current_epoch: Epoch,
+    signed_voluntary_exit: &mut SignedVoluntaryExit,
+    context: &Context,
+) -> Result<(), Error> {
+    let voluntary_exit = &mut signed_voluntary_exit.message;
+    let validator = &validators[voluntary_exit.validator_index];
 
     if !is_active_validator(validator, current_epoch) {
         return Err(invalid_operation_error(InvalidOperation::VoluntaryExit(
@@ -442,8 +553,7 @@ pub fn process_voluntary_exit<
         )));
     }
 
-    let minimum_time_active =
-        validator.activation_eligibility_epoch + context.shard_committee_period;
+    let minimum_time_active = validator.activation_epoch + context.shard_committee_period;
     if current_epoch < minimum_time_active {
         return Err(invalid_operation_error(InvalidOperation::VoluntaryExit(
             InvalidVoluntaryExit::ValidatoIsNotActiveForLongEnough {
@@ -453,10 +563,11 @@ pub fn process_voluntary_exit<
         )));
     }
 
-    let domain = get_domain(
-        state,
+    let domain = domain(
+        fork,
+        genesis_validators_root,
         DomainType::VoluntaryExit,
-        Some(voluntary_exit.epoch),
+        voluntary_exit.epoch,
         context,
     )?;
     let signing_root = compute_signing_root(voluntary_exit, domain)?;
@@ -470,12 +581,17 @@ pub fn process_voluntary_exit<
         )));
     }
 
-    initiate_validator_exit(state, voluntary_exit.validator_index, context);
+    initiate_exit(
+        validators,
+        signed_voluntary_exit.message.validator_index,
+        current_epoch,
+        context,
+    );
 
     Ok(())
 }
 
-fn process_block_header<
+pub fn process_block_header<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
     const ETH1_DATA_VOTES_BOUND: usize,
@@ -510,53 +626,74 @@ fn process_block_header<
     >,
     context: &Context,
 ) -> Result<(), Error> {
-    if block.slot != state.slot {
+    let header = BeaconBlockHeader {
+        slot: block.slot,
+        proposer_index: block.proposer_index,
+        parent_root: block.parent_root,
+        body_root: block.body.hash_tree_root()?,
+        ..Default::default()
+    };
+    apply_block_header(
+        &mut state.latest_block_header,
+        &state.validators,
+        &state.randao_mixes,
+        state.slot,
+        header,
+        context,
+    )
+}
+
+/// Verify `header`, built from the block with the root of the fork's own body,
+/// and cache it as the `latest_block_header`.
+pub(crate) fn apply_block_header(
+    latest_block_header: &mut BeaconBlockHeader,
+    validators: &[Validator],
+    randao_mixes: &[Bytes32],
+    slot: Slot,
+    header: BeaconBlockHeader,
+    context: &Context,
+) -> Result<(), Error> {
+    if header.slot != slot {
         return Err(invalid_header_error(
             InvalidBeaconBlockHeader::StateSlotMismatch {
-                state_slot: state.slot,
-                block_slot: block.slot,
+                state_slot: slot,
+                block_slot: header.slot,
             },
         ));
     }
 
-    if block.slot <= state.latest_block_header.slot {
+    if header.slot <= latest_block_header.slot {
         return Err(invalid_header_error(
             InvalidBeaconBlockHeader::OlderThanLatestBlockHeader {
-                block_slot: block.slot,
-                latest_block_header_slot: state.latest_block_header.slot,
+                block_slot: header.slot,
+                latest_block_header_slot: latest_block_header.slot,
             },
         ));
     }
 
-    let proposer_index = get_beacon_proposer_index(state, context)?;
-    if block.proposer_index != proposer_index {
+    let proposer_index = beacon_proposer_index(validators, randao_mixes, slot, context)?;
+    if header.proposer_index != proposer_index {
         return Err(invalid_header_error(
             InvalidBeaconBlockHeader::ProposerIndexMismatch {
-                block_proposer_index: block.proposer_index,
+                block_proposer_index: header.proposer_index,
                 proposer_index,
             },
         ));
     }
 
-    let expected_parent_root = state.latest_block_header.hash_tree_root()?;
-    if block.parent_root != expected_parent_root {
+    let expected_parent_root = latest_block_header.hash_tree_root()?;
+    if header.parent_root != expected_parent_root {
         return Err(invalid_header_error(
             InvalidBeaconBlockHeader::ParentBlockRootMismatch {
                 expected: expected_parent_root,
-                provided: block.parent_root,
+                provided: header.parent_root,
             },
         ));
     }
 
-    state.latest_block_header = BeaconBlockHeader {
-        slot: block.slot,
-        proposer_index: block.proposer_index,
-        parent_root: block.parent_root,
-        body_root: block.body.hash_tree_root()?,
-        ..Default::default()
-    };
+    *latest_block_header = header;
 
-    let proposer = &state.validators[block.proposer_index];
+    let proposer = &validators[proposer_index];
     if proposer.slashed {
         return Err(invalid_header_error(
             InvalidBeaconBlockHeader::ProposerSlashed(proposer_index),
@@ -575,7 +712,7 @@ fn xor(a: &Bytes32, b: &Bytes32) -> Bytes32 {
     ByteVector::<32>(inner)
 }
 
-fn process_randao<
+pub fn process_randao<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
     const ETH1_DATA_VOTES_BOUND: usize,
@@ -610,33 +747,49 @@ fn process_randao<
     >,
     context: &Context,
 ) -> Result<(), Error> {
-    let mut epoch = get_current_epoch(state, context);
+    let epoch = get_current_epoch(state, context);
+    let domain = get_domain(state, DomainType::Randao, Some(epoch), context)?;
+    apply_randao_reveal(
+        &mut state.randao_mixes,
+        &state.validators,
+        state.slot,
+        &body.randao_reveal,
+        domain,
+        context,
+    )
+}
+
+pub(crate) fn apply_randao_reveal<const EPOCHS_PER_HISTORICAL_VECTOR: usize>(
+    randao_mixes: &mut Vector<Bytes32, EPOCHS_PER_HISTORICAL_VECTOR>,
+    validators: &[Validator],
+    slot: Slot,
+    randao_reveal: &BlsSignature,
+    domain: Domain,
+    context: &Context,
+) -> Result<(), Error> {
+    let mut epoch = compute_epoch_at_slot(slot, context);
 
-    let proposer_index = get_beacon_proposer_index(state, context)?;
-    let proposer = &state.validators[proposer_index];
+    let proposer_index = beacon_proposer_index(validators, randao_mixes, slot, context)?;
+    let proposer = &validators[proposer_index];
 
-    let domain = get_domain(state, DomainType::Randao, Some(epoch), context)?;
     let signing_root = compute_signing_root(&mut epoch, domain)?;
 
-    if !body
-        .randao_reveal
-        .verify(&proposer.public_key, signing_root.as_bytes())
-    {
+    if !randao_reveal.verify(&proposer.public_key, signing_root.as_bytes()) {
         return Err(invalid_operation_error(InvalidOperation::Randao(
-            body.randao_reveal.clone(),
+            randao_reveal.clone(),
         )));
     }
 
+    let mix_index = epoch % context.epochs_per_historical_vector;
     let mix = xor(
-        get_randao_mix(state, epoch),
-        &hash(body.randao_reveal.as_bytes()),
+        &randao_mixes[mix_index as usize],
+        &hash(randao_reveal.as_bytes()),
     );
-    let mix_index = epoch % context.epochs_per_historical_vector;
-    state.randao_mixes[mix_index as usize] = mix;
+    randao_mixes[mix_index as usize] = mix;
     Ok(())
 }
 
-fn process_eth1_data<
+pub fn process_eth1_data<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
     const ETH1_DATA_VOTES_BOUND: usize,
@@ -671,20 +824,30 @@ fn process_eth1_data<
     >,
     context: &Context,
 ) {
-    state.eth1_data_votes.push(body.eth1_data.clone());
+    apply_eth1_vote(
+        &mut state.eth1_data,
+        &mut state.eth1_data_votes,
+        &body.eth1_data,
+        context,
+    );
+}
 
-    let votes_count = state
-        .eth1_data_votes
-        .iter()
-        .filter(|&vote| *vote == body.eth1_data)
-        .count() as u64;
+pub(crate) fn apply_eth1_vote<const ETH1_DATA_VOTES_BOUND: usize>(
+    eth1_data: &mut Eth1Data,
+    eth1_data_votes: &mut List<Eth1Data, ETH1_DATA_VOTES_BOUND>,
+    vote: &Eth1Data,
+    context: &Context,
+) {
+    eth1_data_votes.push(vote.clone());
+
+    let votes_count = eth1_data_votes.iter().filter(|&v| v == vote).count() as u64;
 
     if votes_count * 2 > context.epochs_per_eth1_voting_period * context.slots_per_epoch {
-        state.eth1_data = body.eth1_data.clone();
+        *eth1_data = vote.clone();
     }
 }
 
-fn process_operations<
+pub fn process_operations<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
     const ETH1_DATA_VOTES_BOUND: usize,
@@ -719,19 +882,12 @@ fn process_operations<
     >,
     context: &Context,
 ) -> Result<(), Error> {
-    let expected_deposit_count = usize::min(
-        context.max_deposits,
-        (state.eth1_data.deposit_count - state.eth1_deposit_index) as usize,
-    );
-
-    if body.deposits.len() != expected_deposit_count {
-        return Err(invalid_operation_error(InvalidOperation::Deposit(
-            InvalidDeposit::IncorrectCount {
-                expected: expected_deposit_count,
-                count: body.deposits.len(),
-            },
-        )));
-    }
+    verify_deposit_count(
+        &state.eth1_data,
+        state.eth1_deposit_index,
+        body.deposits.len(),
+        context,
+    )?;
 
     body.proposer_slashings
         .iter_mut()
@@ -751,6 +907,28 @@ fn process_operations<
     Ok(())
 }
 
+pub(crate) fn verify_deposit_count(
+    eth1_data: &Eth1Data,
+    eth1_deposit_index: u64,
+    count: usize,
+    context: &Context,
+) -> Result<(), Error> {
+    let expected_deposit_count = usize::min(
+        context.max_deposits,
+        (eth1_data.deposit_count - eth1_deposit_index) as usize,
+    );
+
+    if count != expected_deposit_count {
+        return Err(invalid_operation_error(InvalidOperation::Deposit(
+            InvalidDeposit::IncorrectCount {
+                expected: expected_deposit_count,
+                count,
+            },
+        )));
+    }
+    Ok(())
+}
+
 pub fn process_block<
     const SLOTS_PER_HISTORICAL_ROOT: usize,
     const HISTORICAL_ROOTS_LIMIT: usize,
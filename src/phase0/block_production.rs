@@ -0,0 +1,418 @@
+use crate::phase0 as spec;
+
+use crate::crypto::SecretKey;
+#[cfg(feature = "signer")]
+use crate::primitives::{BlsPublicKey, Bytes32, Root, Slot};
+use crate::primitives::{BlsSignature, DomainType};
+#[cfg(feature = "signer")]
+use crate::signer::{self, ForkInfo, SignableMessage, Signer, SigningRequest};
+use crate::state_transition::{Context, Error};
+use spec::{
+    compute_epoch_at_slot, get_domain, sign_with_domain, Attestation, AttesterSlashing,
+    BeaconBlock, BeaconState, Deposit, ProposerSlashing, SignedVoluntaryExit,
+};
+#[cfg(feature = "signer")]
+use spec::{
+    get_beacon_proposer_index, get_eth1_vote, process_attestation, process_attester_slashing,
+    process_block, process_block_header, process_deposit, process_eth1_data,
+    process_proposer_slashing, process_randao, process_slots, process_voluntary_exit, Eth1Block,
+    Eth1Data, Fork,
+};
+#[cfg(feature = "signer")]
+use ssz_rs::prelude::*;
+
+/// Candidate operations for inclusion in a block.
+///
+/// Each collection may contain more operations than fit in a block and
+/// may contain operations that are invalid against the state the block is
+/// built on; `produce_block` only includes those that can be processed.
+#[derive(Default, Debug)]
+pub struct BlockOperations<const MAX_VALIDATORS_PER_COMMITTEE: usize> {
+    pub proposer_slashings: Vec<ProposerSlashing>,
+    pub attester_slashings: Vec<AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>>,
+    pub attestations: Vec<Attestation<MAX_VALIDATORS_PER_COMMITTEE>>,
+    pub deposits: Vec<Deposit>,
+    pub voluntary_exits: Vec<SignedVoluntaryExit>,
+}
+
+pub fn get_epoch_signature<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    block: &BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+    >,
+    signing_key: &SecretKey,
+    context: &Context,
+) -> Result<BlsSignature, Error> {
+    let mut epoch = compute_epoch_at_slot(block.slot, context);
+    let domain = get_domain(state, DomainType::Randao, Some(epoch), context)?;
+    sign_with_domain(&mut epoch, signing_key, domain)
+}
+
+pub fn get_block_signature<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    block: &mut BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+    >,
+    signing_key: &SecretKey,
+    context: &Context,
+) -> Result<BlsSignature, Error> {
+    let epoch = compute_epoch_at_slot(block.slot, context);
+    let domain = get_domain(state, DomainType::BeaconProposer, Some(epoch), context)?;
+    sign_with_domain(block, signing_key, domain)
+}
+
+/// Sign the randao reveal for a block at `slot` with the key for `public_key`.
+#[cfg(feature = "signer")]
+pub(crate) async fn get_randao_reveal(
+    signer: &impl Signer,
+    public_key: &BlsPublicKey,
+    fork: &Fork,
+    genesis_validators_root: Root,
+    slot: Slot,
+    context: &Context,
+) -> Result<BlsSignature, signer::Error> {
+    let fork_info = ForkInfo {
+        fork: fork.clone(),
+        genesis_validators_root,
+    };
+    let message = SignableMessage::RandaoReveal {
+        epoch: compute_epoch_at_slot(slot, context),
+    };
+    let request = SigningRequest::new(message, fork_info, context)?;
+    signer.sign(public_key, &request).await
+}
+
+/// Build an unsigned block for `slot` on top of `state`.
+///
+/// The `state` is not modified; a copy is advanced to `slot` (if needed) and
+/// the resulting block is applied to it to compute the block's `state_root`.
+/// The randao reveal is signed by the `signer` with the key of the proposer and
+/// the eth1 vote is taken from the `eth1_chain` following `get_eth1_vote`.
+/// Operations are included in the order they are given, up to the `max_*`
+/// limits in the `context`, skipping any that are not valid against the state.
+/// Deposits are processed in order and inclusion stops at the first invalid one,
+/// given the block must contain the exact number of pending deposits.
+#[cfg(feature = "signer")]
+pub async fn produce_block<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    slot: Slot,
+    signer: &impl Signer,
+    eth1_chain: &[Eth1Block],
+    graffiti: Bytes32,
+    operations: BlockOperations<MAX_VALIDATORS_PER_COMMITTEE>,
+    context: &Context,
+) -> Result<
+    BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+    >,
+    signer::Error,
+> {
+    let mut state = state.clone();
+    if state.slot < slot {
+        process_slots(&mut state, slot, context)?;
+    }
+
+    let proposer_index = get_beacon_proposer_index(&state, context)?;
+    let randao_reveal = get_randao_reveal(
+        signer,
+        &state.validators[proposer_index].public_key,
+        &state.fork,
+        state.genesis_validators_root,
+        slot,
+        context,
+    )
+    .await?;
+    let eth1_data = get_eth1_vote(&state, eth1_chain, context);
+    let block = build_block(
+        &mut state,
+        randao_reveal,
+        eth1_data,
+        graffiti,
+        operations,
+        context,
+    )?;
+    Ok(block)
+}
+
+// Assemble the block for the slot of `state` from the given operations
+// and apply it to `state`, recording the resulting `state_root`.
+#[cfg(feature = "signer")]
+fn build_block<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+>(
+    state: &mut BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    randao_reveal: BlsSignature,
+    eth1_data: Eth1Data,
+    graffiti: Bytes32,
+    operations: BlockOperations<MAX_VALIDATORS_PER_COMMITTEE>,
+    context: &Context,
+) -> Result<
+    BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+    >,
+    Error,
+> {
+    let mut block = BeaconBlock {
+        slot: state.slot,
+        proposer_index: get_beacon_proposer_index(state, context)?,
+        parent_root: state.latest_block_header.hash_tree_root()?,
+        ..Default::default()
+    };
+    block.body.randao_reveal = randao_reveal;
+    block.body.eth1_data = eth1_data;
+    block.body.graffiti = graffiti;
+
+    // NOTE: operations are screened against a scratch copy of the state
+    // in the same order `process_operations` applies them so that the
+    // effects of earlier operations are visible to later ones
+    let mut scratch_state = state.clone();
+    process_block_header(&mut scratch_state, &mut block, context)?;
+    process_randao(&mut scratch_state, &block.body, context)?;
+    process_eth1_data(&mut scratch_state, &block.body, context);
+
+    let BlockOperations {
+        proposer_slashings,
+        attester_slashings,
+        attestations,
+        deposits,
+        voluntary_exits,
+    } = operations;
+
+    for mut proposer_slashing in proposer_slashings {
+        if block.body.proposer_slashings.len() == context.max_proposer_slashings {
+            break;
+        }
+        if process_proposer_slashing(&mut scratch_state, &mut proposer_slashing, context).is_ok() {
+            block.body.proposer_slashings.push(proposer_slashing);
+        }
+    }
+
+    for mut attester_slashing in attester_slashings {
+        if block.body.attester_slashings.len() == context.max_attester_slashings {
+            break;
+        }
+        if process_attester_slashing(&mut scratch_state, &mut attester_slashing, context).is_ok() {
+            block.body.attester_slashings.push(attester_slashing);
+        }
+    }
+
+    for attestation in attestations {
+        if block.body.attestations.len() == context.max_attestations {
+            break;
+        }
+        if process_attestation(&mut scratch_state, &attestation, context).is_ok() {
+            block.body.attestations.push(attestation);
+        }
+    }
+
+    let pending_deposit_count =
+        scratch_state.eth1_data.deposit_count - scratch_state.eth1_deposit_index;
+    let deposit_count = usize::min(context.max_deposits, pending_deposit_count as usize);
+    for mut deposit in deposits.into_iter().take(deposit_count) {
+        if process_deposit(&mut scratch_state, &mut deposit, context).is_err() {
+            break;
+        }
+        block.body.deposits.push(deposit);
+    }
+
+    for mut voluntary_exit in voluntary_exits {
+        if block.body.voluntary_exits.len() == context.max_voluntary_exits {
+            break;
+        }
+        if process_voluntary_exit(&mut scratch_state, &mut voluntary_exit, context).is_ok() {
+            block.body.voluntary_exits.push(voluntary_exit);
+        }
+    }
+
+    process_block(state, &mut block, context)?;
+    block.state_root = state.hash_tree_root()?;
+
+    Ok(block)
+}
+
+#[cfg(all(test, feature = "signer"))]
+mod tests {
+    use super::*;
+    use crate::interop::interop_secret_key;
+    use crate::interop::phase0::create_mainnet_genesis_state;
+    use crate::phase0::mainnet;
+    use crate::phase0::{get_eth1_data, state_transition, voting_period_start_time, VoluntaryExit};
+    use crate::primitives::Hash32;
+    use crate::signer::LocalSigner;
+    use crate::state_transition::Validation;
+
+    #[tokio::test]
+    async fn test_produced_block_passes_state_transition() {
+        let context = Context::for_mainnet();
+        let genesis_state = create_mainnet_genesis_state(64, &context);
+        let slot = 1;
+
+        let mut state = genesis_state.clone();
+        process_slots(&mut state, slot, &context).unwrap();
+        let proposer_index = get_beacon_proposer_index(&state, &context).unwrap();
+        let signing_key = interop_secret_key(proposer_index).unwrap();
+        let signer = LocalSigner::new([signing_key.clone()]);
+
+        let follow_time = context.seconds_per_eth1_block * context.eth1_follow_distance;
+        let eth1_block = Eth1Block {
+            timestamp: voting_period_start_time(&state, &context) - follow_time,
+            deposit_count: state.eth1_data.deposit_count,
+            block_hash: Hash32::try_from([1u8; 32].as_ref()).unwrap(),
+            ..Default::default()
+        };
+
+        // validators can not exit this early, so the exit must be left out
+        let operations = BlockOperations {
+            voluntary_exits: vec![SignedVoluntaryExit {
+                message: VoluntaryExit {
+                    epoch: 0,
+                    validator_index: 0,
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let mut block: mainnet::BeaconBlock = produce_block(
+            &genesis_state,
+            slot,
+            &signer,
+            &[eth1_block.clone()],
+            Bytes32::default(),
+            operations,
+            &context,
+        )
+        .await
+        .unwrap();
+        assert_eq!(block.proposer_index, proposer_index);
+        assert_eq!(block.body.eth1_data, get_eth1_data(&eth1_block));
+        assert!(block.body.voluntary_exits.is_empty());
+
+        let signature = get_block_signature(&state, &mut block, &signing_key, &context).unwrap();
+        let mut signed_block = mainnet::SignedBeaconBlock {
+            message: block,
+            signature,
+        };
+        let mut post_state = genesis_state.clone();
+        state_transition(
+            &mut post_state,
+            &mut signed_block,
+            Validation::Enabled,
+            &context,
+        )
+        .unwrap();
+        assert_eq!(
+            post_state.hash_tree_root().unwrap(),
+            signed_block.message.state_root
+        );
+    }
+}
@@ -0,0 +1,318 @@
+use crate::phase0 as spec;
+
+use crate::crypto::hash;
+use crate::primitives::{BlsPublicKey, CommitteeIndex, DomainType, Epoch, Slot, ValidatorIndex};
+use crate::state_transition::{Context, Error};
+use spec::{
+    compute_proposer_index, compute_start_slot_at_epoch, get_active_validator_indices,
+    get_beacon_committee, get_committee_count_per_slot, get_current_epoch, get_previous_epoch,
+    get_seed, BeaconState,
+};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AttesterDuty {
+    #[serde(rename = "pubkey")]
+    pub public_key: BlsPublicKey,
+    #[serde(with = "crate::serde::as_string")]
+    pub validator_index: ValidatorIndex,
+    #[serde(with = "crate::serde::as_string")]
+    pub committee_index: CommitteeIndex,
+    #[serde(with = "crate::serde::as_string")]
+    pub committee_length: usize,
+    #[serde(with = "crate::serde::as_string")]
+    pub committees_at_slot: usize,
+    #[serde(with = "crate::serde::as_string")]
+    pub validator_committee_index: usize,
+    #[serde(with = "crate::serde::as_string")]
+    pub slot: Slot,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProposerDuty {
+    #[serde(rename = "pubkey")]
+    pub public_key: BlsPublicKey,
+    #[serde(with = "crate::serde::as_string")]
+    pub validator_index: ValidatorIndex,
+    #[serde(with = "crate::serde::as_string")]
+    pub slot: Slot,
+}
+
+fn ensure_epoch_in_lookahead<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    epoch: Epoch,
+    context: &Context,
+) -> Result<(), Error> {
+    let lower_bound = get_previous_epoch(state, context);
+    let upper_bound = get_current_epoch(state, context) + context.min_seed_lookahead;
+    if epoch < lower_bound || epoch > upper_bound {
+        return Err(Error::EpochOutOfRange {
+            requested: epoch,
+            lower_bound,
+            upper_bound,
+        });
+    }
+    Ok(())
+}
+
+/// Return the committee assignment in the `epoch` for `validator_index` as
+/// `(committee, committee_index, slot)`, if the validator is assigned.
+///
+/// The `epoch` can be at most `min_seed_lookahead` epochs past the current epoch.
+pub fn get_committee_assignment<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    epoch: Epoch,
+    validator_index: ValidatorIndex,
+    context: &Context,
+) -> Result<Option<(Vec<ValidatorIndex>, CommitteeIndex, Slot)>, Error> {
+    ensure_epoch_in_lookahead(state, epoch, context)?;
+
+    let start_slot = compute_start_slot_at_epoch(epoch, context);
+    let committee_count_per_slot = get_committee_count_per_slot(state, epoch, context);
+    for slot in start_slot..start_slot + context.slots_per_epoch {
+        for index in 0..committee_count_per_slot {
+            let committee = get_beacon_committee(state, slot, index, context)?;
+            if committee.contains(&validator_index) {
+                return Ok(Some((committee, index, slot)));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Return the attester duties in the `epoch` for each of the `indices`
+/// assigned to a committee.
+///
+/// The `epoch` can be at most `min_seed_lookahead` epochs past the current epoch.
+pub fn get_attester_duties<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    epoch: Epoch,
+    indices: &[ValidatorIndex],
+    context: &Context,
+) -> Result<Vec<AttesterDuty>, Error> {
+    ensure_epoch_in_lookahead(state, epoch, context)?;
+
+    let requested: HashSet<&ValidatorIndex> = HashSet::from_iter(indices);
+    let start_slot = compute_start_slot_at_epoch(epoch, context);
+    let committees_at_slot = get_committee_count_per_slot(state, epoch, context);
+    let mut duties = vec![];
+    for slot in start_slot..start_slot + context.slots_per_epoch {
+        for committee_index in 0..committees_at_slot {
+            let committee = get_beacon_committee(state, slot, committee_index, context)?;
+            for (validator_committee_index, validator_index) in committee.iter().enumerate() {
+                if !requested.contains(validator_index) {
+                    continue;
+                }
+                duties.push(AttesterDuty {
+                    public_key: state.validators[*validator_index].public_key.clone(),
+                    validator_index: *validator_index,
+                    committee_index,
+                    committee_length: committee.len(),
+                    committees_at_slot,
+                    validator_committee_index,
+                    slot,
+                });
+            }
+        }
+    }
+    Ok(duties)
+}
+
+/// Return the block proposer for each slot in the `epoch`.
+///
+/// Proposers depend on the effective balances at the start of the `epoch`
+/// so only duties for the current epoch of `state` can be computed.
+pub fn get_proposer_duties<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    epoch: Epoch,
+    context: &Context,
+) -> Result<Vec<ProposerDuty>, Error> {
+    let current_epoch = get_current_epoch(state, context);
+    if epoch != current_epoch {
+        return Err(Error::EpochOutOfRange {
+            requested: epoch,
+            lower_bound: current_epoch,
+            upper_bound: current_epoch,
+        });
+    }
+
+    let seed = get_seed(state, epoch, DomainType::BeaconProposer, context);
+    let indices = get_active_validator_indices(state, epoch);
+    let start_slot = compute_start_slot_at_epoch(epoch, context);
+    let mut duties = Vec::with_capacity(context.slots_per_epoch as usize);
+    for slot in start_slot..start_slot + context.slots_per_epoch {
+        let mut input = [0u8; 40];
+        input[..32].copy_from_slice(seed.as_ref());
+        input[32..40].copy_from_slice(&slot.to_le_bytes());
+        let seed = hash(input);
+        let validator_index = compute_proposer_index(state, &indices, &seed, context)?;
+        duties.push(ProposerDuty {
+            public_key: state.validators[validator_index].public_key.clone(),
+            validator_index,
+            slot,
+        });
+    }
+    Ok(duties)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interop::phase0::create_mainnet_genesis_state;
+    use crate::phase0::{compute_epoch_at_slot, get_beacon_proposer_index};
+
+    #[test]
+    fn test_attester_duties_match_committees() {
+        let context = Context::for_mainnet();
+        let validator_count = 256;
+        let state = create_mainnet_genesis_state(validator_count, &context);
+        let indices = (0..validator_count).collect::<Vec<_>>();
+
+        for epoch in [0, 1] {
+            let duties = get_attester_duties(&state, epoch, &indices, &context).unwrap();
+            // each active validator attests exactly once per epoch
+            assert_eq!(duties.len(), validator_count);
+            for duty in &duties {
+                assert_eq!(compute_epoch_at_slot(duty.slot, &context), epoch);
+                assert_eq!(
+                    duty.committees_at_slot,
+                    get_committee_count_per_slot(&state, epoch, &context)
+                );
+                let committee =
+                    get_beacon_committee(&state, duty.slot, duty.committee_index, &context)
+                        .unwrap();
+                assert_eq!(duty.committee_length, committee.len());
+                assert_eq!(
+                    committee[duty.validator_committee_index],
+                    duty.validator_index
+                );
+                assert_eq!(
+                    duty.public_key,
+                    state.validators[duty.validator_index].public_key
+                );
+
+                // NOTE: only spot-check assignments as each one recomputes the committees
+                if duty.validator_index % 32 == 0 {
+                    let (assigned_committee, committee_index, slot) =
+                        get_committee_assignment(&state, epoch, duty.validator_index, &context)
+                            .unwrap()
+                            .unwrap();
+                    assert_eq!(assigned_committee, committee);
+                    assert_eq!(committee_index, duty.committee_index);
+                    assert_eq!(slot, duty.slot);
+                }
+            }
+        }
+
+        let duties = get_attester_duties(&state, 0, &[3, 5], &context).unwrap();
+        let mut validator_indices = duties
+            .iter()
+            .map(|duty| duty.validator_index)
+            .collect::<Vec<_>>();
+        validator_indices.sort();
+        assert_eq!(validator_indices, vec![3, 5]);
+
+        // duties are only known up to `min_seed_lookahead` epochs ahead
+        let epoch = context.min_seed_lookahead + 1;
+        assert!(get_attester_duties(&state, epoch, &indices, &context).is_err());
+    }
+
+    #[test]
+    fn test_proposer_duties_match_proposer_index() {
+        let context = Context::for_mainnet();
+        let state = create_mainnet_genesis_state(256, &context);
+
+        let duties = get_proposer_duties(&state, 0, &context).unwrap();
+        assert_eq!(duties.len(), context.slots_per_epoch as usize);
+        let mut state_at_slot = state.clone();
+        for (slot, duty) in (0..).zip(duties.iter()) {
+            assert_eq!(duty.slot, slot);
+            // NOTE: the proposer only depends on the slot within the same epoch
+            state_at_slot.slot = slot;
+            assert_eq!(
+                duty.validator_index,
+                get_beacon_proposer_index(&state_at_slot, &context).unwrap()
+            );
+            assert_eq!(
+                duty.public_key,
+                state.validators[duty.validator_index].public_key
+            );
+        }
+
+        assert!(get_proposer_duties(&state, 1, &context).is_err());
+    }
+}
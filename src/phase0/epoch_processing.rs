@@ -1,15 +1,15 @@
 use crate::phase0 as spec;
 
-use crate::primitives::{Epoch, Gwei, ValidatorIndex, GENESIS_EPOCH};
+use crate::primitives::{Epoch, Gwei, Root, ValidatorIndex, FAR_FUTURE_EPOCH, GENESIS_EPOCH};
 use crate::state_transition::{Context, Error};
 use integer_sqrt::IntegerSquareRoot;
 use spec::{
     compute_activation_exit_epoch, decrease_balance, get_attesting_indices, get_block_root,
     get_block_root_at_slot, get_current_epoch, get_previous_epoch, get_randao_mix,
-    get_total_active_balance, get_total_balance, get_validator_churn_limit, increase_balance,
-    initiate_validator_exit, is_active_validator, is_eligible_for_activation,
-    is_eligible_for_activation_queue, BeaconState, Checkpoint, HistoricalBatchAccumulator,
-    PendingAttestation, BASE_REWARDS_PER_EPOCH, JUSTIFICATION_BITS_LENGTH,
+    get_total_active_balance, get_total_balance, increase_balance, initiate_exit,
+    is_active_validator, is_eligible_for_activation_queue, total_active_balance,
+    validator_churn_limit, BeaconState, Checkpoint, HistoricalBatchAccumulator, PendingAttestation,
+    Validator, BASE_REWARDS_PER_EPOCH, JUSTIFICATION_BITS_LENGTH,
 };
 use ssz_rs::prelude::*;
 use std::collections::HashSet;
@@ -260,11 +260,26 @@ pub fn process_registry_updates<
     >,
     context: &Context,
 ) {
-    // Process activation eligibility and ejections
     let current_epoch = get_current_epoch(state, context);
+    update_registry(
+        &mut state.validators,
+        state.finalized_checkpoint.epoch,
+        current_epoch,
+        context,
+    );
+}
 
-    for i in 0..state.validators.len() {
-        let validator = &mut state.validators[i];
+// NOTE: the functions below take only the fields of the `BeaconState` they use, so that
+// each fork processes epochs with the same logic for its own `BeaconState`
+pub(crate) fn update_registry<const VALIDATOR_REGISTRY_LIMIT: usize>(
+    validators: &mut List<Validator, VALIDATOR_REGISTRY_LIMIT>,
+    finalized_epoch: Epoch,
+    current_epoch: Epoch,
+    context: &Context,
+) {
+    // Process activation eligibility and ejections
+    for i in 0..validators.len() {
+        let validator = &mut validators[i];
         if is_eligible_for_activation_queue(validator, context) {
             validator.activation_eligibility_epoch = current_epoch + 1;
         }
@@ -272,17 +287,18 @@ pub fn process_registry_updates<
         if is_active_validator(validator, current_epoch)
             && validator.effective_balance <= context.ejection_balance
         {
-            initiate_validator_exit(state, i, context);
+            initiate_exit(validators, i, current_epoch, context);
         }
     }
 
     // Queue validators eligible for activation and not yet dequeued for activation
-    let mut activation_queue = state
-        .validators
+    let mut activation_queue = validators
         .iter()
         .enumerate()
         .filter_map(|(index, validator)| {
-            if is_eligible_for_activation(state, validator) {
+            let is_eligible = validator.activation_eligibility_epoch <= finalized_epoch
+                && validator.activation_epoch == FAR_FUTURE_EPOCH;
+            if is_eligible {
                 Some(index)
             } else {
                 None
@@ -291,18 +307,16 @@ pub fn process_registry_updates<
         .collect::<Vec<ValidatorIndex>>();
     // Order by the sequence of activation_eligibility_epoch setting and then index
     activation_queue.sort_by(|&i, &j| {
-        let a = &state.validators[i];
-        let b = &state.validators[j];
+        let a = &validators[i];
+        let b = &validators[j];
         (a.activation_eligibility_epoch, i).cmp(&(b.activation_eligibility_epoch, j))
     });
 
     // Dequeued validators for activation up to churn limit
     let activation_exit_epoch = compute_activation_exit_epoch(current_epoch, context);
-    for i in activation_queue
-        .into_iter()
-        .take(get_validator_churn_limit(state, context))
-    {
-        let validator = &mut state.validators[i];
+    let churn_limit = validator_churn_limit(validators, current_epoch, context);
+    for i in activation_queue.into_iter().take(churn_limit) {
+        let validator = &mut validators[i];
         validator.activation_epoch = activation_exit_epoch;
     }
 }
@@ -330,14 +344,33 @@ pub fn process_slashings<
     context: &Context,
 ) -> Result<(), Error> {
     let epoch = get_current_epoch(state, context);
-    let total_balance = get_total_active_balance(state, context)?;
+    apply_slashings(
+        &state.validators,
+        &mut state.balances,
+        &state.slashings,
+        epoch,
+        context.proportional_slashing_multiplier,
+        context,
+    )
+}
+
+/// Penalize the slashed validators halfway to their withdrawable epoch; each fork
+/// passes its own `proportional_slashing_multiplier`.
+pub(crate) fn apply_slashings<const VALIDATOR_REGISTRY_LIMIT: usize>(
+    validators: &[Validator],
+    balances: &mut List<Gwei, VALIDATOR_REGISTRY_LIMIT>,
+    slashings: &[Gwei],
+    epoch: Epoch,
+    proportional_slashing_multiplier: u64,
+    context: &Context,
+) -> Result<(), Error> {
+    let total_balance = total_active_balance(validators, epoch, context)?;
     let adjusted_total_slashing_balance = Gwei::min(
-        state.slashings.iter().sum::<Gwei>() * context.proportional_slashing_multiplier,
+        slashings.iter().sum::<Gwei>() * proportional_slashing_multiplier,
         total_balance,
     );
 
-    for i in 0..state.validators.len() {
-        let validator = &state.validators[i];
+    for (i, validator) in validators.iter().enumerate() {
         if validator.slashed
             && (epoch + context.epochs_per_slashings_vector / 2) == validator.withdrawable_epoch
         {
@@ -345,7 +378,7 @@ pub fn process_slashings<
             let penalty_numerator =
                 validator.effective_balance / increment * adjusted_total_slashing_balance;
             let penalty = penalty_numerator / total_balance * increment;
-            decrease_balance(state, i, penalty);
+            balances[i] = balances[i].saturating_sub(penalty);
         }
     }
 
@@ -402,14 +435,20 @@ pub fn process_effective_balance_updates<
         PENDING_ATTESTATIONS_BOUND,
     >,
     context: &Context,
+) {
+    update_effective_balances(&mut state.validators, &state.balances, context);
+}
+
+pub(crate) fn update_effective_balances<const VALIDATOR_REGISTRY_LIMIT: usize>(
+    validators: &mut List<Validator, VALIDATOR_REGISTRY_LIMIT>,
+    balances: &[Gwei],
+    context: &Context,
 ) {
     // Update effective balances with hysteresis
     let hysteresis_increment = context.effective_balance_increment / context.hysteresis_quotient;
     let downward_threshold = hysteresis_increment * context.hysteresis_downward_multiplier;
     let upward_threshold = hysteresis_increment * context.hysteresis_upward_multiplier;
-    for i in 0..state.validators.len() {
-        let validator = &mut state.validators[i];
-        let balance = state.balances[i];
+    for (validator, &balance) in validators.iter_mut().zip(balances.iter()) {
         if balance + downward_threshold < validator.effective_balance
             || validator.effective_balance + upward_threshold < balance
         {
@@ -499,16 +538,34 @@ pub fn process_historical_roots_update<
     >,
     context: &Context,
 ) -> Result<(), Error> {
-    let next_epoch = get_current_epoch(state, context) + 1;
+    let current_epoch = get_current_epoch(state, context);
+    update_historical_roots(
+        &mut state.historical_roots,
+        &mut state.block_roots,
+        &mut state.state_roots,
+        current_epoch,
+        context,
+    )
+}
+
+pub(crate) fn update_historical_roots<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+>(
+    historical_roots: &mut List<Root, HISTORICAL_ROOTS_LIMIT>,
+    block_roots: &mut Vector<Root, SLOTS_PER_HISTORICAL_ROOT>,
+    state_roots: &mut Vector<Root, SLOTS_PER_HISTORICAL_ROOT>,
+    current_epoch: Epoch,
+    context: &Context,
+) -> Result<(), Error> {
+    let next_epoch = current_epoch + 1;
     let epochs_per_historical_root = context.slots_per_historical_root / context.slots_per_epoch;
     if next_epoch % epochs_per_historical_root == 0 {
         let mut historical_batch = HistoricalBatchAccumulator {
-            block_roots_root: state.block_roots.hash_tree_root()?,
-            state_roots_root: state.state_roots.hash_tree_root()?,
+            block_roots_root: block_roots.hash_tree_root()?,
+            state_roots_root: state_roots.hash_tree_root()?,
         };
-        state
-            .historical_roots
-            .push(historical_batch.hash_tree_root()?)
+        historical_roots.push(historical_batch.hash_tree_root()?)
     }
     Ok(())
 }
@@ -598,52 +655,74 @@ pub fn weigh_justification_and_finalization<
     current_epoch_target_balance: Gwei,
     context: &Context,
 ) -> Result<(), Error> {
-    let previous_epoch = get_current_epoch(state, context);
+    let previous_epoch = get_previous_epoch(state, context);
     let current_epoch = get_current_epoch(state, context);
-    let old_previous_justified_checkpoint = state.previous_justified_checkpoint.clone();
-    let old_current_justified_checkpoint = state.current_justified_checkpoint.clone();
+    let previous_epoch_target = Checkpoint {
+        epoch: previous_epoch,
+        root: *get_block_root(state, previous_epoch, context)?,
+    };
+    let current_epoch_target = Checkpoint {
+        epoch: current_epoch,
+        root: *get_block_root(state, current_epoch, context)?,
+    };
+    justify_and_finalize(
+        &mut state.justification_bits,
+        &mut state.previous_justified_checkpoint,
+        &mut state.current_justified_checkpoint,
+        &mut state.finalized_checkpoint,
+        total_active_balance,
+        (previous_epoch_target, previous_epoch_target_balance),
+        (current_epoch_target, current_epoch_target_balance),
+    );
+    Ok(())
+}
+
+/// Justify each target checkpoint attested to by a supermajority of `total_active_balance`
+/// and finalize accordingly; the current target carries the current epoch.
+pub(crate) fn justify_and_finalize(
+    justification_bits: &mut Bitvector<JUSTIFICATION_BITS_LENGTH>,
+    previous_justified_checkpoint: &mut Checkpoint,
+    current_justified_checkpoint: &mut Checkpoint,
+    finalized_checkpoint: &mut Checkpoint,
+    total_active_balance: Gwei,
+    (previous_epoch_target, previous_epoch_target_balance): (Checkpoint, Gwei),
+    (current_epoch_target, current_epoch_target_balance): (Checkpoint, Gwei),
+) {
+    let current_epoch = current_epoch_target.epoch;
+    let old_previous_justified_checkpoint = previous_justified_checkpoint.clone();
+    let old_current_justified_checkpoint = current_justified_checkpoint.clone();
 
     // Process justifications
-    state.previous_justified_checkpoint = state.current_justified_checkpoint.clone();
-    state
-        .justification_bits
-        .copy_within(..JUSTIFICATION_BITS_LENGTH - 1, 1);
-    state.justification_bits.set(0, false);
+    *previous_justified_checkpoint = current_justified_checkpoint.clone();
+    justification_bits.copy_within(..JUSTIFICATION_BITS_LENGTH - 1, 1);
+    justification_bits.set(0, false);
     if previous_epoch_target_balance * 3 >= total_active_balance * 2 {
-        state.current_justified_checkpoint = Checkpoint {
-            epoch: previous_epoch,
-            root: *get_block_root(state, previous_epoch, context)?,
-        };
-        state.justification_bits.set(1, true);
+        *current_justified_checkpoint = previous_epoch_target;
+        justification_bits.set(1, true);
     }
     if current_epoch_target_balance * 3 >= total_active_balance * 2 {
-        state.current_justified_checkpoint = Checkpoint {
-            epoch: current_epoch,
-            root: *get_block_root(state, current_epoch, context)?,
-        };
-        state.justification_bits.set(0, true);
+        *current_justified_checkpoint = current_epoch_target;
+        justification_bits.set(0, true);
     }
 
     // Process finalizations
-    let bits = &state.justification_bits;
+    let bits = &*justification_bits;
     // The 2nd/3rd/4th most recent epochs are justified, the 2nd using the 4th as source
     if bits[1..4].all() && old_previous_justified_checkpoint.epoch + 3 == current_epoch {
-        state.finalized_checkpoint = old_previous_justified_checkpoint.clone();
+        *finalized_checkpoint = old_previous_justified_checkpoint.clone();
     }
     // The 2nd/3rd most recent epochs are justified, the 2nd using the 3rd as source
     if bits[1..3].all() && old_previous_justified_checkpoint.epoch + 2 == current_epoch {
-        state.finalized_checkpoint = old_previous_justified_checkpoint;
+        *finalized_checkpoint = old_previous_justified_checkpoint;
     }
     // The 1st/2nd/3rd most recent epochs are justified, the 1st using the 3rd as source
     if bits[0..3].all() && old_current_justified_checkpoint.epoch + 2 == current_epoch {
-        state.finalized_checkpoint = old_current_justified_checkpoint.clone();
+        *finalized_checkpoint = old_current_justified_checkpoint.clone();
     }
     // The 1st/2nd most recent epochs are justified, the 1st using the 2nd as source
     if bits[0..2].all() && old_current_justified_checkpoint.epoch + 1 == current_epoch {
-        state.finalized_checkpoint = old_current_justified_checkpoint;
+        *finalized_checkpoint = old_current_justified_checkpoint;
     }
-
-    Ok(())
 }
 
 pub fn get_base_reward<
@@ -776,8 +855,14 @@ pub fn get_eligible_validator_indices<
     context: &Context,
 ) -> impl Iterator<Item = ValidatorIndex> + 'a {
     let previous_epoch = get_previous_epoch(state, context);
-    state
-        .validators
+    eligible_validator_indices(&state.validators, previous_epoch)
+}
+
+pub(crate) fn eligible_validator_indices(
+    validators: &[Validator],
+    previous_epoch: Epoch,
+) -> impl Iterator<Item = ValidatorIndex> + '_ {
+    validators
         .iter()
         .enumerate()
         .filter_map(move |(i, validator)| {
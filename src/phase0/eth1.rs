@@ -0,0 +1,621 @@
+use crate::phase0 as spec;
+
+use crate::crypto::hash;
+use crate::primitives::{Bytes32, Hash32, Root, Slot, GENESIS_SLOT};
+use crate::state_transition::{Context, Error};
+use spec::{BeaconState, Deposit, DepositData, Eth1Data, DEPOSIT_CONTRACT_TREE_DEPTH};
+use ssz_rs::prelude::*;
+
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Eth1Block {
+    pub timestamp: u64,
+    pub deposit_root: Root,
+    pub deposit_count: u64,
+    pub block_hash: Hash32,
+}
+
+pub fn get_eth1_data(block: &Eth1Block) -> Eth1Data {
+    Eth1Data {
+        deposit_root: block.deposit_root,
+        deposit_count: block.deposit_count,
+        block_hash: block.block_hash.clone(),
+    }
+}
+
+/// A source of eth1 blocks and deposits, as observed by following the
+/// deposit contract on the eth1 chain.
+pub trait Eth1Cache {
+    /// Return the blocks with a timestamp in `[start_time, end_time]`,
+    /// ordered by increasing timestamp.
+    fn blocks_in_range(&self, start_time: u64, end_time: u64) -> Vec<Eth1Block>;
+
+    /// Return the number of deposits observed so far.
+    fn deposit_count(&self) -> u64;
+
+    /// Return the `DepositData` of the deposit with the given `index`, if observed.
+    fn deposit_data(&self, index: u64) -> Option<DepositData>;
+
+    /// Return the `DepositTree` over all deposits observed so far.
+    ///
+    /// Implementations should extend the tree as deposits are observed
+    /// rather than rebuilding it on each call.
+    fn deposit_tree(&self) -> &DepositTree;
+}
+
+/// An `Eth1Cache` held entirely in memory, e.g. for testing.
+#[derive(Default, Debug)]
+pub struct InMemoryEth1Cache {
+    blocks: Vec<Eth1Block>,
+    deposits: Vec<DepositData>,
+    deposit_tree: DepositTree,
+}
+
+impl InMemoryEth1Cache {
+    /// Blocks are kept ordered by timestamp regardless of the order they are inserted.
+    pub fn insert_block(&mut self, block: Eth1Block) {
+        let index = self
+            .blocks
+            .partition_point(|b| b.timestamp <= block.timestamp);
+        self.blocks.insert(index, block);
+    }
+
+    pub fn insert_deposit(&mut self, deposit_data: DepositData) -> Result<(), Error> {
+        self.deposit_tree.push(&deposit_data)?;
+        self.deposits.push(deposit_data);
+        Ok(())
+    }
+}
+
+impl Eth1Cache for InMemoryEth1Cache {
+    fn blocks_in_range(&self, start_time: u64, end_time: u64) -> Vec<Eth1Block> {
+        self.blocks
+            .iter()
+            .filter(|block| start_time <= block.timestamp && block.timestamp <= end_time)
+            .cloned()
+            .collect()
+    }
+
+    fn deposit_count(&self) -> u64 {
+        self.deposits.len() as u64
+    }
+
+    fn deposit_data(&self, index: u64) -> Option<DepositData> {
+        self.deposits.get(index as usize).cloned()
+    }
+
+    fn deposit_tree(&self) -> &DepositTree {
+        &self.deposit_tree
+    }
+}
+
+pub fn compute_time_at_slot<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    slot: Slot,
+    context: &Context,
+) -> u64 {
+    let slots_since_genesis = slot - GENESIS_SLOT;
+    state.genesis_time + slots_since_genesis * context.seconds_per_slot
+}
+
+pub fn voting_period_start_time<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    context: &Context,
+) -> u64 {
+    voting_period_start(state.genesis_time, state.slot, context)
+}
+
+pub(crate) fn voting_period_start(genesis_time: u64, slot: Slot, context: &Context) -> u64 {
+    let slots_per_voting_period = context.epochs_per_eth1_voting_period * context.slots_per_epoch;
+    let eth1_voting_period_start_slot = slot - slot % slots_per_voting_period;
+    let slots_since_genesis = eth1_voting_period_start_slot - GENESIS_SLOT;
+    genesis_time + slots_since_genesis * context.seconds_per_slot
+}
+
+pub fn is_candidate_block(block: &Eth1Block, period_start: u64, context: &Context) -> bool {
+    let follow_time = context.seconds_per_eth1_block * context.eth1_follow_distance;
+    block.timestamp + follow_time <= period_start
+        && block.timestamp + follow_time * 2 >= period_start
+}
+
+pub fn get_eth1_vote<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    eth1_chain: &[Eth1Block],
+    context: &Context,
+) -> Eth1Data {
+    eth1_vote(
+        state.genesis_time,
+        state.slot,
+        &state.eth1_data,
+        &state.eth1_data_votes,
+        eth1_chain,
+        context,
+    )
+}
+
+// NOTE: takes only the fields of the `BeaconState` it uses, so that later forks share it
+// with their own `BeaconState`
+pub(crate) fn eth1_vote(
+    genesis_time: u64,
+    slot: Slot,
+    eth1_data: &Eth1Data,
+    eth1_data_votes: &[Eth1Data],
+    eth1_chain: &[Eth1Block],
+    context: &Context,
+) -> Eth1Data {
+    let period_start = voting_period_start(genesis_time, slot, context);
+    let votes_to_consider = eth1_chain
+        .iter()
+        .filter(|block| {
+            is_candidate_block(block, period_start, context)
+                && block.deposit_count >= eth1_data.deposit_count
+        })
+        .map(get_eth1_data)
+        .collect::<Vec<_>>();
+
+    let valid_votes = eth1_data_votes
+        .iter()
+        .filter(|&vote| votes_to_consider.contains(vote))
+        .collect::<Vec<_>>();
+
+    // NOTE: ties are broken in favor of the vote cast first
+    let mut best_vote: Option<(&Eth1Data, usize)> = None;
+    for &vote in &valid_votes {
+        let count = valid_votes.iter().filter(|&&other| other == vote).count();
+        if best_vote
+            .map(|(_, best_count)| count > best_count)
+            .unwrap_or(true)
+        {
+            best_vote = Some((vote, count));
+        }
+    }
+
+    best_vote.map(|(vote, _)| vote.clone()).unwrap_or_else(|| {
+        votes_to_consider
+            .last()
+            .cloned()
+            .unwrap_or_else(|| eth1_data.clone())
+    })
+}
+
+/// Compute the vote for the `Eth1Data` of a block built on `state`
+/// from the blocks known to the `cache`.
+pub fn get_eth1_vote_from_cache<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    cache: &impl Eth1Cache,
+    context: &Context,
+) -> Eth1Data {
+    let period_start = voting_period_start_time(state, context);
+    let follow_time = context.seconds_per_eth1_block * context.eth1_follow_distance;
+    let eth1_chain = cache.blocks_in_range(
+        period_start.saturating_sub(follow_time * 2),
+        period_start.saturating_sub(follow_time),
+    );
+    get_eth1_vote(state, &eth1_chain, context)
+}
+
+/// An incremental merkle tree over the deposits made to the deposit contract,
+/// used to produce the proofs for a block's `Deposit`s.
+///
+/// Like the deposit contract, the tree only computes the nodes whose subtrees are
+/// complete as deposits are pushed, so pushing a deposit hashes one node per level
+/// at most. Roots and proofs against an earlier deposit count are computed from the
+/// complete nodes, only hashing the nodes on the right edge of the truncated tree.
+#[derive(Debug, Clone)]
+pub struct DepositTree {
+    // the nodes of each level with complete subtrees, starting with the leaves
+    layers: Vec<Vec<Root>>,
+    zero_hashes: Vec<Root>,
+}
+
+impl Default for DepositTree {
+    fn default() -> Self {
+        Self {
+            layers: vec![vec![]; DEPOSIT_CONTRACT_TREE_DEPTH + 1],
+            zero_hashes: zero_hashes(),
+        }
+    }
+}
+
+fn hash_pair(left: &Root, right: &Root) -> Root {
+    let mut input = Vec::with_capacity(64);
+    input.extend_from_slice(&left.as_bytes()[..]);
+    input.extend_from_slice(&right.as_bytes()[..]);
+    Node::from_bytes(hash(input).as_ref().try_into().unwrap())
+}
+
+fn zero_hashes() -> Vec<Root> {
+    let mut zero_hashes = vec![Node::default()];
+    for depth in 0..DEPOSIT_CONTRACT_TREE_DEPTH {
+        let next = hash_pair(&zero_hashes[depth], &zero_hashes[depth]);
+        zero_hashes.push(next);
+    }
+    zero_hashes
+}
+
+fn length_node(length: usize) -> Root {
+    let mut bytes = [0u8; 32];
+    bytes[..8].copy_from_slice(&(length as u64).to_le_bytes());
+    Node::from_bytes(bytes)
+}
+
+impl DepositTree {
+    pub fn push(&mut self, deposit_data: &DepositData) -> Result<(), Error> {
+        let leaf = deposit_data.clone().hash_tree_root()?;
+        self.layers[0].push(leaf);
+        // complete the parents of the new leaf while it is a right child
+        let mut index = self.layers[0].len() - 1;
+        for depth in 0..DEPOSIT_CONTRACT_TREE_DEPTH {
+            if index % 2 == 0 {
+                break;
+            }
+            let parent = hash_pair(&self.layers[depth][index - 1], &self.layers[depth][index]);
+            self.layers[depth + 1].push(parent);
+            index /= 2;
+        }
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.layers[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers[0].is_empty()
+    }
+
+    /// Return the deposit root after the first `deposit_count` deposits,
+    /// including the mix-in of the `deposit_count`.
+    pub fn root(&self, deposit_count: usize) -> Result<Root, Error> {
+        self.check_deposit_count(deposit_count)?;
+        let root = self.node(DEPOSIT_CONTRACT_TREE_DEPTH, 0, deposit_count);
+        Ok(hash_pair(&root, &length_node(deposit_count)))
+    }
+
+    /// Return the proof of the deposit at `index` against the deposit root after
+    /// the first `deposit_count` deposits, as expected in a `Deposit`.
+    pub fn proof(&self, index: usize, deposit_count: usize) -> Result<Vec<Root>, Error> {
+        self.check_deposit_count(deposit_count)?;
+        if index >= deposit_count {
+            return Err(Error::OutOfBounds {
+                requested: index,
+                bound: deposit_count,
+            });
+        }
+        let mut branch = (0..DEPOSIT_CONTRACT_TREE_DEPTH)
+            .map(|depth| self.node(depth, (index >> depth) ^ 1, deposit_count))
+            .collect::<Vec<_>>();
+        branch.push(length_node(deposit_count));
+        Ok(branch)
+    }
+
+    fn check_deposit_count(&self, deposit_count: usize) -> Result<(), Error> {
+        if deposit_count > self.len() {
+            return Err(Error::OutOfBounds {
+                requested: deposit_count,
+                bound: self.len(),
+            });
+        }
+        Ok(())
+    }
+
+    // Return the node at `depth` above the leaves and position `index` in the tree
+    // over the first `deposit_count` deposits.
+    fn node(&self, depth: usize, index: usize, deposit_count: usize) -> Root {
+        let first_leaf = index << depth;
+        if first_leaf >= deposit_count {
+            return self.zero_hashes[depth];
+        }
+        if first_leaf + (1 << depth) <= deposit_count {
+            return self.layers[depth][index];
+        }
+        // NOTE: only reached for the one node per level covering the last deposit
+        let left = self.node(depth - 1, 2 * index, deposit_count);
+        let right = self.node(depth - 1, 2 * index + 1, deposit_count);
+        hash_pair(&left, &right)
+    }
+}
+
+/// Select the deposits a block built on `state` must include, with proofs
+/// against `state.eth1_data.deposit_root`.
+pub fn get_deposits_for_block<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const PENDING_ATTESTATIONS_BOUND: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+    >,
+    cache: &impl Eth1Cache,
+    context: &Context,
+) -> Result<Vec<Deposit>, Error> {
+    let deposit_count = state.eth1_data.deposit_count;
+    let start = state.eth1_deposit_index;
+    let end = u64::min(deposit_count, start + context.max_deposits as u64);
+    if start >= end {
+        return Ok(vec![]);
+    }
+
+    let tree = cache.deposit_tree();
+    let mut deposits = Vec::with_capacity((end - start) as usize);
+    for index in start..end {
+        let data = cache.deposit_data(index).ok_or(Error::OutOfBounds {
+            requested: index as usize,
+            bound: cache.deposit_count() as usize,
+        })?;
+        let proof = tree
+            .proof(index as usize, deposit_count as usize)?
+            .iter()
+            .map(|node| Bytes32::try_from(&node.as_bytes()[..]).expect("correct input"))
+            .collect::<Vec<_>>();
+        deposits.push(Deposit {
+            proof: Vector::from_iter(proof),
+            data,
+        });
+    }
+    Ok(deposits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::phase0::mainnet;
+
+    const DEPOSIT_DATA_LIST_BOUND: usize = 2usize.pow(DEPOSIT_CONTRACT_TREE_DEPTH as u32);
+
+    fn eth1_block(timestamp: u64, deposit_count: u64) -> Eth1Block {
+        Eth1Block {
+            timestamp,
+            deposit_count,
+            block_hash: Hash32::try_from([timestamp as u8; 32].as_ref()).unwrap(),
+            ..Default::default()
+        }
+    }
+
+    // returns a state at the start of the second voting period and that period's start time
+    fn state_in_voting_period(context: &Context) -> (mainnet::BeaconState, u64) {
+        let state = mainnet::BeaconState {
+            genesis_time: 100_000,
+            slot: context.epochs_per_eth1_voting_period * context.slots_per_epoch,
+            eth1_data: Eth1Data {
+                deposit_count: 1,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let period_start = voting_period_start_time(&state, context);
+        (state, period_start)
+    }
+
+    #[test]
+    fn test_eth1_vote_follow_distance() {
+        let context = Context::for_mainnet();
+        let (state, period_start) = state_in_voting_period(&context);
+        let follow_time = context.seconds_per_eth1_block * context.eth1_follow_distance;
+
+        let too_old = eth1_block(period_start - follow_time * 2 - 1, 4);
+        let oldest = eth1_block(period_start - follow_time * 2, 2);
+        let missing_deposits = eth1_block(period_start - follow_time - 2, 0);
+        let newest = eth1_block(period_start - follow_time, 3);
+        let too_recent = eth1_block(period_start - follow_time + 1, 4);
+        assert!(!is_candidate_block(&too_old, period_start, &context));
+        assert!(is_candidate_block(&oldest, period_start, &context));
+        assert!(is_candidate_block(&newest, period_start, &context));
+        assert!(!is_candidate_block(&too_recent, period_start, &context));
+
+        // without votes, the newest candidate is the default vote
+        let eth1_chain = [
+            too_old.clone(),
+            oldest,
+            newest.clone(),
+            missing_deposits.clone(),
+            too_recent,
+        ];
+        let vote = get_eth1_vote(&state, &eth1_chain[..3], &context);
+        assert_eq!(vote, get_eth1_data(&newest));
+
+        // blocks that do not include the deposits already processed are not candidates
+        let vote = get_eth1_vote(&state, &eth1_chain[..4], &context);
+        assert_eq!(vote, get_eth1_data(&newest));
+
+        // without candidates, the vote repeats the current `eth1_data`
+        let vote = get_eth1_vote(&state, &[too_old, missing_deposits], &context);
+        assert_eq!(vote, state.eth1_data);
+
+        let mut cache = InMemoryEth1Cache::default();
+        for block in eth1_chain.into_iter().rev() {
+            cache.insert_block(block);
+        }
+        let vote = get_eth1_vote_from_cache(&state, &cache, &context);
+        assert_eq!(vote, get_eth1_data(&newest));
+    }
+
+    #[test]
+    fn test_eth1_vote_majority() {
+        let context = Context::for_mainnet();
+        let (mut state, period_start) = state_in_voting_period(&context);
+        let follow_time = context.seconds_per_eth1_block * context.eth1_follow_distance;
+
+        let older = eth1_block(period_start - follow_time * 2, 1);
+        let newer = eth1_block(period_start - follow_time, 2);
+        let too_recent = eth1_block(period_start, 3);
+        let eth1_chain = [older.clone(), newer.clone(), too_recent.clone()];
+
+        // votes for blocks outside of the window are not counted
+        for block in [&too_recent, &too_recent, &older, &newer, &older] {
+            state.eth1_data_votes.push(get_eth1_data(block));
+        }
+        let vote = get_eth1_vote(&state, &eth1_chain, &context);
+        assert_eq!(vote, get_eth1_data(&older));
+
+        // ties are broken by the first vote
+        state.eth1_data_votes.push(get_eth1_data(&newer));
+        let vote = get_eth1_vote(&state, &eth1_chain, &context);
+        assert_eq!(vote, get_eth1_data(&older));
+    }
+
+    #[test]
+    fn test_deposits_for_block_proofs() {
+        let context = Context::for_mainnet();
+        let mut cache = InMemoryEth1Cache::default();
+        let mut leaves = List::<DepositData, DEPOSIT_DATA_LIST_BOUND>::default();
+        for amount in 1..=5 {
+            let deposit_data = DepositData {
+                amount,
+                ..Default::default()
+            };
+            leaves.push(deposit_data.clone());
+            cache.insert_deposit(deposit_data).unwrap();
+        }
+        // a deposit observed after the one voted for is not included
+        let deposit_root = leaves.hash_tree_root().unwrap();
+        cache.insert_deposit(DepositData::default()).unwrap();
+        assert_eq!(cache.deposit_tree().root(5).unwrap(), deposit_root);
+
+        let state = mainnet::BeaconState {
+            eth1_data: Eth1Data {
+                deposit_root,
+                deposit_count: 5,
+                ..Default::default()
+            },
+            eth1_deposit_index: 2,
+            ..Default::default()
+        };
+        let deposits = get_deposits_for_block(&state, &cache, &context).unwrap();
+        assert_eq!(deposits.len(), 3);
+        for (index, deposit) in (2..).zip(deposits.iter()) {
+            assert_eq!(deposit.data.amount, index as u64 + 1);
+            let leaf = deposit.data.clone().hash_tree_root().unwrap();
+            let branch = deposit
+                .proof
+                .iter()
+                .map(|node| Node::from_bytes(node.as_ref().try_into().unwrap()))
+                .collect::<Vec<_>>();
+            assert!(is_valid_merkle_branch(
+                &leaf,
+                branch.iter(),
+                DEPOSIT_CONTRACT_TREE_DEPTH + 1,
+                index,
+                &deposit_root
+            ));
+        }
+    }
+
+    #[test]
+    fn test_deposit_tree_against_earlier_counts() {
+        let mut tree = DepositTree::default();
+        let mut leaves = List::<DepositData, DEPOSIT_DATA_LIST_BOUND>::default();
+        let mut roots = vec![leaves.hash_tree_root().unwrap()];
+        for amount in 1..=13 {
+            let deposit_data = DepositData {
+                amount,
+                ..Default::default()
+            };
+            tree.push(&deposit_data).unwrap();
+            leaves.push(deposit_data);
+            roots.push(leaves.hash_tree_root().unwrap());
+        }
+        assert_eq!(tree.len(), 13);
+
+        for (deposit_count, root) in roots.iter().enumerate() {
+            assert_eq!(&tree.root(deposit_count).unwrap(), root);
+            for index in 0..deposit_count {
+                let leaf = leaves[index].clone().hash_tree_root().unwrap();
+                let branch = tree.proof(index, deposit_count).unwrap();
+                assert!(is_valid_merkle_branch(
+                    &leaf,
+                    branch.iter(),
+                    DEPOSIT_CONTRACT_TREE_DEPTH + 1,
+                    index,
+                    root
+                ));
+            }
+        }
+
+        assert!(tree.root(14).is_err());
+        assert!(tree.proof(3, 3).is_err());
+    }
+}
@@ -4,7 +4,7 @@ use crate::primitives::{Gwei, Hash32, GENESIS_EPOCH};
 use crate::state_transition::{Context, Error};
 use spec::{
     get_active_validator_indices, process_deposit, BeaconBlock, BeaconBlockBody, BeaconBlockHeader,
-    BeaconState, Deposit, DepositData, Eth1Data, Fork, DEPOSIT_CONTRACT_TREE_DEPTH,
+    BeaconState, Deposit, DepositData, Eth1Data, Fork, Validator, DEPOSIT_CONTRACT_TREE_DEPTH,
 };
 use ssz_rs::prelude::*;
 
@@ -84,9 +84,20 @@ pub fn initialize_beacon_state_from_eth1<
         process_deposit(&mut state, deposit, context)?;
     }
 
-    for i in 0..state.validators.len() {
-        let validator = &mut state.validators[i];
-        let balance = state.balances[i];
+    activate_genesis_validators(&mut state.validators, &state.balances, context);
+
+    state.genesis_validators_root = state.validators.hash_tree_root()?;
+
+    Ok(state)
+}
+
+pub(crate) fn activate_genesis_validators<const VALIDATOR_REGISTRY_LIMIT: usize>(
+    validators: &mut List<Validator, VALIDATOR_REGISTRY_LIMIT>,
+    balances: &[Gwei],
+    context: &Context,
+) {
+    for (i, &balance) in balances.iter().enumerate() {
+        let validator = &mut validators[i];
         let effective_balance = Gwei::min(
             balance - balance % context.effective_balance_increment,
             context.max_effective_balance,
@@ -97,10 +108,6 @@ pub fn initialize_beacon_state_from_eth1<
             validator.activation_epoch = GENESIS_EPOCH;
         }
     }
-
-    state.genesis_validators_root = state.validators.hash_tree_root()?;
-
-    Ok(state)
 }
 
 pub fn is_valid_genesis_state<
@@ -10,8 +10,8 @@ use crate::state_transition::{
     InvalidOperation,
 };
 use spec::{
-    Attestation, AttestationData, BeaconState, ForkData, IndexedAttestation, SignedBeaconBlock,
-    SigningData, Validator,
+    Attestation, AttestationData, BeaconState, Fork, ForkData, IndexedAttestation,
+    SignedBeaconBlock, SigningData, Validator,
 };
 use ssz_rs::prelude::*;
 use std::cmp;
@@ -87,6 +87,22 @@ pub fn is_valid_indexed_attestation<
     >,
     indexed_attestation: &mut IndexedAttestation<MAX_VALIDATORS_PER_COMMITTEE>,
     context: &Context,
+) -> Result<(), Error> {
+    verify_indexed_attestation(
+        &state.validators,
+        &state.fork,
+        state.genesis_validators_root,
+        indexed_attestation,
+        context,
+    )
+}
+
+pub(crate) fn verify_indexed_attestation<const MAX_VALIDATORS_PER_COMMITTEE: usize>(
+    validators: &[Validator],
+    fork: &Fork,
+    genesis_validators_root: Root,
+    indexed_attestation: &mut IndexedAttestation<MAX_VALIDATORS_PER_COMMITTEE>,
+    context: &Context,
 ) -> Result<(), Error> {
     let attesting_indices = &indexed_attestation.attesting_indices;
 
@@ -129,8 +145,7 @@ pub fn is_valid_indexed_attestation<
             )),
         ));
     }
-    let public_keys = state
-        .validators
+    let public_keys = validators
         .iter()
         .enumerate()
         .filter_map(|(i, v)| {
@@ -142,10 +157,11 @@ pub fn is_valid_indexed_attestation<
         })
         .collect::<Vec<_>>();
 
-    let domain = get_domain(
-        state,
+    let domain = domain(
+        fork,
+        genesis_validators_root,
         DomainType::BeaconAttester,
-        Some(indexed_attestation.data.target.epoch),
+        indexed_attestation.data.target.epoch,
         context,
     )?;
     let signing_root = compute_signing_root(&mut indexed_attestation.data, domain)?;
@@ -241,16 +257,32 @@ pub fn get_domain<
     context: &Context,
 ) -> Result<Domain, Error> {
     let epoch = epoch.unwrap_or_else(|| get_current_epoch(state, context));
-    let fork_version = if epoch < state.fork.epoch {
-        state.fork.previous_version
+    domain(
+        &state.fork,
+        state.genesis_validators_root,
+        domain_type,
+        epoch,
+        context,
+    )
+}
+
+pub(crate) fn domain(
+    fork: &Fork,
+    genesis_validators_root: Root,
+    domain_type: DomainType,
+    epoch: Epoch,
+    context: &Context,
+) -> Result<Domain, Error> {
+    let fork_version = if epoch < fork.epoch {
+        fork.previous_version
     } else {
-        state.fork.current_version
+        fork.current_version
     };
 
     compute_domain(
         domain_type,
         Some(fork_version),
-        Some(state.genesis_validators_root),
+        Some(genesis_validators_root),
         context,
     )
 }
@@ -355,6 +387,15 @@ pub fn compute_proposer_index<
     indices: &[ValidatorIndex],
     seed: &Bytes32,
     context: &Context,
+) -> Result<ValidatorIndex, Error> {
+    select_proposer(&state.validators, indices, seed, context)
+}
+
+pub(crate) fn select_proposer(
+    validators: &[Validator],
+    indices: &[ValidatorIndex],
+    seed: &Bytes32,
+    context: &Context,
 ) -> Result<ValidatorIndex, Error> {
     if indices.is_empty() {
         return Err(Error::CollectionCannotBeEmpty);
@@ -373,7 +414,7 @@ pub fn compute_proposer_index<
         hash_input[32..].copy_from_slice(&i_bytes);
         let random_byte = hash(hash_input).as_ref()[(i % 32)] as u64;
 
-        let effective_balance = state.validators[candidate_index].effective_balance;
+        let effective_balance = validators[candidate_index].effective_balance;
         if effective_balance * max_byte >= context.max_effective_balance * random_byte {
             return Ok(candidate_index);
         }
@@ -388,12 +429,12 @@ pub fn compute_committee(
     count: usize,
     context: &Context,
 ) -> Result<Vec<ValidatorIndex>, Error> {
-    let mut committee = vec![0usize; count];
     let start = (indices.len() * index) / count;
     let end = (indices.len()) * (index + 1) / count;
+    let mut committee = Vec::with_capacity(end - start);
     for i in start..end {
         let index = compute_shuffled_index(i, indices.len(), seed, context)?;
-        committee[index] = indices[index];
+        committee.push(indices[index]);
     }
     Ok(committee)
 }
@@ -526,14 +567,23 @@ pub fn get_block_root_at_slot<
     >,
     slot: Slot,
 ) -> Result<&Root, Error> {
-    if slot < state.slot || state.slot <= (slot + SLOTS_PER_HISTORICAL_ROOT as Slot) {
+    block_root_at_slot(&state.block_roots, state.slot, slot)
+}
+
+pub(crate) fn block_root_at_slot(
+    block_roots: &[Root],
+    state_slot: Slot,
+    slot: Slot,
+) -> Result<&Root, Error> {
+    let slots_per_historical_root = block_roots.len() as Slot;
+    if !(slot < state_slot && state_slot <= slot + slots_per_historical_root) {
         return Err(Error::SlotOutOfRange {
             requested: slot,
-            lower_bound: state.slot - 1,
-            upper_bound: state.slot + SLOTS_PER_HISTORICAL_ROOT as Slot,
+            lower_bound: state_slot.saturating_sub(slots_per_historical_root),
+            upper_bound: state_slot,
         });
     }
-    Ok(&state.block_roots[(slot as usize % SLOTS_PER_HISTORICAL_ROOT)])
+    Ok(&block_roots[(slot % slots_per_historical_root) as usize])
 }
 
 pub fn get_randao_mix<
@@ -584,9 +634,18 @@ pub fn get_active_validator_indices<
     >,
     epoch: Epoch,
 ) -> Vec<ValidatorIndex> {
-    let mut active = Vec::with_capacity(state.validators.len());
+    active_validator_indices(&state.validators, epoch)
+}
+
+// NOTE: helpers like this one take only the fields of the `BeaconState` they read,
+// so that later forks can share them with their own `BeaconState`
+pub(crate) fn active_validator_indices(
+    validators: &[Validator],
+    epoch: Epoch,
+) -> Vec<ValidatorIndex> {
+    let mut active = Vec::with_capacity(validators.len());
 
-    for (i, v) in state.validators.iter().enumerate() {
+    for (i, v) in validators.iter().enumerate() {
         if is_active_validator(v, epoch) {
             active.push(i)
         }
@@ -616,8 +675,19 @@ pub fn get_validator_churn_limit<
     >,
     context: &Context,
 ) -> usize {
-    let active_validator_indices =
-        get_active_validator_indices(state, get_current_epoch(state, context));
+    validator_churn_limit(
+        &state.validators,
+        get_current_epoch(state, context),
+        context,
+    )
+}
+
+pub(crate) fn validator_churn_limit(
+    validators: &[Validator],
+    epoch: Epoch,
+    context: &Context,
+) -> usize {
+    let active_validator_indices = active_validator_indices(validators, epoch);
     u64::max(
         context.min_per_epoch_churn_limit,
         active_validator_indices.len() as u64 / context.churn_limit_quotient,
@@ -647,10 +717,19 @@ pub fn get_seed<
     epoch: Epoch,
     domain_type: DomainType,
     context: &Context,
+) -> Bytes32 {
+    seed(&state.randao_mixes, epoch, domain_type, context)
+}
+
+pub(crate) fn seed(
+    randao_mixes: &[Bytes32],
+    epoch: Epoch,
+    domain_type: DomainType,
+    context: &Context,
 ) -> Bytes32 {
     let mix_epoch =
         epoch + (context.epochs_per_historical_vector as u64 - context.min_seed_lookahead) - 1;
-    let mix = get_randao_mix(state, mix_epoch);
+    let mix = &randao_mixes[mix_epoch as usize % randao_mixes.len()];
     let mut input = [0u8; 44];
     input[..4].copy_from_slice(&domain_type.as_bytes());
     input[4..12].copy_from_slice(&epoch.to_le_bytes());
@@ -680,12 +759,20 @@ pub fn get_committee_count_per_slot<
     >,
     epoch: Epoch,
     context: &Context,
+) -> usize {
+    committee_count_per_slot(&state.validators, epoch, context)
+}
+
+pub(crate) fn committee_count_per_slot(
+    validators: &[Validator],
+    epoch: Epoch,
+    context: &Context,
 ) -> usize {
     u64::max(
         1,
         u64::min(
             context.max_committees_per_slot,
-            get_active_validator_indices(state, epoch).len() as u64
+            active_validator_indices(validators, epoch).len() as u64
                 / context.slots_per_epoch
                 / context.target_committee_size,
         ),
@@ -715,11 +802,21 @@ pub fn get_beacon_committee<
     slot: Slot,
     index: CommitteeIndex,
     context: &Context,
+) -> Result<Vec<ValidatorIndex>, Error> {
+    beacon_committee(&state.validators, &state.randao_mixes, slot, index, context)
+}
+
+pub(crate) fn beacon_committee(
+    validators: &[Validator],
+    randao_mixes: &[Bytes32],
+    slot: Slot,
+    index: CommitteeIndex,
+    context: &Context,
 ) -> Result<Vec<ValidatorIndex>, Error> {
     let epoch = compute_epoch_at_slot(slot, context);
-    let committees_per_slot = get_committee_count_per_slot(state, epoch, context);
-    let indices = get_active_validator_indices(state, epoch);
-    let seed = get_seed(state, epoch, DomainType::BeaconAttester, context);
+    let committees_per_slot = committee_count_per_slot(validators, epoch, context);
+    let indices = active_validator_indices(validators, epoch);
+    let seed = seed(randao_mixes, epoch, DomainType::BeaconAttester, context);
     let index = (slot % context.slots_per_epoch) * committees_per_slot as u64 + index as u64;
     let count = committees_per_slot as u64 * context.slots_per_epoch;
     compute_committee(&indices, &seed, index as usize, count as usize, context)
@@ -747,14 +844,23 @@ pub fn get_beacon_proposer_index<
     >,
     context: &Context,
 ) -> Result<ValidatorIndex, Error> {
-    let epoch = get_current_epoch(state, context);
+    beacon_proposer_index(&state.validators, &state.randao_mixes, state.slot, context)
+}
+
+pub(crate) fn beacon_proposer_index(
+    validators: &[Validator],
+    randao_mixes: &[Bytes32],
+    slot: Slot,
+    context: &Context,
+) -> Result<ValidatorIndex, Error> {
+    let epoch = compute_epoch_at_slot(slot, context);
     let mut input = [0u8; 40];
     input[..32]
-        .copy_from_slice(get_seed(state, epoch, DomainType::BeaconProposer, context).as_ref());
-    input[32..40].copy_from_slice(&state.slot.to_le_bytes());
+        .copy_from_slice(seed(randao_mixes, epoch, DomainType::BeaconProposer, context).as_ref());
+    input[32..40].copy_from_slice(&slot.to_le_bytes());
     let seed = hash(input);
-    let indices = get_active_validator_indices(state, epoch);
-    compute_proposer_index(state, &indices, &seed, context)
+    let indices = active_validator_indices(validators, epoch);
+    select_proposer(validators, &indices, &seed, context)
 }
 
 pub fn get_total_balance<
@@ -779,11 +885,19 @@ pub fn get_total_balance<
     >,
     indices: &HashSet<ValidatorIndex>,
     context: &Context,
+) -> Result<Gwei, Error> {
+    total_balance(&state.validators, indices, context)
+}
+
+pub(crate) fn total_balance(
+    validators: &[Validator],
+    indices: &HashSet<ValidatorIndex>,
+    context: &Context,
 ) -> Result<Gwei, Error> {
     let total_balance = indices
         .iter()
         .try_fold(Gwei::default(), |acc, i| {
-            acc.checked_add(state.validators[*i].effective_balance)
+            acc.checked_add(validators[*i].effective_balance)
         })
         .ok_or(Error::Overflow)?;
     Ok(u64::max(total_balance, context.effective_balance_increment))
@@ -811,8 +925,20 @@ pub fn get_total_active_balance<
     >,
     context: &Context,
 ) -> Result<Gwei, Error> {
-    let indices = get_active_validator_indices(state, get_current_epoch(state, context));
-    get_total_balance(state, &HashSet::from_iter(indices), context)
+    total_active_balance(
+        &state.validators,
+        get_current_epoch(state, context),
+        context,
+    )
+}
+
+pub(crate) fn total_active_balance(
+    validators: &[Validator],
+    epoch: Epoch,
+    context: &Context,
+) -> Result<Gwei, Error> {
+    let indices = active_validator_indices(validators, epoch);
+    total_balance(validators, &HashSet::from_iter(indices), context)
 }
 
 pub fn get_indexed_attestation<
@@ -837,11 +963,21 @@ pub fn get_indexed_attestation<
     >,
     attestation: &Attestation<MAX_VALIDATORS_PER_COMMITTEE>,
     context: &Context,
+) -> Result<IndexedAttestation<MAX_VALIDATORS_PER_COMMITTEE>, Error> {
+    indexed_attestation(&state.validators, &state.randao_mixes, attestation, context)
+}
+
+pub(crate) fn indexed_attestation<const MAX_VALIDATORS_PER_COMMITTEE: usize>(
+    validators: &[Validator],
+    randao_mixes: &[Bytes32],
+    attestation: &Attestation<MAX_VALIDATORS_PER_COMMITTEE>,
+    context: &Context,
 ) -> Result<IndexedAttestation<MAX_VALIDATORS_PER_COMMITTEE>, Error> {
     let bits = &attestation.aggregation_bits;
-    let mut attesting_indices = get_attesting_indices(state, &attestation.data, bits, context)?
-        .into_iter()
-        .collect::<Vec<_>>();
+    let mut attesting_indices =
+        attesting_indices(validators, randao_mixes, &attestation.data, bits, context)?
+            .into_iter()
+            .collect::<Vec<_>>();
     // NOTE: the spec uses a "stable" sort; however, we cannot discriminate
     // a stable from unstable sort on the primitive `ValidatorIndex` type here.
     attesting_indices.sort_unstable();
@@ -879,7 +1015,17 @@ pub fn get_attesting_indices<
     bits: &Bitlist<MAX_VALIDATORS_PER_COMMITTEE>,
     context: &Context,
 ) -> Result<HashSet<ValidatorIndex>, Error> {
-    let committee = get_beacon_committee(state, data.slot, data.index, context)?;
+    attesting_indices(&state.validators, &state.randao_mixes, data, bits, context)
+}
+
+pub(crate) fn attesting_indices<const MAX_VALIDATORS_PER_COMMITTEE: usize>(
+    validators: &[Validator],
+    randao_mixes: &[Bytes32],
+    data: &AttestationData,
+    bits: &Bitlist<MAX_VALIDATORS_PER_COMMITTEE>,
+    context: &Context,
+) -> Result<HashSet<ValidatorIndex>, Error> {
+    let committee = beacon_committee(validators, randao_mixes, data.slot, data.index, context)?;
 
     if bits.len() != committee.len() {
         return Err(invalid_operation_error(InvalidOperation::Attestation(
@@ -980,37 +1126,42 @@ pub fn initiate_validator_exit<
     index: ValidatorIndex,
     context: &Context,
 ) {
-    if state.validators[index].exit_epoch != FAR_FUTURE_EPOCH {
+    let current_epoch = get_current_epoch(state, context);
+    initiate_exit(&mut state.validators, index, current_epoch, context)
+}
+
+pub(crate) fn initiate_exit<const VALIDATOR_REGISTRY_LIMIT: usize>(
+    validators: &mut List<Validator, VALIDATOR_REGISTRY_LIMIT>,
+    index: ValidatorIndex,
+    current_epoch: Epoch,
+    context: &Context,
+) {
+    if validators[index].exit_epoch != FAR_FUTURE_EPOCH {
         return;
     }
 
-    let mut exit_epochs: Vec<Epoch> = state
-        .validators
+    let mut exit_epochs: Vec<Epoch> = validators
         .iter()
         .filter(|v| v.exit_epoch != FAR_FUTURE_EPOCH)
         .map(|v| v.exit_epoch)
         .collect();
 
-    exit_epochs.push(compute_activation_exit_epoch(
-        get_current_epoch(state, context),
-        context,
-    ));
+    exit_epochs.push(compute_activation_exit_epoch(current_epoch, context));
 
     let mut exit_queue_epoch = *exit_epochs.iter().max().unwrap();
 
-    let exit_queue_churn = state
-        .validators
+    let exit_queue_churn = validators
         .iter()
         .filter(|v| v.exit_epoch == exit_queue_epoch)
         .count();
 
-    if exit_queue_churn >= get_validator_churn_limit(state, context) as usize {
+    if exit_queue_churn >= validator_churn_limit(validators, current_epoch, context) {
         exit_queue_epoch += 1
     }
 
-    state.validators[index].exit_epoch = exit_queue_epoch;
-    state.validators[index].withdrawable_epoch =
-        state.validators[index].exit_epoch + context.min_validator_withdrawability_delay;
+    validators[index].exit_epoch = exit_queue_epoch;
+    validators[index].withdrawable_epoch =
+        validators[index].exit_epoch + context.min_validator_withdrawability_delay;
 }
 
 pub fn slash_validator<
@@ -1038,18 +1189,14 @@ pub fn slash_validator<
     context: &Context,
 ) -> Result<(), Error> {
     let epoch = get_current_epoch(state, context);
-    initiate_validator_exit(state, slashed_index, context);
-    state.validators[slashed_index].slashed = true;
-    state.validators[slashed_index].withdrawable_epoch = u64::max(
-        state.validators[slashed_index].withdrawable_epoch,
-        epoch + context.epochs_per_slashings_vector as u64,
-    );
-    let slashings_index = epoch as usize % EPOCHS_PER_SLASHINGS_VECTOR;
-    state.slashings[slashings_index] += state.validators[slashed_index].effective_balance;
-    decrease_balance(
-        state,
+    slash_and_penalize(
+        &mut state.validators,
+        &mut state.balances,
+        &mut state.slashings,
         slashed_index,
-        state.validators[slashed_index].effective_balance / context.min_slashing_penalty_quotient,
+        epoch,
+        context.min_slashing_penalty_quotient,
+        context,
     );
 
     let proposer_index = get_beacon_proposer_index(state, context)?;
@@ -1067,3 +1214,30 @@ pub fn slash_validator<
     );
     Ok(())
 }
+
+/// Mark `slashed_index` as slashed in `epoch` and apply the initial penalty; each fork
+/// passes its own `min_slashing_penalty_quotient` and pays out its own rewards.
+pub(crate) fn slash_and_penalize<
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+>(
+    validators: &mut List<Validator, VALIDATOR_REGISTRY_LIMIT>,
+    balances: &mut List<Gwei, VALIDATOR_REGISTRY_LIMIT>,
+    slashings: &mut Vector<Gwei, EPOCHS_PER_SLASHINGS_VECTOR>,
+    slashed_index: ValidatorIndex,
+    epoch: Epoch,
+    min_slashing_penalty_quotient: u64,
+    context: &Context,
+) {
+    initiate_exit(validators, slashed_index, epoch, context);
+    let validator = &mut validators[slashed_index];
+    validator.slashed = true;
+    validator.withdrawable_epoch = u64::max(
+        validator.withdrawable_epoch,
+        epoch + context.epochs_per_slashings_vector as u64,
+    );
+    let slashings_index = epoch as usize % EPOCHS_PER_SLASHINGS_VECTOR;
+    slashings[slashings_index] += validator.effective_balance;
+    let penalty = validator.effective_balance / min_slashing_penalty_quotient;
+    balances[slashed_index] = balances[slashed_index].saturating_sub(penalty);
+}
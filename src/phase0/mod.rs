@@ -1,10 +1,14 @@
 //! This module provides an implementation of the `phase0` fork
 //! of the consensus spec. The primary entrypoints should be one of
 //! the "presets" like `mainnet` or `minimal`.
+mod aggregation;
 mod beacon_block;
 mod beacon_state;
 mod block_processing;
+mod block_production;
+mod duties;
 mod epoch_processing;
+mod eth1;
 mod fork;
 pub mod genesis;
 mod helpers;
@@ -15,16 +19,21 @@ mod slot_processing;
 mod state_transition;
 mod validator;
 
+pub use aggregation::*;
 pub use beacon_block::*;
 pub use beacon_state::*;
 pub use block_processing::*;
+pub use block_production::*;
+pub use duties::*;
 pub use epoch_processing::*;
+pub use eth1::*;
 pub use fork::*;
 pub use helpers::*;
 pub use operations::*;
 pub use presets::Preset;
 pub use signing::*;
 pub use slot_processing::process_slots;
+pub(crate) use slot_processing::store_slot_roots;
 pub use state_transition::state_transition;
 pub use validator::*;
 
@@ -36,4 +45,6 @@ pub mod mainnet {
     pub use super::presets::mainnet::*;
 }
 
-pub mod minimal {}
+pub mod minimal {
+    pub use super::presets::minimal::*;
+}
@@ -37,7 +37,6 @@ pub const MAX_ATTESTATIONS: usize = 128;
 pub const MAX_DEPOSITS: usize = 16;
 pub const MAX_VOLUNTARY_EXITS: usize = 16;
 
-pub const TARGET_AGGREGATORS_PER_COMMITTEE: usize = 16;
 pub const RANDOM_SUBNETS_PER_VALIDATOR: usize = 1;
 pub const EPOCHS_PER_RANDOM_SUBNET_SUBSCRIPTION: Epoch = 256;
 
@@ -124,5 +123,7 @@ pub type SignedBeaconBlock = phase0::SignedBeaconBlock<
     MAX_VOLUNTARY_EXITS,
 >;
 
+pub type BlockOperations = phase0::BlockOperations<MAX_VALIDATORS_PER_COMMITTEE>;
+
 pub type AggregateAndProof = phase0::AggregateAndProof<MAX_VALIDATORS_PER_COMMITTEE>;
 pub type SignedAggregateAndProof = phase0::SignedAggregateAndProof<MAX_VALIDATORS_PER_COMMITTEE>;
@@ -1,8 +1,8 @@
 use crate::phase0 as spec;
 
-use crate::primitives::Slot;
+use crate::primitives::{Root, Slot};
 use crate::state_transition::{Context, Error, Result};
-use spec::{process_epoch, BeaconState};
+use spec::{process_epoch, BeaconBlockHeader, BeaconState};
 use ssz_rs::prelude::*;
 
 pub fn process_slots<
@@ -67,16 +67,32 @@ pub fn process_slot<
     context: &Context,
 ) -> Result<()> {
     let previous_state_root = state.hash_tree_root()?;
-    let root_index = state.slot % context.slots_per_historical_root as u64;
-    state.state_roots[root_index as usize] = previous_state_root;
+    store_slot_roots(
+        &mut state.state_roots,
+        &mut state.block_roots,
+        &mut state.latest_block_header,
+        previous_state_root,
+        state.slot,
+    )
+}
+
+/// Cache the roots of the state and block at `slot`, given the root of the state at `slot`.
+pub(crate) fn store_slot_roots<const SLOTS_PER_HISTORICAL_ROOT: usize>(
+    state_roots: &mut Vector<Root, SLOTS_PER_HISTORICAL_ROOT>,
+    block_roots: &mut Vector<Root, SLOTS_PER_HISTORICAL_ROOT>,
+    latest_block_header: &mut BeaconBlockHeader,
+    previous_state_root: Root,
+    slot: Slot,
+) -> Result<()> {
+    let root_index = slot % SLOTS_PER_HISTORICAL_ROOT as u64;
+    state_roots[root_index as usize] = previous_state_root;
 
-    if state.latest_block_header.state_root == Node::default() {
-        state.latest_block_header.state_root = previous_state_root;
+    if latest_block_header.state_root == Node::default() {
+        latest_block_header.state_root = previous_state_root;
     }
 
-    let previous_block_root = state.latest_block_header.hash_tree_root()?;
-    let root_index = state.slot % context.slots_per_historical_root as u64;
-    state.block_roots[root_index as usize] = previous_block_root;
+    let previous_block_root = latest_block_header.hash_tree_root()?;
+    block_roots[root_index as usize] = previous_block_root;
 
     Ok(())
 }
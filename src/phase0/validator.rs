@@ -1,7 +1,13 @@
+use crate::networking::ATTESTATION_SUBNET_COUNT;
 use crate::phase0::Attestation;
-use crate::primitives::{BlsPublicKey, BlsSignature, Bytes32, Epoch, Gwei, ValidatorIndex};
+use crate::primitives::{
+    BlsPublicKey, BlsSignature, Bytes32, CommitteeIndex, Epoch, Gwei, Slot, ValidatorIndex,
+};
+use crate::state_transition::Context;
 use ssz_rs::prelude::*;
 
+pub const TARGET_AGGREGATORS_PER_COMMITTEE: usize = 16;
+
 #[derive(Default, Debug, SimpleSerialize, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Validator {
@@ -37,3 +43,14 @@ pub struct SignedAggregateAndProof<const MAX_VALIDATORS_PER_COMMITTEE: usize> {
     pub message: AggregateAndProof<MAX_VALIDATORS_PER_COMMITTEE>,
     pub signature: BlsSignature,
 }
+
+pub fn compute_subnet_for_attestation(
+    committees_per_slot: usize,
+    slot: Slot,
+    committee_index: CommitteeIndex,
+    context: &Context,
+) -> u64 {
+    let slots_since_epoch_start = slot % context.slots_per_epoch;
+    let committees_since_epoch_start = committees_per_slot as u64 * slots_since_epoch_start;
+    (committees_since_epoch_start + committee_index as u64) % ATTESTATION_SUBNET_COUNT as u64
+}
@@ -11,6 +11,7 @@ pub type Epoch = u64;
 
 pub type CommitteeIndex = usize;
 pub type ValidatorIndex = usize;
+pub type WithdrawalIndex = u64;
 pub type Gwei = u64;
 pub type Hash32 = Bytes32;
 
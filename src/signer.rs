@@ -0,0 +1,657 @@
+//! Signing of consensus messages with keys held locally or by a remote signer
+//! implementing the Web3Signer API.
+use crate::crypto::{self, SecretKey};
+use crate::phase0::{
+    compute_domain, compute_epoch_at_slot, compute_signing_root, AttestationData,
+    BeaconBlockHeader, Fork,
+};
+use crate::primitives::{
+    BlsPublicKey, BlsSignature, DomainType, Epoch, Root, Slot, ValidatorIndex,
+};
+use crate::state_transition::{self, Context};
+use crate::{altair, bellatrix, phase0};
+use async_trait::async_trait;
+use reqwest::Url;
+use ssz_rs::prelude::*;
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("no signing key for public key {0}")]
+    UnknownPublicKey(BlsPublicKey),
+    #[error("{0}")]
+    StateTransition(#[from] state_transition::Error),
+    #[error("{0}")]
+    Crypto(#[from] crypto::Error),
+    #[error("{0}")]
+    Merkleization(#[from] MerkleizationError),
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+    #[error("{0}")]
+    Url(#[from] url::ParseError),
+    #[error("{0}")]
+    Http(#[from] reqwest::Error),
+}
+
+/// The fork context a message is signed in.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ForkInfo {
+    pub fork: Fork,
+    pub genesis_validators_root: Root,
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum BlockVersion {
+    Phase0,
+    Altair,
+    Bellatrix,
+}
+
+/// A message to be signed by a validator.
+///
+/// Blocks are signed via their `BeaconBlockHeader` which shares
+/// the signing root of the full block. Remote signers expect the full
+/// `block` (in JSON) up to altair and only the header from bellatrix on,
+/// so blocks are best built with the constructor for their fork.
+#[derive(Debug, Clone)]
+pub enum SignableMessage {
+    Block {
+        version: BlockVersion,
+        block_header: BeaconBlockHeader,
+        block: Option<serde_json::Value>,
+    },
+    Attestation(AttestationData),
+    RandaoReveal {
+        epoch: Epoch,
+    },
+    AggregationSlot {
+        slot: Slot,
+    },
+    SyncCommitteeMessage {
+        beacon_block_root: Root,
+        slot: Slot,
+    },
+}
+
+impl SignableMessage {
+    pub fn phase0_block<
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+    >(
+        block: &mut phase0::BeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+        >,
+    ) -> Result<Self, Error> {
+        let block_header = block_header(
+            block.slot,
+            block.proposer_index,
+            block.parent_root,
+            block.state_root,
+            &mut block.body,
+        )?;
+        Ok(Self::Block {
+            version: BlockVersion::Phase0,
+            block_header,
+            block: Some(serde_json::to_value(&*block)?),
+        })
+    }
+
+    pub fn altair_block<
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+    >(
+        block: &mut altair::BeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+        >,
+    ) -> Result<Self, Error> {
+        let block_header = block_header(
+            block.slot,
+            block.proposer_index,
+            block.parent_root,
+            block.state_root,
+            &mut block.body,
+        )?;
+        Ok(Self::Block {
+            version: BlockVersion::Altair,
+            block_header,
+            block: Some(serde_json::to_value(&*block)?),
+        })
+    }
+
+    pub fn bellatrix_block<
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    >(
+        block: &mut bellatrix::BeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+        >,
+    ) -> Result<Self, Error> {
+        let block_header = block_header(
+            block.slot,
+            block.proposer_index,
+            block.parent_root,
+            block.state_root,
+            &mut block.body,
+        )?;
+        Ok(Self::Block {
+            version: BlockVersion::Bellatrix,
+            block_header,
+            block: None,
+        })
+    }
+
+    fn domain_type_and_epoch(&self, context: &Context) -> (DomainType, Epoch) {
+        let epoch_at_slot = |slot: Slot| compute_epoch_at_slot(slot, context);
+        match self {
+            Self::Block { block_header, .. } => {
+                (DomainType::BeaconProposer, epoch_at_slot(block_header.slot))
+            }
+            Self::Attestation(data) => (DomainType::BeaconAttester, data.target.epoch),
+            Self::RandaoReveal { epoch } => (DomainType::Randao, *epoch),
+            Self::AggregationSlot { slot } => (DomainType::SelectionProof, epoch_at_slot(*slot)),
+            Self::SyncCommitteeMessage { slot, .. } => {
+                (DomainType::SyncCommittee, epoch_at_slot(*slot))
+            }
+        }
+    }
+
+    pub fn signing_root(&self, fork_info: &ForkInfo, context: &Context) -> Result<Root, Error> {
+        let (domain_type, epoch) = self.domain_type_and_epoch(context);
+        let fork = &fork_info.fork;
+        let fork_version = if epoch < fork.epoch {
+            fork.previous_version
+        } else {
+            fork.current_version
+        };
+        let domain = compute_domain(
+            domain_type,
+            Some(fork_version),
+            Some(fork_info.genesis_validators_root),
+            context,
+        )?;
+        let signing_root = match self {
+            Self::Block { block_header, .. } => {
+                compute_signing_root(&mut block_header.clone(), domain)
+            }
+            Self::Attestation(data) => compute_signing_root(&mut data.clone(), domain),
+            Self::RandaoReveal { epoch } => compute_signing_root(&mut { *epoch }, domain),
+            Self::AggregationSlot { slot } => compute_signing_root(&mut { *slot }, domain),
+            Self::SyncCommitteeMessage {
+                beacon_block_root, ..
+            } => compute_signing_root(&mut { *beacon_block_root }, domain),
+        }?;
+        Ok(signing_root)
+    }
+}
+
+fn block_header(
+    slot: Slot,
+    proposer_index: ValidatorIndex,
+    parent_root: Root,
+    state_root: Root,
+    body: &mut impl SimpleSerialize,
+) -> Result<BeaconBlockHeader, Error> {
+    Ok(BeaconBlockHeader {
+        slot,
+        proposer_index,
+        parent_root,
+        state_root,
+        body_root: body.hash_tree_root()?,
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct SigningRequest {
+    pub message: SignableMessage,
+    pub fork_info: ForkInfo,
+    pub signing_root: Root,
+}
+
+impl SigningRequest {
+    pub fn new(
+        message: SignableMessage,
+        fork_info: ForkInfo,
+        context: &Context,
+    ) -> Result<Self, Error> {
+        let signing_root = message.signing_root(&fork_info, context)?;
+        Ok(Self {
+            message,
+            fork_info,
+            signing_root,
+        })
+    }
+}
+
+#[async_trait]
+pub trait Signer: Send + Sync {
+    /// Sign the `request` with the key for `public_key`.
+    async fn sign(
+        &self,
+        public_key: &BlsPublicKey,
+        request: &SigningRequest,
+    ) -> Result<BlsSignature, Error>;
+}
+
+/// A `Signer` over keys held in memory.
+#[derive(Default)]
+pub struct LocalSigner {
+    keys: HashMap<BlsPublicKey, SecretKey>,
+}
+
+impl LocalSigner {
+    pub fn new(keys: impl IntoIterator<Item = SecretKey>) -> Self {
+        let keys = keys
+            .into_iter()
+            .map(|key| (key.public_key(), key))
+            .collect();
+        Self { keys }
+    }
+
+    pub fn insert(&mut self, key: SecretKey) {
+        self.keys.insert(key.public_key(), key);
+    }
+}
+
+#[async_trait]
+impl Signer for LocalSigner {
+    async fn sign(
+        &self,
+        public_key: &BlsPublicKey,
+        request: &SigningRequest,
+    ) -> Result<BlsSignature, Error> {
+        let key = self
+            .keys
+            .get(public_key)
+            .ok_or_else(|| Error::UnknownPublicKey(public_key.clone()))?;
+        Ok(key.sign(request.signing_root.as_bytes()))
+    }
+}
+
+#[derive(serde::Serialize)]
+struct EpochPayload {
+    #[serde(with = "crate::serde::as_string")]
+    epoch: Epoch,
+}
+
+#[derive(serde::Serialize)]
+struct SlotPayload {
+    #[serde(with = "crate::serde::as_string")]
+    slot: Slot,
+}
+
+#[derive(serde::Serialize)]
+struct BlockPayload<'a> {
+    version: BlockVersion,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    block: Option<&'a serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    block_header: Option<&'a BeaconBlockHeader>,
+}
+
+#[derive(serde::Serialize)]
+struct SyncCommitteeMessagePayload<'a> {
+    beacon_block_root: &'a Root,
+    #[serde(with = "crate::serde::as_string")]
+    slot: Slot,
+}
+
+#[derive(serde::Serialize)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+enum Web3SignerPayload<'a> {
+    #[serde(rename = "BLOCK_V2")]
+    Block {
+        beacon_block: BlockPayload<'a>,
+    },
+    Attestation {
+        attestation: &'a AttestationData,
+    },
+    RandaoReveal {
+        randao_reveal: EpochPayload,
+    },
+    AggregationSlot {
+        aggregation_slot: SlotPayload,
+    },
+    SyncCommitteeMessage {
+        sync_committee_message: SyncCommitteeMessagePayload<'a>,
+    },
+}
+
+impl<'a> From<&'a SignableMessage> for Web3SignerPayload<'a> {
+    fn from(message: &'a SignableMessage) -> Self {
+        match message {
+            SignableMessage::Block {
+                version,
+                block_header,
+                block,
+            } => Self::Block {
+                beacon_block: BlockPayload {
+                    version: *version,
+                    block: block.as_ref(),
+                    block_header: block.is_none().then_some(block_header),
+                },
+            },
+            SignableMessage::Attestation(attestation) => Self::Attestation { attestation },
+            SignableMessage::RandaoReveal { epoch } => Self::RandaoReveal {
+                randao_reveal: EpochPayload { epoch: *epoch },
+            },
+            SignableMessage::AggregationSlot { slot } => Self::AggregationSlot {
+                aggregation_slot: SlotPayload { slot: *slot },
+            },
+            SignableMessage::SyncCommitteeMessage {
+                beacon_block_root,
+                slot,
+            } => Self::SyncCommitteeMessage {
+                sync_committee_message: SyncCommitteeMessagePayload {
+                    beacon_block_root,
+                    slot: *slot,
+                },
+            },
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct Web3SignerRequest<'a> {
+    #[serde(flatten)]
+    payload: Web3SignerPayload<'a>,
+    fork_info: &'a ForkInfo,
+    #[serde(rename = "signingRoot")]
+    signing_root: &'a Root,
+}
+
+#[derive(serde::Deserialize)]
+struct Web3SignerResponse {
+    signature: BlsSignature,
+}
+
+/// A `Signer` delegating to a remote signer implementing the Web3Signer API.
+#[derive(Clone)]
+pub struct Web3Signer {
+    http: reqwest::Client,
+    endpoint: Url,
+}
+
+impl Web3Signer {
+    /// Create a signer for the Web3Signer API served at `endpoint`,
+    /// which may include a base path.
+    pub fn new(mut endpoint: Url) -> Self {
+        // NOTE: `Url::join` replaces the last path segment unless the path ends with `/`
+        if !endpoint.path().ends_with('/') {
+            let path = format!("{}/", endpoint.path());
+            endpoint.set_path(&path);
+        }
+        Self {
+            http: reqwest::Client::new(),
+            endpoint,
+        }
+    }
+}
+
+#[async_trait]
+impl Signer for Web3Signer {
+    async fn sign(
+        &self,
+        public_key: &BlsPublicKey,
+        request: &SigningRequest,
+    ) -> Result<BlsSignature, Error> {
+        let url = self
+            .endpoint
+            .join(&format!("api/v1/eth2/sign/{public_key}"))?;
+        let body = Web3SignerRequest {
+            payload: (&request.message).into(),
+            fork_info: &request.fork_info,
+            signing_root: &request.signing_root,
+        };
+        let response: Web3SignerResponse = self
+            .http
+            .post(url)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(response.signature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serde::try_bytes_from_hex_str;
+    use axum::{extract::Path, routing::post, Extension, Json, Router};
+    use rand::prelude::*;
+    use serde_json::json;
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    type Bodies = Arc<Mutex<Vec<serde_json::Value>>>;
+
+    async fn sign_handler(
+        Path(identifier): Path<String>,
+        Extension(key): Extension<Arc<SecretKey>>,
+        Extension(bodies): Extension<Bodies>,
+        Json(body): Json<serde_json::Value>,
+    ) -> Json<serde_json::Value> {
+        assert_eq!(identifier, format!("{}", key.public_key()));
+        let signing_root = try_bytes_from_hex_str(body["signingRoot"].as_str().unwrap()).unwrap();
+        let signature = key.sign(&signing_root);
+        bodies.lock().unwrap().push(body);
+        Json(json!({ "signature": format!("{}", signature) }))
+    }
+
+    // returns the endpoint of the signer and the bodies of the requests it received
+    fn spawn_mock_web3signer(key: SecretKey) -> (Url, Bodies) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let bodies = Bodies::default();
+        let app = Router::new()
+            .route("/signer/api/v1/eth2/sign/:identifier", post(sign_handler))
+            .layer(Extension(Arc::new(key)))
+            .layer(Extension(bodies.clone()));
+        let server = axum::Server::from_tcp(listener)
+            .unwrap()
+            .serve(app.into_make_service());
+        tokio::spawn(server);
+        // NOTE: serve under a base path to check it is preserved
+        let endpoint = Url::parse(&format!("http://{addr}/signer")).unwrap();
+        (endpoint, bodies)
+    }
+
+    fn requests(context: &Context) -> Vec<SigningRequest> {
+        let fork_info = ForkInfo {
+            fork: Fork::default(),
+            genesis_validators_root: Root::default(),
+        };
+        let mut phase0_block = phase0::mainnet::BeaconBlock {
+            slot: 33,
+            ..Default::default()
+        };
+        let mut altair_block = altair::mainnet::BeaconBlock {
+            slot: 33,
+            ..Default::default()
+        };
+        let mut bellatrix_block = bellatrix::mainnet::BeaconBlock {
+            slot: 33,
+            ..Default::default()
+        };
+        let messages = vec![
+            SignableMessage::phase0_block(&mut phase0_block).unwrap(),
+            SignableMessage::altair_block(&mut altair_block).unwrap(),
+            SignableMessage::bellatrix_block(&mut bellatrix_block).unwrap(),
+            SignableMessage::Attestation(AttestationData::default()),
+            SignableMessage::RandaoReveal { epoch: 1 },
+            SignableMessage::AggregationSlot { slot: 33 },
+            SignableMessage::SyncCommitteeMessage {
+                beacon_block_root: Root::default(),
+                slot: 33,
+            },
+        ];
+        messages
+            .into_iter()
+            .map(|message| SigningRequest::new(message, fork_info.clone(), context).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_web3signer_matches_local_signer() {
+        let context = Context::for_mainnet();
+        let mut rng = thread_rng();
+        let key = SecretKey::random(&mut rng).unwrap();
+        let public_key = key.public_key();
+
+        let local_signer = LocalSigner::new([key.clone()]);
+        let (endpoint, _) = spawn_mock_web3signer(key);
+        let remote_signer = Web3Signer::new(endpoint);
+
+        for request in requests(&context) {
+            let expected = local_signer.sign(&public_key, &request).await.unwrap();
+            let signature = remote_signer.sign(&public_key, &request).await.unwrap();
+            assert_eq!(signature, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_web3signer_block_bodies() {
+        let context = Context::for_mainnet();
+        let mut rng = thread_rng();
+        let key = SecretKey::random(&mut rng).unwrap();
+        let public_key = key.public_key();
+        let (endpoint, bodies) = spawn_mock_web3signer(key);
+        let signer = Web3Signer::new(endpoint);
+
+        let requests = requests(&context);
+        for request in &requests[..3] {
+            signer.sign(&public_key, request).await.unwrap();
+        }
+
+        let fork_info = json!({
+            "fork": {
+                "previous_version": "0x00000000",
+                "current_version": "0x00000000",
+                "epoch": "0",
+            },
+            "genesis_validators_root": Root::default(),
+        });
+        let mut phase0_block = phase0::mainnet::BeaconBlock {
+            slot: 33,
+            ..Default::default()
+        };
+        let mut altair_block = altair::mainnet::BeaconBlock {
+            slot: 33,
+            ..Default::default()
+        };
+        let mut bellatrix_block = bellatrix::mainnet::BeaconBlock {
+            slot: 33,
+            ..Default::default()
+        };
+        let body_root = bellatrix_block.body.hash_tree_root().unwrap();
+        let expected = [
+            json!({
+                "type": "BLOCK_V2",
+                "fork_info": fork_info,
+                "signingRoot": requests[0].signing_root,
+                "beacon_block": {
+                    "version": "PHASE0",
+                    "block": serde_json::to_value(&phase0_block).unwrap(),
+                },
+            }),
+            json!({
+                "type": "BLOCK_V2",
+                "fork_info": fork_info,
+                "signingRoot": requests[1].signing_root,
+                "beacon_block": {
+                    "version": "ALTAIR",
+                    "block": serde_json::to_value(&altair_block).unwrap(),
+                },
+            }),
+            json!({
+                "type": "BLOCK_V2",
+                "fork_info": fork_info,
+                "signingRoot": requests[2].signing_root,
+                "beacon_block": {
+                    "version": "BELLATRIX",
+                    "block_header": {
+                        "slot": "33",
+                        "proposer_index": "0",
+                        "parent_root": Root::default(),
+                        "state_root": Root::default(),
+                        "body_root": body_root,
+                    },
+                },
+            }),
+        ];
+        assert_eq!(*bodies.lock().unwrap(), expected);
+
+        // the full blocks carry their contents
+        assert_eq!(expected[0]["beacon_block"]["block"]["slot"], "33");
+        let block_roots = [
+            phase0_block.hash_tree_root().unwrap(),
+            altair_block.hash_tree_root().unwrap(),
+            bellatrix_block.hash_tree_root().unwrap(),
+        ];
+        for (request, block_root) in requests.iter().zip(block_roots) {
+            match &request.message {
+                SignableMessage::Block { block_header, .. } => {
+                    assert_eq!(block_header.clone().hash_tree_root().unwrap(), block_root)
+                }
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_local_signer_rejects_unknown_key() {
+        let context = Context::for_mainnet();
+        let mut rng = thread_rng();
+        let signer = LocalSigner::new([SecretKey::random(&mut rng).unwrap()]);
+        let other_key = SecretKey::random(&mut rng).unwrap();
+        let request = requests(&context).remove(0);
+        assert!(matches!(
+            signer.sign(&other_key.public_key(), &request).await,
+            Err(Error::UnknownPublicKey(_))
+        ));
+    }
+}
@@ -0,0 +1,543 @@
+//! Reading and writing SSZ-encoded states and blocks to files, either uncompressed
+//! (`.ssz`) or compressed with snappy (`.ssz_snappy`) in the block or frame format.
+//!
+//! The fork of a state is detected from its `fork.current_version` and the fork of a
+//! block from its slot, both against the fork schedule of a `Context`.
+//!
+//! NOTE: the preset is not encoded in a state or block, so callers pick the `Preset`
+//! the `BeaconState` and `SignedBeaconBlock` types hold; it defaults to `Mainnet`.
+use crate::phase0::compute_epoch_at_slot;
+use crate::primitives::{Epoch, Root, Slot, Version};
+use crate::state_transition::Context;
+use crate::{altair, bellatrix, capella, deneb, phase0};
+use ssz_rs::prelude::*;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use thiserror::Error;
+
+/// The stream identifier chunk that begins every snappy-framed stream.
+const SNAPPY_FRAME_STREAM_IDENTIFIER: [u8; 10] =
+    [0xff, 0x06, 0x00, 0x00, b's', b'N', b'a', b'P', b'p', b'Y'];
+
+// NOTE: offsets are the same in every fork as the leading fields are all fixed-size
+// `genesis_time`, `genesis_validators_root`, `slot`, `fork.previous_version`
+const STATE_FORK_VERSION_OFFSET: usize = 8 + 32 + 8 + 4;
+// the offset of `message` and the `signature` of the `SignedBeaconBlock`
+const BLOCK_SLOT_OFFSET: usize = 4 + 96;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("{0}")]
+    Io(#[from] io::Error),
+    #[error("{0}")]
+    Snappy(#[from] snap::Error),
+    #[error("{0}")]
+    Serialize(#[from] SerializeError),
+    #[error("{0}")]
+    Deserialize(#[from] DeserializeError),
+    #[error("{0}")]
+    Merkleization(#[from] MerkleizationError),
+    #[error("input of {provided} bytes is too short to contain {field}")]
+    InputTooShort {
+        field: &'static str,
+        provided: usize,
+    },
+    #[error("fork version {0:?} is not in the fork schedule")]
+    UnknownForkVersion(Version),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Ssz,
+    Snappy,
+    FramedSnappy,
+}
+
+impl Encoding {
+    /// Determine the encoding of the file at `path` with contents `bytes` from its extension,
+    /// only inspecting the contents when the extension does not name an encoding.
+    ///
+    /// A `.ssz` file is always uncompressed and a `.ssz_snappy` file is compressed in
+    /// whichever snappy format its contents begin with.
+    pub fn detect(path: &Path, bytes: &[u8]) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("ssz") => Self::Ssz,
+            Some("ssz_snappy") if bytes.starts_with(&SNAPPY_FRAME_STREAM_IDENTIFIER) => {
+                Self::FramedSnappy
+            }
+            Some("ssz_snappy") => Self::Snappy,
+            _ => Self::sniff(bytes),
+        }
+    }
+
+    /// Guess the encoding of `bytes` from their contents alone, preferring to treat them
+    /// as uncompressed SSZ unless they decompress successfully.
+    pub fn sniff(bytes: &[u8]) -> Self {
+        if bytes.starts_with(&SNAPPY_FRAME_STREAM_IDENTIFIER) {
+            Self::FramedSnappy
+        } else if snap::raw::Decoder::new().decompress_vec(bytes).is_ok() {
+            Self::Snappy
+        } else {
+            Self::Ssz
+        }
+    }
+}
+
+pub fn decode(bytes: &[u8], encoding: Encoding) -> Result<Vec<u8>, Error> {
+    match encoding {
+        Encoding::Ssz => Ok(bytes.to_vec()),
+        Encoding::Snappy => Ok(snap::raw::Decoder::new().decompress_vec(bytes)?),
+        Encoding::FramedSnappy => {
+            let mut decoded = vec![];
+            snap::read::FrameDecoder::new(bytes).read_to_end(&mut decoded)?;
+            Ok(decoded)
+        }
+    }
+}
+
+pub fn encode(bytes: &[u8], encoding: Encoding) -> Result<Vec<u8>, Error> {
+    match encoding {
+        Encoding::Ssz => Ok(bytes.to_vec()),
+        Encoding::Snappy => Ok(snap::raw::Encoder::new().compress_vec(bytes)?),
+        Encoding::FramedSnappy => {
+            let mut encoded = vec![];
+            let mut encoder = snap::write::FrameEncoder::new(&mut encoded);
+            encoder.write_all(bytes)?;
+            encoder.flush()?;
+            drop(encoder);
+            Ok(encoded)
+        }
+    }
+}
+
+/// Read the file at `path`, returning its contents as uncompressed SSZ.
+pub fn read_ssz_file<P: AsRef<Path>>(path: P) -> Result<Vec<u8>, Error> {
+    let path = path.as_ref();
+    let bytes = fs::read(path)?;
+    let encoding = Encoding::detect(path, &bytes);
+    decode(&bytes, encoding)
+}
+
+/// Write the SSZ `bytes` to the file at `path` with the given `encoding`.
+pub fn write_ssz_file<P: AsRef<Path>>(
+    path: P,
+    bytes: &[u8],
+    encoding: Encoding,
+) -> Result<(), Error> {
+    let encoded = encode(bytes, encoding)?;
+    fs::write(path, encoded)?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fork {
+    Phase0,
+    Altair,
+    Bellatrix,
+    Capella,
+    Deneb,
+}
+
+impl Fork {
+    pub fn from_version(version: &Version, context: &Context) -> Option<Self> {
+        if version == &context.genesis_fork_version {
+            Some(Self::Phase0)
+        } else if version == &context.altair_fork_version {
+            Some(Self::Altair)
+        } else if version == &context.bellatrix_fork_version {
+            Some(Self::Bellatrix)
+        } else if version == &context.capella_fork_version {
+            Some(Self::Capella)
+        } else if version == &context.deneb_fork_version {
+            Some(Self::Deneb)
+        } else {
+            None
+        }
+    }
+
+    pub fn at_epoch(epoch: Epoch, context: &Context) -> Self {
+        let schedule = context.fork_schedule();
+        if epoch >= schedule.deneb {
+            Self::Deneb
+        } else if epoch >= schedule.capella {
+            Self::Capella
+        } else if epoch >= schedule.bellatrix {
+            Self::Bellatrix
+        } else if epoch >= schedule.altair {
+            Self::Altair
+        } else {
+            Self::Phase0
+        }
+    }
+}
+
+/// A container that can be read from and written to a SSZ file.
+#[cfg(feature = "serde")]
+pub trait Container: SimpleSerialize + fmt::Debug + serde::Serialize {}
+
+#[cfg(feature = "serde")]
+impl<T: SimpleSerialize + fmt::Debug + serde::Serialize> Container for T {}
+
+/// A container that can be read from and written to a SSZ file.
+#[cfg(not(feature = "serde"))]
+pub trait Container: SimpleSerialize + fmt::Debug {}
+
+#[cfg(not(feature = "serde"))]
+impl<T: SimpleSerialize + fmt::Debug> Container for T {}
+
+/// The `BeaconState` and `SignedBeaconBlock` types of each fork under a given preset.
+pub trait Preset {
+    type Phase0BeaconState: Container;
+    type AltairBeaconState: Container;
+    type BellatrixBeaconState: Container;
+    type CapellaBeaconState: Container;
+    type DenebBeaconState: Container;
+    type Phase0SignedBeaconBlock: Container;
+    type AltairSignedBeaconBlock: Container;
+    type BellatrixSignedBeaconBlock: Container;
+    type CapellaSignedBeaconBlock: Container;
+    type DenebSignedBeaconBlock: Container;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mainnet;
+
+impl Preset for Mainnet {
+    type Phase0BeaconState = phase0::mainnet::BeaconState;
+    type AltairBeaconState = altair::mainnet::BeaconState;
+    type BellatrixBeaconState = bellatrix::mainnet::BeaconState;
+    type CapellaBeaconState = capella::mainnet::BeaconState;
+    type DenebBeaconState = deneb::mainnet::BeaconState;
+    type Phase0SignedBeaconBlock = phase0::mainnet::SignedBeaconBlock;
+    type AltairSignedBeaconBlock = altair::mainnet::SignedBeaconBlock;
+    type BellatrixSignedBeaconBlock = bellatrix::mainnet::SignedBeaconBlock;
+    type CapellaSignedBeaconBlock = capella::mainnet::SignedBeaconBlock;
+    type DenebSignedBeaconBlock = deneb::mainnet::SignedBeaconBlock;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Minimal;
+
+impl Preset for Minimal {
+    type Phase0BeaconState = phase0::minimal::BeaconState;
+    type AltairBeaconState = altair::minimal::BeaconState;
+    type BellatrixBeaconState = bellatrix::minimal::BeaconState;
+    type CapellaBeaconState = capella::minimal::BeaconState;
+    type DenebBeaconState = deneb::minimal::BeaconState;
+    type Phase0SignedBeaconBlock = phase0::minimal::SignedBeaconBlock;
+    type AltairSignedBeaconBlock = altair::minimal::SignedBeaconBlock;
+    type BellatrixSignedBeaconBlock = bellatrix::minimal::SignedBeaconBlock;
+    type CapellaSignedBeaconBlock = capella::minimal::SignedBeaconBlock;
+    type DenebSignedBeaconBlock = deneb::minimal::SignedBeaconBlock;
+}
+
+// NOTE: the SSZ `Serialize` is named explicitly as `Container` may also imply `serde::Serialize`
+fn serialize<T: Container>(value: &T) -> Result<Vec<u8>, Error> {
+    let mut buffer = vec![];
+    Serialize::serialize(value, &mut buffer)?;
+    Ok(buffer)
+}
+
+/// A `BeaconState` of any fork, under the mainnet preset unless another `Preset` is given.
+#[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(untagged, bound = "")
+)]
+pub enum BeaconState<P: Preset = Mainnet> {
+    Phase0(Box<P::Phase0BeaconState>),
+    Altair(Box<P::AltairBeaconState>),
+    Bellatrix(Box<P::BellatrixBeaconState>),
+    Capella(Box<P::CapellaBeaconState>),
+    Deneb(Box<P::DenebBeaconState>),
+}
+
+impl<P: Preset> BeaconState<P> {
+    pub fn fork(&self) -> Fork {
+        match self {
+            Self::Phase0(_) => Fork::Phase0,
+            Self::Altair(_) => Fork::Altair,
+            Self::Bellatrix(_) => Fork::Bellatrix,
+            Self::Capella(_) => Fork::Capella,
+            Self::Deneb(_) => Fork::Deneb,
+        }
+    }
+
+    /// Deserialize `bytes` into the state type of the fork named by its `fork.current_version`.
+    pub fn deserialize(bytes: &[u8], context: &Context) -> Result<Self, Error> {
+        let version: Version = bytes
+            .get(STATE_FORK_VERSION_OFFSET..STATE_FORK_VERSION_OFFSET + 4)
+            .ok_or(Error::InputTooShort {
+                field: "fork.current_version",
+                provided: bytes.len(),
+            })?
+            .try_into()
+            .expect("correct input");
+        let fork =
+            Fork::from_version(&version, context).ok_or(Error::UnknownForkVersion(version))?;
+        let state = match fork {
+            Fork::Phase0 => Self::Phase0(Box::new(P::Phase0BeaconState::deserialize(bytes)?)),
+            Fork::Altair => Self::Altair(Box::new(P::AltairBeaconState::deserialize(bytes)?)),
+            Fork::Bellatrix => {
+                Self::Bellatrix(Box::new(P::BellatrixBeaconState::deserialize(bytes)?))
+            }
+            Fork::Capella => Self::Capella(Box::new(P::CapellaBeaconState::deserialize(bytes)?)),
+            Fork::Deneb => Self::Deneb(Box::new(P::DenebBeaconState::deserialize(bytes)?)),
+        };
+        Ok(state)
+    }
+
+    pub fn serialize(&self) -> Result<Vec<u8>, Error> {
+        match self {
+            Self::Phase0(state) => serialize(state.as_ref()),
+            Self::Altair(state) => serialize(state.as_ref()),
+            Self::Bellatrix(state) => serialize(state.as_ref()),
+            Self::Capella(state) => serialize(state.as_ref()),
+            Self::Deneb(state) => serialize(state.as_ref()),
+        }
+    }
+
+    pub fn hash_tree_root(&mut self) -> Result<Root, Error> {
+        let root = match self {
+            Self::Phase0(state) => state.hash_tree_root(),
+            Self::Altair(state) => state.hash_tree_root(),
+            Self::Bellatrix(state) => state.hash_tree_root(),
+            Self::Capella(state) => state.hash_tree_root(),
+            Self::Deneb(state) => state.hash_tree_root(),
+        }?;
+        Ok(root)
+    }
+}
+
+/// A `SignedBeaconBlock` of any fork, under the mainnet preset unless another `Preset`
+/// is given.
+#[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(untagged, bound = "")
+)]
+pub enum SignedBeaconBlock<P: Preset = Mainnet> {
+    Phase0(Box<P::Phase0SignedBeaconBlock>),
+    Altair(Box<P::AltairSignedBeaconBlock>),
+    Bellatrix(Box<P::BellatrixSignedBeaconBlock>),
+    Capella(Box<P::CapellaSignedBeaconBlock>),
+    Deneb(Box<P::DenebSignedBeaconBlock>),
+}
+
+impl<P: Preset> SignedBeaconBlock<P> {
+    pub fn fork(&self) -> Fork {
+        match self {
+            Self::Phase0(_) => Fork::Phase0,
+            Self::Altair(_) => Fork::Altair,
+            Self::Bellatrix(_) => Fork::Bellatrix,
+            Self::Capella(_) => Fork::Capella,
+            Self::Deneb(_) => Fork::Deneb,
+        }
+    }
+
+    /// Deserialize `bytes` into the block type of the fork scheduled at the slot of the block.
+    pub fn deserialize(bytes: &[u8], context: &Context) -> Result<Self, Error> {
+        let slot =
+            bytes
+                .get(BLOCK_SLOT_OFFSET..BLOCK_SLOT_OFFSET + 8)
+                .ok_or(Error::InputTooShort {
+                    field: "message.slot",
+                    provided: bytes.len(),
+                })?;
+        let slot = Slot::from_le_bytes(slot.try_into().expect("correct input"));
+        let fork = Fork::at_epoch(compute_epoch_at_slot(slot, context), context);
+        let block = match fork {
+            Fork::Phase0 => Self::Phase0(Box::new(P::Phase0SignedBeaconBlock::deserialize(bytes)?)),
+            Fork::Altair => Self::Altair(Box::new(P::AltairSignedBeaconBlock::deserialize(bytes)?)),
+            Fork::Bellatrix => {
+                Self::Bellatrix(Box::new(P::BellatrixSignedBeaconBlock::deserialize(bytes)?))
+            }
+            Fork::Capella => {
+                Self::Capella(Box::new(P::CapellaSignedBeaconBlock::deserialize(bytes)?))
+            }
+            Fork::Deneb => Self::Deneb(Box::new(P::DenebSignedBeaconBlock::deserialize(bytes)?)),
+        };
+        Ok(block)
+    }
+
+    pub fn serialize(&self) -> Result<Vec<u8>, Error> {
+        match self {
+            Self::Phase0(block) => serialize(block.as_ref()),
+            Self::Altair(block) => serialize(block.as_ref()),
+            Self::Bellatrix(block) => serialize(block.as_ref()),
+            Self::Capella(block) => serialize(block.as_ref()),
+            Self::Deneb(block) => serialize(block.as_ref()),
+        }
+    }
+
+    pub fn hash_tree_root(&mut self) -> Result<Root, Error> {
+        let root = match self {
+            Self::Phase0(block) => block.hash_tree_root(),
+            Self::Altair(block) => block.hash_tree_root(),
+            Self::Bellatrix(block) => block.hash_tree_root(),
+            Self::Capella(block) => block.hash_tree_root(),
+            Self::Deneb(block) => block.hash_tree_root(),
+        }?;
+        Ok(root)
+    }
+}
+
+pub fn read_state<P: Preset, Q: AsRef<Path>>(
+    path: Q,
+    context: &Context,
+) -> Result<BeaconState<P>, Error> {
+    let bytes = read_ssz_file(path)?;
+    BeaconState::deserialize(&bytes, context)
+}
+
+pub fn write_state<P: Preset, Q: AsRef<Path>>(
+    path: Q,
+    state: &BeaconState<P>,
+    encoding: Encoding,
+) -> Result<(), Error> {
+    write_ssz_file(path, &state.serialize()?, encoding)
+}
+
+pub fn read_block<P: Preset, Q: AsRef<Path>>(
+    path: Q,
+    context: &Context,
+) -> Result<SignedBeaconBlock<P>, Error> {
+    let bytes = read_ssz_file(path)?;
+    SignedBeaconBlock::deserialize(&bytes, context)
+}
+
+pub fn write_block<P: Preset, Q: AsRef<Path>>(
+    path: Q,
+    block: &SignedBeaconBlock<P>,
+    encoding: Encoding,
+) -> Result<(), Error> {
+    write_ssz_file(path, &block.serialize()?, encoding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encoding_round_trip() {
+        let bytes = (0..1024u32)
+            .flat_map(|i| i.to_le_bytes())
+            .collect::<Vec<_>>();
+        for encoding in [Encoding::Ssz, Encoding::Snappy, Encoding::FramedSnappy] {
+            let encoded = encode(&bytes, encoding).unwrap();
+            if encoding != Encoding::Ssz {
+                assert_eq!(Encoding::sniff(&encoded), encoding);
+            }
+            assert_eq!(decode(&encoded, encoding).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn test_detect() {
+        let bytes = encode(b"some bytes", Encoding::Snappy).unwrap();
+        assert_eq!(
+            Encoding::detect(Path::new("state.ssz"), &bytes),
+            Encoding::Ssz
+        );
+        assert_eq!(
+            Encoding::detect(Path::new("state.ssz_snappy"), &bytes),
+            Encoding::Snappy
+        );
+        let bytes = encode(b"some bytes", Encoding::FramedSnappy).unwrap();
+        assert_eq!(
+            Encoding::detect(Path::new("state.ssz_snappy"), &bytes),
+            Encoding::FramedSnappy
+        );
+        // without a known extension the contents decide
+        assert_eq!(
+            Encoding::detect(Path::new("state"), &bytes),
+            Encoding::FramedSnappy
+        );
+        assert_eq!(
+            Encoding::detect(Path::new("state.bin"), b"some bytes"),
+            Encoding::Ssz
+        );
+    }
+
+    #[test]
+    fn test_state_fork_detection() {
+        let context = Context::for_mainnet();
+        let mut state = altair::mainnet::BeaconState::default();
+        state.fork.current_version = context.altair_fork_version;
+        let state: BeaconState = BeaconState::Altair(Box::new(state));
+
+        let path = std::env::temp_dir().join(format!(
+            "ethereum-consensus-test-state-fork-detection-{}.ssz_snappy",
+            std::process::id()
+        ));
+        write_state(&path, &state, Encoding::FramedSnappy).unwrap();
+        let recovered: BeaconState = read_state(&path, &context).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(recovered.fork(), Fork::Altair);
+        assert_eq!(recovered.serialize().unwrap(), state.serialize().unwrap());
+
+        let mut state = deneb::mainnet::BeaconState::default();
+        state.fork.current_version = context.deneb_fork_version;
+        let bytes = BeaconState::<Mainnet>::Deneb(Box::new(state))
+            .serialize()
+            .unwrap();
+        let state: BeaconState = BeaconState::deserialize(&bytes, &context).unwrap();
+        assert_eq!(state.fork(), Fork::Deneb);
+
+        let mut bytes = bytes;
+        bytes[STATE_FORK_VERSION_OFFSET..STATE_FORK_VERSION_OFFSET + 4].copy_from_slice(&[0xff; 4]);
+        assert!(matches!(
+            BeaconState::<Mainnet>::deserialize(&bytes, &context),
+            Err(Error::UnknownForkVersion(_))
+        ));
+    }
+
+    #[test]
+    fn test_minimal_state() {
+        let context = Context::for_minimal();
+        let mut state = bellatrix::minimal::BeaconState::default();
+        state.fork.current_version = context.bellatrix_fork_version;
+        let bytes = BeaconState::<Minimal>::Bellatrix(Box::new(state))
+            .serialize()
+            .unwrap();
+        let state: BeaconState<Minimal> = BeaconState::deserialize(&bytes, &context).unwrap();
+        assert_eq!(state.fork(), Fork::Bellatrix);
+        assert_eq!(state.serialize().unwrap(), bytes);
+
+        // the vectors of a minimal state are shorter than those of the mainnet preset
+        assert!(BeaconState::<Mainnet>::deserialize(&bytes, &context).is_err());
+    }
+
+    #[test]
+    fn test_block_fork_detection() {
+        let mut context = Context::for_mainnet();
+        let mut block = phase0::mainnet::SignedBeaconBlock::default();
+        block.message.slot = 1;
+        let bytes = SignedBeaconBlock::<Mainnet>::Phase0(Box::new(block))
+            .serialize()
+            .unwrap();
+        let block: SignedBeaconBlock = SignedBeaconBlock::deserialize(&bytes, &context).unwrap();
+        assert_eq!(block.fork(), Fork::Phase0);
+
+        let mut block = altair::mainnet::SignedBeaconBlock::default();
+        block.message.slot = context.altair_fork_epoch * context.slots_per_epoch;
+        let bytes = SignedBeaconBlock::<Mainnet>::Altair(Box::new(block))
+            .serialize()
+            .unwrap();
+        let block: SignedBeaconBlock = SignedBeaconBlock::deserialize(&bytes, &context).unwrap();
+        assert_eq!(block.fork(), Fork::Altair);
+
+        context.deneb_fork_epoch = context.altair_fork_epoch + 1;
+        let mut block = deneb::mainnet::SignedBeaconBlock::default();
+        block.message.slot = context.deneb_fork_epoch * context.slots_per_epoch;
+        let bytes = SignedBeaconBlock::<Mainnet>::Deneb(Box::new(block))
+            .serialize()
+            .unwrap();
+        let block: SignedBeaconBlock = SignedBeaconBlock::deserialize(&bytes, &context).unwrap();
+        assert_eq!(block.fork(), Fork::Deneb);
+    }
+}
@@ -1,5 +1,6 @@
 mod byte_list;
 mod byte_vector;
+pub mod io;
 
 pub use byte_list::ByteList;
 pub use byte_vector::ByteVector;
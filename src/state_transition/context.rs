@@ -1,6 +1,8 @@
 use crate::altair;
 use crate::bellatrix;
+use crate::capella;
 use crate::configs::{self, Config};
+use crate::deneb;
 use crate::phase0;
 use crate::primitives::{Epoch, ExecutionAddress, Gwei, Hash32, Slot, Version, U256};
 
@@ -8,6 +10,8 @@ use crate::primitives::{Epoch, ExecutionAddress, Gwei, Hash32, Slot, Version, U2
 pub struct ForkSchedule {
     pub altair: Epoch,
     pub bellatrix: Epoch,
+    pub capella: Epoch,
+    pub deneb: Epoch,
 }
 
 #[derive(Debug, Default)]
@@ -66,6 +70,17 @@ pub struct Context {
     pub bytes_per_logs_bloom: usize,
     pub max_extra_data_bytes: usize,
 
+    // capella preset
+    pub max_bls_to_execution_changes: usize,
+    pub max_withdrawals_per_payload: usize,
+    pub max_validators_per_withdrawals_sweep: usize,
+
+    // deneb preset
+    pub field_elements_per_blob: usize,
+    pub max_blob_commitments_per_block: usize,
+    pub max_blobs_per_block: usize,
+    pub kzg_commitment_inclusion_proof_depth: usize,
+
     // config
     pub name: &'static str,
 
@@ -84,6 +99,8 @@ pub struct Context {
     pub bellatrix_fork_epoch: Epoch,
     pub capella_fork_version: Version,
     pub capella_fork_epoch: Epoch,
+    pub deneb_fork_version: Version,
+    pub deneb_fork_epoch: Epoch,
     pub sharding_fork_version: Version,
     pub sharding_fork_epoch: Epoch,
 
@@ -111,11 +128,15 @@ impl Context {
         phase0_preset: &phase0::Preset,
         altair_preset: &altair::Preset,
         bellatrix_preset: &bellatrix::Preset,
+        capella_preset: &capella::Preset,
+        deneb_preset: &deneb::Preset,
         config: &Config,
     ) -> Self {
         let fork_schedule = ForkSchedule {
             altair: config.altair_fork_epoch,
             bellatrix: config.bellatrix_fork_epoch,
+            capella: config.capella_fork_epoch,
+            deneb: config.deneb_fork_epoch,
         };
         Self {
             fork_schedule,
@@ -173,6 +194,16 @@ impl Context {
             max_transactions_per_payload: bellatrix_preset.max_transactions_per_payload,
             bytes_per_logs_bloom: bellatrix_preset.bytes_per_logs_bloom,
             max_extra_data_bytes: bellatrix_preset.max_extra_data_bytes,
+            // capella
+            max_bls_to_execution_changes: capella_preset.max_bls_to_execution_changes,
+            max_withdrawals_per_payload: capella_preset.max_withdrawals_per_payload,
+            max_validators_per_withdrawals_sweep: capella_preset
+                .max_validators_per_withdrawals_sweep,
+            // deneb
+            field_elements_per_blob: deneb_preset.field_elements_per_blob,
+            max_blob_commitments_per_block: deneb_preset.max_blob_commitments_per_block,
+            max_blobs_per_block: deneb_preset.max_blobs_per_block,
+            kzg_commitment_inclusion_proof_depth: deneb_preset.kzg_commitment_inclusion_proof_depth,
 
             // config
             name: config.name,
@@ -189,6 +220,8 @@ impl Context {
             bellatrix_fork_epoch: config.bellatrix_fork_epoch,
             capella_fork_version: config.capella_fork_version,
             capella_fork_epoch: config.capella_fork_epoch,
+            deneb_fork_version: config.deneb_fork_version,
+            deneb_fork_epoch: config.deneb_fork_epoch,
             sharding_fork_version: config.sharding_fork_version,
             sharding_fork_epoch: config.sharding_fork_epoch,
             seconds_per_slot: config.seconds_per_slot,
@@ -213,7 +246,16 @@ impl Context {
         let phase0_preset = &phase0::mainnet::PRESET;
         let altair_preset = &altair::mainnet::PRESET;
         let bellatrix_preset = &bellatrix::mainnet::PRESET;
-        Self::from(phase0_preset, altair_preset, bellatrix_preset, config)
+        let capella_preset = &capella::mainnet::PRESET;
+        let deneb_preset = &deneb::mainnet::PRESET;
+        Self::from(
+            phase0_preset,
+            altair_preset,
+            bellatrix_preset,
+            capella_preset,
+            deneb_preset,
+            config,
+        )
     }
 
     pub fn for_minimal() -> Self {
@@ -224,6 +266,8 @@ impl Context {
         ForkSchedule {
             altair: self.altair_fork_epoch,
             bellatrix: self.bellatrix_fork_epoch,
+            capella: self.capella_fork_epoch,
+            deneb: self.deneb_fork_epoch,
         }
     }
 }
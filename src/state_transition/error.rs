@@ -1,5 +1,9 @@
+use crate::capella::Withdrawal;
+use crate::crypto;
 use crate::phase0::{AttestationData, BeaconBlockHeader, Checkpoint};
-use crate::primitives::{BlsSignature, Epoch, Root, Slot, ValidatorIndex};
+use crate::primitives::{
+    BlsPublicKey, BlsSignature, Bytes32, Epoch, Hash32, Root, Slot, ValidatorIndex,
+};
 use ssz_rs::prelude::*;
 use thiserror::Error;
 
@@ -25,6 +29,12 @@ pub enum Error {
         lower_bound: Slot,
         upper_bound: Slot,
     },
+    #[error("epoch {requested} is outside of allowed range ({lower_bound}, {upper_bound})")]
+    EpochOutOfRange {
+        requested: Epoch,
+        lower_bound: Epoch,
+        upper_bound: Epoch,
+    },
     #[error("overflow")]
     Overflow,
     #[error("{0}")]
@@ -41,6 +51,31 @@ pub enum Error {
         previous: Epoch,
         current: Epoch,
     },
+    #[error("mismatch between the execution payload header root {expected:?} and the root of the provided payload {provided:?}")]
+    ExecutionPayloadMismatch { expected: Root, provided: Root },
+    #[error("invalid merge block: {0}")]
+    InvalidMergeBlock(InvalidMergeBlock),
+    #[error("sync committee member with public key {0:?} is not in the validator registry")]
+    UnknownSyncCommitteeMember(BlsPublicKey),
+    #[error("{0}")]
+    Crypto(#[from] crypto::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum InvalidMergeBlock {
+    #[error("terminal block hash override is not active in epoch {epoch}, only from epoch {activation_epoch}")]
+    TerminalBlockHashNotActive {
+        epoch: Epoch,
+        activation_epoch: Epoch,
+    },
+    #[error(
+        "execution payload builds on {provided:?} instead of the terminal block hash {expected:?}"
+    )]
+    TerminalBlockHashMismatch { expected: Hash32, provided: Hash32 },
+    #[error("unknown PoW block with hash {0:?}")]
+    UnknownPowBlock(Hash32),
+    #[error("PoW block with hash {0:?} is not a valid terminal block")]
+    InvalidTerminalPowBlock(Hash32),
 }
 
 #[derive(Debug, Error)]
@@ -49,6 +84,21 @@ pub enum InvalidBlock {
     Header(InvalidBeaconBlockHeader),
     #[error("invalid operation: {0}")]
     InvalidOperation(InvalidOperation),
+    #[error("invalid execution payload: {0}")]
+    InvalidExecutionPayload(InvalidExecutionPayload),
+}
+
+#[derive(Debug, Error)]
+pub enum InvalidExecutionPayload {
+    #[error("expected parent hash {expected:?} but payload has parent hash {provided:?}")]
+    InvalidParentHash { expected: Hash32, provided: Hash32 },
+    #[error("expected randao {expected:?} but payload has randao {provided:?}")]
+    InvalidPrevRandao {
+        expected: Bytes32,
+        provided: Bytes32,
+    },
+    #[error("expected timestamp {expected} but payload has timestamp {provided}")]
+    InvalidTimestamp { expected: u64, provided: u64 },
 }
 
 #[derive(Debug, Error)]
@@ -67,6 +117,10 @@ pub enum InvalidOperation {
     AttesterSlashing(InvalidAttesterSlashing),
     #[error("invalid voluntary exit: {0}")]
     VoluntaryExit(InvalidVoluntaryExit),
+    #[error("invalid withdrawals: {0}")]
+    Withdrawal(InvalidWithdrawals),
+    #[error("invalid BLS to execution change: {0}")]
+    BlsToExecutionChange(InvalidBlsToExecutionChange),
 }
 
 #[derive(Debug, Error)]
@@ -189,6 +243,29 @@ pub enum InvalidVoluntaryExit {
     InvalidSignature(BlsSignature),
 }
 
+#[derive(Debug, Error)]
+pub enum InvalidWithdrawals {
+    #[error("expected {expected} withdrawals but payload has {provided} withdrawals")]
+    IncorrectCount { expected: usize, provided: usize },
+    #[error("expected withdrawal {expected:?} but payload has withdrawal {provided:?}")]
+    IncorrectWithdrawal {
+        expected: Box<Withdrawal>,
+        provided: Box<Withdrawal>,
+    },
+}
+
+#[derive(Debug, Error)]
+pub enum InvalidBlsToExecutionChange {
+    #[error("validator with index {0} is not in the registry")]
+    ValidatorIndexOutOfBounds(ValidatorIndex),
+    #[error("withdrawal credentials have prefix {0:#04x} instead of a BLS withdrawal prefix")]
+    NonBlsWithdrawalCredentials(u8),
+    #[error("public key {0:?} does not match the withdrawal credentials")]
+    PublicKeyMismatch(BlsPublicKey),
+    #[error("BLS to execution change has invalid signature: {0:?}")]
+    InvalidSignature(BlsSignature),
+}
+
 pub(crate) fn invalid_header_error(error: InvalidBeaconBlockHeader) -> Error {
     Error::InvalidBlock(Box::new(InvalidBlock::Header(error)))
 }
@@ -196,3 +273,7 @@ pub(crate) fn invalid_header_error(error: InvalidBeaconBlockHeader) -> Error {
 pub(crate) fn invalid_operation_error(error: InvalidOperation) -> Error {
     Error::InvalidBlock(Box::new(InvalidBlock::InvalidOperation(error)))
 }
+
+pub(crate) fn invalid_execution_payload_error(error: InvalidExecutionPayload) -> Error {
+    Error::InvalidBlock(Box::new(InvalidBlock::InvalidExecutionPayload(error)))
+}